@@ -3,25 +3,39 @@
 //! Usage:
 //!   sift              # Launch TUI (default)
 //!   sift status       # Show installation status
+//!   sift status --probe  # Also start each MCP server and check it answers
 //!   sift install ...  # CLI operations
+//!   sift upgrade      # Move locked versions forward
+//!   sift outdated     # Compare locked versions with upstream
+//!   sift doctor       # Diagnose the environment
+//!   sift secret ...   # Manage the encrypted secret file
+//!   sift mcp tools <name>  # List what an MCP server serves
 //!   sift --gui        # Launch GUI
 
 mod interactive;
 
+use std::io::{IsTerminal, Read};
+use std::time::Duration;
+
 use anyhow::Result;
 use clap::{Args, Parser, Subcommand, ValueEnum};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 use sift_core::commands::context::InstallContext;
 use sift_core::commands::{
-    InstallCommand, InstallOptions, InstallTarget, StatusCommand, StatusOptions, UninstallCommand,
-    UninstallOptions,
+    ClientInstallStatus, DiagnosticSeverity, DoctorCommand, DoctorOptions, DoctorReport,
+    InstallCommand, InstallOptions, InstallTarget, ListCommand, ListOptions, ListReport,
+    OutdatedCommand, OutdatedOptions, OutdatedReport, ProbeCommand, ProbeOptions, ProbeReport,
+    StatusCommand, StatusOptions, SyncCommand, SyncOptions, SyncReport, ToolsCommand, ToolsOptions,
+    ToolsReport, UninstallCommand, UninstallOptions, UpgradeCommand, UpgradeOptions, UpgradeReport,
+    UpgradeStatus,
 };
 use sift_core::commands::{
     RegistryAddOptions, RegistryCommand, RegistryEntry, RegistryListOptions, RegistryRemoveOptions,
 };
 use sift_core::registry::RegistryType;
-use sift_core::status::{EntryState, McpServerStatus, SkillStatus, SystemStatus};
+use sift_core::secrets::FileSecretStore;
+use sift_core::status::{ClientStatus, EntryState, McpServerStatus, SkillStatus, SystemStatus};
 use sift_core::types::ConfigScope;
 
 use crate::interactive::{InteractiveFlow, PrefilledOptions};
@@ -61,9 +75,20 @@ enum Commands {
         /// Show per-client deployment details
         #[arg(short = 'v', long)]
         verbose: bool,
+
+        /// Start each MCP server and check that it answers initialize and tools/list
+        /// (limited to --scope/--global when given)
+        #[arg(long)]
+        probe: bool,
+
+        /// Seconds each server gets to answer when probing
+        #[arg(long, default_value_t = 30, requires = "probe")]
+        timeout: u64,
     },
 
     /// Install an MCP server or skill
+    ///
+    /// With no arguments, syncs every client with sift.toml
     Install(Box<InstallArgs>),
 
     /// Uninstall an MCP server or skill
@@ -91,10 +116,63 @@ enum Commands {
         format: OutputFormat,
     },
 
-    /// List MCP servers or skills
+    /// Upgrade installed MCP servers and skills within their constraints
+    ///
+    /// Re-resolves each entry against its source, updates the lockfile and
+    /// redelivers only the entries whose version changed
+    Upgrade {
+        /// What to upgrade (mcp or skill); omit to upgrade both
+        kind: Option<String>,
+        /// Name of a single entry to upgrade
+        name: Option<String>,
+        /// Output format
+        #[arg(short, long, default_value = "table")]
+        format: OutputFormat,
+    },
+
+    /// Show locked entries that are behind their upstream source
+    ///
+    /// Compares the lockfile with the newest version allowed by each
+    /// constraint and the newest version published overall. Nothing is
+    /// changed; quiet mode exits non-zero when anything is outdated
+    Outdated {
+        /// What to check (mcp or skill); omit to check both
+        kind: Option<String>,
+        /// Output format
+        #[arg(short, long, default_value = "table")]
+        format: OutputFormat,
+    },
+
+    /// Diagnose the environment sift depends on
+    ///
+    /// Checks runtimes, git, write access to client config paths, registry
+    /// reachability, the lockfile and the git cache, and suggests fixes
+    Doctor {
+        /// Skip registry reachability checks
+        #[arg(long)]
+        offline: bool,
+        /// Output format
+        #[arg(short, long, default_value = "table")]
+        format: OutputFormat,
+    },
+
+    /// List declared MCP servers and skills
+    ///
+    /// Shows every entry from the merged sift.toml with its locked version
+    /// and the clients it is delivered to
+    #[command(alias = "ls")]
     List {
-        /// What to list (mcp or skill)
+        /// What to list (mcp or skill); omit to list both
         kind: Option<String>,
+        /// Filter by declared scope (global, shared, local)
+        #[arg(long)]
+        scope: Option<String>,
+        /// Only list entries delivered to this client
+        #[arg(long)]
+        client: Option<String>,
+        /// Output format
+        #[arg(short, long, default_value = "table")]
+        format: OutputFormat,
     },
 
     /// Set configuration scope
@@ -105,6 +183,14 @@ enum Commands {
 
     /// Manage registries
     Registry(RegistryArgs),
+
+    /// Inspect declared MCP servers
+    Mcp(McpArgs),
+
+    /// Manage secrets in Sift's encrypted secret file
+    ///
+    /// Reference them from env or headers as "secret:file/<name>"
+    Secret(SecretArgs),
 }
 
 #[derive(Clone, Copy, ValueEnum, Default)]
@@ -181,6 +267,57 @@ enum RegistrySubcommand {
     },
 }
 
+#[derive(Args)]
+struct McpArgs {
+    #[command(subcommand)]
+    command: McpSubcommand,
+}
+
+#[derive(Subcommand)]
+enum McpSubcommand {
+    /// Start a server and list its tools, prompts and resources
+    ///
+    /// For MCPB bundles the live tools and prompts are compared with the
+    /// ones manifest.json declares; quiet mode exits non-zero on a mismatch
+    Tools {
+        /// MCP server name as declared in sift.toml
+        name: String,
+
+        /// Seconds the server gets to answer
+        #[arg(long, default_value_t = 30)]
+        timeout: u64,
+
+        /// Output format
+        #[arg(short, long, default_value = "table")]
+        format: OutputFormat,
+    },
+}
+
+#[derive(Args)]
+struct SecretArgs {
+    #[command(subcommand)]
+    command: SecretSubcommand,
+}
+
+#[derive(Subcommand)]
+enum SecretSubcommand {
+    /// Store a secret, read from a prompt (or stdin when piped)
+    Set {
+        /// Secret name
+        name: String,
+    },
+
+    /// List stored secret names
+    List,
+
+    /// Remove a secret
+    #[command(alias = "rm")]
+    Remove {
+        /// Secret name
+        name: String,
+    },
+}
+
 fn main() -> Result<()> {
     // Initialize tracing
     tracing_subscriber::registry()
@@ -213,8 +350,11 @@ fn run_cli(command: Commands) -> Result<()> {
             verify,
             format,
             verbose,
+            probe,
+            timeout,
         } => {
-            run_status(scope, global, verify, format, verbose)?;
+            let probe = probe.then(|| Duration::from_secs(timeout));
+            run_status(scope, global, verify, format, verbose, probe)?;
         }
         Commands::Install(args) => {
             run_install(*args)?;
@@ -227,17 +367,35 @@ fn run_cli(command: Commands) -> Result<()> {
         } => {
             run_uninstall(kind, name, scope, format)?;
         }
-        Commands::List { kind } => match kind.as_deref() {
-            Some("mcp") => println!("Listing MCP servers"),
-            Some("skill") => println!("Listing skills"),
-            Some(_) | None => println!("Listing all"),
-        },
+        Commands::Upgrade { kind, name, format } => {
+            run_upgrade(kind, name, format)?;
+        }
+        Commands::Outdated { kind, format } => {
+            run_outdated(kind, format)?;
+        }
+        Commands::Doctor { offline, format } => {
+            run_doctor(offline, format)?;
+        }
+        Commands::List {
+            kind,
+            scope,
+            client,
+            format,
+        } => {
+            run_list(kind, scope, client, format)?;
+        }
         Commands::Config { scope } => {
             println!("Setting config scope to: {scope}");
         }
         Commands::Registry(args) => {
             run_registry(args)?;
         }
+        Commands::Mcp(args) => {
+            run_mcp(args)?;
+        }
+        Commands::Secret(args) => {
+            run_secret(args)?;
+        }
     }
     Ok(())
 }
@@ -246,7 +404,7 @@ fn run_cli(command: Commands) -> Result<()> {
 struct InstallArgs {
     /// What to install (mcp or skill)
     ///
-    /// Required unless --interactive is used
+    /// Omit together with name to sync everything declared in sift.toml
     kind: Option<String>,
     /// Name/ID of the package to install
     ///
@@ -270,6 +428,9 @@ struct InstallArgs {
     /// Force overwrite existing entries
     #[arg(long, short)]
     force: bool,
+    /// Remove orphaned entries no longer declared in sift.toml (sync only)
+    #[arg(long)]
+    prune: bool,
     /// Runtime type for MCP servers (node, bun, docker, etc.)
     #[arg(long, short)]
     runtime: Option<String>,
@@ -305,6 +466,14 @@ fn run_install(args: InstallArgs) -> Result<()> {
         return run_install_interactive(args);
     }
 
+    // Bare `sift install` syncs everything declared in sift.toml
+    if args.kind.is_none() && args.name.is_none() {
+        return run_sync(&args);
+    }
+    if args.prune {
+        anyhow::bail!("--prune can only be used with a bare `sift install`");
+    }
+
     // Non-interactive mode requires kind and name
     let kind = args
         .kind
@@ -386,11 +555,140 @@ fn run_install(args: InstallArgs) -> Result<()> {
     Ok(())
 }
 
+fn run_sync(args: &InstallArgs) -> Result<()> {
+    let options = SyncOptions::new()
+        .with_force(args.force)
+        .with_prune(args.prune);
+    let cmd = SyncCommand::with_defaults()?;
+    let report = cmd.execute(&options)?;
+
+    print_sync_result(args.format, &report)?;
+
+    if report.has_errors() {
+        match args.format {
+            OutputFormat::Quiet => std::process::exit(1),
+            _ => anyhow::bail!("Sync completed with errors"),
+        }
+    }
+    Ok(())
+}
+
+fn print_sync_result(format: OutputFormat, report: &SyncReport) -> Result<()> {
+    match format {
+        OutputFormat::Table => {
+            if !report.pruned.is_empty() {
+                let noun = if report.pruned.len() == 1 {
+                    "entry"
+                } else {
+                    "entries"
+                };
+                println!("Removing {} orphaned {}:", report.pruned.len(), noun);
+                for pruned in &report.pruned {
+                    println!("  - {} ({})", pruned.name, scope_short(&pruned.scope));
+                }
+            }
+            for warning in &report.warnings {
+                println!("⚠ {}", warning);
+            }
+            for entry in &report.entries {
+                let kind = entry.kind.as_str();
+                if let Some(error) = &entry.error {
+                    println!("✗ {} '{}': {}", kind, entry.name, error);
+                    continue;
+                }
+                let applied: Vec<_> = entry
+                    .clients
+                    .iter()
+                    .filter(|c| c.status == ClientInstallStatus::Applied)
+                    .map(|c| c.client_id.as_str())
+                    .collect();
+                if applied.is_empty() {
+                    println!(
+                        "• {} '{}' ({}) not deployed to any client",
+                        kind,
+                        entry.name,
+                        scope_short(&entry.scope)
+                    );
+                } else {
+                    println!(
+                        "✓ {} '{}' ({}) → {}",
+                        kind,
+                        entry.name,
+                        scope_short(&entry.scope),
+                        applied.join(", ")
+                    );
+                }
+                for client in &entry.clients {
+                    if client.status == ClientInstallStatus::Failed {
+                        println!(
+                            "  ⚠ {}: {}",
+                            client.client_id,
+                            client.message.as_deref().unwrap_or("failed")
+                        );
+                    }
+                }
+            }
+        }
+        OutputFormat::Json => {
+            let entries: Vec<_> = report
+                .entries
+                .iter()
+                .map(|e| {
+                    let clients: Vec<_> = e
+                        .clients
+                        .iter()
+                        .map(|c| {
+                            serde_json::json!({
+                                "client": c.client_id,
+                                "status": c.status.as_str(),
+                                "message": c.message,
+                            })
+                        })
+                        .collect();
+                    serde_json::json!({
+                        "name": e.name,
+                        "kind": e.kind.as_str(),
+                        "scope": e.scope.as_str(),
+                        "applied": e.applied(),
+                        "error": e.error,
+                        "clients": clients,
+                    })
+                })
+                .collect();
+            let pruned: Vec<_> = report
+                .pruned
+                .iter()
+                .map(|p| {
+                    serde_json::json!({
+                        "name": p.name,
+                        "kind": p.kind.as_str(),
+                        "scope": p.scope.as_str(),
+                    })
+                })
+                .collect();
+            let output = serde_json::json!({
+                "entries": entries,
+                "pruned": pruned,
+                "warnings": report.warnings,
+            });
+            println!("{}", serde_json::to_string_pretty(&output)?);
+        }
+        OutputFormat::Quiet => {
+            for entry in &report.entries {
+                if let Some(error) = &entry.error {
+                    println!("{}: {}", entry.name, error);
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
 fn run_install_interactive(args: InstallArgs) -> Result<()> {
     // Load context to get registries and client registry
     let ctx = InstallContext::with_defaults()?;
     let registries = ctx.registries()?;
-    let client_registry = ctx.client_registry();
+    let client_registry = ctx.client_registry()?;
 
     // Parse pre-filled options from CLI args
     let kind = args
@@ -485,17 +783,37 @@ fn print_install_result(
                 println!("  Applied to client configurations");
             }
 
+            for client in &report.clients {
+                if client.status == ClientInstallStatus::Applied {
+                    let scope = client.scope.as_ref().map(scope_short).unwrap_or("-");
+                    println!("    {} ({})", client.client_id, scope);
+                }
+            }
+
             for warning in &report.warnings {
                 println!("  ⚠ {}", warning);
             }
         }
         OutputFormat::Json => {
+            let clients: Vec<_> = report
+                .clients
+                .iter()
+                .map(|c| {
+                    serde_json::json!({
+                        "client": c.client_id,
+                        "status": c.status.as_str(),
+                        "scope": c.scope.as_ref().map(ConfigScope::as_str),
+                        "message": c.message,
+                    })
+                })
+                .collect();
             let output = serde_json::json!({
                 "name": report.name,
                 "kind": kind,
                 "changed": report.changed,
                 "applied": report.applied,
                 "warnings": report.warnings,
+                "clients": clients,
             });
             println!("{}", serde_json::to_string_pretty(&output)?);
         }
@@ -550,6 +868,544 @@ fn run_uninstall(
     Ok(())
 }
 
+fn run_upgrade(kind: Option<String>, name: Option<String>, format: OutputFormat) -> Result<()> {
+    let mut options = UpgradeOptions::new();
+    match kind.as_deref().map(str::to_lowercase).as_deref() {
+        Some("mcp") => options = options.with_kind(InstallTarget::Mcp),
+        Some("skill") => options = options.with_kind(InstallTarget::Skill),
+        // `sift upgrade <name>` upgrades a single entry of either kind
+        Some(_) if name.is_none() => options = options.with_name(kind.clone().unwrap_or_default()),
+        Some(other) => anyhow::bail!("Unknown upgrade type: {}. Use 'mcp' or 'skill'", other),
+        None => {}
+    }
+    if let Some(name) = name {
+        options = options.with_name(name);
+    }
+
+    let cmd = UpgradeCommand::with_defaults()?;
+    let report = cmd.execute(&options)?;
+
+    print_upgrade_result(format, &report)?;
+
+    if report.has_errors() {
+        match format {
+            OutputFormat::Quiet => std::process::exit(1),
+            _ => anyhow::bail!("Upgrade completed with errors"),
+        }
+    }
+    Ok(())
+}
+
+fn print_upgrade_result(format: OutputFormat, report: &UpgradeReport) -> Result<()> {
+    match format {
+        OutputFormat::Table => {
+            for warning in &report.warnings {
+                println!("⚠ {}", warning);
+            }
+            for entry in &report.entries {
+                let kind = entry.kind.as_str();
+                match entry.status {
+                    UpgradeStatus::Upgraded => {
+                        println!("✓ {} '{}': {} → {}", kind, entry.name, entry.from, entry.to)
+                    }
+                    UpgradeStatus::UpToDate => println!(
+                        "• {} '{}': {} (up to date, {})",
+                        kind, entry.name, entry.from, entry.constraint
+                    ),
+                    UpgradeStatus::Failed => println!(
+                        "✗ {} '{}': {}",
+                        kind,
+                        entry.name,
+                        entry.error.as_deref().unwrap_or("upgrade failed")
+                    ),
+                }
+            }
+            if report.entries.is_empty() && report.warnings.is_empty() {
+                println!("Nothing to upgrade");
+            }
+        }
+        OutputFormat::Json => {
+            let entries: Vec<_> = report
+                .entries
+                .iter()
+                .map(|e| {
+                    let clients: Vec<_> = e
+                        .clients
+                        .iter()
+                        .filter(|c| c.status == ClientInstallStatus::Applied)
+                        .map(|c| c.client_id.as_str())
+                        .collect();
+                    serde_json::json!({
+                        "name": e.name,
+                        "kind": e.kind.as_str(),
+                        "constraint": e.constraint,
+                        "from": e.from,
+                        "to": e.to,
+                        "status": match e.status {
+                            UpgradeStatus::Upgraded => "upgraded",
+                            UpgradeStatus::UpToDate => "up-to-date",
+                            UpgradeStatus::Failed => "failed",
+                        },
+                        "clients": clients,
+                        "error": e.error,
+                    })
+                })
+                .collect();
+            let output = serde_json::json!({
+                "entries": entries,
+                "warnings": report.warnings,
+            });
+            println!("{}", serde_json::to_string_pretty(&output)?);
+        }
+        OutputFormat::Quiet => {
+            for entry in &report.entries {
+                if let Some(error) = &entry.error {
+                    println!("{}: {}", entry.name, error);
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn run_outdated(kind: Option<String>, format: OutputFormat) -> Result<()> {
+    let options = match kind.as_deref().map(str::to_lowercase).as_deref() {
+        Some("mcp") => OutdatedOptions::new().with_kind(InstallTarget::Mcp),
+        Some("skill") => OutdatedOptions::new().with_kind(InstallTarget::Skill),
+        Some(other) => anyhow::bail!("Unknown outdated type: {}. Use 'mcp' or 'skill'", other),
+        None => OutdatedOptions::new(),
+    };
+
+    let cmd = OutdatedCommand::with_defaults()?;
+    let report = cmd.execute(&options)?;
+
+    print_outdated_result(format, &report)?;
+
+    if matches!(format, OutputFormat::Quiet) && (report.has_outdated() || report.has_errors()) {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+fn print_outdated_result(format: OutputFormat, report: &OutdatedReport) -> Result<()> {
+    match format {
+        OutputFormat::Table => {
+            for warning in &report.warnings {
+                println!("⚠ {}", warning);
+            }
+            let rows: Vec<_> = report
+                .entries
+                .iter()
+                .filter(|e| e.outdated && e.error.is_none())
+                .collect();
+            if !rows.is_empty() {
+                println!(
+                    "{:<24} {:<6} {:<22} {:<22} {:<22}",
+                    "NAME", "KIND", "CURRENT", "WANTED", "LATEST"
+                );
+                for entry in rows {
+                    println!(
+                        "{:<24} {:<6} {:<22} {:<22} {:<22}",
+                        entry.name,
+                        entry.kind.as_str(),
+                        entry.current,
+                        entry.wanted.as_deref().unwrap_or("-"),
+                        entry.latest.as_deref().unwrap_or("-"),
+                    );
+                    if let (Some(current), Some(latest)) =
+                        (&entry.registry_current, &entry.registry_latest)
+                        && current != latest
+                    {
+                        println!("  registry version: {} → {}", current, latest);
+                    }
+                }
+            }
+            for entry in &report.entries {
+                if let Some(error) = &entry.error {
+                    println!("✗ {} '{}': {}", entry.kind.as_str(), entry.name, error);
+                }
+            }
+            if !report.has_outdated() && !report.has_errors() {
+                println!("All entries are up to date");
+            }
+        }
+        OutputFormat::Json => {
+            let entries: Vec<_> = report
+                .entries
+                .iter()
+                .map(|e| {
+                    serde_json::json!({
+                        "name": e.name,
+                        "kind": e.kind.as_str(),
+                        "scope": e.scope.as_str(),
+                        "constraint": e.constraint,
+                        "current": e.current,
+                        "wanted": e.wanted,
+                        "latest": e.latest,
+                        "registry_current": e.registry_current,
+                        "registry_latest": e.registry_latest,
+                        "upgradable": e.upgradable,
+                        "outdated": e.outdated,
+                        "error": e.error,
+                    })
+                })
+                .collect();
+            let output = serde_json::json!({
+                "entries": entries,
+                "warnings": report.warnings,
+            });
+            println!("{}", serde_json::to_string_pretty(&output)?);
+        }
+        OutputFormat::Quiet => {
+            for entry in &report.entries {
+                if let Some(error) = &entry.error {
+                    println!("{}: {}", entry.name, error);
+                } else if entry.outdated {
+                    println!("{}", entry.name);
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn run_list(
+    kind: Option<String>,
+    scope: Option<String>,
+    client: Option<String>,
+    format: OutputFormat,
+) -> Result<()> {
+    let mut options = match kind.as_deref().map(str::to_lowercase).as_deref() {
+        Some("mcp") => ListOptions::new().with_kind(InstallTarget::Mcp),
+        Some("skill") => ListOptions::new().with_kind(InstallTarget::Skill),
+        Some(other) => anyhow::bail!("Unknown list type: {}. Use 'mcp' or 'skill'", other),
+        None => ListOptions::new(),
+    };
+    if let Some(scope) = scope {
+        options = options.with_scope(parse_scope(&scope)?);
+    }
+    if let Some(client) = client {
+        options = options.with_client(client);
+    }
+
+    let cmd = ListCommand::with_defaults()?;
+    let report = cmd.execute(&options)?;
+
+    print_list_result(format, &report)?;
+
+    let not_installed = report.entries.iter().any(|e| e.resolved_version.is_none());
+    if matches!(format, OutputFormat::Quiet) && not_installed {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+fn print_list_result(format: OutputFormat, report: &ListReport) -> Result<()> {
+    match format {
+        OutputFormat::Table => {
+            for warning in &report.warnings {
+                println!("⚠ {}", warning);
+            }
+            if report.entries.is_empty() {
+                println!("No MCP servers or skills declared.");
+                return Ok(());
+            }
+
+            println!(
+                "{:<20} {:<6} {:<8} {:<12} {:<20} {:<12} Source",
+                "Name", "Kind", "Scope", "Declared", "Resolved", "Runtime"
+            );
+            println!("{}", "-".repeat(100));
+            for entry in &report.entries {
+                let runtime = match (&entry.runtime, &entry.transport) {
+                    (Some(runtime), _) => runtime.as_str(),
+                    (None, Some(transport)) => transport.as_str(),
+                    (None, None) => "-",
+                };
+                println!(
+                    "{:<20} {:<6} {:<8} {:<12} {:<20} {:<12} {}",
+                    entry.name,
+                    entry.kind.as_str(),
+                    entry.scope.as_str(),
+                    entry.declared_version.as_deref().unwrap_or("-"),
+                    entry.resolved_version.as_deref().unwrap_or("not installed"),
+                    runtime,
+                    entry.source
+                );
+                if !entry.targets.is_empty() {
+                    println!("  targets: {}", entry.targets.join(", "));
+                }
+            }
+        }
+        OutputFormat::Json => {
+            let entries: Vec<_> = report
+                .entries
+                .iter()
+                .map(|e| {
+                    serde_json::json!({
+                        "name": e.name,
+                        "kind": e.kind.as_str(),
+                        "scope": e.scope.as_str(),
+                        "source": e.source,
+                        "declared_version": e.declared_version,
+                        "resolved_version": e.resolved_version,
+                        "runtime": e.runtime,
+                        "transport": e.transport,
+                        "targets": e.targets,
+                    })
+                })
+                .collect();
+            let output = serde_json::json!({
+                "entries": entries,
+                "warnings": report.warnings,
+            });
+            println!("{}", serde_json::to_string_pretty(&output)?);
+        }
+        OutputFormat::Quiet => {
+            for entry in &report.entries {
+                if entry.resolved_version.is_none() {
+                    println!("{}: not installed", entry.name);
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn run_doctor(offline: bool, format: OutputFormat) -> Result<()> {
+    let cmd = DoctorCommand::with_defaults()?;
+    let report = cmd.execute(&DoctorOptions::new().with_offline(offline))?;
+
+    print_doctor_result(format, &report)?;
+
+    if report.has_errors() {
+        match format {
+            OutputFormat::Quiet => std::process::exit(1),
+            _ => anyhow::bail!("Doctor found problems"),
+        }
+    }
+    Ok(())
+}
+
+fn print_doctor_result(format: OutputFormat, report: &DoctorReport) -> Result<()> {
+    match format {
+        OutputFormat::Table => {
+            let mut category = None;
+            for diagnostic in &report.diagnostics {
+                if category != Some(diagnostic.category) {
+                    category = Some(diagnostic.category);
+                    println!("{}:", diagnostic.category.as_str());
+                }
+                let marker = match diagnostic.severity {
+                    DiagnosticSeverity::Ok => "✓",
+                    DiagnosticSeverity::Warning => "⚠",
+                    DiagnosticSeverity::Error => "✗",
+                };
+                println!(
+                    "  {} {}: {}",
+                    marker, diagnostic.subject, diagnostic.message
+                );
+                if let Some(fix) = &diagnostic.fix {
+                    println!("      → {}", fix);
+                }
+            }
+        }
+        OutputFormat::Json => {
+            let diagnostics: Vec<_> = report
+                .diagnostics
+                .iter()
+                .map(|d| {
+                    serde_json::json!({
+                        "category": d.category.as_str(),
+                        "subject": d.subject,
+                        "severity": match d.severity {
+                            DiagnosticSeverity::Ok => "ok",
+                            DiagnosticSeverity::Warning => "warning",
+                            DiagnosticSeverity::Error => "error",
+                        },
+                        "message": d.message,
+                        "fix": d.fix,
+                    })
+                })
+                .collect();
+            let output = serde_json::json!({
+                "diagnostics": diagnostics,
+                "errors": report.has_errors(),
+                "warnings": report.has_warnings(),
+            });
+            println!("{}", serde_json::to_string_pretty(&output)?);
+        }
+        OutputFormat::Quiet => {
+            for diagnostic in &report.diagnostics {
+                if diagnostic.severity == DiagnosticSeverity::Error {
+                    println!(
+                        "{} {}: {}",
+                        diagnostic.category.as_str(),
+                        diagnostic.subject,
+                        diagnostic.message
+                    );
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn run_mcp(args: McpArgs) -> Result<()> {
+    match args.command {
+        McpSubcommand::Tools {
+            name,
+            timeout,
+            format,
+        } => {
+            let options = ToolsOptions::new(name).with_timeout(Duration::from_secs(timeout));
+            let report = ToolsCommand::with_defaults()?.execute(&options)?;
+            print_tools_result(format, &report)?;
+            if matches!(format, OutputFormat::Quiet) && report.has_mismatches() {
+                std::process::exit(1);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn print_tools_result(format: OutputFormat, report: &ToolsReport) -> Result<()> {
+    match format {
+        OutputFormat::Table => {
+            for warning in &report.warnings {
+                println!("⚠ {}", warning);
+            }
+            for entry in &report.entries {
+                let inventory = &entry.inventory;
+                let probe = &inventory.probe;
+                let server = match (&probe.server_name, &probe.server_version) {
+                    (Some(name), Some(version)) => format!("{} {}", name, version),
+                    (Some(name), None) => name.clone(),
+                    _ => "-".to_string(),
+                };
+                println!(
+                    "{} ({}, protocol {}, started in {}ms)",
+                    entry.name,
+                    server,
+                    probe.protocol_version.as_deref().unwrap_or("-"),
+                    probe.startup_ms
+                );
+
+                println!();
+                println!("Tools ({}):", inventory.tools.len());
+                for tool in &inventory.tools {
+                    let params: Vec<&str> = tool
+                        .input_schema
+                        .get("properties")
+                        .and_then(|p| p.as_object())
+                        .map(|p| p.keys().map(String::as_str).collect())
+                        .unwrap_or_default();
+                    println!(
+                        "  {:<32} {}",
+                        format!("{}({})", tool.name, params.join(", ")),
+                        tool.description.as_deref().unwrap_or("-")
+                    );
+                }
+                if !inventory.prompts.is_empty() {
+                    println!();
+                    println!("Prompts ({}):", inventory.prompts.len());
+                    for prompt in &inventory.prompts {
+                        println!(
+                            "  {:<32} {}",
+                            format!("{}({})", prompt.name, prompt.arguments.join(", ")),
+                            prompt.description.as_deref().unwrap_or("-")
+                        );
+                    }
+                }
+                if !inventory.resources.is_empty() {
+                    println!();
+                    println!("Resources ({}):", inventory.resources.len());
+                    for resource in &inventory.resources {
+                        let name = resource
+                            .name
+                            .as_deref()
+                            .or(resource.description.as_deref())
+                            .unwrap_or("-");
+                        match &resource.mime_type {
+                            Some(mime) => {
+                                println!("  {:<32} {} ({})", resource.uri, name, mime)
+                            }
+                            None => println!("  {:<32} {}", resource.uri, name),
+                        }
+                    }
+                }
+                if let Some(diff) = &entry.manifest {
+                    println!();
+                    let mismatches = diff.mismatches();
+                    if mismatches.is_empty() {
+                        println!("Manifest: ✓ matches manifest.json");
+                    } else {
+                        println!("Manifest:");
+                        for mismatch in mismatches {
+                            println!("  ⚠ {}", mismatch);
+                        }
+                    }
+                }
+            }
+        }
+        OutputFormat::Json => {
+            let output = serde_json::json!({
+                "schema_version": 1,
+                "servers": report.entries,
+                "warnings": report.warnings,
+            });
+            println!("{}", serde_json::to_string_pretty(&output)?);
+        }
+        OutputFormat::Quiet => {
+            for entry in &report.entries {
+                if let Some(diff) = &entry.manifest {
+                    for mismatch in diff.mismatches() {
+                        println!("{}: {}", entry.name, mismatch);
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn run_secret(args: SecretArgs) -> Result<()> {
+    let store = FileSecretStore::with_defaults()?;
+
+    match args.command {
+        SecretSubcommand::Set { name } => {
+            let value = if std::io::stdin().is_terminal() {
+                dialoguer::Password::new()
+                    .with_prompt(format!("Value for '{}'", name))
+                    .interact()?
+            } else {
+                let mut value = String::new();
+                std::io::stdin().read_to_string(&mut value)?;
+                value.trim_end_matches(['\r', '\n']).to_string()
+            };
+            store.set(&name, &value)?;
+            println!("Stored secret '{}' (use \"secret:file/{}\")", name, name);
+        }
+        SecretSubcommand::List => {
+            let names = store.names()?;
+            if names.is_empty() {
+                println!("No secrets stored");
+            }
+            for name in names {
+                println!("{}", name);
+            }
+        }
+        SecretSubcommand::Remove { name } => {
+            if store.remove(&name)? {
+                println!("Removed secret '{}'", name);
+            } else {
+                anyhow::bail!("Secret '{}' is not stored", name);
+            }
+        }
+    }
+    Ok(())
+}
+
 fn run_registry(args: RegistryArgs) -> Result<()> {
     let cmd = RegistryCommand::with_defaults()?;
 
@@ -699,11 +1555,7 @@ fn print_registry_table(entries: &[RegistryEntry]) {
             RegistryType::Sift => "sift",
             RegistryType::ClaudeMarketplace => "claude-marketplace",
         };
-        let scope_str = match entry.scope {
-            ConfigScope::Global => "global",
-            ConfigScope::PerProjectShared => "shared",
-            ConfigScope::PerProjectLocal => "local",
-        };
+        let scope_str = entry.scope.as_str();
         let source = entry
             .url
             .as_deref()
@@ -727,11 +1579,7 @@ fn print_registry_json(entries: &[RegistryEntry]) -> Result<()> {
                     RegistryType::Sift => "sift",
                     RegistryType::ClaudeMarketplace => "claude-marketplace",
                 },
-                "scope": match e.scope {
-                    ConfigScope::Global => "global",
-                    ConfigScope::PerProjectShared => "shared",
-                    ConfigScope::PerProjectLocal => "local",
-                },
+                "scope": e.scope.as_str(),
                 "url": e.url,
                 "source": e.source,
             })
@@ -784,6 +1632,7 @@ fn run_status(
     verify: bool,
     format: OutputFormat,
     verbose: bool,
+    probe: Option<Duration>,
 ) -> Result<()> {
     // Determine scope filter
     let scope_filter = if global {
@@ -803,13 +1652,38 @@ fn run_status(
     // Collect status using the command pattern
     let cmd = StatusCommand::with_defaults()?;
     let report = cmd.execute(&options)?;
+    let probes = match probe {
+        Some(timeout) => {
+            let mut probe_options = ProbeOptions::new().with_timeout(timeout);
+            if let Some(scope) = scope_filter {
+                probe_options = probe_options.with_scope(scope);
+            }
+            Some(ProbeCommand::with_defaults()?.execute(&probe_options)?)
+        }
+        None => None,
+    };
 
     // Output based on format
     match format {
-        OutputFormat::Table => print_table(&report, verbose),
-        OutputFormat::Json => print_json(&report)?,
+        OutputFormat::Table => {
+            print_table(&report, verbose);
+            if let Some(probes) = &probes {
+                print_probe_table(probes);
+            }
+        }
+        OutputFormat::Json => print_json(&report, probes.as_ref())?,
         OutputFormat::Quiet => {
-            let exit_code = print_quiet(&report);
+            let mut exit_code = print_quiet(&report);
+            if let Some(probes) = &probes
+                && probes.has_failures()
+            {
+                for entry in &probes.entries {
+                    if let Some(error) = &entry.error {
+                        println!("{}: {}", entry.name, error);
+                    }
+                }
+                exit_code = 1;
+            }
             if exit_code != 0 {
                 std::process::exit(exit_code);
             }
@@ -819,6 +1693,48 @@ fn run_status(
     Ok(())
 }
 
+fn print_probe_table(report: &ProbeReport) {
+    println!();
+    println!("MCP Probes:");
+    for warning in &report.warnings {
+        println!("  ⚠ {}", warning);
+    }
+    if report.entries.is_empty() {
+        println!("  (none)");
+        return;
+    }
+    println!(
+        "  {:<24} {:<8} {:<28} {:<12} {:>6} {:>9}",
+        "NAME", "RESULT", "SERVER", "PROTOCOL", "TOOLS", "STARTUP"
+    );
+    for entry in &report.entries {
+        match (&entry.result, &entry.error) {
+            (Some(result), _) => {
+                let server = match (&result.server_name, &result.server_version) {
+                    (Some(name), Some(version)) => format!("{} {}", name, version),
+                    (Some(name), None) => name.clone(),
+                    _ => "-".to_string(),
+                };
+                println!(
+                    "  {:<24} {:<8} {:<28} {:<12} {:>6} {:>7}ms",
+                    entry.name,
+                    "ok",
+                    server,
+                    result.protocol_version.as_deref().unwrap_or("-"),
+                    result.tool_count,
+                    result.startup_ms
+                );
+            }
+            (None, error) => {
+                println!("  {:<24} failed", entry.name);
+                for line in error.as_deref().unwrap_or("unknown error").lines() {
+                    println!("      {}", line);
+                }
+            }
+        }
+    }
+}
+
 fn parse_scope(s: &str) -> Result<ConfigScope> {
     match s.to_lowercase().as_str() {
         "global" | "g" => Ok(ConfigScope::Global),
@@ -866,6 +1782,13 @@ fn print_table(status: &SystemStatus, verbose: bool) {
         println!();
     }
 
+    // Clients
+    if verbose && !status.clients.is_empty() {
+        println!("Clients ({}):", status.clients.len());
+        print_clients(&status.clients);
+        println!();
+    }
+
     // Summary
     if status.mcp_servers.is_empty() && status.skills.is_empty() {
         println!("No MCP servers or skills configured.");
@@ -995,9 +1918,22 @@ fn print_skills_verbose(skills: &[SkillStatus]) {
     }
 }
 
-fn print_json(status: &SystemStatus) -> Result<()> {
+fn print_clients(clients: &[ClientStatus]) {
+    for client in clients {
+        let state = if !client.enabled {
+            "disabled"
+        } else if !client.installed {
+            "not installed"
+        } else {
+            "enabled"
+        };
+        println!("  {:<15} {}", truncate(&client.id, 15), state);
+    }
+}
+
+fn print_json(status: &SystemStatus, probes: Option<&ProbeReport>) -> Result<()> {
     // Wrap in versioned output
-    let output = serde_json::json!({
+    let mut output = serde_json::json!({
         "schema_version": 1,
         "project_root": status.project_root,
         "scope_filter": status.scope_filter,
@@ -1007,6 +1943,9 @@ fn print_json(status: &SystemStatus) -> Result<()> {
         "clients": status.clients,
         "summary": status.summary,
     });
+    if let Some(probes) = probes {
+        output["probes"] = serde_json::to_value(probes)?;
+    }
 
     println!("{}", serde_json::to_string_pretty(&output)?);
     Ok(())
@@ -1,10 +1,12 @@
 //! Amp client implementation.
 
+use std::collections::HashSet;
+
 use serde_json::{Map, Value, json};
 
 use crate::client::{
-    ClientAdapter, ClientCapabilities, ClientContext, ManagedJsonPlan, McpConfigFormat, PathRoot,
-    ScopeSupport, SkillDeliveryMode, SkillDeliveryPlan,
+    ClientAdapter, ClientCapabilities, ClientContext, EnvExpansion, ManagedJsonPlan,
+    McpConfigFormat, PathRoot, ScopeSupport, SkillDeliveryMode, SkillDeliveryPlan,
 };
 use crate::mcp::spec::{McpResolvedServer, McpTransport};
 use crate::types::ConfigScope;
@@ -40,11 +42,14 @@ impl ClientAdapter for AmpClient {
                 global_path: "~/.config/agents/skills".to_string(),
                 project_path: Some(".agents/skills".to_string()),
             },
-            mcp_config_format: McpConfigFormat::Generic,
+            mcp_config_format: McpConfigFormat::Jsonc,
             supported_transports: ["stdio", "http"]
                 .into_iter()
                 .map(|s| s.to_string())
                 .collect(),
+            supports_http_headers: true,
+            supported_oauth: HashSet::new(),
+            env_expansion: EnvExpansion::None,
         }
     }
 
@@ -61,14 +66,16 @@ impl ClientAdapter for AmpClient {
                 relative_path: ".config/amp/settings.json".into(),
                 config_path: vec!["amp.mcpServers".to_string()],
                 entries,
-                format: McpConfigFormat::Generic,
+                format: McpConfigFormat::Jsonc,
+                preserved_fields: Vec::new(),
             }),
             ConfigScope::PerProjectShared => Ok(ManagedJsonPlan {
                 root: PathRoot::Project,
                 relative_path: ".vscode/settings.json".into(),
                 config_path: vec!["amp.mcpServers".to_string()],
                 entries,
-                format: McpConfigFormat::Generic,
+                format: McpConfigFormat::Jsonc,
+                preserved_fields: Vec::new(),
             }),
             ConfigScope::PerProjectLocal => {
                 anyhow::bail!("Amp does not support local (per-project private) MCP configuration")
@@ -5,11 +5,12 @@ use std::path::PathBuf;
 use serde_json::{Map, Value, json};
 
 use crate::client::{
-    ClientAdapter, ClientCapabilities, ClientContext, ManagedJsonPlan, McpConfigFormat, PathRoot,
-    ScopeSupport, SkillDeliveryMode, SkillDeliveryPlan,
+    ClientAdapter, ClientCapabilities, ClientContext, EnvExpansion, ManagedJsonPlan,
+    McpConfigFormat, PathRoot, ScopeSupport, SkillDeliveryMode, SkillDeliveryPlan,
 };
 use crate::config::managed_json::{apply_managed_entries_in_field, apply_managed_entries_in_path};
 use crate::lockfile::LockfileService;
+use crate::mcp::OAuthConfig;
 use crate::mcp::spec::{McpResolvedServer, McpTransport};
 use crate::types::ConfigScope;
 
@@ -74,6 +75,12 @@ impl ClientAdapter for ClaudeCodeClient {
                 .into_iter()
                 .map(|s| s.to_string())
                 .collect(),
+            supports_http_headers: true,
+            supported_oauth: [OAuthConfig::CLIENT_ID, OAuthConfig::CALLBACK_PORT]
+                .into_iter()
+                .map(|s| s.to_string())
+                .collect(),
+            env_expansion: EnvExpansion::Braces,
         }
     }
 
@@ -91,6 +98,7 @@ impl ClientAdapter for ClaudeCodeClient {
                 config_path: vec!["mcpServers".to_string()],
                 entries,
                 format: McpConfigFormat::ClaudeCode,
+                preserved_fields: Vec::new(),
             }),
             ConfigScope::PerProjectShared => Ok(ManagedJsonPlan {
                 root: PathRoot::Project,
@@ -98,6 +106,7 @@ impl ClientAdapter for ClaudeCodeClient {
                 config_path: vec!["mcpServers".to_string()],
                 entries,
                 format: McpConfigFormat::ClaudeCode,
+                preserved_fields: Vec::new(),
             }),
             ConfigScope::PerProjectLocal => {
                 let project_key = ctx.project_root.to_string_lossy().to_string();
@@ -111,6 +120,7 @@ impl ClientAdapter for ClaudeCodeClient {
                     ],
                     entries,
                     format: McpConfigFormat::ClaudeCode,
+                    preserved_fields: Vec::new(),
                 })
             }
         }
@@ -259,10 +269,26 @@ fn render_server(server: &McpResolvedServer) -> anyhow::Result<Value> {
             "args": server.args.clone(),
             "env": server.env.clone(),
         })),
-        McpTransport::Http => Ok(json!({
-            "type": "http",
-            "url": server.url.clone().unwrap_or_default(),
-            "headers": server.headers.clone(),
-        })),
+        McpTransport::Http => {
+            let mut entry = json!({
+                "type": "http",
+                "url": server.url.clone().unwrap_or_default(),
+                "headers": server.headers.clone(),
+            });
+            // Claude Code requests the scopes the server advertises
+            if let Some(oauth) = &server.oauth {
+                let mut auth = Map::new();
+                if let Some(client_id) = &oauth.client_id {
+                    auth.insert("clientId".to_string(), json!(client_id));
+                }
+                if let Some(port) = oauth.callback_port {
+                    auth.insert("callbackPort".to_string(), json!(port));
+                }
+                if !auth.is_empty() {
+                    entry["oauth"] = Value::Object(auth);
+                }
+            }
+            Ok(entry)
+        }
     }
 }
@@ -0,0 +1,175 @@
+//! Claude Desktop client implementation.
+//!
+//! Claude Desktop reads MCP servers from `claude_desktop_config.json` in the
+//! per-user application data directory. It has no project configuration and
+//! no filesystem skills, and only launches stdio servers, so HTTP servers
+//! are bridged through `mcp-remote`.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use serde_json::{Map, Value, json};
+
+use crate::client::{
+    ClientAdapter, ClientCapabilities, ClientContext, EnvExpansion, ManagedJsonPlan,
+    McpConfigFormat, PathRoot, ScopeSupport, SkillDeliveryMode, SkillDeliveryPlan,
+};
+use crate::mcp::spec::{McpResolvedServer, McpTransport};
+use crate::types::ConfigScope;
+
+#[derive(Debug, Default)]
+pub struct ClaudeDesktopClient;
+
+impl ClaudeDesktopClient {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Location of `claude_desktop_config.json` relative to the home directory.
+    pub fn config_relative_path() -> PathBuf {
+        if cfg!(target_os = "macos") {
+            "Library/Application Support/Claude/claude_desktop_config.json".into()
+        } else if cfg!(target_os = "windows") {
+            "AppData/Roaming/Claude/claude_desktop_config.json".into()
+        } else {
+            ".config/Claude/claude_desktop_config.json".into()
+        }
+    }
+}
+
+impl ClientAdapter for ClaudeDesktopClient {
+    fn id(&self) -> &'static str {
+        "claude-desktop"
+    }
+
+    fn capabilities(&self) -> ClientCapabilities {
+        ClientCapabilities {
+            // Claude Desktop only has a per-user configuration
+            mcp: ScopeSupport {
+                global: true,
+                project: false,
+                local: false,
+            },
+            skills: ScopeSupport::default(),
+            supports_symlinked_skills: false,
+            skill_delivery: SkillDeliveryMode::None,
+            mcp_config_format: McpConfigFormat::ClaudeDesktop,
+            supported_transports: ["stdio"].into_iter().map(|s| s.to_string()).collect(),
+            // mcp-remote forwards headers for bridged HTTP servers
+            supports_http_headers: true,
+            supported_oauth: HashSet::new(),
+            env_expansion: EnvExpansion::None,
+        }
+    }
+
+    fn plan_mcp(
+        &self,
+        ctx: &ClientContext,
+        scope: ConfigScope,
+        servers: &[McpResolvedServer],
+    ) -> anyhow::Result<ManagedJsonPlan> {
+        match scope {
+            ConfigScope::Global => Ok(ManagedJsonPlan {
+                root: PathRoot::User,
+                relative_path: Self::config_relative_path(),
+                config_path: vec!["mcpServers".to_string()],
+                entries: build_mcp_entries(&ctx.home_dir, servers),
+                format: McpConfigFormat::ClaudeDesktop,
+                preserved_fields: Vec::new(),
+            }),
+            ConfigScope::PerProjectShared | ConfigScope::PerProjectLocal => {
+                anyhow::bail!("Claude Desktop only supports global MCP configuration")
+            }
+        }
+    }
+
+    fn plan_skill(
+        &self,
+        _ctx: &ClientContext,
+        _scope: ConfigScope,
+    ) -> anyhow::Result<SkillDeliveryPlan> {
+        anyhow::bail!("Claude Desktop does not support skills")
+    }
+}
+
+fn build_mcp_entries(home_dir: &Path, servers: &[McpResolvedServer]) -> Map<String, Value> {
+    let mut map = Map::new();
+    for server in servers {
+        map.insert(server.name.clone(), render_server(home_dir, server));
+    }
+    map
+}
+
+fn render_server(home_dir: &Path, server: &McpResolvedServer) -> Value {
+    match server.transport {
+        McpTransport::Stdio => {
+            let expand = |value: &str| expand_variables(value, home_dir);
+            let env: Map<String, Value> = server
+                .env
+                .iter()
+                .map(|(key, value)| (key.clone(), Value::String(expand(value))))
+                .collect();
+            json!({
+                "command": expand(server.command.as_deref().unwrap_or_default()),
+                "args": server.args.iter().map(|a| expand(a)).collect::<Vec<_>>(),
+                "env": env,
+            })
+        }
+        McpTransport::Http => render_remote(server),
+    }
+}
+
+/// Bridge an HTTP server through `mcp-remote`.
+///
+/// Header values go through env variables so they are not split on spaces
+/// when the desktop app builds the command line.
+fn render_remote(server: &McpResolvedServer) -> Value {
+    let mut args = vec![
+        "-y".to_string(),
+        "mcp-remote".to_string(),
+        server.url.clone().unwrap_or_default(),
+    ];
+    let mut env = Map::new();
+    let mut headers: Vec<_> = server.headers.iter().collect();
+    headers.sort();
+    for (name, value) in headers {
+        let var = format!(
+            "MCP_HEADER_{}",
+            name.to_uppercase()
+                .replace(|c: char| !c.is_ascii_alphanumeric(), "_")
+        );
+        args.push("--header".to_string());
+        args.push(format!("{}:${{{}}}", name, var));
+        env.insert(var, Value::String(value.clone()));
+    }
+    json!({
+        "command": "npx",
+        "args": args,
+        "env": env,
+    })
+}
+
+/// Expand MCPB manifest variables the desktop app would substitute for an
+/// installed extension.
+///
+/// `${user_config.KEY}` is resolved earlier from the bundle's manifest (see
+/// [`crate::mcp::expand`]); unknown variables are left untouched.
+fn expand_variables(value: &str, home_dir: &Path) -> String {
+    if !value.contains("${") {
+        return value.to_string();
+    }
+    let separator = std::path::MAIN_SEPARATOR.to_string();
+    value
+        .replace("${HOME}", &home_dir.to_string_lossy())
+        .replace("${DESKTOP}", &home_dir.join("Desktop").to_string_lossy())
+        .replace(
+            "${DOCUMENTS}",
+            &home_dir.join("Documents").to_string_lossy(),
+        )
+        .replace(
+            "${DOWNLOADS}",
+            &home_dir.join("Downloads").to_string_lossy(),
+        )
+        .replace("${pathSeparator}", &separator)
+        .replace("${/}", &separator)
+}
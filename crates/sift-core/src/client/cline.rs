@@ -0,0 +1,151 @@
+//! Cline client implementation.
+//!
+//! Cline is a VS Code extension that keeps its MCP servers in
+//! `cline_mcp_settings.json` in the extension's global storage. It has no
+//! project configuration.
+
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+use serde_json::{Map, Value, json};
+
+use crate::client::vscode::VsCodeClient;
+use crate::client::{
+    ClientAdapter, ClientCapabilities, ClientContext, EnvExpansion, ManagedJsonPlan,
+    McpConfigFormat, PathRoot, ScopeSupport, SkillDeliveryMode, SkillDeliveryPlan,
+};
+use crate::mcp::spec::{McpResolvedServer, McpTransport};
+use crate::types::ConfigScope;
+
+/// VS Code extension id of Cline
+const EXTENSION_ID: &str = "saoudrizwan.claude-dev";
+
+#[derive(Debug, Default)]
+pub struct ClineClient;
+
+impl ClineClient {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Location of `cline_mcp_settings.json` relative to the home directory.
+    pub fn settings_relative_path() -> PathBuf {
+        VsCodeClient::user_dir_relative_path()
+            .join("globalStorage")
+            .join(EXTENSION_ID)
+            .join("settings/cline_mcp_settings.json")
+    }
+}
+
+impl ClientAdapter for ClineClient {
+    fn id(&self) -> &'static str {
+        "cline"
+    }
+
+    fn capabilities(&self) -> ClientCapabilities {
+        ClientCapabilities {
+            // Cline only reads its extension global storage
+            mcp: ScopeSupport {
+                global: true,
+                project: false,
+                local: false,
+            },
+            skills: ScopeSupport::default(),
+            supports_symlinked_skills: false,
+            skill_delivery: SkillDeliveryMode::None,
+            mcp_config_format: McpConfigFormat::ClaudeDesktop,
+            supported_transports: ["stdio", "http"]
+                .into_iter()
+                .map(|s| s.to_string())
+                .collect(),
+            supports_http_headers: true,
+            supported_oauth: HashSet::new(),
+            env_expansion: EnvExpansion::None,
+        }
+    }
+
+    fn plan_mcp(
+        &self,
+        _ctx: &ClientContext,
+        scope: ConfigScope,
+        servers: &[McpResolvedServer],
+    ) -> anyhow::Result<ManagedJsonPlan> {
+        match scope {
+            ConfigScope::Global => Ok(ManagedJsonPlan {
+                root: PathRoot::User,
+                relative_path: Self::settings_relative_path(),
+                config_path: vec!["mcpServers".to_string()],
+                entries: build_mcp_entries(servers, "streamableHttp")?,
+                format: McpConfigFormat::ClaudeDesktop,
+                preserved_fields: preserved_fields(),
+            }),
+            ConfigScope::PerProjectShared | ConfigScope::PerProjectLocal => {
+                anyhow::bail!("Cline only supports global MCP configuration")
+            }
+        }
+    }
+
+    fn plan_skill(
+        &self,
+        _ctx: &ClientContext,
+        _scope: ConfigScope,
+    ) -> anyhow::Result<SkillDeliveryPlan> {
+        anyhow::bail!("Cline does not support skills")
+    }
+}
+
+/// Entry fields the extension's MCP panel toggles. Sift writes their initial
+/// values and then leaves them to the user. `alwaysAllow` is not one of them:
+/// it follows `always_allow` in sift.toml.
+pub(crate) fn preserved_fields() -> Vec<String> {
+    vec!["disabled".to_string()]
+}
+
+/// Render servers in the format shared by Cline and its fork Roo Code,
+/// which differ only in the `type` they give HTTP servers.
+pub(crate) fn build_mcp_entries(
+    servers: &[McpResolvedServer],
+    http_type: &str,
+) -> anyhow::Result<Map<String, Value>> {
+    let mut map = Map::new();
+    for server in servers {
+        map.insert(server.name.clone(), render_server(server, http_type)?);
+    }
+    Ok(map)
+}
+
+fn render_server(server: &McpResolvedServer, http_type: &str) -> anyhow::Result<Value> {
+    // Cline format:
+    // - stdio: { "command": "...", "args": [...], "env": {...}, "disabled": false, "alwaysAllow": [...] }
+    // - http: { "type": "<http_type>", "url": "...", "headers": {...}, "disabled": false, "alwaysAllow": [...] }
+    let mut obj = match server.transport {
+        McpTransport::Stdio => {
+            let mut obj = Map::new();
+            obj.insert(
+                "command".to_string(),
+                json!(server.command.clone().unwrap_or_default()),
+            );
+            obj.insert("args".to_string(), json!(server.args.clone()));
+            obj.insert("env".to_string(), json!(server.env.clone()));
+            obj
+        }
+        McpTransport::Http => {
+            let mut obj = Map::new();
+            obj.insert("type".to_string(), json!(http_type));
+            obj.insert(
+                "url".to_string(),
+                json!(server.url.clone().unwrap_or_default()),
+            );
+            if !server.headers.is_empty() {
+                obj.insert("headers".to_string(), json!(server.headers.clone()));
+            }
+            obj
+        }
+    };
+    obj.insert("disabled".to_string(), json!(false));
+    obj.insert(
+        "alwaysAllow".to_string(),
+        json!(server.always_allow.clone()),
+    );
+    Ok(Value::Object(obj))
+}
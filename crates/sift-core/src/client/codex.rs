@@ -2,11 +2,13 @@
 //!
 //! Codex uses TOML format for MCP configuration (~/.codex/config.toml).
 
+use std::collections::HashSet;
+
 use serde_json::{Map, Value, json};
 
 use crate::client::{
-    ClientAdapter, ClientCapabilities, ClientContext, ManagedJsonPlan, McpConfigFormat, PathRoot,
-    ScopeSupport, SkillDeliveryMode, SkillDeliveryPlan,
+    ClientAdapter, ClientCapabilities, ClientContext, EnvExpansion, ManagedJsonPlan,
+    McpConfigFormat, PathRoot, ScopeSupport, SkillDeliveryMode, SkillDeliveryPlan,
 };
 use crate::mcp::spec::{McpResolvedServer, McpTransport};
 use crate::types::ConfigScope;
@@ -49,6 +51,9 @@ impl ClientAdapter for CodexClient {
                 .into_iter()
                 .map(|s| s.to_string())
                 .collect(),
+            supports_http_headers: true,
+            supported_oauth: HashSet::new(),
+            env_expansion: EnvExpansion::None,
         }
     }
 
@@ -67,6 +72,7 @@ impl ClientAdapter for CodexClient {
                     config_path: vec!["mcp_servers".to_string()],
                     entries,
                     format: McpConfigFormat::Toml,
+                    preserved_fields: Vec::new(),
                 })
             }
             ConfigScope::PerProjectShared | ConfigScope::PerProjectLocal => {
@@ -0,0 +1,154 @@
+//! Cursor client implementation.
+//!
+//! Cursor reads MCP servers from `~/.cursor/mcp.json` (global) and
+//! `.cursor/mcp.json` (project), and skills from `.cursor/skills`.
+
+use serde_json::{Map, Value, json};
+
+use crate::client::{
+    ClientAdapter, ClientCapabilities, ClientContext, EnvExpansion, ManagedJsonPlan,
+    McpConfigFormat, PathRoot, ScopeSupport, SkillDeliveryMode, SkillDeliveryPlan,
+};
+use crate::mcp::OAuthConfig;
+use crate::mcp::spec::{McpResolvedServer, McpTransport};
+use crate::types::ConfigScope;
+
+#[derive(Debug, Default)]
+pub struct CursorClient;
+
+impl CursorClient {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl ClientAdapter for CursorClient {
+    fn id(&self) -> &'static str {
+        "cursor"
+    }
+
+    fn capabilities(&self) -> ClientCapabilities {
+        ClientCapabilities {
+            mcp: ScopeSupport {
+                global: true,
+                project: true,
+                local: false,
+            },
+            skills: ScopeSupport {
+                global: true,
+                project: true,
+                local: false,
+            },
+            supports_symlinked_skills: false,
+            skill_delivery: SkillDeliveryMode::Filesystem {
+                global_path: "~/.cursor/skills".to_string(),
+                project_path: Some(".cursor/skills".to_string()),
+            },
+            mcp_config_format: McpConfigFormat::ClaudeDesktop,
+            supported_transports: ["stdio", "http"]
+                .into_iter()
+                .map(|s| s.to_string())
+                .collect(),
+            supports_http_headers: true,
+            supported_oauth: [OAuthConfig::CLIENT_ID, OAuthConfig::SCOPES]
+                .into_iter()
+                .map(|s| s.to_string())
+                .collect(),
+            env_expansion: EnvExpansion::EnvPrefix,
+        }
+    }
+
+    fn plan_mcp(
+        &self,
+        _ctx: &ClientContext,
+        scope: ConfigScope,
+        servers: &[McpResolvedServer],
+    ) -> anyhow::Result<ManagedJsonPlan> {
+        let entries = build_mcp_entries(servers)?;
+        match scope {
+            ConfigScope::Global => Ok(ManagedJsonPlan {
+                root: PathRoot::User,
+                relative_path: ".cursor/mcp.json".into(),
+                config_path: vec!["mcpServers".to_string()],
+                entries,
+                format: McpConfigFormat::ClaudeDesktop,
+                preserved_fields: Vec::new(),
+            }),
+            ConfigScope::PerProjectShared => Ok(ManagedJsonPlan {
+                root: PathRoot::Project,
+                relative_path: ".cursor/mcp.json".into(),
+                config_path: vec!["mcpServers".to_string()],
+                entries,
+                format: McpConfigFormat::ClaudeDesktop,
+                preserved_fields: Vec::new(),
+            }),
+            ConfigScope::PerProjectLocal => {
+                anyhow::bail!(
+                    "Cursor does not support local (per-project private) MCP configuration"
+                )
+            }
+        }
+    }
+
+    fn plan_skill(
+        &self,
+        _ctx: &ClientContext,
+        scope: ConfigScope,
+    ) -> anyhow::Result<SkillDeliveryPlan> {
+        let root = match scope {
+            ConfigScope::Global => PathRoot::User,
+            ConfigScope::PerProjectLocal | ConfigScope::PerProjectShared => PathRoot::Project,
+        };
+
+        Ok(SkillDeliveryPlan {
+            root,
+            relative_path: ".cursor/skills".into(),
+            use_git_exclude: false,
+        })
+    }
+}
+
+fn build_mcp_entries(servers: &[McpResolvedServer]) -> anyhow::Result<Map<String, Value>> {
+    let mut map = Map::new();
+    for server in servers {
+        map.insert(server.name.clone(), render_server(server)?);
+    }
+    Ok(map)
+}
+
+fn render_server(server: &McpResolvedServer) -> anyhow::Result<Value> {
+    // Cursor format:
+    // - stdio: { "command": "...", "args": [...], "env": {...} }
+    // - http: { "url": "...", "headers": {...}, "auth": { "CLIENT_ID": "...", "scopes": [...] } }
+    match server.transport {
+        McpTransport::Stdio => Ok(json!({
+            "command": server.command.clone().unwrap_or_default(),
+            "args": server.args.clone(),
+            "env": server.env.clone(),
+        })),
+        McpTransport::Http => {
+            let mut obj = Map::new();
+            obj.insert(
+                "url".to_string(),
+                json!(server.url.clone().unwrap_or_default()),
+            );
+            if !server.headers.is_empty() {
+                obj.insert("headers".to_string(), json!(server.headers.clone()));
+            }
+            if let Some(oauth) = &server.oauth {
+                // Cursor's redirect is its own URL scheme, so there is no callback port
+                let mut auth = Map::new();
+                if let Some(client_id) = &oauth.client_id {
+                    auth.insert("CLIENT_ID".to_string(), json!(client_id));
+                }
+                if !oauth.scopes.is_empty() {
+                    auth.insert("scopes".to_string(), json!(oauth.scopes));
+                }
+                if !auth.is_empty() {
+                    obj.insert("auth".to_string(), Value::Object(auth));
+                }
+            }
+            Ok(Value::Object(obj))
+        }
+    }
+}
@@ -0,0 +1,374 @@
+//! User-defined client adapters declared in `sift.toml`.
+//!
+//! A `[clients.<id>]` table that names a config file turns into a
+//! [`CustomClient`], so in-house agents can be targeted without a built-in
+//! adapter:
+//!
+//! ```toml
+//! [clients.acme-agent]
+//! mcp_global = "~/.acme/mcp.json"
+//! mcp_project = ".acme/mcp.json"
+//! format = "json"
+//! config_path = ["servers"]
+//! skills_global = "~/.acme/skills"
+//!
+//! [clients.acme-agent.template]
+//! stdio = { cmd = "${command}", argv = "${args}", env = "${env}" }
+//! http = { endpoint = "${url}", headers = "${headers}" }
+//! ```
+//!
+//! Template strings that are exactly one placeholder are replaced by the
+//! JSON value (so `"${args}"` becomes an array); placeholders inside longer
+//! strings are replaced by their text. Object keys whose value is an unset
+//! OAuth setting (`${oauth_client_id}`, `${oauth_scopes}`,
+//! `${oauth_callback_port}`) are left out. An HTTP template without
+//! `${headers}` drops server headers with a warning.
+
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value, json};
+
+use crate::client::{
+    ClientAdapter, ClientCapabilities, ClientContext, EnvExpansion, ManagedJsonPlan,
+    McpConfigFormat, PathRoot, ScopeSupport, SkillDeliveryMode, SkillDeliveryPlan,
+};
+use crate::mcp::OAuthConfig;
+use crate::mcp::spec::{McpResolvedServer, McpTransport};
+use crate::types::ConfigScope;
+
+/// Shape of one server entry, per transport.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EntryTemplate {
+    /// Template for stdio servers (default: `{command, args, env}`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stdio: Option<Value>,
+
+    /// Template for HTTP servers (default: `{url, headers}`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub http: Option<Value>,
+}
+
+/// Declarative description of a custom client.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CustomClientSpec {
+    /// Global MCP config file, relative to the home directory
+    #[serde(default)]
+    pub mcp_global: Option<String>,
+
+    /// Project MCP config file, relative to the project root
+    #[serde(default)]
+    pub mcp_project: Option<String>,
+
+    /// Config file format: `Generic` (JSON), `Jsonc` or `Toml`
+    pub format: McpConfigFormat,
+
+    /// Path within the config file to the servers map
+    pub config_path: Vec<String>,
+
+    /// Entry shape
+    #[serde(default)]
+    pub template: EntryTemplate,
+
+    /// Global skill directory, relative to the home directory
+    #[serde(default)]
+    pub skills_global: Option<String>,
+
+    /// Project skill directory, relative to the project root
+    #[serde(default)]
+    pub skills_project: Option<String>,
+}
+
+impl CustomClientSpec {
+    /// Parse a `format` value from `sift.toml`.
+    pub fn parse_format(format: &str) -> anyhow::Result<McpConfigFormat> {
+        match format {
+            "json" => Ok(McpConfigFormat::Generic),
+            "jsonc" => Ok(McpConfigFormat::Jsonc),
+            "toml" => Ok(McpConfigFormat::Toml),
+            other => anyhow::bail!(
+                "Unsupported client config format '{}': expected json, jsonc or toml",
+                other
+            ),
+        }
+    }
+
+    /// Validate the declared paths.
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if self.config_path.is_empty() {
+            anyhow::bail!("config_path must name at least one key");
+        }
+        for path in [
+            &self.mcp_global,
+            &self.mcp_project,
+            &self.skills_global,
+            &self.skills_project,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            let relative = strip_home(path);
+            if relative.is_empty() || std::path::Path::new(relative).is_absolute() {
+                anyhow::bail!(
+                    "Client path '{}' must be relative to the home directory or project root",
+                    path
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Client adapter driven by a [`CustomClientSpec`].
+#[derive(Debug)]
+pub struct CustomClient {
+    id: String,
+    spec: CustomClientSpec,
+    capabilities: Option<ClientCapabilities>,
+}
+
+impl CustomClient {
+    pub fn new(id: impl Into<String>, spec: CustomClientSpec) -> Self {
+        Self {
+            id: id.into(),
+            spec,
+            capabilities: None,
+        }
+    }
+
+    /// Replace the capabilities derived from the declared paths.
+    pub fn with_capabilities(mut self, capabilities: ClientCapabilities) -> Self {
+        self.capabilities = Some(capabilities);
+        self
+    }
+
+    /// Whether the HTTP template has a place for `${headers}`.
+    fn http_headers(&self) -> bool {
+        self.spec
+            .template
+            .http
+            .as_ref()
+            .is_none_or(|template| template.to_string().contains("${headers}"))
+    }
+
+    /// OAuth settings the HTTP template has a placeholder for.
+    fn oauth_fields(&self) -> HashSet<String> {
+        let template = self
+            .spec
+            .template
+            .http
+            .as_ref()
+            .map(Value::to_string)
+            .unwrap_or_default();
+        [
+            OAuthConfig::CLIENT_ID,
+            OAuthConfig::SCOPES,
+            OAuthConfig::CALLBACK_PORT,
+        ]
+        .into_iter()
+        .filter(|field| template.contains(&format!("${{oauth_{}}}", field)))
+        .map(|field| field.to_string())
+        .collect()
+    }
+}
+
+impl ClientAdapter for CustomClient {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn capabilities(&self) -> ClientCapabilities {
+        if let Some(capabilities) = &self.capabilities {
+            return capabilities.clone();
+        }
+        let spec = &self.spec;
+        let skills = ScopeSupport {
+            global: spec.skills_global.is_some(),
+            project: spec.skills_project.is_some(),
+            local: false,
+        };
+        let skill_delivery = if skills.global || skills.project {
+            SkillDeliveryMode::Filesystem {
+                global_path: spec
+                    .skills_global
+                    .as_deref()
+                    .map(|path| format!("~/{}", strip_home(path)))
+                    .unwrap_or_default(),
+                project_path: spec.skills_project.clone(),
+            }
+        } else {
+            SkillDeliveryMode::None
+        };
+        ClientCapabilities {
+            mcp: ScopeSupport {
+                global: spec.mcp_global.is_some(),
+                project: spec.mcp_project.is_some(),
+                local: false,
+            },
+            skills,
+            supports_symlinked_skills: false,
+            skill_delivery,
+            mcp_config_format: spec.format.clone(),
+            supported_transports: ["stdio", "http"]
+                .into_iter()
+                .map(|s| s.to_string())
+                .collect(),
+            supports_http_headers: self.http_headers(),
+            supported_oauth: self.oauth_fields(),
+            env_expansion: EnvExpansion::None,
+        }
+    }
+
+    fn plan_mcp(
+        &self,
+        _ctx: &ClientContext,
+        scope: ConfigScope,
+        servers: &[McpResolvedServer],
+    ) -> anyhow::Result<ManagedJsonPlan> {
+        let (root, path) = match scope {
+            ConfigScope::Global => (PathRoot::User, self.spec.mcp_global.as_deref()),
+            ConfigScope::PerProjectShared => (PathRoot::Project, self.spec.mcp_project.as_deref()),
+            ConfigScope::PerProjectLocal => (PathRoot::Project, None),
+        };
+        let Some(path) = path else {
+            anyhow::bail!(
+                "Client '{}' has no MCP configuration for {:?} scope",
+                self.id,
+                scope
+            );
+        };
+
+        Ok(ManagedJsonPlan {
+            root,
+            relative_path: strip_home(path).into(),
+            config_path: self.spec.config_path.clone(),
+            entries: build_mcp_entries(&self.spec.template, servers),
+            format: self.spec.format.clone(),
+            preserved_fields: Vec::new(),
+        })
+    }
+
+    fn plan_skill(
+        &self,
+        _ctx: &ClientContext,
+        scope: ConfigScope,
+    ) -> anyhow::Result<SkillDeliveryPlan> {
+        let (root, path) = match scope {
+            ConfigScope::Global => (PathRoot::User, self.spec.skills_global.as_deref()),
+            ConfigScope::PerProjectShared => {
+                (PathRoot::Project, self.spec.skills_project.as_deref())
+            }
+            ConfigScope::PerProjectLocal => (PathRoot::Project, None),
+        };
+        let Some(path) = path else {
+            anyhow::bail!(
+                "Client '{}' has no skill directory for {:?} scope",
+                self.id,
+                scope
+            );
+        };
+
+        Ok(SkillDeliveryPlan {
+            root,
+            relative_path: strip_home(path).into(),
+            use_git_exclude: false,
+        })
+    }
+}
+
+/// Drop a leading `~/` so paths can be written either way in `sift.toml`.
+fn strip_home(path: &str) -> &str {
+    path.strip_prefix("~/").unwrap_or(path)
+}
+
+fn build_mcp_entries(
+    template: &EntryTemplate,
+    servers: &[McpResolvedServer],
+) -> Map<String, Value> {
+    let mut map = Map::new();
+    for server in servers {
+        map.insert(server.name.clone(), render_server(template, server));
+    }
+    map
+}
+
+fn render_server(template: &EntryTemplate, server: &McpResolvedServer) -> Value {
+    let (template, default) = match server.transport {
+        McpTransport::Stdio => (
+            &template.stdio,
+            json!({"command": "${command}", "args": "${args}", "env": "${env}"}),
+        ),
+        McpTransport::Http => (
+            &template.http,
+            json!({"url": "${url}", "headers": "${headers}"}),
+        ),
+    };
+    let oauth = server.oauth.as_ref();
+    let variables = json!({
+        "name": server.name,
+        "command": server.command.clone().unwrap_or_default(),
+        "args": server.args,
+        "env": server.env,
+        "url": server.url.clone().unwrap_or_default(),
+        "headers": server.headers,
+        "always_allow": server.always_allow,
+        "oauth_client_id": oauth.and_then(|o| o.client_id.clone()),
+        "oauth_scopes": oauth.map(|o| o.scopes.clone()).filter(|s| !s.is_empty()),
+        "oauth_callback_port": oauth.and_then(|o| o.callback_port),
+    });
+    substitute(template.as_ref().unwrap_or(&default), &variables)
+}
+
+/// Replace `${variable}` placeholders throughout a template value.
+fn substitute(template: &Value, variables: &Value) -> Value {
+    match template {
+        Value::String(text) => {
+            let whole = text
+                .strip_prefix("${")
+                .and_then(|rest| rest.strip_suffix('}'))
+                .and_then(|key| variables.get(key));
+            if let Some(value) = whole {
+                return value.clone();
+            }
+            let mut rendered = text.clone();
+            for (key, value) in variables.as_object().into_iter().flatten() {
+                let placeholder = format!("${{{}}}", key);
+                if rendered.contains(&placeholder) {
+                    let text = match value {
+                        Value::String(s) => s.clone(),
+                        other => other.to_string(),
+                    };
+                    rendered = rendered.replace(&placeholder, &text);
+                }
+            }
+            Value::String(rendered)
+        }
+        Value::Array(items) => Value::Array(
+            items
+                .iter()
+                .map(|item| substitute(item, variables))
+                .collect(),
+        ),
+        Value::Object(entries) => Value::Object(
+            entries
+                .iter()
+                .filter_map(|(key, value)| {
+                    let rendered = substitute(value, variables);
+                    if rendered.is_null() && is_oauth_placeholder(value) {
+                        return None;
+                    }
+                    Some((key.clone(), rendered))
+                })
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
+/// Whether a template value is exactly one `${oauth_*}` placeholder.
+fn is_oauth_placeholder(template: &Value) -> bool {
+    template
+        .as_str()
+        .and_then(|text| text.strip_prefix("${oauth_"))
+        .is_some_and(|rest| rest.ends_with('}'))
+}
@@ -1,10 +1,12 @@
 //! Droid (Factory) client implementation.
 
+use std::collections::HashSet;
+
 use serde_json::{Map, Value, json};
 
 use crate::client::{
-    ClientAdapter, ClientCapabilities, ClientContext, ManagedJsonPlan, McpConfigFormat, PathRoot,
-    ScopeSupport, SkillDeliveryMode, SkillDeliveryPlan,
+    ClientAdapter, ClientCapabilities, ClientContext, EnvExpansion, ManagedJsonPlan,
+    McpConfigFormat, PathRoot, ScopeSupport, SkillDeliveryMode, SkillDeliveryPlan,
 };
 use crate::mcp::spec::{McpResolvedServer, McpTransport};
 use crate::types::ConfigScope;
@@ -46,6 +48,9 @@ impl ClientAdapter for DroidClient {
                 .into_iter()
                 .map(|s| s.to_string())
                 .collect(),
+            supports_http_headers: true,
+            supported_oauth: HashSet::new(),
+            env_expansion: EnvExpansion::None,
         }
     }
 
@@ -63,6 +68,7 @@ impl ClientAdapter for DroidClient {
                 config_path: vec!["mcpServers".to_string()],
                 entries,
                 format: McpConfigFormat::ClaudeDesktop,
+                preserved_fields: Vec::new(),
             }),
             ConfigScope::PerProjectShared => Ok(ManagedJsonPlan {
                 root: PathRoot::Project,
@@ -70,6 +76,7 @@ impl ClientAdapter for DroidClient {
                 config_path: vec!["mcpServers".to_string()],
                 entries,
                 format: McpConfigFormat::ClaudeDesktop,
+                preserved_fields: Vec::new(),
             }),
             ConfigScope::PerProjectLocal => {
                 anyhow::bail!(
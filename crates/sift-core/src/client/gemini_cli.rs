@@ -3,9 +3,10 @@
 use serde_json::{Map, Value, json};
 
 use crate::client::{
-    ClientAdapter, ClientCapabilities, ClientContext, ManagedJsonPlan, McpConfigFormat, PathRoot,
-    ScopeSupport, SkillDeliveryMode, SkillDeliveryPlan,
+    ClientAdapter, ClientCapabilities, ClientContext, EnvExpansion, ManagedJsonPlan,
+    McpConfigFormat, PathRoot, ScopeSupport, SkillDeliveryMode, SkillDeliveryPlan,
 };
+use crate::mcp::OAuthConfig;
 use crate::mcp::spec::{McpResolvedServer, McpTransport};
 use crate::types::ConfigScope;
 
@@ -45,6 +46,16 @@ impl ClientAdapter for GeminiCliClient {
                 .into_iter()
                 .map(|s| s.to_string())
                 .collect(),
+            supports_http_headers: true,
+            supported_oauth: [
+                OAuthConfig::CLIENT_ID,
+                OAuthConfig::SCOPES,
+                OAuthConfig::CALLBACK_PORT,
+            ]
+            .into_iter()
+            .map(|s| s.to_string())
+            .collect(),
+            env_expansion: EnvExpansion::None,
         }
     }
 
@@ -62,6 +73,7 @@ impl ClientAdapter for GeminiCliClient {
                 config_path: vec!["mcpServers".to_string()],
                 entries,
                 format: McpConfigFormat::Generic,
+                preserved_fields: Vec::new(),
             }),
             ConfigScope::PerProjectShared => Ok(ManagedJsonPlan {
                 root: PathRoot::Project,
@@ -69,6 +81,7 @@ impl ClientAdapter for GeminiCliClient {
                 config_path: vec!["mcpServers".to_string()],
                 entries,
                 format: McpConfigFormat::Generic,
+                preserved_fields: Vec::new(),
             }),
             ConfigScope::PerProjectLocal => {
                 anyhow::bail!(
@@ -112,7 +125,7 @@ fn build_mcp_entries(servers: &[McpResolvedServer]) -> anyhow::Result<Map<String
 fn render_server(server: &McpResolvedServer) -> anyhow::Result<Value> {
     // Gemini CLI format:
     // - stdio: { "command": "...", "args": [...], "env": {...} }
-    // - http: { "httpUrl": "...", "headers": {...} }
+    // - http: { "httpUrl": "...", "headers": {...}, "oauth": {...} }
     match server.transport {
         McpTransport::Stdio => Ok(json!({
             "command": server.command.clone().unwrap_or_default(),
@@ -129,6 +142,23 @@ fn render_server(server: &McpResolvedServer) -> anyhow::Result<Value> {
             if !server.headers.is_empty() {
                 obj.insert("headers".to_string(), json!(server.headers.clone()));
             }
+            if let Some(oauth) = &server.oauth {
+                let mut auth = serde_json::Map::new();
+                auth.insert("enabled".to_string(), json!(true));
+                if let Some(client_id) = &oauth.client_id {
+                    auth.insert("clientId".to_string(), json!(client_id));
+                }
+                if !oauth.scopes.is_empty() {
+                    auth.insert("scopes".to_string(), json!(oauth.scopes));
+                }
+                if let Some(port) = oauth.callback_port {
+                    auth.insert(
+                        "redirectUri".to_string(),
+                        json!(format!("http://localhost:{}/oauth/callback", port)),
+                    );
+                }
+                obj.insert("oauth".to_string(), Value::Object(auth));
+            }
             Ok(Value::Object(obj))
         }
     }
@@ -4,20 +4,29 @@
 
 use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value};
-use std::collections::HashSet;
-use std::path::PathBuf;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 
+use crate::config::ConfigStore;
 use crate::mcp::spec::McpResolvedServer;
+use crate::secrets::SecretProviders;
 use crate::types::ConfigScope;
 
 pub mod amp;
 pub mod claude_code;
+pub mod claude_desktop;
+pub mod cline;
 pub mod codex;
+pub mod cursor;
+pub mod custom;
 pub mod droid;
 pub mod gemini_cli;
 pub mod opencode;
 pub mod registry;
+pub mod roo_code;
 pub mod vscode;
+pub mod windsurf;
+pub mod zed;
 
 /// Client configuration from sift.toml
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -26,19 +35,34 @@ pub struct ClientConfig {
     #[serde(default = "default_enabled")]
     pub enabled: bool,
 
-    /// Source for external providers: "registry:some-provider"
+    /// Reserved for clients provided by a registry; not supported yet, so
+    /// [`ClientConfig::validate`] rejects it
     #[serde(default)]
     pub source: Option<String>,
 
     /// Capabilities (optional, provided by client implementations)
     #[serde(default)]
     pub capabilities: Option<ClientCapabilities>,
+
+    /// Declarative adapter for a client Sift has no built-in support for
+    #[serde(default)]
+    pub custom: Option<custom::CustomClientSpec>,
+
+    /// User profiles to deliver to, for clients that have them (VS Code)
+    #[serde(default)]
+    pub profiles: Vec<String>,
 }
 
 #[derive(Debug, Clone)]
 pub struct ClientContext {
     pub home_dir: PathBuf,
     pub project_root: PathBuf,
+    /// Project-local values for `${VAR}` references
+    pub variables: HashMap<String, String>,
+    /// Providers for `secret:` references
+    pub secrets: SecretProviders,
+    /// Whether resolved secrets may be written to shared project configs
+    pub allow_shared_secrets: bool,
 }
 
 impl ClientContext {
@@ -46,8 +70,51 @@ impl ClientContext {
         Self {
             home_dir,
             project_root,
+            variables: HashMap::new(),
+            secrets: SecretProviders::new(),
+            allow_shared_secrets: false,
         }
     }
+
+    /// Context for `project_root` with its variables and secret opt-in from
+    /// the global config in `global_config_dir`, and the default secret
+    /// providers.
+    pub fn for_project(home_dir: PathBuf, project_root: PathBuf, global_config_dir: &Path) -> Self {
+        // A global config that fails to load is reported by the command itself
+        let project = ConfigStore::from_paths(
+            ConfigScope::Global,
+            global_config_dir.to_path_buf(),
+            project_root.clone(),
+        )
+        .load()
+        .ok()
+        .and_then(|config| {
+            config
+                .get_project_config(&project_root)
+                .map(|(_, project)| project.clone())
+        })
+        .unwrap_or_default();
+        Self::new(home_dir, project_root)
+            .with_variables(project.vars)
+            .with_secrets(SecretProviders::with_defaults(global_config_dir))
+            .with_shared_secrets(project.allow_shared_secrets)
+    }
+
+    pub fn with_variables(mut self, variables: HashMap<String, String>) -> Self {
+        self.variables = variables;
+        self
+    }
+
+    pub fn with_secrets(mut self, secrets: SecretProviders) -> Self {
+        self.secrets = secrets;
+        self
+    }
+
+    /// Allow resolved `secret:` references in shared project configs.
+    pub fn with_shared_secrets(mut self, allow: bool) -> Self {
+        self.allow_shared_secrets = allow;
+        self
+    }
 }
 
 fn default_enabled() -> bool {
@@ -64,15 +131,24 @@ impl ClientConfig {
         if other.capabilities.is_some() {
             self.capabilities = other.capabilities;
         }
+        if other.custom.is_some() {
+            self.custom = other.custom;
+        }
+        if !other.profiles.is_empty() {
+            self.profiles = other.profiles;
+        }
     }
 
     /// Validate the configuration
     pub fn validate(&self) -> anyhow::Result<()> {
-        if let Some(ref source) = self.source
-            && !source.starts_with("registry:")
-            && !source.starts_with("local:")
-        {
-            anyhow::bail!("Invalid client source format: must be 'registry:name' or 'local:/path'");
+        if let Some(ref source) = self.source {
+            anyhow::bail!(
+                "Client source '{}' is not supported; declare the client's config paths instead",
+                source
+            );
+        }
+        if let Some(ref custom) = self.custom {
+            custom.validate()?;
         }
         Ok(())
     }
@@ -103,6 +179,41 @@ pub struct ClientCapabilities {
     /// Supported transport types
     #[serde(default)]
     pub supported_transports: HashSet<String>,
+
+    /// Whether HTTP servers can carry static headers; headers are dropped
+    /// with a warning otherwise
+    #[serde(default)]
+    pub supports_http_headers: bool,
+
+    /// OAuth settings HTTP servers can carry ("client_id", "scopes",
+    /// "callback_port"); empty if the client has no place for them.
+    ///
+    /// This is the auth side of the `"http"` entry in
+    /// `supported_transports`. It is a separate set because clients support
+    /// these settings one by one, so unsupported ones can be named in
+    /// warnings.
+    #[serde(default)]
+    pub supported_oauth: HashSet<String>,
+
+    /// How the client expands `${VAR}` references in its MCP config
+    #[serde(default)]
+    pub env_expansion: EnvExpansion,
+}
+
+/// Environment variable syntax a client expands when it starts a server.
+///
+/// References in `sift.toml` use `${VAR}`; they are passed through in the
+/// client's own syntax, or resolved by Sift for clients that expand nothing.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EnvExpansion {
+    /// The client writes values as-is; Sift resolves references
+    #[default]
+    None,
+    /// `${VAR}` (Claude Code)
+    Braces,
+    /// `${env:VAR}` (VS Code, Cursor)
+    EnvPrefix,
 }
 
 fn default_mcp_config_format() -> McpConfigFormat {
@@ -135,6 +246,10 @@ pub enum McpConfigFormat {
     ClaudeCode,
     /// TOML format (e.g., Codex's config.toml)
     Toml,
+    /// Zed settings format: { "context_servers": {...} }
+    Zed,
+    /// JSON with comments (e.g., VS Code settings.json)
+    Jsonc,
     /// Generic JSON format
     Generic,
 }
@@ -153,6 +268,9 @@ pub struct ManagedConfigPlan {
     pub config_path: Vec<String>,
     pub entries: Map<String, Value>,
     pub format: McpConfigFormat,
+    /// Entry fields the client's own UI changes (e.g. Cline's `disabled`).
+    /// Updates keep their current values and ownership hashes ignore them.
+    pub preserved_fields: Vec<String>,
 }
 
 /// Deprecated alias for backward compatibility.
@@ -181,7 +299,7 @@ pub trait Client: Send + Sync {
 }
 
 pub trait ClientAdapter: Send + Sync + std::fmt::Debug {
-    fn id(&self) -> &'static str;
+    fn id(&self) -> &str;
     fn capabilities(&self) -> ClientCapabilities;
 
     fn plan_mcp(
@@ -191,6 +309,19 @@ pub trait ClientAdapter: Send + Sync + std::fmt::Debug {
         servers: &[McpResolvedServer],
     ) -> anyhow::Result<ManagedJsonPlan>;
 
+    /// Plan MCP delivery to every config file the client reads at `scope`.
+    ///
+    /// Most clients read a single file; clients with several user profiles
+    /// return one plan per selected profile.
+    fn plan_mcp_all(
+        &self,
+        ctx: &ClientContext,
+        scope: ConfigScope,
+        servers: &[McpResolvedServer],
+    ) -> anyhow::Result<Vec<ManagedJsonPlan>> {
+        Ok(vec![self.plan_mcp(ctx, scope, servers)?])
+    }
+
     fn plan_skill(
         &self,
         ctx: &ClientContext,
@@ -1,10 +1,12 @@
 //! OpenCode client implementation.
 
+use std::collections::HashSet;
+
 use serde_json::{Map, Value, json};
 
 use crate::client::{
-    ClientAdapter, ClientCapabilities, ClientContext, ManagedJsonPlan, McpConfigFormat, PathRoot,
-    ScopeSupport, SkillDeliveryMode, SkillDeliveryPlan,
+    ClientAdapter, ClientCapabilities, ClientContext, EnvExpansion, ManagedJsonPlan,
+    McpConfigFormat, PathRoot, ScopeSupport, SkillDeliveryMode, SkillDeliveryPlan,
 };
 use crate::mcp::spec::{McpResolvedServer, McpTransport};
 use crate::types::ConfigScope;
@@ -45,6 +47,9 @@ impl ClientAdapter for OpenCodeClient {
                 .into_iter()
                 .map(|s| s.to_string())
                 .collect(),
+            supports_http_headers: true,
+            supported_oauth: HashSet::new(),
+            env_expansion: EnvExpansion::None,
         }
     }
 
@@ -67,6 +72,7 @@ impl ClientAdapter for OpenCodeClient {
                 config_path: vec!["mcp".to_string()],
                 entries,
                 format: McpConfigFormat::Generic,
+                preserved_fields: Vec::new(),
             }),
             ConfigScope::PerProjectLocal => {
                 anyhow::bail!(
@@ -3,20 +3,30 @@
 //! The registry provides a central place to discover and filter clients
 //! based on their capabilities and scope support.
 
+use std::collections::{HashMap, HashSet};
+
+use anyhow::Context;
+
+use crate::config::ClientConfigEntry;
 use crate::types::ConfigScope;
 
 use super::{
-    ClientAdapter, amp::AmpClient, claude_code::ClaudeCodeClient, codex::CodexClient,
-    droid::DroidClient, gemini_cli::GeminiCliClient, opencode::OpenCodeClient,
-    vscode::VsCodeClient,
+    ClientAdapter, ClientConfig, amp::AmpClient, claude_code::ClaudeCodeClient,
+    claude_desktop::ClaudeDesktopClient, cline::ClineClient, codex::CodexClient,
+    cursor::CursorClient, custom::CustomClient, droid::DroidClient, gemini_cli::GeminiCliClient,
+    opencode::OpenCodeClient, roo_code::RooCodeClient, vscode::VsCodeClient,
+    windsurf::WindsurfClient, zed::ZedClient,
 };
 
 /// Registry of available client adapters.
 ///
 /// Holds all known clients and provides filtering based on scope support.
+/// Clients disabled in sift.toml stay registered so they can be reported,
+/// but are left out of the scope queries used for delivery.
 #[derive(Debug)]
 pub struct ClientRegistry {
     clients: Vec<Box<dyn ClientAdapter>>,
+    disabled: HashSet<String>,
 }
 
 impl Default for ClientRegistry {
@@ -30,6 +40,7 @@ impl ClientRegistry {
     pub fn new() -> Self {
         Self {
             clients: Vec::new(),
+            disabled: HashSet::new(),
         }
     }
 
@@ -37,19 +48,81 @@ impl ClientRegistry {
     pub fn with_default_clients() -> Self {
         let clients: Vec<Box<dyn ClientAdapter>> = vec![
             Box::new(ClaudeCodeClient::new()),
+            Box::new(ClaudeDesktopClient::new()),
+            Box::new(CursorClient::new()),
             Box::new(VsCodeClient::new()),
             Box::new(AmpClient::new()),
             Box::new(CodexClient::new()),
             Box::new(DroidClient::new()),
             Box::new(GeminiCliClient::new()),
             Box::new(OpenCodeClient::new()),
+            Box::new(WindsurfClient::new()),
+            Box::new(ZedClient::new()),
+            Box::new(ClineClient::new()),
+            Box::new(RooCodeClient::new()),
         ];
-        Self { clients }
+        Self {
+            clients,
+            disabled: HashSet::new(),
+        }
+    }
+
+    /// Create a registry with all default clients plus the custom clients
+    /// declared under `[clients.<id>]` in sift.toml.
+    ///
+    /// A custom client with the ID of a built-in one replaces it. Clients
+    /// with `enabled = false` are marked disabled, and `profiles` selects
+    /// the VS Code profiles that receive global MCP servers.
+    pub fn with_config_clients(
+        clients: &HashMap<String, ClientConfigEntry>,
+    ) -> anyhow::Result<Self> {
+        let mut registry = Self::with_default_clients();
+        let mut ids: Vec<&String> = clients.keys().collect();
+        ids.sort();
+        for id in ids {
+            if !clients[id].enabled {
+                registry.disable(id);
+            }
+            let config: ClientConfig = clients[id]
+                .clone()
+                .try_into()
+                .with_context(|| format!("Invalid client configuration: '{}'", id))?;
+            config
+                .validate()
+                .with_context(|| format!("Invalid client configuration: '{}'", id))?;
+            if id == "vscode" && !config.profiles.is_empty() && config.custom.is_none() {
+                registry.register(Box::new(
+                    VsCodeClient::new().with_profiles(config.profiles.clone()),
+                ));
+            }
+            let Some(spec) = config.custom else {
+                continue;
+            };
+            let mut client = CustomClient::new(id.clone(), spec);
+            if let Some(capabilities) = config.capabilities {
+                client = client.with_capabilities(capabilities);
+            }
+            registry.register(Box::new(client));
+        }
+        Ok(registry)
     }
 
-    /// Register a client adapter.
+    /// Register a client adapter, replacing any client with the same ID.
     pub fn register(&mut self, client: Box<dyn ClientAdapter>) {
-        self.clients.push(client);
+        match self.clients.iter().position(|c| c.id() == client.id()) {
+            Some(index) => self.clients[index] = client,
+            None => self.clients.push(client),
+        }
+    }
+
+    /// Mark a client as disabled.
+    pub fn disable(&mut self, id: &str) {
+        self.disabled.insert(id.to_string());
+    }
+
+    /// Whether a client may receive deliveries.
+    pub fn is_enabled(&self, id: &str) -> bool {
+        !self.disabled.contains(id)
     }
 
     /// Get all registered clients.
@@ -65,19 +138,21 @@ impl ClientRegistry {
             .map(|c| c.as_ref())
     }
 
-    /// Get clients that support MCP at the given scope.
+    /// Get enabled clients that support MCP at the given scope.
     pub fn mcp_clients_for_scope(&self, scope: ConfigScope) -> Vec<&dyn ClientAdapter> {
         self.clients
             .iter()
+            .filter(|c| self.is_enabled(c.id()))
             .filter(|c| scope_supported(&c.capabilities().mcp, scope))
             .map(|c| c.as_ref())
             .collect()
     }
 
-    /// Get clients that support skills at the given scope.
+    /// Get enabled clients that support skills at the given scope.
     pub fn skill_clients_for_scope(&self, scope: ConfigScope) -> Vec<&dyn ClientAdapter> {
         self.clients
             .iter()
+            .filter(|c| self.is_enabled(c.id()))
             .filter(|c| scope_supported(&c.capabilities().skills, scope))
             .map(|c| c.as_ref())
             .collect()
@@ -122,7 +197,7 @@ impl ClientRegistry {
     }
 
     /// List all client IDs.
-    pub fn client_ids(&self) -> Vec<&'static str> {
+    pub fn client_ids(&self) -> Vec<&str> {
         self.clients.iter().map(|c| c.id()).collect()
     }
 }
@@ -145,13 +220,19 @@ mod tests {
         let ids = registry.client_ids();
 
         assert!(ids.contains(&"claude-code"));
+        assert!(ids.contains(&"claude-desktop"));
+        assert!(ids.contains(&"cursor"));
         assert!(ids.contains(&"vscode"));
         assert!(ids.contains(&"amp"));
         assert!(ids.contains(&"codex"));
         assert!(ids.contains(&"droid"));
         assert!(ids.contains(&"gemini-cli"));
         assert!(ids.contains(&"opencode"));
-        assert_eq!(ids.len(), 7);
+        assert!(ids.contains(&"windsurf"));
+        assert!(ids.contains(&"zed"));
+        assert!(ids.contains(&"cline"));
+        assert!(ids.contains(&"roo-code"));
+        assert_eq!(ids.len(), 13);
     }
 
     #[test]
@@ -174,7 +255,11 @@ mod tests {
 
         // These clients support global MCP
         assert!(ids.contains(&"claude-code"));
+        assert!(ids.contains(&"claude-desktop"));
         assert!(ids.contains(&"codex"));
+        assert!(ids.contains(&"windsurf"));
+        assert!(ids.contains(&"cline"));
+        assert!(ids.contains(&"roo-code"));
         assert!(ids.contains(&"amp"));
         assert!(ids.contains(&"droid"));
         assert!(ids.contains(&"gemini-cli"));
@@ -189,13 +274,19 @@ mod tests {
 
         // These clients support project MCP
         assert!(ids.contains(&"claude-code"));
+        assert!(ids.contains(&"cursor"));
         assert!(ids.contains(&"vscode"));
         assert!(ids.contains(&"amp"));
         assert!(ids.contains(&"droid"));
         assert!(ids.contains(&"gemini-cli"));
         assert!(ids.contains(&"opencode"));
-        // codex only supports global
+        assert!(ids.contains(&"zed"));
+        assert!(ids.contains(&"roo-code"));
+        // codex, claude-desktop, windsurf and cline only support global
         assert!(!ids.contains(&"codex"));
+        assert!(!ids.contains(&"claude-desktop"));
+        assert!(!ids.contains(&"windsurf"));
+        assert!(!ids.contains(&"cline"));
     }
 
     #[test]
@@ -223,6 +314,9 @@ mod tests {
         assert!(ids.contains(&"droid"));
         assert!(ids.contains(&"gemini-cli"));
         assert!(ids.contains(&"opencode"));
+        // Claude Desktop and Zed have no filesystem skills
+        assert!(!ids.contains(&"claude-desktop"));
+        assert!(!ids.contains(&"zed"));
     }
 
     #[test]
@@ -268,7 +362,7 @@ mod tests {
         let ids: Vec<_> = clients.iter().map(|c| c.id()).collect();
 
         assert!(!ids.contains(&"claude-code"));
-        assert!(ids.len() == 6);
+        assert!(ids.len() == 12);
     }
 
     #[test]
@@ -276,7 +370,7 @@ mod tests {
         let registry = ClientRegistry::with_default_clients();
         let clients = registry.applicable_clients(None, None);
 
-        assert_eq!(clients.len(), 7);
+        assert_eq!(clients.len(), 13);
     }
 
     #[test]
@@ -292,6 +386,117 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_register_replaces_client_with_same_id() {
+        let mut registry = ClientRegistry::with_default_clients();
+
+        registry.register(Box::new(ClaudeCodeClient::new()));
+
+        assert_eq!(registry.all().len(), 13);
+    }
+
+    #[test]
+    fn test_config_clients_registered() {
+        let config: crate::config::SiftConfig = toml::from_str(
+            r#"
+[clients.acme]
+mcp_global = "~/.acme/mcp.json"
+format = "toml"
+config_path = ["servers"]
+
+[clients.cursor]
+mcp_project = ".cursor/agents.json"
+
+[clients.claude-code]
+enabled = false
+"#,
+        )
+        .unwrap();
+
+        let registry = ClientRegistry::with_config_clients(&config.clients).unwrap();
+
+        assert_eq!(registry.all().len(), 14);
+        let acme = registry.get("acme").expect("custom client registered");
+        assert!(acme.capabilities().mcp.global);
+        assert_eq!(
+            acme.capabilities().mcp_config_format,
+            super::super::McpConfigFormat::Toml
+        );
+        // A custom client shadows the built-in adapter with the same ID
+        let cursor = registry.get("cursor").expect("cursor registered");
+        assert!(!cursor.capabilities().mcp.global);
+        // Disabled clients stay registered but receive no deliveries
+        assert!(registry.get("claude-code").is_some());
+        assert!(!registry.is_enabled("claude-code"));
+        let ids: Vec<_> = registry
+            .mcp_clients_for_scope(ConfigScope::PerProjectShared)
+            .iter()
+            .map(|c| c.id())
+            .collect();
+        assert!(!ids.contains(&"claude-code"));
+        assert!(ids.contains(&"vscode"));
+    }
+
+    #[test]
+    fn test_config_clients_select_vscode_profiles() {
+        let config: crate::config::SiftConfig =
+            toml::from_str("[clients.vscode]\nprofiles = [\"Work\"]\n").unwrap();
+
+        let registry = ClientRegistry::with_config_clients(&config.clients).unwrap();
+
+        assert_eq!(registry.all().len(), 13);
+        let ctx = super::super::ClientContext::new(
+            std::path::PathBuf::from("/nonexistent/home"),
+            std::path::PathBuf::from("/work/project"),
+        );
+        let err = registry
+            .get("vscode")
+            .expect("vscode registered")
+            .plan_mcp_all(&ctx, ConfigScope::Global, &[])
+            .unwrap_err();
+        assert!(err.to_string().contains("'Work'"), "got: {err:#}");
+    }
+
+    #[test]
+    fn test_config_clients_reject_unknown_format() {
+        let config: crate::config::SiftConfig = toml::from_str(
+            r#"
+[clients.acme]
+mcp_global = ".acme/mcp.yaml"
+format = "yaml"
+"#,
+        )
+        .unwrap();
+
+        assert!(ClientRegistry::with_config_clients(&config.clients).is_err());
+    }
+
+    #[test]
+    fn test_config_clients_reject_absolute_paths() {
+        let config: crate::config::SiftConfig = toml::from_str(
+            r#"
+[clients.acme]
+mcp_global = "/etc/acme/mcp.json"
+"#,
+        )
+        .unwrap();
+
+        let err = ClientRegistry::with_config_clients(&config.clients).unwrap_err();
+        assert!(
+            format!("{err:#}").contains("/etc/acme/mcp.json"),
+            "got: {err:#}"
+        );
+    }
+
+    #[test]
+    fn test_config_clients_reject_source() {
+        let config: crate::config::SiftConfig =
+            toml::from_str("[clients.acme]\nsource = \"registry:acme\"\n").unwrap();
+
+        let err = ClientRegistry::with_config_clients(&config.clients).unwrap_err();
+        assert!(format!("{err:#}").contains("source"), "got: {err:#}");
+    }
+
     #[test]
     fn test_empty_registry() {
         let registry = ClientRegistry::new();
@@ -0,0 +1,98 @@
+//! Roo Code client implementation.
+//!
+//! Roo Code is a VS Code extension that keeps global MCP servers in
+//! `mcp_settings.json` in the extension's global storage, and project
+//! servers in `.roo/mcp.json`.
+
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+use crate::client::cline::{build_mcp_entries, preserved_fields};
+use crate::client::vscode::VsCodeClient;
+use crate::client::{
+    ClientAdapter, ClientCapabilities, ClientContext, EnvExpansion, ManagedJsonPlan,
+    McpConfigFormat, PathRoot, ScopeSupport, SkillDeliveryMode, SkillDeliveryPlan,
+};
+use crate::mcp::spec::McpResolvedServer;
+use crate::types::ConfigScope;
+
+/// VS Code extension id of Roo Code
+const EXTENSION_ID: &str = "rooveterinaryinc.roo-cline";
+
+#[derive(Debug, Default)]
+pub struct RooCodeClient;
+
+impl RooCodeClient {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Location of the global `mcp_settings.json` relative to the home directory.
+    pub fn settings_relative_path() -> PathBuf {
+        VsCodeClient::user_dir_relative_path()
+            .join("globalStorage")
+            .join(EXTENSION_ID)
+            .join("settings/mcp_settings.json")
+    }
+}
+
+impl ClientAdapter for RooCodeClient {
+    fn id(&self) -> &'static str {
+        "roo-code"
+    }
+
+    fn capabilities(&self) -> ClientCapabilities {
+        ClientCapabilities {
+            mcp: ScopeSupport {
+                global: true,
+                project: true,
+                local: false,
+            },
+            skills: ScopeSupport::default(),
+            supports_symlinked_skills: false,
+            skill_delivery: SkillDeliveryMode::None,
+            mcp_config_format: McpConfigFormat::ClaudeDesktop,
+            supported_transports: ["stdio", "http"]
+                .into_iter()
+                .map(|s| s.to_string())
+                .collect(),
+            supports_http_headers: true,
+            supported_oauth: HashSet::new(),
+            env_expansion: EnvExpansion::None,
+        }
+    }
+
+    fn plan_mcp(
+        &self,
+        _ctx: &ClientContext,
+        scope: ConfigScope,
+        servers: &[McpResolvedServer],
+    ) -> anyhow::Result<ManagedJsonPlan> {
+        let (root, relative_path) = match scope {
+            ConfigScope::Global => (PathRoot::User, Self::settings_relative_path()),
+            ConfigScope::PerProjectShared => (PathRoot::Project, ".roo/mcp.json".into()),
+            ConfigScope::PerProjectLocal => {
+                anyhow::bail!(
+                    "Roo Code does not support local (per-project private) MCP configuration"
+                )
+            }
+        };
+
+        Ok(ManagedJsonPlan {
+            root,
+            relative_path,
+            config_path: vec!["mcpServers".to_string()],
+            entries: build_mcp_entries(servers, "streamable-http")?,
+            format: McpConfigFormat::ClaudeDesktop,
+            preserved_fields: preserved_fields(),
+        })
+    }
+
+    fn plan_skill(
+        &self,
+        _ctx: &ClientContext,
+        _scope: ConfigScope,
+    ) -> anyhow::Result<SkillDeliveryPlan> {
+        anyhow::bail!("Roo Code does not support skills")
+    }
+}
@@ -1,20 +1,124 @@
 //! VS Code (GitHub Copilot) client implementation.
+//!
+//! Project MCP servers live in `.vscode/mcp.json`. User-level servers live in
+//! `mcp.json` in the profile's User directory: `Code/User` for the default
+//! profile and `Code/User/profiles/<location>` for named profiles, which are
+//! listed in `Code/User/globalStorage/storage.json`.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
 
 use serde_json::{Map, Value, json};
 
 use crate::client::{
-    ClientAdapter, ClientCapabilities, ClientContext, ManagedJsonPlan, McpConfigFormat, PathRoot,
-    ScopeSupport, SkillDeliveryMode, SkillDeliveryPlan,
+    ClientAdapter, ClientCapabilities, ClientContext, EnvExpansion, ManagedJsonPlan,
+    McpConfigFormat, PathRoot, ScopeSupport, SkillDeliveryMode, SkillDeliveryPlan,
 };
 use crate::mcp::spec::{McpResolvedServer, McpTransport};
 use crate::types::ConfigScope;
 
+/// Name VS Code gives the default profile
+pub const DEFAULT_PROFILE: &str = "Default";
+
+/// Profile selector matching every profile
+pub const ALL_PROFILES: &str = "*";
+
 #[derive(Debug, Default)]
-pub struct VsCodeClient;
+pub struct VsCodeClient {
+    /// Profiles that receive global MCP servers (default profile when empty)
+    profiles: Vec<String>,
+}
 
 impl VsCodeClient {
     pub fn new() -> Self {
-        Self
+        Self::default()
+    }
+
+    /// Deliver global MCP servers to these profiles, by name or location.
+    pub fn with_profiles(mut self, profiles: Vec<String>) -> Self {
+        self.profiles = profiles;
+        self
+    }
+
+    /// Named profiles as `(name, location)` pairs, read from VS Code's
+    /// `storage.json`. A missing or unreadable file means no named profiles.
+    pub fn named_profiles(home_dir: &Path) -> Vec<(String, String)> {
+        let storage = home_dir
+            .join(Self::user_dir_relative_path())
+            .join("globalStorage/storage.json");
+        let Ok(text) = std::fs::read_to_string(storage) else {
+            return Vec::new();
+        };
+        let Ok(storage) = serde_json::from_str::<Value>(&text) else {
+            return Vec::new();
+        };
+        storage["userDataProfiles"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|profile| {
+                Some((
+                    profile["name"].as_str()?.to_string(),
+                    profile["location"].as_str()?.to_string(),
+                ))
+            })
+            .collect()
+    }
+
+    /// User directories of the selected profiles, relative to the home directory.
+    fn profile_dirs(&self, home_dir: &Path) -> anyhow::Result<Vec<PathBuf>> {
+        let user_dir = Self::user_dir_relative_path();
+        let named = Self::named_profiles(home_dir);
+        let default = [DEFAULT_PROFILE.to_string()];
+        let selected = if self.profiles.is_empty() {
+            &default[..]
+        } else {
+            &self.profiles[..]
+        };
+
+        let mut dirs = Vec::new();
+        let mut push = |dir: PathBuf| {
+            if !dirs.contains(&dir) {
+                dirs.push(dir);
+            }
+        };
+        for profile in selected {
+            if profile == DEFAULT_PROFILE {
+                push(user_dir.clone());
+            } else if profile == ALL_PROFILES {
+                push(user_dir.clone());
+                for (_, location) in &named {
+                    push(user_dir.join("profiles").join(location));
+                }
+            } else if let Some((_, location)) = named
+                .iter()
+                .find(|(name, location)| name == profile || location == profile)
+            {
+                push(user_dir.join("profiles").join(location));
+            } else {
+                let known: Vec<&str> = std::iter::once(DEFAULT_PROFILE)
+                    .chain(named.iter().map(|(name, _)| name.as_str()))
+                    .collect();
+                anyhow::bail!(
+                    "VS Code profile '{}' not found. Known profiles: {}",
+                    profile,
+                    known.join(", ")
+                );
+            }
+        }
+        Ok(dirs)
+    }
+
+    /// VS Code's per-user data directory (`Code/User`) relative to the home
+    /// directory. Extensions keep their global storage under it.
+    pub fn user_dir_relative_path() -> PathBuf {
+        if cfg!(target_os = "macos") {
+            "Library/Application Support/Code/User".into()
+        } else if cfg!(target_os = "windows") {
+            "AppData/Roaming/Code/User".into()
+        } else {
+            ".config/Code/User".into()
+        }
     }
 }
 
@@ -42,28 +146,29 @@ impl ClientAdapter for VsCodeClient {
                 // VS Code recommends .github/skills/ but also supports .claude/skills/
                 project_path: Some(".github/skills".to_string()),
             },
-            mcp_config_format: McpConfigFormat::Generic,
+            mcp_config_format: McpConfigFormat::Jsonc,
             supported_transports: ["stdio", "http"]
                 .into_iter()
                 .map(|s| s.to_string())
                 .collect(),
+            supports_http_headers: true,
+            supported_oauth: HashSet::new(),
+            env_expansion: EnvExpansion::EnvPrefix,
         }
     }
 
     fn plan_mcp(
         &self,
-        _ctx: &ClientContext,
+        ctx: &ClientContext,
         scope: ConfigScope,
         servers: &[McpResolvedServer],
     ) -> anyhow::Result<ManagedJsonPlan> {
         let entries = build_mcp_entries(servers)?;
         match scope {
             ConfigScope::Global => {
-                // VS Code user-level MCP config is in user profile settings
-                // This is typically managed through VS Code UI, not direct file editing
-                anyhow::bail!(
-                    "VS Code global MCP configuration is managed through VS Code profile settings"
-                )
+                // The first selected profile; see plan_mcp_all for the rest
+                let mut plans = self.plan_mcp_all(ctx, scope, servers)?;
+                Ok(plans.remove(0))
             }
             ConfigScope::PerProjectShared => Ok(ManagedJsonPlan {
                 root: PathRoot::Project,
@@ -71,7 +176,8 @@ impl ClientAdapter for VsCodeClient {
                 // VS Code uses "servers" instead of "mcpServers"
                 config_path: vec!["servers".to_string()],
                 entries,
-                format: McpConfigFormat::Generic,
+                format: McpConfigFormat::Jsonc,
+                preserved_fields: Vec::new(),
             }),
             ConfigScope::PerProjectLocal => {
                 anyhow::bail!(
@@ -81,6 +187,30 @@ impl ClientAdapter for VsCodeClient {
         }
     }
 
+    fn plan_mcp_all(
+        &self,
+        ctx: &ClientContext,
+        scope: ConfigScope,
+        servers: &[McpResolvedServer],
+    ) -> anyhow::Result<Vec<ManagedJsonPlan>> {
+        if scope != ConfigScope::Global {
+            return Ok(vec![self.plan_mcp(ctx, scope, servers)?]);
+        }
+        let entries = build_mcp_entries(servers)?;
+        Ok(self
+            .profile_dirs(&ctx.home_dir)?
+            .into_iter()
+            .map(|dir| ManagedJsonPlan {
+                root: PathRoot::User,
+                relative_path: dir.join("mcp.json"),
+                config_path: vec!["servers".to_string()],
+                entries: entries.clone(),
+                format: McpConfigFormat::Jsonc,
+                preserved_fields: Vec::new(),
+            })
+            .collect())
+    }
+
     fn plan_skill(
         &self,
         _ctx: &ClientContext,
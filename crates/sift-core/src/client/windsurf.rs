@@ -0,0 +1,116 @@
+//! Windsurf client implementation.
+//!
+//! Windsurf (Codeium) only has a per-user MCP configuration at
+//! `~/.codeium/windsurf/mcp_config.json`.
+
+use std::collections::HashSet;
+
+use serde_json::{Map, Value, json};
+
+use crate::client::{
+    ClientAdapter, ClientCapabilities, ClientContext, EnvExpansion, ManagedJsonPlan,
+    McpConfigFormat, PathRoot, ScopeSupport, SkillDeliveryMode, SkillDeliveryPlan,
+};
+use crate::mcp::spec::{McpResolvedServer, McpTransport};
+use crate::types::ConfigScope;
+
+#[derive(Debug, Default)]
+pub struct WindsurfClient;
+
+impl WindsurfClient {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl ClientAdapter for WindsurfClient {
+    fn id(&self) -> &'static str {
+        "windsurf"
+    }
+
+    fn capabilities(&self) -> ClientCapabilities {
+        ClientCapabilities {
+            // Windsurf only supports global configuration
+            mcp: ScopeSupport {
+                global: true,
+                project: false,
+                local: false,
+            },
+            skills: ScopeSupport::default(),
+            supports_symlinked_skills: false,
+            skill_delivery: SkillDeliveryMode::None,
+            mcp_config_format: McpConfigFormat::ClaudeDesktop,
+            supported_transports: ["stdio", "http"]
+                .into_iter()
+                .map(|s| s.to_string())
+                .collect(),
+            supports_http_headers: true,
+            supported_oauth: HashSet::new(),
+            env_expansion: EnvExpansion::None,
+        }
+    }
+
+    fn plan_mcp(
+        &self,
+        _ctx: &ClientContext,
+        scope: ConfigScope,
+        servers: &[McpResolvedServer],
+    ) -> anyhow::Result<ManagedJsonPlan> {
+        match scope {
+            ConfigScope::Global => {
+                let entries = build_mcp_entries(servers)?;
+                Ok(ManagedJsonPlan {
+                    root: PathRoot::User,
+                    relative_path: ".codeium/windsurf/mcp_config.json".into(),
+                    config_path: vec!["mcpServers".to_string()],
+                    entries,
+                    format: McpConfigFormat::ClaudeDesktop,
+                    preserved_fields: Vec::new(),
+                })
+            }
+            ConfigScope::PerProjectShared | ConfigScope::PerProjectLocal => {
+                anyhow::bail!("Windsurf only supports global MCP configuration")
+            }
+        }
+    }
+
+    fn plan_skill(
+        &self,
+        _ctx: &ClientContext,
+        _scope: ConfigScope,
+    ) -> anyhow::Result<SkillDeliveryPlan> {
+        anyhow::bail!("Windsurf does not support skills")
+    }
+}
+
+fn build_mcp_entries(servers: &[McpResolvedServer]) -> anyhow::Result<Map<String, Value>> {
+    let mut map = Map::new();
+    for server in servers {
+        map.insert(server.name.clone(), render_server(server)?);
+    }
+    Ok(map)
+}
+
+fn render_server(server: &McpResolvedServer) -> anyhow::Result<Value> {
+    // Windsurf format:
+    // - stdio: { "command": "...", "args": [...], "env": {...} }
+    // - http: { "serverUrl": "...", "headers": {...} }
+    match server.transport {
+        McpTransport::Stdio => Ok(json!({
+            "command": server.command.clone().unwrap_or_default(),
+            "args": server.args.clone(),
+            "env": server.env.clone(),
+        })),
+        McpTransport::Http => {
+            let mut obj = Map::new();
+            obj.insert(
+                "serverUrl".to_string(),
+                json!(server.url.clone().unwrap_or_default()),
+            );
+            if !server.headers.is_empty() {
+                obj.insert("headers".to_string(), json!(server.headers.clone()));
+            }
+            Ok(Value::Object(obj))
+        }
+    }
+}
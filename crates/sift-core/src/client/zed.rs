@@ -0,0 +1,129 @@
+//! Zed client implementation.
+//!
+//! Zed reads MCP servers from `context_servers` in its settings files:
+//! `~/.config/zed/settings.json` (global) and `.zed/settings.json`
+//! (project). Settings files are JSON with comments and are edited in
+//! place so the user's comments survive.
+
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+use serde_json::{Map, Value, json};
+
+use crate::client::{
+    ClientAdapter, ClientCapabilities, ClientContext, EnvExpansion, ManagedJsonPlan,
+    McpConfigFormat, PathRoot, ScopeSupport, SkillDeliveryMode, SkillDeliveryPlan,
+};
+use crate::mcp::spec::{McpResolvedServer, McpTransport};
+use crate::types::ConfigScope;
+
+#[derive(Debug, Default)]
+pub struct ZedClient;
+
+impl ZedClient {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Location of the global `settings.json` relative to the home directory.
+    pub fn settings_relative_path() -> PathBuf {
+        if cfg!(target_os = "windows") {
+            "AppData/Roaming/Zed/settings.json".into()
+        } else {
+            ".config/zed/settings.json".into()
+        }
+    }
+}
+
+impl ClientAdapter for ZedClient {
+    fn id(&self) -> &'static str {
+        "zed"
+    }
+
+    fn capabilities(&self) -> ClientCapabilities {
+        ClientCapabilities {
+            mcp: ScopeSupport {
+                global: true,
+                project: true,
+                local: false,
+            },
+            skills: ScopeSupport::default(),
+            supports_symlinked_skills: false,
+            skill_delivery: SkillDeliveryMode::None,
+            mcp_config_format: McpConfigFormat::Zed,
+            supported_transports: ["stdio", "http"]
+                .into_iter()
+                .map(|s| s.to_string())
+                .collect(),
+            supports_http_headers: true,
+            supported_oauth: HashSet::new(),
+            env_expansion: EnvExpansion::None,
+        }
+    }
+
+    fn plan_mcp(
+        &self,
+        _ctx: &ClientContext,
+        scope: ConfigScope,
+        servers: &[McpResolvedServer],
+    ) -> anyhow::Result<ManagedJsonPlan> {
+        let (root, relative_path) = match scope {
+            ConfigScope::Global => (PathRoot::User, Self::settings_relative_path()),
+            ConfigScope::PerProjectShared => (PathRoot::Project, ".zed/settings.json".into()),
+            ConfigScope::PerProjectLocal => {
+                anyhow::bail!("Zed does not support local (per-project private) MCP configuration")
+            }
+        };
+
+        Ok(ManagedJsonPlan {
+            root,
+            relative_path,
+            config_path: vec!["context_servers".to_string()],
+            entries: build_mcp_entries(servers)?,
+            format: McpConfigFormat::Zed,
+            preserved_fields: Vec::new(),
+        })
+    }
+
+    fn plan_skill(
+        &self,
+        _ctx: &ClientContext,
+        _scope: ConfigScope,
+    ) -> anyhow::Result<SkillDeliveryPlan> {
+        anyhow::bail!("Zed does not support skills")
+    }
+}
+
+fn build_mcp_entries(servers: &[McpResolvedServer]) -> anyhow::Result<Map<String, Value>> {
+    let mut map = Map::new();
+    for server in servers {
+        map.insert(server.name.clone(), render_server(server)?);
+    }
+    Ok(map)
+}
+
+fn render_server(server: &McpResolvedServer) -> anyhow::Result<Value> {
+    // Zed format:
+    // - stdio: { "source": "custom", "command": "...", "args": [...], "env": {...} }
+    // - http: { "source": "custom", "url": "...", "headers": {...} }
+    match server.transport {
+        McpTransport::Stdio => Ok(json!({
+            "source": "custom",
+            "command": server.command.clone().unwrap_or_default(),
+            "args": server.args.clone(),
+            "env": server.env.clone(),
+        })),
+        McpTransport::Http => {
+            let mut obj = Map::new();
+            obj.insert("source".to_string(), json!("custom"));
+            obj.insert(
+                "url".to_string(),
+                json!(server.url.clone().unwrap_or_default()),
+            );
+            if !server.headers.is_empty() {
+                obj.insert("headers".to_string(), json!(server.headers.clone()));
+            }
+            Ok(Value::Object(obj))
+        }
+    }
+}
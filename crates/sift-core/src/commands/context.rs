@@ -235,14 +235,19 @@ impl InstallContext {
         ))
     }
 
-    /// Create a ClientRegistry with all default clients.
-    pub fn client_registry(&self) -> ClientRegistry {
-        ClientRegistry::with_default_clients()
+    /// Create a ClientRegistry with all default clients and the custom
+    /// clients declared in the merged config.
+    pub fn client_registry(&self) -> anyhow::Result<ClientRegistry> {
+        ClientRegistry::with_config_clients(&self.merged_config()?.clients)
     }
 
     /// Create a ClientContext for client adapters.
     pub fn client_context(&self) -> ClientContext {
-        ClientContext::new(self.home_dir.clone(), self.project_root.clone())
+        ClientContext::for_project(
+            self.home_dir.clone(),
+            self.project_root.clone(),
+            &self.global_config_dir,
+        )
     }
 }
 
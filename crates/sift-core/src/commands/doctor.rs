@@ -0,0 +1,833 @@
+//! Doctor command implementation.
+//!
+//! Checks the environment sift depends on — runtimes, git, client config
+//! paths, registries, the lockfile and the git cache — and reports each
+//! finding as a diagnostic with a suggested fix. Configuration is never
+//! changed.
+
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::client::registry::ClientRegistry;
+use crate::client::{PathRoot, SkillDeliveryMode};
+use crate::config::SiftConfig;
+use crate::context::AppContext;
+use crate::fs::LinkMode;
+use crate::git::{GitFetcher, GitSpec};
+use crate::lockfile::{Lockfile, LockfileStore};
+use crate::registry::marketplace::MarketplaceAdapter;
+use crate::registry::sift::SiftRegistryClient;
+use crate::registry::{RegistryConfig, RegistryType};
+use crate::types::ConfigScope;
+
+use super::context::InstallContext;
+
+/// Area of the environment a diagnostic belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagnosticCategory {
+    /// sift.toml could not be loaded
+    Config,
+    /// MCP server runtimes (node, bun, uv, python, docker)
+    Runtime,
+    /// git installation
+    Git,
+    /// Write access to client config and skill paths
+    Permissions,
+    /// Registry reachability and manifests
+    Registry,
+    /// Lockfile parsing and consistency with sift.toml
+    Lockfile,
+    /// Cached bare repositories in the state directory
+    Cache,
+}
+
+impl DiagnosticCategory {
+    /// Lowercase label used in CLI output
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DiagnosticCategory::Config => "config",
+            DiagnosticCategory::Runtime => "runtime",
+            DiagnosticCategory::Git => "git",
+            DiagnosticCategory::Permissions => "permissions",
+            DiagnosticCategory::Registry => "registry",
+            DiagnosticCategory::Lockfile => "lockfile",
+            DiagnosticCategory::Cache => "cache",
+        }
+    }
+}
+
+/// Outcome of a single check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum DiagnosticSeverity {
+    /// Check passed
+    Ok,
+    /// Something is missing or stale but sift can still work
+    Warning,
+    /// Something sift needs is broken
+    Error,
+}
+
+/// A single finding from `sift doctor`
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    /// Area of the environment checked
+    pub category: DiagnosticCategory,
+    /// What was checked (runtime name, path, registry key, entry name)
+    pub subject: String,
+    /// Outcome of the check
+    pub severity: DiagnosticSeverity,
+    /// What was found
+    pub message: String,
+    /// Suggested fix for warnings and errors
+    pub fix: Option<String>,
+}
+
+impl Diagnostic {
+    fn ok(category: DiagnosticCategory, subject: impl Into<String>, message: String) -> Self {
+        Self {
+            category,
+            subject: subject.into(),
+            severity: DiagnosticSeverity::Ok,
+            message,
+            fix: None,
+        }
+    }
+
+    fn warning(
+        category: DiagnosticCategory,
+        subject: impl Into<String>,
+        message: String,
+        fix: String,
+    ) -> Self {
+        Self {
+            category,
+            subject: subject.into(),
+            severity: DiagnosticSeverity::Warning,
+            message,
+            fix: Some(fix),
+        }
+    }
+
+    fn error(
+        category: DiagnosticCategory,
+        subject: impl Into<String>,
+        message: String,
+        fix: String,
+    ) -> Self {
+        Self {
+            category,
+            subject: subject.into(),
+            severity: DiagnosticSeverity::Error,
+            message,
+            fix: Some(fix),
+        }
+    }
+}
+
+/// Options for the doctor command
+#[derive(Debug, Clone, Default)]
+pub struct DoctorOptions {
+    /// Skip checks that need the network (registry reachability)
+    pub offline: bool,
+}
+
+impl DoctorOptions {
+    /// Run every check
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Skip registry reachability checks
+    pub fn with_offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+}
+
+/// Report from a doctor run
+#[derive(Debug, Clone, Default)]
+pub struct DoctorReport {
+    /// Diagnostics in the order the checks ran
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl DoctorReport {
+    /// Whether any check failed.
+    pub fn has_errors(&self) -> bool {
+        self.diagnostics
+            .iter()
+            .any(|d| d.severity == DiagnosticSeverity::Error)
+    }
+
+    /// Whether any check produced a warning.
+    pub fn has_warnings(&self) -> bool {
+        self.diagnostics
+            .iter()
+            .any(|d| d.severity == DiagnosticSeverity::Warning)
+    }
+
+    /// Diagnostics for one category.
+    pub fn category(&self, category: DiagnosticCategory) -> Vec<&Diagnostic> {
+        self.diagnostics
+            .iter()
+            .filter(|d| d.category == category)
+            .collect()
+    }
+}
+
+/// A runtime tool probed with `--version`
+struct RuntimeProbe {
+    name: &'static str,
+    /// Executables to try, in order
+    programs: &'static [&'static str],
+    /// `runtime = "..."` values in sift.toml that need this tool
+    runtimes: &'static [&'static str],
+    /// Other probes that can stand in for this one for the runtimes both list
+    alternatives: &'static [&'static str],
+    fix: &'static str,
+}
+
+const RUNTIME_PROBES: &[RuntimeProbe] = &[
+    RuntimeProbe {
+        name: "node",
+        programs: &["node"],
+        runtimes: &["node"],
+        alternatives: &[],
+        fix: "Install Node.js from https://nodejs.org, or use runtime = \"bun\"",
+    },
+    RuntimeProbe {
+        name: "npx",
+        programs: &["npx"],
+        runtimes: &["node", "npx"],
+        alternatives: &[],
+        fix: "Install Node.js from https://nodejs.org (npx ships with npm)",
+    },
+    RuntimeProbe {
+        name: "bun",
+        programs: &["bun"],
+        runtimes: &["bun", "bunx"],
+        alternatives: &[],
+        fix: "Install Bun from https://bun.sh",
+    },
+    RuntimeProbe {
+        name: "uv",
+        programs: &["uvx"],
+        runtimes: &["uv", "uvx", "python"],
+        alternatives: &["pipx"],
+        fix: "Install uv from https://docs.astral.sh/uv/ (python servers can also run through pipx)",
+    },
+    RuntimeProbe {
+        name: "pipx",
+        programs: &["pipx"],
+        runtimes: &["python"],
+        alternatives: &["uv"],
+        fix: "Install uv from https://docs.astral.sh/uv/, or pipx from https://pipx.pypa.io",
+    },
+    RuntimeProbe {
+        name: "docker",
+        programs: &["docker"],
+        runtimes: &["docker"],
+        alternatives: &[],
+        fix: "Install Docker from https://docs.docker.com/get-docker/",
+    },
+];
+
+/// Doctor command orchestrator
+pub struct DoctorCommand {
+    /// Shared context for dependency injection
+    ctx: InstallContext,
+}
+
+impl DoctorCommand {
+    /// Create a new doctor command with custom global config directory
+    pub fn with_global_config_dir(
+        home_dir: PathBuf,
+        project_root: PathBuf,
+        state_dir: PathBuf,
+        global_config_dir: PathBuf,
+        link_mode: LinkMode,
+    ) -> Self {
+        Self {
+            ctx: InstallContext::new(
+                home_dir,
+                project_root,
+                state_dir,
+                global_config_dir,
+                link_mode,
+            ),
+        }
+    }
+
+    /// Create a doctor command with default paths
+    pub fn with_defaults() -> anyhow::Result<Self> {
+        Ok(Self {
+            ctx: InstallContext::with_defaults()?,
+        })
+    }
+
+    /// Create from AppContext (preferred).
+    pub fn from_context(ctx: AppContext) -> Self {
+        Self {
+            ctx: InstallContext::from_app_context(ctx),
+        }
+    }
+
+    /// Run every check and collect the diagnostics.
+    ///
+    /// Individual check failures become diagnostics; this only errors if the
+    /// report itself cannot be produced.
+    pub fn execute(&self, options: &DoctorOptions) -> anyhow::Result<DoctorReport> {
+        let mut report = DoctorReport::default();
+
+        let config = match self.ctx.merged_config() {
+            Ok(config) => Some(config),
+            Err(err) => {
+                report.diagnostics.push(Diagnostic::error(
+                    DiagnosticCategory::Config,
+                    "sift.toml",
+                    format!("{:#}", err),
+                    "Fix the reported problem in sift.toml".to_string(),
+                ));
+                None
+            }
+        };
+
+        self.check_runtimes(config, &mut report);
+        self.check_git(config, &mut report);
+        self.check_permissions(&mut report);
+        if !options.offline {
+            self.check_registries(config, &mut report);
+        }
+        self.check_lockfile(config, &mut report);
+        self.check_bare_repos(config, &mut report);
+
+        Ok(report)
+    }
+
+    /// Probe each runtime; a missing runtime is an error only when a
+    /// declared MCP server needs it.
+    fn check_runtimes(&self, config: Option<&SiftConfig>, report: &mut DoctorReport) {
+        let mut required: BTreeMap<&str, BTreeSet<&str>> = BTreeMap::new();
+        if let Some(config) = config {
+            for (name, entry) in &config.mcp {
+                if entry.transport.as_deref().is_some_and(|t| t != "stdio") {
+                    continue;
+                }
+                if let Some(runtime) = entry.runtime.as_deref() {
+                    required.entry(runtime).or_default().insert(name.as_str());
+                }
+            }
+        }
+
+        let versions: Vec<Option<String>> = RUNTIME_PROBES
+            .iter()
+            .map(|probe| {
+                probe
+                    .programs
+                    .iter()
+                    .find_map(|program| probe_version(program))
+            })
+            .collect();
+        let available: BTreeSet<&str> = RUNTIME_PROBES
+            .iter()
+            .zip(&versions)
+            .filter(|(_, version)| version.is_some())
+            .map(|(probe, _)| probe.name)
+            .collect();
+
+        for (probe, found) in RUNTIME_PROBES.iter().zip(versions) {
+            let users = probe_users(probe, &required, &available);
+
+            let diagnostic = match found {
+                Some(version) => Diagnostic::ok(DiagnosticCategory::Runtime, probe.name, version),
+                None if users.is_empty() => Diagnostic::warning(
+                    DiagnosticCategory::Runtime,
+                    probe.name,
+                    "not found on PATH".to_string(),
+                    probe.fix.to_string(),
+                ),
+                None => Diagnostic::error(
+                    DiagnosticCategory::Runtime,
+                    probe.name,
+                    format!(
+                        "not found on PATH, required by {}",
+                        users.into_iter().collect::<Vec<_>>().join(", ")
+                    ),
+                    probe.fix.to_string(),
+                ),
+            };
+            report.diagnostics.push(diagnostic);
+        }
+    }
+
+    /// Git is required as soon as any skill is declared.
+    fn check_git(&self, config: Option<&SiftConfig>, report: &mut DoctorReport) {
+        let diagnostic = match GitFetcher::ensure_git_version() {
+            Ok(()) => Diagnostic::ok(
+                DiagnosticCategory::Git,
+                "git",
+                probe_version("git").unwrap_or_else(|| "git 2.25+".to_string()),
+            ),
+            Err(err) => {
+                let message = format!("{:#}", err);
+                let fix = "Install git 2.25 or newer from https://git-scm.com".to_string();
+                if config.is_some_and(|c| !c.skill.is_empty()) {
+                    Diagnostic::error(DiagnosticCategory::Git, "git", message, fix)
+                } else {
+                    Diagnostic::warning(DiagnosticCategory::Git, "git", message, fix)
+                }
+            }
+        };
+        report.diagnostics.push(diagnostic);
+    }
+
+    /// Check write access to every path a client adapter plans to touch,
+    /// plus the sift state directory.
+    fn check_permissions(&self, report: &mut DoctorReport) {
+        // An invalid config is reported by the config check
+        let registry = self
+            .ctx
+            .client_registry()
+            .unwrap_or_else(|_| ClientRegistry::with_default_clients());
+        let client_ctx = self.ctx.client_context();
+        let root_dir = |root: PathRoot| match root {
+            PathRoot::User => self.ctx.home_dir(),
+            PathRoot::Project => self.ctx.project_root(),
+        };
+
+        let mut paths: BTreeMap<PathBuf, BTreeSet<&str>> = BTreeMap::new();
+        paths
+            .entry(self.ctx.state_dir().to_path_buf())
+            .or_default()
+            .insert("sift");
+        for client in registry.all() {
+            let caps = client.capabilities();
+            for scope in [
+                ConfigScope::Global,
+                ConfigScope::PerProjectShared,
+                ConfigScope::PerProjectLocal,
+            ] {
+                if scope_supported(&caps.mcp, scope)
+                    && let Ok(plans) = client.plan_mcp_all(&client_ctx, scope, &[])
+                {
+                    for plan in plans {
+                        paths
+                            .entry(root_dir(plan.root).join(&plan.relative_path))
+                            .or_default()
+                            .insert(client.id());
+                    }
+                }
+                if scope_supported(&caps.skills, scope)
+                    && !matches!(caps.skill_delivery, SkillDeliveryMode::None)
+                    && let Ok(plan) = client.plan_skill(&client_ctx, scope)
+                {
+                    paths
+                        .entry(root_dir(plan.root).join(&plan.relative_path))
+                        .or_default()
+                        .insert(client.id());
+                }
+            }
+        }
+
+        let total = paths.len();
+        let mut failed = 0;
+        for (path, clients) in paths {
+            if let Err(reason) = check_writable(&path) {
+                failed += 1;
+                report.diagnostics.push(Diagnostic::error(
+                    DiagnosticCategory::Permissions,
+                    path.display().to_string(),
+                    format!(
+                        "not writable ({}), used by {}",
+                        reason,
+                        clients.into_iter().collect::<Vec<_>>().join(", ")
+                    ),
+                    format!("Check ownership and permissions of {}", path.display()),
+                ));
+            }
+        }
+        if failed == 0 {
+            report.diagnostics.push(Diagnostic::ok(
+                DiagnosticCategory::Permissions,
+                "client paths",
+                format!("{} paths writable", total),
+            ));
+        }
+    }
+
+    /// Fetch each registry's manifest and make sure it parses.
+    fn check_registries(&self, config: Option<&SiftConfig>, report: &mut DoctorReport) {
+        if config.is_none() {
+            return;
+        }
+        let registries = match self.ctx.registries() {
+            Ok(registries) => registries,
+            Err(err) => {
+                report.diagnostics.push(Diagnostic::error(
+                    DiagnosticCategory::Registry,
+                    "sift.toml",
+                    format!("{:#}", err),
+                    "Fix the [registry] tables in sift.toml".to_string(),
+                ));
+                return;
+            }
+        };
+
+        let registries: BTreeMap<_, _> = registries.into_iter().collect();
+        for (key, registry) in registries {
+            let diagnostic = match self.check_registry(&registry) {
+                Ok(message) => Diagnostic::ok(DiagnosticCategory::Registry, key, message),
+                Err(err) => Diagnostic::error(
+                    DiagnosticCategory::Registry,
+                    key,
+                    format!("{:#}", err),
+                    format!(
+                        "Check that {} is reachable and serves a valid manifest",
+                        registry.display_name()
+                    ),
+                ),
+            };
+            report.diagnostics.push(diagnostic);
+        }
+    }
+
+    fn check_registry(&self, registry: &RegistryConfig) -> anyhow::Result<String> {
+        registry.validate()?;
+        match registry.r#type {
+            RegistryType::Sift => {
+                let url = registry
+                    .url
+                    .clone()
+                    .ok_or_else(|| anyhow::anyhow!("Sift registry requires 'url' field"))?;
+                let index = SiftRegistryClient::new(url).fetch_index()?;
+                Ok(format!(
+                    "index reachable, {} packages",
+                    index.packages.len()
+                ))
+            }
+            RegistryType::ClaudeMarketplace => {
+                let source = registry.source.as_deref().unwrap_or_default();
+                let spec = GitSpec::parse(source)?;
+                let fetcher = self.ctx.git_fetcher();
+                // ls-remote first so an unreachable remote is not masked by the cache
+                fetcher.list_remote_refs(&spec)?;
+                let content = fetcher.read_root_file(&spec, ".claude-plugin/marketplace.json")?;
+                let manifest = MarketplaceAdapter::parse(&content)?;
+                Ok(format!(
+                    "marketplace reachable, {} plugins",
+                    manifest.plugins.len()
+                ))
+            }
+        }
+    }
+
+    /// Load the lockfile and compare it with the declared entries.
+    fn check_lockfile(&self, config: Option<&SiftConfig>, report: &mut DoctorReport) {
+        let path = self.lockfile_path();
+        let lockfile = match self.ctx.lockfile_service().load() {
+            Ok(lockfile) => lockfile,
+            Err(err) => {
+                report.diagnostics.push(Diagnostic::error(
+                    DiagnosticCategory::Lockfile,
+                    path.display().to_string(),
+                    format!("{:#}", err),
+                    format!(
+                        "Delete {} and run `sift install` to regenerate it",
+                        path.display()
+                    ),
+                ));
+                return;
+            }
+        };
+
+        let mut issues = 0;
+        if let Some(config) = config {
+            let kinds = [
+                (
+                    "MCP server",
+                    config.mcp.keys().collect::<BTreeSet<_>>(),
+                    lockfile.mcp_servers.keys().collect::<BTreeSet<_>>(),
+                ),
+                (
+                    "Skill",
+                    config.skill.keys().collect(),
+                    lockfile.skills.keys().collect(),
+                ),
+            ];
+            for (kind, declared, locked) in kinds {
+                for name in declared.difference(&locked) {
+                    issues += 1;
+                    report.diagnostics.push(Diagnostic::warning(
+                        DiagnosticCategory::Lockfile,
+                        name.as_str(),
+                        format!("{} is declared in sift.toml but not installed", kind),
+                        "Run `sift install`".to_string(),
+                    ));
+                }
+                for name in locked.difference(&declared) {
+                    issues += 1;
+                    report.diagnostics.push(Diagnostic::warning(
+                        DiagnosticCategory::Lockfile,
+                        name.as_str(),
+                        format!("{} is locked but no longer declared in sift.toml", kind),
+                        "Run `sift install --prune`".to_string(),
+                    ));
+                }
+            }
+        }
+        if issues == 0 {
+            report.diagnostics.push(Diagnostic::ok(
+                DiagnosticCategory::Lockfile,
+                "lockfile",
+                format!(
+                    "{} MCP servers and {} skills locked",
+                    lockfile.mcp_servers.len(),
+                    lockfile.skills.len()
+                ),
+            ));
+        }
+    }
+
+    /// Path of the current project's lockfile.
+    fn lockfile_path(&self) -> PathBuf {
+        self.ctx.lockfile_service().store_dir().join(format!(
+            "{}.lock.json",
+            LockfileStore::project_key(self.ctx.project_root())
+        ))
+    }
+
+    /// Find bare repos in the git cache that nothing refers to any more.
+    ///
+    /// A repo is in use when a lockfile for any project records it, or a
+    /// configured skill or marketplace registry points at it. If any lockfile
+    /// cannot be read, no repo is reported as unused.
+    fn check_bare_repos(&self, config: Option<&SiftConfig>, report: &mut DoctorReport) {
+        let state_dir = self.ctx.state_dir();
+        let Ok(cached) = std::fs::read_dir(state_dir.join("git")) else {
+            return;
+        };
+
+        let (lockfiles, unreadable) = all_lockfiles(&state_dir.join("locks"));
+        if !unreadable.is_empty() {
+            // The current project's lockfile is reported by check_lockfile
+            let own = self.lockfile_path();
+            for (path, err) in &unreadable {
+                if *path == own {
+                    continue;
+                }
+                report.diagnostics.push(Diagnostic::error(
+                    DiagnosticCategory::Lockfile,
+                    path.display().to_string(),
+                    err.clone(),
+                    format!(
+                        "Fix or delete {} and run `sift install` in its project",
+                        path.display()
+                    ),
+                ));
+            }
+            report.diagnostics.push(Diagnostic::warning(
+                DiagnosticCategory::Cache,
+                "git cache",
+                format!(
+                    "{} lockfile(s) could not be read; unused repositories were not checked",
+                    unreadable.len()
+                ),
+                "Fix the unreadable lockfiles and run `sift doctor` again".to_string(),
+            ));
+            return;
+        }
+
+        let mut in_use: HashSet<PathBuf> = HashSet::new();
+        for lockfile in lockfiles {
+            for skill in lockfile.skills.values() {
+                if let Some(repo) = &skill.git_repo {
+                    in_use.insert(GitSpec::new(repo.as_str()).bare_repo_dir(state_dir));
+                }
+            }
+        }
+        if let Some(config) = config {
+            let sources = config
+                .skill
+                .values()
+                .map(|s| s.source.as_str())
+                .chain(config.registry.values().filter_map(|r| r.source.as_deref()));
+            for source in sources {
+                if (source.starts_with("git:") || source.starts_with("github:"))
+                    && let Ok(spec) = GitSpec::parse(source)
+                {
+                    in_use.insert(spec.bare_repo_dir(state_dir));
+                }
+            }
+        }
+
+        let mut total = 0;
+        let mut stale = 0;
+        for entry in cached.flatten() {
+            let path = entry.path();
+            if path.extension().is_none_or(|ext| ext != "git") {
+                continue;
+            }
+            total += 1;
+            if !in_use.contains(&path) {
+                stale += 1;
+                report.diagnostics.push(Diagnostic::warning(
+                    DiagnosticCategory::Cache,
+                    path.display().to_string(),
+                    "bare repository is not used by any lockfile or configured source".to_string(),
+                    format!("Remove {} to reclaim disk space", path.display()),
+                ));
+            }
+        }
+        if stale == 0 {
+            report.diagnostics.push(Diagnostic::ok(
+                DiagnosticCategory::Cache,
+                "git cache",
+                format!("{} cached repositories in use", total),
+            ));
+        }
+    }
+}
+
+fn scope_supported(support: &crate::client::ScopeSupport, scope: ConfigScope) -> bool {
+    match scope {
+        ConfigScope::Global => support.global,
+        ConfigScope::PerProjectShared => support.project,
+        ConfigScope::PerProjectLocal => support.local,
+    }
+}
+
+/// Run `<program> --version` and return the first line of output.
+/// Servers that need `probe`, leaving out those whose runtime an
+/// `available` alternative probe also covers.
+fn probe_users<'a>(
+    probe: &RuntimeProbe,
+    required: &BTreeMap<&str, BTreeSet<&'a str>>,
+    available: &BTreeSet<&str>,
+) -> BTreeSet<&'a str> {
+    probe
+        .runtimes
+        .iter()
+        .filter(|runtime| {
+            !RUNTIME_PROBES.iter().any(|other| {
+                probe.alternatives.contains(&other.name)
+                    && available.contains(other.name)
+                    && other.runtimes.contains(runtime)
+            })
+        })
+        .filter_map(|runtime| required.get(runtime))
+        .flatten()
+        .copied()
+        .collect()
+}
+
+fn probe_version(program: &str) -> Option<String> {
+    let output = Command::new(program).arg("--version").output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    stdout
+        .lines()
+        .chain(stderr.lines())
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .map(str::to_string)
+}
+
+/// Check that `path` can be written, or created inside its nearest
+/// existing ancestor.
+fn check_writable(path: &Path) -> Result<(), String> {
+    if path.is_file() {
+        return std::fs::OpenOptions::new()
+            .append(true)
+            .open(path)
+            .map(|_| ())
+            .map_err(|e| e.to_string());
+    }
+    let dir = path
+        .ancestors()
+        .find(|p| p.exists())
+        .ok_or_else(|| "no existing parent directory".to_string())?;
+    if !dir.is_dir() {
+        return Err(format!("{} is not a directory", dir.display()));
+    }
+    let probe = dir.join(format!(".sift-doctor-{}", std::process::id()));
+    std::fs::File::create(&probe).map_err(|e| e.to_string())?;
+    let _ = std::fs::remove_file(&probe);
+    Ok(())
+}
+
+/// Every lockfile in the store, and the paths of any that could not be read
+/// or parsed with the reason.
+fn all_lockfiles(store_dir: &Path) -> (Vec<Lockfile>, Vec<(PathBuf, String)>) {
+    let mut lockfiles = Vec::new();
+    let mut unreadable = Vec::new();
+    let Ok(entries) = std::fs::read_dir(store_dir) else {
+        return (lockfiles, unreadable);
+    };
+    for path in entries.flatten().map(|e| e.path()) {
+        if !path.to_string_lossy().ends_with(".lock.json") {
+            continue;
+        }
+        let parsed = std::fs::read(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|bytes| serde_json::from_slice(&bytes).map_err(|e| e.to_string()));
+        match parsed {
+            Ok(lockfile) => lockfiles.push(lockfile),
+            Err(err) => unreadable.push((path, format!("Failed to read lockfile: {}", err))),
+        }
+    }
+    (lockfiles, unreadable)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_writable_accepts_missing_file_in_writable_dir() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path().join("nested").join("config.json");
+        assert!(check_writable(&path).is_ok());
+        assert!(!temp.path().join("nested").exists());
+    }
+
+    #[test]
+    fn check_writable_rejects_file_as_parent() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let file = temp.path().join("file");
+        std::fs::write(&file, "").unwrap();
+        let err = check_writable(&file.join("child.json")).unwrap_err();
+        assert!(err.contains("not a directory"));
+    }
+
+    fn probe(name: &str) -> &'static RuntimeProbe {
+        RUNTIME_PROBES
+            .iter()
+            .find(|probe| probe.name == name)
+            .unwrap()
+    }
+
+    #[test]
+    fn probe_users_accepts_pipx_for_python_servers() {
+        let required = BTreeMap::from([
+            ("python", BTreeSet::from(["fetch"])),
+            ("uvx", BTreeSet::from(["git"])),
+        ]);
+
+        let users = probe_users(probe("uv"), &required, &BTreeSet::from(["pipx"]));
+        assert_eq!(users, BTreeSet::from(["git"]));
+
+        let users = probe_users(probe("uv"), &required, &BTreeSet::new());
+        assert_eq!(users, BTreeSet::from(["fetch", "git"]));
+        let users = probe_users(probe("pipx"), &required, &BTreeSet::new());
+        assert_eq!(users, BTreeSet::from(["fetch"]));
+    }
+
+    #[test]
+    fn probe_version_returns_none_for_missing_program() {
+        assert!(probe_version("sift-doctor-no-such-program").is_none());
+    }
+}
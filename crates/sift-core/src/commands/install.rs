@@ -6,14 +6,10 @@
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
-use crate::client::ClientAdapter;
-use crate::client::ClientContext;
 use crate::config::{ConfigStore, McpConfigEntry, SkillConfigEntry};
 use crate::context::AppContext;
-use crate::deploy::scope::{
-    RepoStatus, ResourceKind, ScopeRequest, ScopeResolution, resolve_scope,
-};
-use crate::deploy::{InstallMcpRequest, InstallOrchestrator};
+use crate::deploy::scope::{RepoStatus, ResourceKind, ScopeRequest, ScopeResolution};
+use crate::deploy::{InstallMcpRequest, InstallOrchestrator, TargetingPolicy};
 use crate::fs::LinkMode;
 use crate::mcp::McpServerBuilder;
 use crate::source::{ResolvedInput, SourceResolver};
@@ -30,6 +26,16 @@ pub enum InstallTarget {
     Skill,
 }
 
+impl InstallTarget {
+    /// Lowercase label used in CLI output
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            InstallTarget::Mcp => "mcp",
+            InstallTarget::Skill => "skill",
+        }
+    }
+}
+
 /// Options for the install command
 #[derive(Debug, Clone)]
 pub struct InstallOptions {
@@ -210,6 +216,43 @@ pub struct InstallReport {
     pub applied: bool,
     /// Any warnings generated during installation
     pub warnings: Vec<String>,
+    /// Per-client results, in registry order
+    pub clients: Vec<ClientInstallResult>,
+}
+
+/// Outcome of an install for a single client
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClientInstallStatus {
+    /// Config or skill was delivered to the client
+    Applied,
+    /// Client was skipped (unsupported scope or not targeted)
+    Skipped,
+    /// Delivery failed (best-effort installs only)
+    Failed,
+}
+
+impl ClientInstallStatus {
+    /// Lowercase label used in CLI output
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ClientInstallStatus::Applied => "applied",
+            ClientInstallStatus::Skipped => "skipped",
+            ClientInstallStatus::Failed => "failed",
+        }
+    }
+}
+
+/// Install result for a single client
+#[derive(Debug, Clone)]
+pub struct ClientInstallResult {
+    /// Client identifier
+    pub client_id: String,
+    /// What happened for this client
+    pub status: ClientInstallStatus,
+    /// Scope the client was written at, if applied
+    pub scope: Option<ConfigScope>,
+    /// Skip reason or error message
+    pub message: Option<String>,
 }
 
 /// Install command orchestrator
@@ -398,6 +441,7 @@ impl InstallCommand {
             args,
             url,
             headers,
+            oauth: None,
             targets: options.targets.clone(),
             ignore_targets: options.ignore_targets.clone(),
             env,
@@ -405,79 +449,77 @@ impl InstallCommand {
             reset_ignore_targets: false,
             reset_env: None,
             reset_env_all: false,
+            volumes: Vec::new(),
+            network: None,
+            always_allow: Vec::new(),
         };
 
-        // Create client adapter and context
-        let registry = self.ctx.client_registry();
-        let client = registry
-            .get("claude-code")
-            .ok_or_else(|| anyhow::anyhow!("claude-code client not found in registry"))?;
         let client_ctx = self.ctx.client_context();
 
         // Build resolved server spec (simplified for now)
-        let servers = self.create_mcp_builder()?.build(
+        let (mut servers, resolved_version) = self.create_mcp_builder()?.build_with_version(
             &name,
             &source,
             &entry,
             version.as_deref(),
             options.force,
         )?;
-
-        // Determine scope request
-        let scope_request = match options.scope {
-            Some(s) => ScopeRequest::Explicit(s),
-            None => ScopeRequest::Auto,
+        let checksum = match McpServerBuilder::pin_docker_image(&mut servers, None) {
+            Ok(digest) => digest,
+            Err(err) => {
+                warnings.push(format!(
+                    "Could not pin Docker image for '{}' to a digest: {:#}",
+                    name, err
+                ));
+                None
+            }
         };
 
-        let repo = RepoStatus::from_project_root(&client_ctx.project_root);
-        let resolution = resolve_scope(
+        // Select target clients and a single config scope
+        let registry = self.ctx.client_registry()?;
+        let policy = TargetingPolicy::new(entry.targets.clone(), entry.ignore_targets.clone());
+        let selection = policy.select_clients(
+            &registry,
             ResourceKind::Mcp,
-            scope_request,
-            client.capabilities().mcp,
-            repo,
+            scope_request(options.scope),
+            RepoStatus::from_project_root(&client_ctx.project_root),
         )?;
+        if selection.clients.is_empty() {
+            anyhow::bail!("No clients match the requested targets for '{}'", name);
+        }
 
-        let config_scope = match &resolution {
-            ScopeResolution::Apply(decision) => decision.scope,
-            ScopeResolution::Skip { .. } => options.scope.unwrap_or(ConfigScope::PerProjectShared),
-        };
-
-        // Create orchestrator
-        let config_store = self.create_config_store(config_scope);
-        let lockfile_service = self.create_lockfile_service();
-        let skill_installer = self.create_skill_installer();
-        let source_resolver = self.create_source_resolver()?;
-        let git_fetcher = self.create_git_fetcher();
-        let orchestrator = InstallOrchestrator::new(
-            config_store,
-            lockfile_service,
-            skill_installer,
-            source_resolver,
-            git_fetcher,
-            self.ctx.link_mode(),
-        );
-
-        // Execute installation
-        let report = orchestrator.install_mcp(
-            client,
-            &client_ctx,
-            InstallMcpRequest {
-                name: &name,
-                entry,
-                servers: &servers,
-                resolution,
-                force: options.force,
-                declared_version: version.as_deref(),
-            },
-        )?;
+        let orchestrator = self.create_orchestrator(selection.config_scope)?;
+        let mut fanout = FanOut::new(selection.explicit);
+        for target in selection.clients {
+            let client_id = target.client.id();
+            let scope = applied_scope(&target.resolution);
+            let result = orchestrator.install_mcp(
+                target.client,
+                &client_ctx,
+                InstallMcpRequest {
+                    name: &name,
+                    entry: entry.clone(),
+                    servers: &servers,
+                    resolution: target.resolution,
+                    force: options.force,
+                    declared_version: version.as_deref(),
+                    resolved_version: resolved_version.as_deref(),
+                    checksum: checksum.as_deref(),
+                },
+            );
+            match result {
+                Ok(report) => fanout.record(
+                    client_id,
+                    scope,
+                    matches!(report.outcome, crate::deploy::InstallOutcome::Changed),
+                    report.applied,
+                    report.warnings,
+                ),
+                Err(err) => fanout.fail(client_id, err)?,
+            }
+        }
 
-        warnings.extend(report.warnings);
-        Ok(InstallReport {
-            name,
-            changed: matches!(report.outcome, crate::deploy::InstallOutcome::Changed),
-            applied: report.applied,
-            warnings,
-        })
+        fanout.finish(name, warnings)
     }
 
     /// Install a skill
@@ -499,13 +541,6 @@ impl InstallCommand {
         warnings.extend(resolved_warnings);
         warnings.extend(self.registry_warnings(source_is_registry, source_explicit)?);
 
-        // Create client adapter and context
-        let registry = self.ctx.client_registry();
-        let client = registry
-            .get("claude-code")
-            .ok_or_else(|| anyhow::anyhow!("claude-code client not found in registry"))?;
-        let client_ctx = self.ctx.client_context();
-
         // Check if this is a registry source that might have nested marketplaces
         if let Some(registry_part) = source.strip_prefix("registry:") {
             let source_resolver = self.create_source_resolver()?;
@@ -523,6 +558,7 @@ impl InstallCommand {
             {
                 let mut changed = false;
                 let mut applied = false;
+                let mut clients = Vec::new();
                 let mut nested_warnings = warnings;
 
                 for resolution in &resolutions {
@@ -546,9 +582,7 @@ impl InstallCommand {
                         reset_version: false,
                     };
 
-                    match self.install_skill_with_orchestrator(
-                        client,
-                        &client_ctx,
+                    match self.install_skill_to_clients(
                         &nested_name,
                         nested_entry,
                         &resolution.metadata.original_source,
@@ -558,6 +592,7 @@ impl InstallCommand {
                         Ok(report) => {
                             changed = changed || report.changed;
                             applied = applied || report.applied;
+                            clients.extend(report.clients);
                             nested_warnings.extend(report.warnings);
                         }
                         Err(e) => {
@@ -581,6 +616,7 @@ impl InstallCommand {
                     changed,
                     applied,
                     warnings: nested_warnings,
+                    clients,
                 });
             }
         }
@@ -594,15 +630,8 @@ impl InstallCommand {
             reset_version: false,
         };
 
-        let report = self.install_skill_with_orchestrator(
-            client,
-            &client_ctx,
-            &name,
-            entry,
-            &source,
-            options.scope,
-            options.force,
-        )?;
+        let report =
+            self.install_skill_to_clients(&name, entry, &source, options.scope, options.force)?;
 
         let mut all_warnings = warnings;
         all_warnings.extend(report.warnings);
@@ -611,65 +640,70 @@ impl InstallCommand {
             changed: report.changed,
             applied: report.applied,
             warnings: all_warnings,
+            clients: report.clients,
         })
     }
 
-    /// Helper to install a skill using the orchestrator's active method.
-    #[allow(clippy::too_many_arguments)]
-    fn install_skill_with_orchestrator(
+    /// Install a skill to every applicable client.
+    fn install_skill_to_clients(
         &self,
-        client: &dyn ClientAdapter,
-        ctx: &ClientContext,
         name: &str,
         entry: SkillConfigEntry,
         source: &str,
         scope: Option<ConfigScope>,
         force: bool,
     ) -> anyhow::Result<InstallReport> {
-        // Determine scope request
-        let scope_request = match scope {
-            Some(s) => ScopeRequest::Explicit(s),
-            None => ScopeRequest::Auto,
-        };
-
-        let repo = RepoStatus::from_project_root(&ctx.project_root);
-        let resolution = resolve_scope(
+        let client_ctx = self.ctx.client_context();
+        let registry = self.ctx.client_registry()?;
+        let policy = TargetingPolicy::new(entry.targets.clone(), entry.ignore_targets.clone());
+        let selection = policy.select_clients(
+            &registry,
             ResourceKind::Skill,
-            scope_request,
-            client.capabilities().skills,
-            repo,
+            scope_request(scope),
+            RepoStatus::from_project_root(&client_ctx.project_root),
         )?;
+        if selection.clients.is_empty() {
+            anyhow::bail!("No clients match the requested targets for '{}'", name);
+        }
 
-        let config_scope = match &resolution {
-            ScopeResolution::Apply(decision) => decision.scope,
-            ScopeResolution::Skip { .. } => scope.unwrap_or(ConfigScope::PerProjectShared),
-        };
-
-        // Create orchestrator with all active dependencies
-        let config_store = self.create_config_store(config_scope);
-        let lockfile_service = self.create_lockfile_service();
-        let skill_installer = self.create_skill_installer();
-        let source_resolver = self.create_source_resolver()?;
-        let git_fetcher = self.create_git_fetcher();
-        let orchestrator = InstallOrchestrator::new(
-            config_store,
-            lockfile_service,
-            skill_installer,
-            source_resolver,
-            git_fetcher,
-            self.ctx.link_mode(),
-        );
+        let orchestrator = self.create_orchestrator(selection.config_scope)?;
+        let mut fanout = FanOut::new(selection.explicit);
+        for target in selection.clients {
+            let client_id = target.client.id();
+            let applied_at = applied_scope(&target.resolution);
+            let result = orchestrator.install_skill_from_source(
+                target.client,
+                &client_ctx,
+                name,
+                entry.clone(),
+                source,
+                target.resolution,
+                force,
+            );
+            match result {
+                Ok(report) => fanout.record(
+                    client_id,
+                    applied_at,
+                    matches!(report.outcome, crate::deploy::InstallOutcome::Changed),
+                    report.applied,
+                    report.warnings,
+                ),
+                Err(err) => fanout.fail(client_id, err)?,
+            }
+        }
 
-        // Use the new active installation method
-        let report = orchestrator
-            .install_skill_from_source(client, ctx, name, entry, source, resolution, force)?;
+        fanout.finish(name.to_string(), Vec::new())
+    }
 
-        Ok(InstallReport {
-            name: name.to_string(),
-            changed: matches!(report.outcome, crate::deploy::InstallOutcome::Changed),
-            applied: report.applied,
-            warnings: report.warnings,
-        })
+    fn create_orchestrator(&self, scope: ConfigScope) -> anyhow::Result<InstallOrchestrator> {
+        Ok(InstallOrchestrator::new(
+            self.create_config_store(scope),
+            self.create_lockfile_service(),
+            self.create_skill_installer(),
+            self.create_source_resolver()?,
+            self.create_git_fetcher(),
+            self.ctx.link_mode(),
+        ))
     }
 
     // Helper methods - delegate to InstallContext
@@ -761,6 +795,104 @@ impl InstallCommand {
     }
 }
 
+fn scope_request(scope: Option<ConfigScope>) -> ScopeRequest {
+    match scope {
+        Some(s) => ScopeRequest::Explicit(s),
+        None => ScopeRequest::Auto,
+    }
+}
+
+fn applied_scope(resolution: &ScopeResolution) -> Option<ConfigScope> {
+    match resolution {
+        ScopeResolution::Apply(decision) => Some(decision.scope),
+        ScopeResolution::Skip { .. } => None,
+    }
+}
+
+/// Accumulates per-client results while fanning an install out.
+struct FanOut {
+    explicit: bool,
+    changed: bool,
+    applied: bool,
+    warnings: Vec<String>,
+    clients: Vec<ClientInstallResult>,
+}
+
+impl FanOut {
+    fn new(explicit: bool) -> Self {
+        Self {
+            explicit,
+            changed: false,
+            applied: false,
+            warnings: Vec::new(),
+            clients: Vec::new(),
+        }
+    }
+
+    fn record(
+        &mut self,
+        client_id: &str,
+        scope: Option<ConfigScope>,
+        changed: bool,
+        applied: bool,
+        warnings: Vec<String>,
+    ) {
+        self.changed |= changed;
+        self.applied |= applied;
+        let status = if applied {
+            ClientInstallStatus::Applied
+        } else {
+            ClientInstallStatus::Skipped
+        };
+        self.clients.push(ClientInstallResult {
+            client_id: client_id.to_string(),
+            status,
+            scope: scope.filter(|_| applied),
+            message: warnings.first().cloned(),
+        });
+        self.warnings.extend(warnings);
+    }
+
+    /// Record a failed client; explicit targets abort the install.
+    fn fail(&mut self, client_id: &str, err: anyhow::Error) -> anyhow::Result<()> {
+        if self.explicit {
+            return Err(err.context(format!("Failed to install to '{}'", client_id)));
+        }
+        let message = format!("{:#}", err);
+        self.warnings
+            .push(format!("Failed to install to '{}': {}", client_id, message));
+        self.clients.push(ClientInstallResult {
+            client_id: client_id.to_string(),
+            status: ClientInstallStatus::Failed,
+            scope: None,
+            message: Some(message),
+        });
+        Ok(())
+    }
+
+    fn finish(self, name: String, mut warnings: Vec<String>) -> anyhow::Result<InstallReport> {
+        let all_failed = self
+            .clients
+            .iter()
+            .all(|c| c.status == ClientInstallStatus::Failed);
+        if all_failed && let Some(first) = self.clients.first() {
+            anyhow::bail!(
+                "Failed to install '{}' to any client: {}",
+                name,
+                first.message.as_deref().unwrap_or("unknown error")
+            );
+        }
+        warnings.extend(self.warnings);
+        Ok(InstallReport {
+            name,
+            changed: self.changed,
+            applied: self.applied,
+            warnings,
+            clients: self.clients,
+        })
+    }
+}
+
 fn resolve_transport(
     transport: Option<&str>,
     has_command: bool,
@@ -872,6 +1004,54 @@ source = "github:anthropics/skills"
         (temp, cmd)
     }
 
+    /// A test environment whose only registry is a local marketplace that
+    /// lists no plugins, so registry MCP sources fall back to npm-style.
+    fn setup_local_registry_env() -> (TempDir, InstallCommand) {
+        let temp = TempDir::new().unwrap();
+        let home = temp.path().join("home");
+        let project = temp.path().join("project");
+        let state = temp.path().join("state");
+        let global_config = temp.path().join("config");
+        let marketplace = temp.path().join("marketplace");
+        for dir in [&home, &project, &state, &global_config] {
+            std::fs::create_dir_all(dir).unwrap();
+        }
+
+        std::fs::create_dir_all(marketplace.join(".claude-plugin")).unwrap();
+        std::fs::write(
+            marketplace.join(".claude-plugin/marketplace.json"),
+            r#"{"marketplace": {"name": "local"}, "plugins": []}"#,
+        )
+        .unwrap();
+        run_git(&marketplace, &["init"]);
+        run_git(&marketplace, &["checkout", "-b", "main"]);
+        run_git(&marketplace, &["config", "user.email", "test@example.com"]);
+        run_git(&marketplace, &["config", "user.name", "Test User"]);
+        run_git(&marketplace, &["config", "commit.gpgsign", "false"]);
+        run_git(&marketplace, &["add", "."]);
+        run_git(&marketplace, &["commit", "-m", "init"]);
+        std::fs::write(
+            global_config.join("sift.toml"),
+            format!(
+                "[registry.local]\ntype = \"claude-marketplace\"\nsource = \"git:{}\"\n",
+                url::Url::from_directory_path(&marketplace)
+                    .unwrap()
+                    .as_str()
+                    .trim_end_matches('/')
+            ),
+        )
+        .unwrap();
+
+        let cmd = InstallCommand::with_global_config_dir(
+            home,
+            project,
+            state,
+            global_config,
+            LinkMode::Copy,
+        );
+        (temp, cmd)
+    }
+
     fn run_git(dir: &std::path::Path, args: &[&str]) {
         let status = git_command()
             .args(args)
@@ -928,7 +1108,7 @@ source = "github:anthropics/skills"
 
     #[test]
     fn test_install_mcp_creates_config_entry() {
-        let (_temp, cmd) = setup_test_env();
+        let (_temp, cmd) = setup_local_registry_env();
 
         let opts = InstallOptions::mcp("demo-mcp")
             .with_scope(ConfigScope::PerProjectShared)
@@ -952,7 +1132,7 @@ source = "github:anthropics/skills"
 
     #[test]
     fn test_install_mcp_is_idempotent() {
-        let (_temp, cmd) = setup_test_env();
+        let (_temp, cmd) = setup_local_registry_env();
 
         let opts = InstallOptions::mcp("idempotent-mcp")
             .with_scope(ConfigScope::PerProjectShared)
@@ -1071,9 +1251,28 @@ Test instructions."#,
         assert_eq!(entry.source, format!("git:{}", repo_dir.display()));
     }
 
+    #[test]
+    fn test_install_mcp_reports_registry_failure() {
+        let (temp, cmd) = setup_test_env();
+        std::fs::write(
+            temp.path().join("config/sift.toml"),
+            "[registry.broken]\ntype = \"claude-marketplace\"\nsource = \"git:/nonexistent/marketplace\"\n",
+        )
+        .unwrap();
+
+        let opts = InstallOptions::mcp("demo-mcp")
+            .with_scope(ConfigScope::PerProjectShared)
+            .with_source("registry:broken/demo-mcp");
+
+        // An unreachable registry is an error, not a guessed npm package
+        let err = cmd.execute(&opts).unwrap_err();
+        assert!(format!("{:#}", err).contains("Failed to resolve 'demo-mcp'"));
+        assert!(!cmd.project_root().join(".mcp.json").exists());
+    }
+
     #[test]
     fn test_install_report_includes_warnings() {
-        let (_temp, cmd) = setup_test_env();
+        let (_temp, cmd) = setup_local_registry_env();
 
         // Install to global scope which may generate warnings for some clients
         let opts = InstallOptions::mcp("warning-test")
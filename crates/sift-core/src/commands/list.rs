@@ -0,0 +1,288 @@
+//! List command implementation.
+//!
+//! Enumerates what the project expects: every MCP server and skill declared
+//! across the global, project and project-local configs, joined with the
+//! lockfile and the clients each entry would be delivered to.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use crate::client::registry::ClientRegistry;
+use crate::config::{McpConfigEntry, SkillConfigEntry};
+use crate::context::AppContext;
+use crate::deploy::scope::{RepoStatus, ResourceKind, ScopeRequest, ScopeResolution};
+use crate::deploy::targeting::TargetingPolicy;
+use crate::fs::LinkMode;
+use crate::lockfile::{LockedMcpServer, LockedSkill};
+use crate::types::ConfigScope;
+
+use super::context::InstallContext;
+use super::install::InstallTarget;
+use super::sync::DeclaredScopes;
+use super::upgrade::describe_commit;
+
+/// Options for the list command
+#[derive(Debug, Clone, Default)]
+pub struct ListOptions {
+    /// Only list MCP servers or skills
+    pub kind: Option<InstallTarget>,
+    /// Only list entries declared in this scope
+    pub scope: Option<ConfigScope>,
+    /// Only list entries delivered to this client
+    pub client: Option<String>,
+}
+
+impl ListOptions {
+    /// List every declared entry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Restrict the listing to one kind of entry
+    pub fn with_kind(mut self, kind: InstallTarget) -> Self {
+        self.kind = Some(kind);
+        self
+    }
+
+    /// Restrict the listing to one scope
+    pub fn with_scope(mut self, scope: ConfigScope) -> Self {
+        self.scope = Some(scope);
+        self
+    }
+
+    /// Restrict the listing to entries targeting one client
+    pub fn with_client(mut self, client: impl Into<String>) -> Self {
+        self.client = Some(client.into());
+        self
+    }
+
+    fn matches(&self, entry: &ListEntry) -> bool {
+        self.scope.is_none_or(|s| s == entry.scope)
+            && self
+                .client
+                .as_ref()
+                .is_none_or(|c| entry.targets.contains(c))
+    }
+}
+
+/// A single declared entry
+#[derive(Debug, Clone)]
+pub struct ListEntry {
+    /// Whether this is an MCP server or a skill
+    pub kind: InstallTarget,
+    /// Entry name as declared in sift.toml
+    pub name: String,
+    /// Scope the entry is declared in
+    pub scope: ConfigScope,
+    /// Declared source
+    pub source: String,
+    /// Declared version constraint, if any
+    pub declared_version: Option<String>,
+    /// Locked version, if installed
+    pub resolved_version: Option<String>,
+    /// MCP runtime (stdio servers only)
+    pub runtime: Option<String>,
+    /// MCP transport
+    pub transport: Option<String>,
+    /// Clients the entry is delivered to
+    pub targets: Vec<String>,
+}
+
+/// Report from a list command
+#[derive(Debug, Clone, Default)]
+pub struct ListReport {
+    /// Matching entries, MCP servers first, then by name
+    pub entries: Vec<ListEntry>,
+    /// Entries whose targets could not be resolved
+    pub warnings: Vec<String>,
+}
+
+/// List command orchestrator
+pub struct ListCommand {
+    /// Shared context for dependency injection
+    ctx: InstallContext,
+}
+
+impl ListCommand {
+    /// Create a new list command with custom global config directory
+    pub fn with_global_config_dir(
+        home_dir: PathBuf,
+        project_root: PathBuf,
+        state_dir: PathBuf,
+        global_config_dir: PathBuf,
+        link_mode: LinkMode,
+    ) -> Self {
+        Self {
+            ctx: InstallContext::new(
+                home_dir,
+                project_root,
+                state_dir,
+                global_config_dir,
+                link_mode,
+            ),
+        }
+    }
+
+    /// Create a list command with default paths
+    pub fn with_defaults() -> anyhow::Result<Self> {
+        Ok(Self {
+            ctx: InstallContext::with_defaults()?,
+        })
+    }
+
+    /// Create from AppContext (preferred).
+    pub fn from_context(ctx: AppContext) -> Self {
+        Self {
+            ctx: InstallContext::from_app_context(ctx),
+        }
+    }
+
+    /// Execute the list command
+    pub fn execute(&self, options: &ListOptions) -> anyhow::Result<ListReport> {
+        let registry = self.ctx.client_registry()?;
+        if let Some(client) = &options.client
+            && registry.get(client).is_none()
+        {
+            anyhow::bail!(
+                "Unknown client '{}'. Known clients: {}",
+                client,
+                registry.client_ids().join(", ")
+            );
+        }
+
+        let global = self.ctx.config_store(ConfigScope::Global).load()?;
+        let project = self
+            .ctx
+            .config_store(ConfigScope::PerProjectShared)
+            .load()?;
+        let declared = DeclaredScopes::collect(&global, &project, self.ctx.project_root());
+        let merged = self.ctx.merged_config()?;
+        let lockfile = self.ctx.lockfile_service().load()?;
+        let repo = RepoStatus::from_project_root(self.ctx.project_root());
+
+        let mut report = ListReport::default();
+
+        if options.kind.is_none_or(|k| k == InstallTarget::Mcp) {
+            let entries: BTreeMap<_, _> = merged.mcp.iter().collect();
+            for (name, entry) in entries {
+                let scope = declared.mcp_scope(name);
+                let locked = lockfile.mcp_servers.get(name.as_str());
+                let policy =
+                    TargetingPolicy::new(entry.targets.clone(), entry.ignore_targets.clone());
+                let targets = match delivered_to(&policy, &registry, ResourceKind::Mcp, scope, repo)
+                {
+                    Ok(targets) => targets,
+                    Err(err) => {
+                        report
+                            .warnings
+                            .push(format!("MCP server '{}': {:#}", name, err));
+                        Vec::new()
+                    }
+                };
+                let listed = mcp_entry(name, scope, entry, locked, targets);
+                if options.matches(&listed) {
+                    report.entries.push(listed);
+                }
+            }
+        }
+
+        if options.kind.is_none_or(|k| k == InstallTarget::Skill) {
+            let entries: BTreeMap<_, _> = merged.skill.iter().collect();
+            for (name, entry) in entries {
+                let scope = declared.skill_scope(name);
+                let locked = lockfile.skills.get(name.as_str());
+                let policy =
+                    TargetingPolicy::new(entry.targets.clone(), entry.ignore_targets.clone());
+                let targets =
+                    match delivered_to(&policy, &registry, ResourceKind::Skill, scope, repo) {
+                        Ok(targets) => targets,
+                        Err(err) => {
+                            report.warnings.push(format!("Skill '{}': {:#}", name, err));
+                            Vec::new()
+                        }
+                    };
+                let listed = skill_entry(name, scope, entry, locked, targets);
+                if options.matches(&listed) {
+                    report.entries.push(listed);
+                }
+            }
+        }
+
+        Ok(report)
+    }
+}
+
+/// Client ids that would receive an entry declared at `scope`.
+fn delivered_to(
+    policy: &TargetingPolicy,
+    registry: &ClientRegistry,
+    kind: ResourceKind,
+    scope: ConfigScope,
+    repo: RepoStatus,
+) -> anyhow::Result<Vec<String>> {
+    let selection = policy.select_clients(registry, kind, ScopeRequest::Explicit(scope), repo)?;
+    Ok(selection
+        .clients
+        .iter()
+        .filter(|t| matches!(t.resolution, ScopeResolution::Apply(_)))
+        .map(|t| t.client.id().to_string())
+        .collect())
+}
+
+fn mcp_entry(
+    name: &str,
+    scope: ConfigScope,
+    entry: &McpConfigEntry,
+    locked: Option<&LockedMcpServer>,
+    targets: Vec<String>,
+) -> ListEntry {
+    let transport = entry
+        .transport
+        .clone()
+        .unwrap_or_else(|| "stdio".to_string());
+    let runtime = if transport == "stdio" {
+        entry.runtime.clone()
+    } else {
+        None
+    };
+    // MCP constraints live only in the lockfile
+    let versioned = locked.filter(|l| l.constraint != "unmanaged");
+    ListEntry {
+        kind: InstallTarget::Mcp,
+        name: name.to_string(),
+        scope,
+        source: entry.source.clone(),
+        declared_version: versioned.map(|l| l.constraint.clone()),
+        resolved_version: versioned.map(|l| l.resolved_version.clone()),
+        runtime,
+        transport: Some(transport),
+        targets,
+    }
+}
+
+fn skill_entry(
+    name: &str,
+    scope: ConfigScope,
+    entry: &SkillConfigEntry,
+    locked: Option<&LockedSkill>,
+    targets: Vec<String>,
+) -> ListEntry {
+    let resolved_version = locked.map(|l| {
+        if l.git_repo.is_some() {
+            describe_commit(l.git_ref.as_deref(), &l.resolved_version)
+        } else {
+            l.resolved_version.clone()
+        }
+    });
+    ListEntry {
+        kind: InstallTarget::Skill,
+        name: name.to_string(),
+        scope,
+        source: entry.source.clone(),
+        declared_version: entry.version.clone(),
+        resolved_version,
+        runtime: None,
+        transport: None,
+        targets,
+    }
+}
@@ -1,23 +1,42 @@
 //! High-level commands for sift operations.
 //!
 //! This module provides the public API for orchestrating install, uninstall,
-//! and other sift operations. These commands are designed to be called by
-//! CLI, TUI, and GUI frontends.
+//! upgrade and other sift operations. These commands are designed to be called
+//! by CLI, TUI, and GUI frontends.
 
 pub mod context;
+pub mod doctor;
 pub mod install;
+pub mod list;
+pub mod outdated;
+pub mod probe;
 pub mod registry;
+pub mod sync;
+pub mod tools;
 pub mod uninstall;
+pub mod upgrade;
 
 pub use context::InstallContext;
-pub use install::{InstallCommand, InstallOptions, InstallReport, InstallTarget};
+pub use doctor::{
+    Diagnostic, DiagnosticCategory, DiagnosticSeverity, DoctorCommand, DoctorOptions, DoctorReport,
+};
+pub use install::{
+    ClientInstallResult, ClientInstallStatus, InstallCommand, InstallOptions, InstallReport,
+    InstallTarget,
+};
+pub use list::{ListCommand, ListEntry, ListOptions, ListReport};
+pub use outdated::{OutdatedCommand, OutdatedEntry, OutdatedOptions, OutdatedReport};
+pub use probe::{ProbeCommand, ProbeEntry, ProbeOptions, ProbeReport};
 pub use registry::{
     AddOptions as RegistryAddOptions, ListOptions as RegistryListOptions, RegistryCommand,
     RegistryEntry, RegistryReport, RemoveOptions as RegistryRemoveOptions,
 };
+pub use sync::{PrunedEntry, SyncCommand, SyncEntryReport, SyncOptions, SyncReport};
+pub use tools::{ManifestDiff, ToolsCommand, ToolsEntry, ToolsOptions, ToolsReport};
 pub use uninstall::{
     UninstallCommand, UninstallOptions, UninstallReport, UninstallScope, UninstallTarget,
 };
+pub use upgrade::{UpgradeCommand, UpgradeEntry, UpgradeOptions, UpgradeReport, UpgradeStatus};
 
 // Re-export status command types from the status module
 pub use crate::status::{StatusCommand, StatusOptions, StatusReport};
@@ -0,0 +1,285 @@
+//! Outdated command implementation.
+//!
+//! Compares every locked entry with its upstream source without changing
+//! anything: the version `sift upgrade` would move to ("wanted") and the
+//! newest version upstream regardless of the constraint ("latest").
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use crate::config::{McpConfigEntry, SkillConfigEntry};
+use crate::context::AppContext;
+use crate::fs::LinkMode;
+use crate::lockfile::{LockedMcpServer, LockedSkill};
+use crate::source::{ResolvedSource, SourceResolver};
+use crate::types::ConfigScope;
+use crate::version::VersionConstraint;
+use crate::version::git::resolve_git_constraint;
+
+use super::context::InstallContext;
+use super::install::InstallTarget;
+use super::upgrade::{describe_commit, is_same_commit, not_installed, skill_constraint};
+
+/// Options for the outdated command
+#[derive(Debug, Clone, Default)]
+pub struct OutdatedOptions {
+    /// Only check MCP servers or skills
+    pub kind: Option<InstallTarget>,
+}
+
+impl OutdatedOptions {
+    /// Check every locked entry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Restrict the check to one kind of entry
+    pub fn with_kind(mut self, kind: InstallTarget) -> Self {
+        self.kind = Some(kind);
+        self
+    }
+}
+
+/// Upstream state of a single locked entry
+#[derive(Debug, Clone)]
+pub struct OutdatedEntry {
+    /// Whether this is an MCP server or a skill
+    pub kind: InstallTarget,
+    /// Entry name as declared in sift.toml
+    pub name: String,
+    /// Scope the entry is locked at
+    pub scope: ConfigScope,
+    /// Constraint the entry is resolved against
+    pub constraint: String,
+    /// Locked version
+    pub current: String,
+    /// Newest version satisfying the constraint
+    pub wanted: Option<String>,
+    /// Newest version upstream, ignoring the constraint
+    pub latest: Option<String>,
+    /// Registry-declared version recorded at install time
+    pub registry_current: Option<String>,
+    /// Registry-declared version published now
+    pub registry_latest: Option<String>,
+    /// Whether `sift upgrade` would move this entry
+    pub upgradable: bool,
+    /// Whether the entry is behind upstream at all
+    pub outdated: bool,
+    /// Error that prevented the upstream check
+    pub error: Option<String>,
+}
+
+impl OutdatedEntry {
+    fn new(kind: InstallTarget, name: &str, scope: ConfigScope, current: String) -> Self {
+        Self {
+            kind,
+            name: name.to_string(),
+            scope,
+            constraint: String::new(),
+            current,
+            wanted: None,
+            latest: None,
+            registry_current: None,
+            registry_latest: None,
+            upgradable: false,
+            outdated: false,
+            error: None,
+        }
+    }
+
+    fn failed(mut self, err: anyhow::Error) -> Self {
+        self.error = Some(format!("{:#}", err));
+        self
+    }
+}
+
+/// Report from an outdated check
+#[derive(Debug, Clone, Default)]
+pub struct OutdatedReport {
+    /// One report per versioned entry, MCP servers first
+    pub entries: Vec<OutdatedEntry>,
+    /// Entries that could not be checked, e.g. not installed
+    pub warnings: Vec<String>,
+}
+
+impl OutdatedReport {
+    /// Whether any entry is behind upstream.
+    pub fn has_outdated(&self) -> bool {
+        self.entries.iter().any(|e| e.outdated)
+    }
+
+    /// Whether any entry could not be checked.
+    pub fn has_errors(&self) -> bool {
+        self.entries.iter().any(|e| e.error.is_some())
+    }
+}
+
+/// Outdated command orchestrator
+pub struct OutdatedCommand {
+    /// Shared context for dependency injection
+    ctx: InstallContext,
+}
+
+impl OutdatedCommand {
+    /// Create a new outdated command with custom global config directory
+    pub fn with_global_config_dir(
+        home_dir: PathBuf,
+        project_root: PathBuf,
+        state_dir: PathBuf,
+        global_config_dir: PathBuf,
+        link_mode: LinkMode,
+    ) -> Self {
+        Self {
+            ctx: InstallContext::new(
+                home_dir,
+                project_root,
+                state_dir,
+                global_config_dir,
+                link_mode,
+            ),
+        }
+    }
+
+    /// Create an outdated command with default paths
+    pub fn with_defaults() -> anyhow::Result<Self> {
+        Ok(Self {
+            ctx: InstallContext::with_defaults()?,
+        })
+    }
+
+    /// Create from AppContext (preferred).
+    pub fn from_context(ctx: AppContext) -> Self {
+        Self {
+            ctx: InstallContext::from_app_context(ctx),
+        }
+    }
+
+    /// Execute the outdated check
+    pub fn execute(&self, options: &OutdatedOptions) -> anyhow::Result<OutdatedReport> {
+        let merged = self.ctx.merged_config()?;
+        let lockfile = self.ctx.lockfile_service().load()?;
+        let resolver = self.ctx.source_resolver()?;
+        let mut report = OutdatedReport::default();
+
+        if options.kind.is_none_or(|k| k == InstallTarget::Mcp) {
+            let entries: BTreeMap<_, _> = merged.mcp.iter().collect();
+            for (name, entry) in entries {
+                if !entry.source.starts_with("registry:") {
+                    continue;
+                }
+                let Some(locked) = lockfile.mcp_servers.get(name.as_str()) else {
+                    report.warnings.push(not_installed("MCP server", name));
+                    continue;
+                };
+                let mut checked = OutdatedEntry::new(
+                    InstallTarget::Mcp,
+                    name,
+                    locked.scope,
+                    locked.resolved_version.clone(),
+                );
+                checked.constraint = locked.constraint.clone();
+                match check_mcp(&resolver, entry, locked, &mut checked) {
+                    Ok(true) => report.entries.push(checked),
+                    Ok(false) => {}
+                    Err(err) => report.entries.push(checked.failed(err)),
+                }
+            }
+        }
+
+        if options.kind.is_none_or(|k| k == InstallTarget::Skill) {
+            let entries: BTreeMap<_, _> = merged.skill.iter().collect();
+            for (name, entry) in entries {
+                let Some(locked) = lockfile.skills.get(name.as_str()) else {
+                    report.warnings.push(not_installed("Skill", name));
+                    continue;
+                };
+                let current = describe_commit(locked.git_ref.as_deref(), &locked.resolved_version);
+                let mut checked =
+                    OutdatedEntry::new(InstallTarget::Skill, name, locked.scope, current);
+                checked.registry_current = locked
+                    .origin
+                    .as_ref()
+                    .and_then(|o| o.registry_version.clone());
+                match self.check_skill(&resolver, entry, locked, &mut checked) {
+                    Ok(true) => report.entries.push(checked),
+                    Ok(false) => {}
+                    Err(err) => report.entries.push(checked.failed(err)),
+                }
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Compare a git skill with the remote's refs and its registry version.
+    ///
+    /// Returns `false` for sources without versions (local paths).
+    fn check_skill(
+        &self,
+        resolver: &SourceResolver,
+        entry: &SkillConfigEntry,
+        locked: &LockedSkill,
+        checked: &mut OutdatedEntry,
+    ) -> anyhow::Result<bool> {
+        let (source, metadata) = resolver.resolve_with_metadata(&entry.source)?;
+        let ResolvedSource::Git(spec) = source else {
+            return Ok(false);
+        };
+        let (constraint, label) = skill_constraint(entry, &spec)?;
+        checked.constraint = label;
+        checked.registry_latest = metadata.map(|m| m.marketplace_version);
+
+        let refs = self.ctx.git_fetcher().list_remote_refs(&spec)?;
+        let wanted = resolve_git_constraint(&constraint, &refs)?;
+        // Branch-tracking skills have no newer line than their branch
+        let latest = match constraint {
+            VersionConstraint::Semver(_) | VersionConstraint::Exact(_) => {
+                resolve_git_constraint(&VersionConstraint::Latest, &refs)?
+            }
+            _ => wanted.clone(),
+        };
+
+        checked.upgradable = !is_same_commit(&wanted.commit, locked);
+        checked.outdated = checked.upgradable
+            || !is_same_commit(&latest.commit, locked)
+            || registry_behind(&checked.registry_current, &checked.registry_latest);
+        checked.wanted = Some(describe_commit(wanted.reference.as_deref(), &wanted.commit));
+        checked.latest = Some(describe_commit(latest.reference.as_deref(), &latest.commit));
+        Ok(true)
+    }
+}
+
+/// Compare a registry MCP server with the versions its registry publishes.
+///
+/// Returns `false` when the source is not a registry or the registry does
+/// not publish versions.
+fn check_mcp(
+    resolver: &SourceResolver,
+    entry: &McpConfigEntry,
+    locked: &LockedMcpServer,
+    checked: &mut OutdatedEntry,
+) -> anyhow::Result<bool> {
+    let Some(registry_part) = entry.source.strip_prefix("registry:") else {
+        return Ok(false);
+    };
+    let resolve = |version: Option<&str>| -> anyhow::Result<Option<String>> {
+        Ok(resolver
+            .resolve_mcp_registry_version(registry_part, version)?
+            .and_then(|r| r.into_iter().next())
+            .map(|r| r.plugin_version))
+    };
+
+    let version = Some(locked.constraint.as_str()).filter(|c| *c != "latest");
+    let (Some(wanted), Some(latest)) = (resolve(version)?, resolve(None)?) else {
+        return Ok(false);
+    };
+    checked.upgradable = wanted != locked.resolved_version;
+    checked.outdated = checked.upgradable || latest != locked.resolved_version;
+    checked.wanted = Some(wanted);
+    checked.latest = Some(latest);
+    Ok(true)
+}
+
+fn registry_behind(current: &Option<String>, latest: &Option<String>) -> bool {
+    matches!((current, latest), (Some(current), Some(latest)) if current != latest)
+}
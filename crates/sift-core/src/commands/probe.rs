@@ -0,0 +1,243 @@
+//! Probe command implementation.
+//!
+//! Starts each declared MCP server as its clients would and performs the
+//! `initialize` and `tools/list` exchange, reporting what the server says
+//! about itself or why it failed to answer.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::client::ClientContext;
+use crate::config::McpConfigEntry;
+use crate::context::AppContext;
+use crate::fs::LinkMode;
+use crate::lockfile::{LockedMcpServer, Lockfile};
+use crate::mcp::McpServerBuilder;
+use crate::mcp::expand::{ExpandedServers, resolve_servers};
+use crate::mcp::probe::{DEFAULT_PROBE_TIMEOUT, ProbeResult, probe_server};
+use crate::types::ConfigScope;
+
+use super::context::InstallContext;
+use super::sync::DeclaredScopes;
+
+/// Options for the probe command
+#[derive(Debug, Clone)]
+pub struct ProbeOptions {
+    /// Only probe this MCP server
+    pub name: Option<String>,
+    /// Only probe servers declared at this scope
+    pub scope: Option<ConfigScope>,
+    /// Time each server gets to complete the exchange
+    pub timeout: Duration,
+}
+
+impl Default for ProbeOptions {
+    fn default() -> Self {
+        Self {
+            name: None,
+            scope: None,
+            timeout: DEFAULT_PROBE_TIMEOUT,
+        }
+    }
+}
+
+impl ProbeOptions {
+    /// Probe every declared MCP server
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Probe a single server
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Only probe servers declared at `scope`
+    pub fn with_scope(mut self, scope: ConfigScope) -> Self {
+        self.scope = Some(scope);
+        self
+    }
+
+    /// Set the per-server timeout
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+}
+
+/// Outcome of probing one server
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProbeEntry {
+    /// Server name as declared in sift.toml
+    pub name: String,
+    /// What the server reported, if it answered
+    pub result: Option<ProbeResult>,
+    /// Why the server could not be probed
+    pub error: Option<String>,
+}
+
+/// Report from a probe run
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProbeReport {
+    /// One entry per server, sorted by name
+    pub entries: Vec<ProbeEntry>,
+    /// Problems rendering the servers, e.g. unset variables
+    pub warnings: Vec<String>,
+}
+
+impl ProbeReport {
+    /// Whether any server failed its probe.
+    pub fn has_failures(&self) -> bool {
+        self.entries.iter().any(|e| e.error.is_some())
+    }
+}
+
+/// Probe command orchestrator
+pub struct ProbeCommand {
+    /// Shared context for dependency injection
+    ctx: InstallContext,
+}
+
+impl ProbeCommand {
+    /// Create a new probe command with custom global config directory
+    pub fn with_global_config_dir(
+        home_dir: PathBuf,
+        project_root: PathBuf,
+        state_dir: PathBuf,
+        global_config_dir: PathBuf,
+        link_mode: LinkMode,
+    ) -> Self {
+        Self {
+            ctx: InstallContext::new(
+                home_dir,
+                project_root,
+                state_dir,
+                global_config_dir,
+                link_mode,
+            ),
+        }
+    }
+
+    /// Create a probe command with default paths
+    pub fn with_defaults() -> anyhow::Result<Self> {
+        Ok(Self {
+            ctx: InstallContext::with_defaults()?,
+        })
+    }
+
+    /// Create from AppContext (preferred).
+    pub fn from_context(ctx: AppContext) -> Self {
+        Self {
+            ctx: InstallContext::from_app_context(ctx),
+        }
+    }
+
+    /// Execute the probe
+    pub fn execute(&self, options: &ProbeOptions) -> anyhow::Result<ProbeReport> {
+        let merged = self.ctx.merged_config()?;
+        if let Some(name) = &options.name
+            && !merged.mcp.contains_key(name)
+        {
+            anyhow::bail!("MCP server '{}' is not declared in sift.toml", name);
+        }
+
+        let declared = match options.scope {
+            Some(_) => {
+                let global = self.ctx.config_store(ConfigScope::Global).load()?;
+                let project = self
+                    .ctx
+                    .config_store(ConfigScope::PerProjectShared)
+                    .load()?;
+                Some(DeclaredScopes::collect(
+                    &global,
+                    &project,
+                    self.ctx.project_root(),
+                ))
+            }
+            None => None,
+        };
+
+        let lockfile = self.ctx.lockfile_service().load()?;
+        let client_ctx = self.ctx.client_context();
+        let builder = create_mcp_builder(&self.ctx);
+        let mut report = ProbeReport::default();
+
+        let entries: BTreeMap<_, _> = merged.mcp.iter().collect();
+        for (name, entry) in entries {
+            if options.name.as_ref().is_some_and(|only| only != name) {
+                continue;
+            }
+            if let (Some(scope), Some(declared)) = (options.scope, &declared)
+                && declared.mcp_scope(name) != scope
+            {
+                continue;
+            }
+
+            let expanded = match build_for_launch(&builder, &client_ctx, &lockfile, name, entry) {
+                Ok(expanded) => expanded,
+                Err(err) => {
+                    report.entries.push(ProbeEntry {
+                        name: name.clone(),
+                        result: None,
+                        error: Some(format!("{:#}", err)),
+                    });
+                    continue;
+                }
+            };
+            report.warnings.extend(expanded.warnings);
+
+            for server in &expanded.servers {
+                let outcome = probe_server(server, self.ctx.project_root(), options.timeout);
+                report.entries.push(ProbeEntry {
+                    name: server.name.clone(),
+                    error: outcome.as_ref().err().map(|err| format!("{:#}", err)),
+                    result: outcome.ok(),
+                });
+            }
+        }
+
+        Ok(report)
+    }
+}
+
+pub(super) fn create_mcp_builder(ctx: &InstallContext) -> McpServerBuilder<'_> {
+    let builder = McpServerBuilder::new(ctx.state_dir());
+    match ctx.source_resolver() {
+        Ok(resolver) => builder.with_source_resolver(resolver),
+        Err(_) => builder,
+    }
+}
+
+/// The locked version of `name`, unless the lockfile records none.
+pub(super) fn pinned_version<'a>(lockfile: &'a Lockfile, name: &str) -> Option<&'a str> {
+    lockfile
+        .mcp_servers
+        .get(name)
+        .and_then(LockedMcpServer::pinned_version)
+}
+
+/// Build `entry` the way sync delivers it, with the locked version and image
+/// digest, and resolve its variable and secret references.
+pub(super) fn build_for_launch(
+    builder: &McpServerBuilder<'_>,
+    client_ctx: &ClientContext,
+    lockfile: &Lockfile,
+    name: &str,
+    entry: &McpConfigEntry,
+) -> anyhow::Result<ExpandedServers> {
+    let pinned = pinned_version(lockfile, name);
+    let mut servers = builder.build(name, &entry.source, entry, pinned, false)?;
+    if let Some(digest) = lockfile
+        .mcp_servers
+        .get(name)
+        .filter(|locked| locked.registry == entry.source)
+        .and_then(|locked| locked.checksum.as_deref())
+    {
+        McpServerBuilder::pin_docker_image(&mut servers, Some(digest))?;
+    }
+    resolve_servers(client_ctx, &servers)
+}
@@ -0,0 +1,664 @@
+//! Sync command implementation.
+//!
+//! Reconciles client configs and skill directories with what `sift.toml`
+//! declares across all three scopes. This is what bare `sift install` runs
+//! after a fresh checkout or `git pull`. With `--prune`, entries that are
+//! still locked but no longer declared are removed first.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+
+use crate::client::ClientAdapter;
+use crate::config::SiftConfig;
+use crate::context::AppContext;
+use crate::deploy::scope::{RepoStatus, ResourceKind, ScopeRequest, ScopeResolution};
+use crate::deploy::targeting::TargetingPolicy;
+use crate::deploy::{InstallOrchestrator, UninstallOrchestrator};
+use crate::fs::LinkMode;
+use crate::lockfile::LockedMcpServer;
+use crate::mcp::McpServerBuilder;
+use crate::mcp::spec::McpResolvedServer;
+use crate::types::ConfigScope;
+
+use super::context::InstallContext;
+use super::install::{ClientInstallResult, ClientInstallStatus, InstallTarget};
+
+/// Options for the sync command
+#[derive(Debug, Clone, Default)]
+pub struct SyncOptions {
+    /// Overwrite user-modified entries and re-fetch skills
+    pub force: bool,
+    /// Remove orphaned entries (locked but no longer in sift.toml)
+    pub prune: bool,
+}
+
+impl SyncOptions {
+    /// Create default sync options
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the force flag
+    pub fn with_force(mut self, force: bool) -> Self {
+        self.force = force;
+        self
+    }
+
+    /// Set the prune flag
+    pub fn with_prune(mut self, prune: bool) -> Self {
+        self.prune = prune;
+        self
+    }
+}
+
+/// Result of syncing a single config entry
+#[derive(Debug, Clone)]
+pub struct SyncEntryReport {
+    /// Whether this is an MCP server or a skill
+    pub kind: InstallTarget,
+    /// Entry name as declared in sift.toml
+    pub name: String,
+    /// Scope the entry is declared in
+    pub scope: ConfigScope,
+    /// Per-client results, in registry order
+    pub clients: Vec<ClientInstallResult>,
+    /// Error that prevented the entry from syncing
+    pub error: Option<String>,
+}
+
+impl SyncEntryReport {
+    fn new(kind: InstallTarget, name: &str, scope: ConfigScope) -> Self {
+        Self {
+            kind,
+            name: name.to_string(),
+            scope,
+            clients: Vec::new(),
+            error: None,
+        }
+    }
+
+    /// Whether the entry was delivered to at least one client.
+    pub fn applied(&self) -> bool {
+        self.clients
+            .iter()
+            .any(|c| c.status == ClientInstallStatus::Applied)
+    }
+}
+
+/// An orphaned entry removed by `--prune`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrunedEntry {
+    /// Whether this was an MCP server or a skill
+    pub kind: InstallTarget,
+    /// Entry name from the lockfile
+    pub name: String,
+    /// Scope the entry was installed at
+    pub scope: ConfigScope,
+}
+
+/// Report from a sync operation
+#[derive(Debug, Clone, Default)]
+pub struct SyncReport {
+    /// One report per declared entry, MCP servers first
+    pub entries: Vec<SyncEntryReport>,
+    /// Orphaned entries removed by prune, sorted by name
+    pub pruned: Vec<PrunedEntry>,
+    /// Warnings not tied to a single client
+    pub warnings: Vec<String>,
+}
+
+impl SyncReport {
+    /// Whether any entry failed to sync.
+    pub fn has_errors(&self) -> bool {
+        self.entries.iter().any(|e| e.error.is_some())
+    }
+}
+
+/// Sync command orchestrator
+pub struct SyncCommand {
+    /// Shared context for dependency injection
+    ctx: InstallContext,
+}
+
+impl SyncCommand {
+    /// Create a new sync command with custom global config directory
+    pub fn with_global_config_dir(
+        home_dir: PathBuf,
+        project_root: PathBuf,
+        state_dir: PathBuf,
+        global_config_dir: PathBuf,
+        link_mode: LinkMode,
+    ) -> Self {
+        Self {
+            ctx: InstallContext::new(
+                home_dir,
+                project_root,
+                state_dir,
+                global_config_dir,
+                link_mode,
+            ),
+        }
+    }
+
+    /// Create a sync command with default paths
+    pub fn with_defaults() -> anyhow::Result<Self> {
+        Ok(Self {
+            ctx: InstallContext::with_defaults()?,
+        })
+    }
+
+    /// Create from AppContext (preferred).
+    pub fn from_context(ctx: AppContext) -> Self {
+        Self {
+            ctx: InstallContext::from_app_context(ctx),
+        }
+    }
+
+    /// Execute the sync command
+    pub fn execute(&self, options: &SyncOptions) -> anyhow::Result<SyncReport> {
+        let global = self.ctx.config_store(ConfigScope::Global).load()?;
+        let project = self
+            .ctx
+            .config_store(ConfigScope::PerProjectShared)
+            .load()?;
+        let declared = DeclaredScopes::collect(&global, &project, self.ctx.project_root());
+        let merged = self.ctx.merged_config()?;
+
+        let mut report = SyncReport::default();
+        if options.prune {
+            self.prune(merged, &mut report)?;
+        }
+        if merged.mcp.is_empty() && merged.skill.is_empty() {
+            report
+                .warnings
+                .push("No MCP servers or skills are declared in sift.toml".to_string());
+            return Ok(report);
+        }
+        self.sync_mcp(merged, &declared, options, None, &mut report)?;
+        self.sync_skills(merged, &declared, options, &mut report)?;
+        Ok(report)
+    }
+
+    /// Rewrite the client configs that contain any of `names`.
+    ///
+    /// Used by upgrade after it has re-pinned versions in the lockfile. Other
+    /// Sift-managed servers in the same configs are written unchanged.
+    pub(super) fn redeliver_mcp(
+        &self,
+        names: &BTreeSet<String>,
+        force: bool,
+    ) -> anyhow::Result<Vec<SyncEntryReport>> {
+        let global = self.ctx.config_store(ConfigScope::Global).load()?;
+        let project = self
+            .ctx
+            .config_store(ConfigScope::PerProjectShared)
+            .load()?;
+        let declared = DeclaredScopes::collect(&global, &project, self.ctx.project_root());
+        let merged = self.ctx.merged_config()?;
+        let options = SyncOptions::new().with_force(force);
+
+        let mut report = SyncReport::default();
+        self.sync_mcp(merged, &declared, &options, Some(names), &mut report)?;
+        Ok(report.entries)
+    }
+
+    /// Sync every MCP server.
+    ///
+    /// Servers are grouped per client and scope so each client config is
+    /// written once with the complete set of Sift-managed entries. With
+    /// `only`, just the configs holding those entries are written and only
+    /// those entries are reported. An entry that fails to resolve keeps the
+    /// client entries it was last delivered as.
+    fn sync_mcp(
+        &self,
+        merged: &SiftConfig,
+        declared: &DeclaredScopes,
+        options: &SyncOptions,
+        only: Option<&BTreeSet<String>>,
+        report: &mut SyncReport,
+    ) -> anyhow::Result<()> {
+        let client_ctx = self.ctx.client_context();
+        let registry = self.ctx.client_registry()?;
+        let repo = RepoStatus::from_project_root(self.ctx.project_root());
+        let lockfile = self.ctx.lockfile_service().load()?;
+        let builder = self.create_mcp_builder();
+
+        let mut groups: Vec<McpGroup<'_>> = Vec::new();
+        let mut pending: Vec<PendingLock> = Vec::new();
+        // Client entries of servers that failed to resolve; kept as they are
+        let mut retained: Vec<String> = Vec::new();
+
+        let entries: BTreeMap<_, _> = merged.mcp.iter().collect();
+        for (name, entry) in entries {
+            let scope = declared.mcp_scope(name);
+            let reported = only.is_none_or(|names| names.contains(name));
+            let index = report.entries.len();
+            let mut entry_report = SyncEntryReport::new(InstallTarget::Mcp, name, scope);
+
+            // Deliver the locked version; the constraint only matters to upgrade
+            let locked = lockfile.mcp_servers.get(name.as_str());
+            let constraint = locked
+                .map(|locked| locked.constraint.clone())
+                .filter(|c| c != "latest" && c != "unmanaged");
+            let pinned = locked.and_then(LockedMcpServer::pinned_version);
+            // Reuse the locked image digest unless the source changed
+            let digest = locked
+                .filter(|locked| !options.force && locked.registry == entry.source)
+                .and_then(|locked| locked.checksum.clone());
+            let (mut servers, resolved_version) = match builder.build_with_version(
+                name,
+                &entry.source,
+                entry,
+                pinned.or(constraint.as_deref()),
+                options.force,
+            ) {
+                Ok(built) => built,
+                Err(err) => {
+                    if let Some(locked) = locked {
+                        retained.extend(locked.entry_names());
+                    }
+                    if reported {
+                        entry_report.error = Some(format!("{:#}", err));
+                        report.entries.push(entry_report);
+                    }
+                    continue;
+                }
+            };
+
+            let checksum = match McpServerBuilder::pin_docker_image(&mut servers, digest.as_deref())
+            {
+                Ok(checksum) => checksum,
+                Err(err) => {
+                    report.warnings.push(format!(
+                        "Could not pin Docker image for '{}' to a digest: {:#}",
+                        name, err
+                    ));
+                    None
+                }
+            };
+
+            let policy = TargetingPolicy::new(entry.targets.clone(), entry.ignore_targets.clone());
+            let selection = match policy.select_clients(
+                &registry,
+                ResourceKind::Mcp,
+                ScopeRequest::Explicit(scope),
+                repo,
+            ) {
+                Ok(selection) => selection,
+                Err(err) => {
+                    if let Some(locked) = locked {
+                        retained.extend(locked.entry_names());
+                    }
+                    if reported {
+                        entry_report.error = Some(format!("{:#}", err));
+                        report.entries.push(entry_report);
+                    }
+                    continue;
+                }
+            };
+
+            for target in selection.clients {
+                match target.resolution {
+                    ScopeResolution::Apply(decision) => {
+                        let position = groups.iter().position(|g| {
+                            g.client.id() == target.client.id() && g.scope == decision.scope
+                        });
+                        let group = match position {
+                            Some(position) => &mut groups[position],
+                            None => {
+                                groups.push(McpGroup::new(target.client, decision.scope));
+                                groups.last_mut().expect("group was just pushed")
+                            }
+                        };
+                        group.servers.extend(servers.iter().cloned());
+                        if reported {
+                            group.entries.push(index);
+                        }
+                        entry_report.clients.push(ClientInstallResult {
+                            client_id: target.client.id().to_string(),
+                            status: ClientInstallStatus::Applied,
+                            scope: Some(decision.scope),
+                            message: None,
+                        });
+                    }
+                    ScopeResolution::Skip { warning } => {
+                        entry_report.clients.push(ClientInstallResult {
+                            client_id: target.client.id().to_string(),
+                            status: ClientInstallStatus::Skipped,
+                            scope: None,
+                            message: Some(warning),
+                        });
+                    }
+                }
+            }
+            if reported {
+                pending.push(PendingLock {
+                    index,
+                    constraint,
+                    resolved_version,
+                    checksum,
+                    servers,
+                    explicit: selection.explicit,
+                });
+                report.entries.push(entry_report);
+            }
+        }
+
+        for group in groups {
+            if only.is_some() && group.entries.is_empty() {
+                continue;
+            }
+            let (client_id, scope) = (group.client.id(), group.scope);
+            let orchestrator = self.create_orchestrator(scope)?;
+            let err = match orchestrator.apply_mcp_servers(
+                group.client,
+                &client_ctx,
+                scope,
+                &group.servers,
+                &retained,
+                options.force,
+            ) {
+                Ok(warnings) => {
+                    report.warnings.extend(warnings);
+                    continue;
+                }
+                Err(err) => err,
+            };
+            let message = format!("{:#}", err);
+            for index in group.entries {
+                let entry_report = &mut report.entries[index];
+                if let Some(result) = entry_report
+                    .clients
+                    .iter_mut()
+                    .find(|c| c.client_id == client_id && c.scope == Some(scope))
+                {
+                    result.status = ClientInstallStatus::Failed;
+                    result.scope = None;
+                    result.message = Some(message.clone());
+                }
+            }
+        }
+
+        for lock in pending {
+            let entry_report = &mut report.entries[lock.index];
+            let Some(entry) = merged.mcp.get(&entry_report.name) else {
+                continue;
+            };
+            entry_report.error = entry_error(&entry_report.clients, lock.explicit);
+            if entry_report.error.is_some() {
+                continue;
+            }
+            let orchestrator = self.create_orchestrator(entry_report.scope)?;
+            if let Err(err) = orchestrator.update_mcp_lockfile(
+                &entry_report.name,
+                entry,
+                lock.constraint.as_deref(),
+                lock.resolved_version.as_deref(),
+                lock.checksum.as_deref(),
+                &lock.servers,
+            ) {
+                entry_report.error = Some(format!("{:#}", err));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Sync every skill, fetching sources that are not cached yet.
+    fn sync_skills(
+        &self,
+        merged: &SiftConfig,
+        declared: &DeclaredScopes,
+        options: &SyncOptions,
+        report: &mut SyncReport,
+    ) -> anyhow::Result<()> {
+        let client_ctx = self.ctx.client_context();
+        let registry = self.ctx.client_registry()?;
+        let repo = RepoStatus::from_project_root(self.ctx.project_root());
+
+        let entries: BTreeMap<_, _> = merged.skill.iter().collect();
+        for (name, entry) in entries {
+            let scope = declared.skill_scope(name);
+            let mut entry_report = SyncEntryReport::new(InstallTarget::Skill, name, scope);
+
+            let policy = TargetingPolicy::new(entry.targets.clone(), entry.ignore_targets.clone());
+            let selection = match policy.select_clients(
+                &registry,
+                ResourceKind::Skill,
+                ScopeRequest::Explicit(scope),
+                repo,
+            ) {
+                Ok(selection) => selection,
+                Err(err) => {
+                    entry_report.error = Some(format!("{:#}", err));
+                    report.entries.push(entry_report);
+                    continue;
+                }
+            };
+
+            let orchestrator = self.create_orchestrator(scope)?;
+            for target in selection.clients {
+                let client_id = target.client.id().to_string();
+                let result = match target.resolution {
+                    ScopeResolution::Apply(decision) => match orchestrator.sync_skill(
+                        target.client,
+                        &client_ctx,
+                        name,
+                        &entry.source,
+                        &decision,
+                        options.force,
+                    ) {
+                        Ok(_) => ClientInstallResult {
+                            client_id,
+                            status: ClientInstallStatus::Applied,
+                            scope: Some(decision.scope),
+                            message: None,
+                        },
+                        Err(err) => ClientInstallResult {
+                            client_id,
+                            status: ClientInstallStatus::Failed,
+                            scope: None,
+                            message: Some(format!("{:#}", err)),
+                        },
+                    },
+                    ScopeResolution::Skip { warning } => ClientInstallResult {
+                        client_id,
+                        status: ClientInstallStatus::Skipped,
+                        scope: None,
+                        message: Some(warning),
+                    },
+                };
+                entry_report.clients.push(result);
+            }
+            entry_report.error = entry_error(&entry_report.clients, selection.explicit);
+            report.entries.push(entry_report);
+        }
+
+        Ok(())
+    }
+
+    /// Remove entries that are locked but no longer declared in sift.toml.
+    ///
+    /// Runs before delivery so user-modified orphans are released from
+    /// ownership instead of failing the client config write.
+    fn prune(&self, merged: &SiftConfig, report: &mut SyncReport) -> anyhow::Result<()> {
+        let client_ctx = self.ctx.client_context();
+        let registry = self.ctx.client_registry()?;
+        let lockfile = self.ctx.lockfile_service().load()?;
+
+        let orphaned_mcp: BTreeMap<_, _> = lockfile
+            .mcp_servers
+            .iter()
+            .filter(|(name, _)| !merged.mcp.contains_key(*name))
+            .collect();
+        for (name, locked) in orphaned_mcp {
+            // A registry plugin is delivered as its servers, not its own name
+            let entries = locked.entry_names();
+            let orchestrator = self.create_uninstall_orchestrator(locked.scope);
+            for client in registry.mcp_clients_for_scope(locked.scope) {
+                orchestrator
+                    .prune_mcp(client, &client_ctx, &entries, &mut report.warnings)
+                    .with_context(|| format!("Failed to prune MCP server '{}'", name))?;
+            }
+            orchestrator.forget_mcp(name)?;
+            report.pruned.push(PrunedEntry {
+                kind: InstallTarget::Mcp,
+                name: name.clone(),
+                scope: locked.scope,
+            });
+        }
+
+        let orphaned_skills: BTreeMap<_, _> = lockfile
+            .skills
+            .iter()
+            .filter(|(name, _)| !merged.skill.contains_key(*name))
+            .collect();
+        for (name, locked) in orphaned_skills {
+            let orchestrator = self.create_uninstall_orchestrator(locked.scope);
+            for client in registry.skill_clients_for_scope(locked.scope) {
+                orchestrator
+                    .cleanup_skill_delivery(client, &client_ctx, name)
+                    .with_context(|| format!("Failed to prune skill '{}'", name))?;
+            }
+            self.ctx.lockfile_service().remove_skill(name)?;
+            report.pruned.push(PrunedEntry {
+                kind: InstallTarget::Skill,
+                name: name.clone(),
+                scope: locked.scope,
+            });
+        }
+
+        report.pruned.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(())
+    }
+
+    fn create_uninstall_orchestrator(&self, scope: ConfigScope) -> UninstallOrchestrator {
+        UninstallOrchestrator::new(self.ctx.config_store(scope), self.ctx.lockfile_service())
+    }
+
+    fn create_orchestrator(&self, scope: ConfigScope) -> anyhow::Result<InstallOrchestrator> {
+        Ok(InstallOrchestrator::new(
+            self.ctx.config_store(scope),
+            self.ctx.lockfile_service(),
+            self.ctx.skill_installer(),
+            self.ctx.source_resolver()?,
+            self.ctx.git_fetcher(),
+            self.ctx.link_mode(),
+        ))
+    }
+
+    fn create_mcp_builder(&self) -> McpServerBuilder<'_> {
+        let builder = McpServerBuilder::new(self.ctx.state_dir());
+        match self.ctx.source_resolver() {
+            Ok(resolver) => builder.with_source_resolver(resolver),
+            Err(_) => builder,
+        }
+    }
+}
+
+/// Derive an entry-level error from its client results.
+///
+/// Explicit targets fail on the first failed client; implicit targets only
+/// fail when nothing was delivered.
+pub(super) fn entry_error(clients: &[ClientInstallResult], explicit: bool) -> Option<String> {
+    let failed = clients
+        .iter()
+        .find(|c| c.status == ClientInstallStatus::Failed)?;
+    let delivered = clients
+        .iter()
+        .any(|c| c.status == ClientInstallStatus::Applied);
+    if explicit || !delivered {
+        Some(format!(
+            "Failed to sync to '{}': {}",
+            failed.client_id,
+            failed.message.as_deref().unwrap_or("unknown error")
+        ))
+    } else {
+        None
+    }
+}
+
+/// Lockfile update deferred until every client config has been written.
+struct PendingLock {
+    index: usize,
+    constraint: Option<String>,
+    resolved_version: Option<String>,
+    checksum: Option<String>,
+    servers: Vec<McpResolvedServer>,
+    explicit: bool,
+}
+
+/// Servers destined for one client config at one scope.
+struct McpGroup<'a> {
+    client: &'a dyn ClientAdapter,
+    scope: ConfigScope,
+    servers: Vec<McpResolvedServer>,
+    entries: Vec<usize>,
+}
+
+impl<'a> McpGroup<'a> {
+    fn new(client: &'a dyn ClientAdapter, scope: ConfigScope) -> Self {
+        Self {
+            client,
+            scope,
+            servers: Vec::new(),
+            entries: Vec::new(),
+        }
+    }
+}
+
+/// Scope each entry is declared in.
+///
+/// Uses the same "declaration location" rules as `sift status`: later layers
+/// (project, then project-local) take over entries of the same name.
+#[derive(Debug, Default)]
+pub(super) struct DeclaredScopes {
+    mcp: HashMap<String, ConfigScope>,
+    skill: HashMap<String, ConfigScope>,
+}
+
+impl DeclaredScopes {
+    pub(super) fn collect(global: &SiftConfig, project: &SiftConfig, project_root: &Path) -> Self {
+        let mut scopes = Self::default();
+        scopes.add(global, ConfigScope::Global);
+        scopes.add(project, ConfigScope::PerProjectShared);
+        if let Some((_key, local)) = global.get_project_config(project_root) {
+            for name in local.mcp.keys() {
+                scopes
+                    .mcp
+                    .insert(name.clone(), ConfigScope::PerProjectLocal);
+            }
+            for name in local.skill.keys() {
+                scopes
+                    .skill
+                    .insert(name.clone(), ConfigScope::PerProjectLocal);
+            }
+        }
+        scopes
+    }
+
+    fn add(&mut self, config: &SiftConfig, scope: ConfigScope) {
+        for name in config.mcp.keys() {
+            self.mcp.insert(name.clone(), scope);
+        }
+        for name in config.skill.keys() {
+            self.skill.insert(name.clone(), scope);
+        }
+    }
+
+    pub(super) fn mcp_scope(&self, name: &str) -> ConfigScope {
+        self.mcp
+            .get(name)
+            .copied()
+            .unwrap_or(ConfigScope::PerProjectShared)
+    }
+
+    pub(super) fn skill_scope(&self, name: &str) -> ConfigScope {
+        self.skill
+            .get(name)
+            .copied()
+            .unwrap_or(ConfigScope::PerProjectShared)
+    }
+}
@@ -0,0 +1,323 @@
+//! Tools command implementation.
+//!
+//! Starts one declared MCP server and lists the tools, prompts and resources
+//! it serves. For MCPB bundles the live lists are compared with what
+//! `manifest.json` declares.
+
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::context::AppContext;
+use crate::fs::LinkMode;
+use crate::mcp::probe::{DEFAULT_PROBE_TIMEOUT, ServerInventory, inspect_server};
+use crate::mcpb::McpbManifest;
+
+use super::context::InstallContext;
+use super::probe::{build_for_launch, create_mcp_builder, pinned_version};
+
+/// Options for the tools command
+#[derive(Debug, Clone)]
+pub struct ToolsOptions {
+    /// MCP server to inspect, as declared in sift.toml
+    pub name: String,
+    /// Time the server gets to answer
+    pub timeout: Duration,
+}
+
+impl ToolsOptions {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            timeout: DEFAULT_PROBE_TIMEOUT,
+        }
+    }
+
+    /// Set the timeout
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+}
+
+/// Differences between an MCPB manifest and the server it describes.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ManifestDiff {
+    /// Tools declared in manifest.json that the server does not list
+    pub missing_tools: Vec<String>,
+    /// Tools the server lists that manifest.json does not declare
+    pub undeclared_tools: Vec<String>,
+    /// The manifest allows tools generated at runtime
+    pub tools_generated: bool,
+    /// Prompts declared in manifest.json that the server does not list
+    pub missing_prompts: Vec<String>,
+    /// Prompts the server lists that manifest.json does not declare
+    pub undeclared_prompts: Vec<String>,
+    /// The manifest allows prompts generated at runtime
+    pub prompts_generated: bool,
+}
+
+impl ManifestDiff {
+    /// Compare the declared tools and prompts with what the server listed.
+    pub fn compare(manifest: &McpbManifest, inventory: &ServerInventory) -> Self {
+        let declared_tools = manifest.tools.iter().map(|t| t.name.as_str());
+        let live_tools = inventory.tools.iter().map(|t| t.name.as_str());
+        let (missing_tools, undeclared_tools) = difference(declared_tools, live_tools);
+
+        let declared_prompts = manifest.prompts.iter().map(|p| p.name.as_str());
+        let live_prompts = inventory.prompts.iter().map(|p| p.name.as_str());
+        let (missing_prompts, undeclared_prompts) = difference(declared_prompts, live_prompts);
+
+        Self {
+            missing_tools,
+            undeclared_tools,
+            tools_generated: manifest.tools_generated,
+            missing_prompts,
+            undeclared_prompts,
+            prompts_generated: manifest.prompts_generated,
+        }
+    }
+
+    /// Human-readable mismatches.
+    ///
+    /// Undeclared tools and prompts only count when the manifest does not
+    /// set `tools_generated` / `prompts_generated`.
+    pub fn mismatches(&self) -> Vec<String> {
+        let mut mismatches = Vec::new();
+        for name in &self.missing_tools {
+            mismatches.push(format!(
+                "Tool '{}' is declared in manifest.json but not served",
+                name
+            ));
+        }
+        if !self.tools_generated {
+            for name in &self.undeclared_tools {
+                mismatches.push(format!(
+                    "Tool '{}' is served but not declared in manifest.json \
+                     (set tools_generated if the server creates tools at runtime)",
+                    name
+                ));
+            }
+        }
+        for name in &self.missing_prompts {
+            mismatches.push(format!(
+                "Prompt '{}' is declared in manifest.json but not served",
+                name
+            ));
+        }
+        if !self.prompts_generated {
+            for name in &self.undeclared_prompts {
+                mismatches.push(format!(
+                    "Prompt '{}' is served but not declared in manifest.json \
+                     (set prompts_generated if the server creates prompts at runtime)",
+                    name
+                ));
+            }
+        }
+        mismatches
+    }
+}
+
+/// Names only in `declared`, and names only in `live`, both sorted.
+fn difference<'a>(
+    declared: impl Iterator<Item = &'a str>,
+    live: impl Iterator<Item = &'a str>,
+) -> (Vec<String>, Vec<String>) {
+    let declared: BTreeSet<&str> = declared.collect();
+    let live: BTreeSet<&str> = live.collect();
+    (
+        declared.difference(&live).map(|s| s.to_string()).collect(),
+        live.difference(&declared).map(|s| s.to_string()).collect(),
+    )
+}
+
+/// What one server serves
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolsEntry {
+    /// Server name
+    pub name: String,
+    /// Tools, prompts and resources the server listed
+    pub inventory: ServerInventory,
+    /// Comparison with manifest.json, for MCPB bundles
+    pub manifest: Option<ManifestDiff>,
+}
+
+/// Report from the tools command
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ToolsReport {
+    /// One entry per server the declaration expands to
+    pub entries: Vec<ToolsEntry>,
+    /// Problems rendering the servers, e.g. unset variables
+    pub warnings: Vec<String>,
+}
+
+impl ToolsReport {
+    /// Whether any bundle serves something other than it declares.
+    pub fn has_mismatches(&self) -> bool {
+        self.entries.iter().any(|entry| {
+            entry
+                .manifest
+                .as_ref()
+                .is_some_and(|diff| !diff.mismatches().is_empty())
+        })
+    }
+}
+
+/// Tools command orchestrator
+pub struct ToolsCommand {
+    /// Shared context for dependency injection
+    ctx: InstallContext,
+}
+
+impl ToolsCommand {
+    /// Create a new tools command with custom global config directory
+    pub fn with_global_config_dir(
+        home_dir: PathBuf,
+        project_root: PathBuf,
+        state_dir: PathBuf,
+        global_config_dir: PathBuf,
+        link_mode: LinkMode,
+    ) -> Self {
+        Self {
+            ctx: InstallContext::new(
+                home_dir,
+                project_root,
+                state_dir,
+                global_config_dir,
+                link_mode,
+            ),
+        }
+    }
+
+    /// Create a tools command with default paths
+    pub fn with_defaults() -> anyhow::Result<Self> {
+        Ok(Self {
+            ctx: InstallContext::with_defaults()?,
+        })
+    }
+
+    /// Create from AppContext (preferred).
+    pub fn from_context(ctx: AppContext) -> Self {
+        Self {
+            ctx: InstallContext::from_app_context(ctx),
+        }
+    }
+
+    /// Execute the inspection
+    pub fn execute(&self, options: &ToolsOptions) -> anyhow::Result<ToolsReport> {
+        let merged = self.ctx.merged_config()?;
+        let entry = merged.mcp.get(&options.name).ok_or_else(|| {
+            anyhow::anyhow!("MCP server '{}' is not declared in sift.toml", options.name)
+        })?;
+
+        let lockfile = self.ctx.lockfile_service().load()?;
+        let client_ctx = self.ctx.client_context();
+        let builder = create_mcp_builder(&self.ctx);
+        let expanded = build_for_launch(&builder, &client_ctx, &lockfile, &options.name, entry)?;
+        let version = pinned_version(&lockfile, &options.name);
+        let manifests = builder.bundle_manifests(&options.name, &entry.source, entry, version)?;
+
+        let mut report = ToolsReport {
+            warnings: expanded.warnings,
+            ..Default::default()
+        };
+        for server in &expanded.servers {
+            let inventory = inspect_server(server, self.ctx.project_root(), options.timeout)
+                .map_err(|err| err.context(format!("Failed to inspect '{}'", server.name)))?;
+            report.entries.push(ToolsEntry {
+                name: server.name.clone(),
+                manifest: manifests
+                    .iter()
+                    .find(|(name, _)| *name == server.name)
+                    .map(|(_, manifest)| ManifestDiff::compare(manifest, &inventory)),
+                inventory,
+            });
+        }
+
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mcp::probe::{ProbeResult, PromptInfo, ToolInfo};
+
+    fn inventory(tools: &[&str], prompts: &[&str]) -> ServerInventory {
+        ServerInventory {
+            probe: ProbeResult {
+                server_name: None,
+                server_version: None,
+                protocol_version: None,
+                tool_count: tools.len(),
+                startup_ms: 0,
+            },
+            tools: tools
+                .iter()
+                .map(|name| ToolInfo {
+                    name: name.to_string(),
+                    description: None,
+                    input_schema: serde_json::Value::Null,
+                })
+                .collect(),
+            prompts: prompts
+                .iter()
+                .map(|name| PromptInfo {
+                    name: name.to_string(),
+                    description: None,
+                    arguments: Vec::new(),
+                })
+                .collect(),
+            resources: Vec::new(),
+        }
+    }
+
+    fn manifest(json: serde_json::Value) -> McpbManifest {
+        let mut value = serde_json::json!({
+            "manifest_version": "0.2",
+            "name": "bundle",
+            "version": "1.0.0",
+            "description": "Test bundle",
+            "author": {"name": "Test"},
+            "server": {"type": "node", "entry_point": "server.js"}
+        });
+        value
+            .as_object_mut()
+            .unwrap()
+            .extend(json.as_object().unwrap().clone());
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_manifest_diff_flags_missing_and_undeclared() {
+        let manifest = manifest(serde_json::json!({
+            "tools": [{"name": "read"}, {"name": "write"}],
+            "prompts": [{"name": "review"}]
+        }));
+
+        let diff = ManifestDiff::compare(&manifest, &inventory(&["read", "delete"], &["review"]));
+
+        assert_eq!(diff.missing_tools, vec!["write"]);
+        assert_eq!(diff.undeclared_tools, vec!["delete"]);
+        assert!(diff.missing_prompts.is_empty());
+        let mismatches = diff.mismatches();
+        assert_eq!(mismatches.len(), 2);
+        assert!(mismatches[0].contains("'write' is declared"));
+        assert!(mismatches[1].contains("'delete' is served"));
+    }
+
+    #[test]
+    fn test_manifest_diff_allows_generated_tools() {
+        let manifest = manifest(serde_json::json!({
+            "tools": [{"name": "read"}],
+            "tools_generated": true
+        }));
+
+        let diff = ManifestDiff::compare(&manifest, &inventory(&["read", "query_users"], &[]));
+
+        assert_eq!(diff.undeclared_tools, vec!["query_users"]);
+        assert!(diff.mismatches().is_empty());
+    }
+}
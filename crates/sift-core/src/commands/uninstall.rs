@@ -5,6 +5,7 @@
 use std::path::PathBuf;
 
 use crate::client::claude_code::ClaudeCodeClient;
+use crate::client::registry::ClientRegistry;
 use crate::client::{ClientAdapter, ClientContext};
 use crate::config::{ConfigStore, merge_configs};
 use crate::deploy::UninstallOrchestrator;
@@ -205,6 +206,18 @@ impl UninstallCommand {
         let mut warnings = Vec::new();
         let mut changed = false;
 
+        // A disabled client's files are left alone
+        let registry = self.client_registry()?;
+        let delivery = if registry.is_enabled(client.id()) {
+            Some(&client as &dyn ClientAdapter)
+        } else {
+            warnings.push(format!(
+                "Skipping removal from '{}': client is disabled in sift.toml",
+                client.id()
+            ));
+            None
+        };
+
         for scope in scopes {
             if matches!(options.scope, UninstallScope::All) {
                 let support = match options.target {
@@ -227,9 +240,11 @@ impl UninstallCommand {
             let orchestrator = UninstallOrchestrator::new(config_store, lockfile_service);
 
             let report = match options.target {
-                UninstallTarget::Mcp => orchestrator.uninstall_mcp(&client, &ctx, &options.name)?,
+                UninstallTarget::Mcp => {
+                    orchestrator.uninstall_mcp(delivery, &ctx, &options.name)?
+                }
                 UninstallTarget::Skill => {
-                    orchestrator.uninstall_skill(&client, &ctx, &options.name)?
+                    orchestrator.uninstall_skill(delivery, &ctx, &options.name)?
                 }
             };
 
@@ -325,6 +340,16 @@ impl UninstallCommand {
         Ok(None)
     }
 
+    /// Build the client registry from the merged `[clients]` table.
+    fn client_registry(&self) -> anyhow::Result<ClientRegistry> {
+        let global = self.create_config_store(ConfigScope::Global)?.load()?;
+        let project = self
+            .create_config_store(ConfigScope::PerProjectShared)?
+            .load()?;
+        let merged = merge_configs(Some(global), Some(project), &self.project_root)?;
+        ClientRegistry::with_config_clients(&merged.clients)
+    }
+
     fn create_config_store(&self, scope: ConfigScope) -> anyhow::Result<ConfigStore> {
         Ok(ConfigStore::from_paths(
             scope,
@@ -0,0 +1,528 @@
+//! Upgrade command implementation.
+//!
+//! Re-resolves each installed entry's version constraint against its source
+//! and moves the lockfile to the newest match. Only entries whose resolved
+//! version changed are redelivered to clients. This is the only command that
+//! moves locked versions forward (design.md §8).
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::PathBuf;
+
+use crate::config::{McpConfigEntry, SkillConfigEntry};
+use crate::context::AppContext;
+use crate::deploy::InstallOrchestrator;
+use crate::deploy::scope::{RepoStatus, ResourceKind, ScopeRequest, ScopeResolution};
+use crate::deploy::targeting::TargetingPolicy;
+use crate::fs::LinkMode;
+use crate::git::GitSpec;
+use crate::lockfile::{LockedMcpServer, LockedSkill};
+use crate::source::ResolvedSource;
+use crate::types::ConfigScope;
+use crate::version::git::{ResolvedGitRef, resolve_git_constraint};
+use crate::version::{VersionConstraint, VersionResolver};
+
+use super::context::InstallContext;
+use super::install::{ClientInstallResult, ClientInstallStatus, InstallTarget};
+use super::sync::{SyncCommand, entry_error};
+
+/// Options for the upgrade command
+#[derive(Debug, Clone, Default)]
+pub struct UpgradeOptions {
+    /// Only upgrade MCP servers or skills
+    pub kind: Option<InstallTarget>,
+    /// Only upgrade the entry with this name
+    pub name: Option<String>,
+}
+
+impl UpgradeOptions {
+    /// Upgrade every installed entry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Restrict the upgrade to one kind of entry
+    pub fn with_kind(mut self, kind: InstallTarget) -> Self {
+        self.kind = Some(kind);
+        self
+    }
+
+    /// Restrict the upgrade to a single entry
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    fn includes(&self, kind: InstallTarget, name: &str) -> bool {
+        self.kind.is_none_or(|k| k == kind) && self.name.as_deref().is_none_or(|n| n == name)
+    }
+}
+
+/// Outcome of upgrading a single entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpgradeStatus {
+    /// A newer version was locked and delivered
+    Upgraded,
+    /// The locked version already satisfies the constraint best
+    UpToDate,
+    /// Resolution or delivery failed; the previous version stays locked
+    Failed,
+}
+
+/// Result of upgrading a single entry
+#[derive(Debug, Clone)]
+pub struct UpgradeEntry {
+    /// Whether this is an MCP server or a skill
+    pub kind: InstallTarget,
+    /// Entry name as declared in sift.toml
+    pub name: String,
+    /// Scope the entry is locked at
+    pub scope: ConfigScope,
+    /// Constraint the entry was resolved against
+    pub constraint: String,
+    /// Previously locked version
+    pub from: String,
+    /// Newly resolved version (equal to `from` when up to date)
+    pub to: String,
+    /// Outcome of the upgrade
+    pub status: UpgradeStatus,
+    /// Per-client delivery results for upgraded entries
+    pub clients: Vec<ClientInstallResult>,
+    /// Error that prevented the upgrade
+    pub error: Option<String>,
+}
+
+/// Report from an upgrade operation
+#[derive(Debug, Clone, Default)]
+pub struct UpgradeReport {
+    /// One report per versioned entry, MCP servers first
+    pub entries: Vec<UpgradeEntry>,
+    /// Entries that were skipped, e.g. not installed or not versioned
+    pub warnings: Vec<String>,
+}
+
+impl UpgradeReport {
+    /// Whether any entry failed to upgrade.
+    pub fn has_errors(&self) -> bool {
+        self.entries
+            .iter()
+            .any(|e| e.status == UpgradeStatus::Failed)
+    }
+
+    /// Entries that moved to a new version.
+    pub fn upgraded(&self) -> impl Iterator<Item = &UpgradeEntry> {
+        self.entries
+            .iter()
+            .filter(|e| e.status == UpgradeStatus::Upgraded)
+    }
+}
+
+/// Upgrade command orchestrator
+pub struct UpgradeCommand {
+    /// Shared context for dependency injection
+    ctx: InstallContext,
+}
+
+impl UpgradeCommand {
+    /// Create a new upgrade command with custom global config directory
+    pub fn with_global_config_dir(
+        home_dir: PathBuf,
+        project_root: PathBuf,
+        state_dir: PathBuf,
+        global_config_dir: PathBuf,
+        link_mode: LinkMode,
+    ) -> Self {
+        Self {
+            ctx: InstallContext::new(
+                home_dir,
+                project_root,
+                state_dir,
+                global_config_dir,
+                link_mode,
+            ),
+        }
+    }
+
+    /// Create an upgrade command with default paths
+    pub fn with_defaults() -> anyhow::Result<Self> {
+        Ok(Self {
+            ctx: InstallContext::with_defaults()?,
+        })
+    }
+
+    /// Create from AppContext (preferred).
+    pub fn from_context(ctx: AppContext) -> Self {
+        Self {
+            ctx: InstallContext::from_app_context(ctx),
+        }
+    }
+
+    /// Execute the upgrade command
+    pub fn execute(&self, options: &UpgradeOptions) -> anyhow::Result<UpgradeReport> {
+        let merged = self.ctx.merged_config()?;
+        if let Some(name) = &options.name {
+            let declared = (options.includes(InstallTarget::Mcp, name)
+                && merged.mcp.contains_key(name))
+                || (options.includes(InstallTarget::Skill, name)
+                    && merged.skill.contains_key(name));
+            if !declared {
+                anyhow::bail!("'{}' is not declared in sift.toml", name);
+            }
+        }
+
+        let mut report = UpgradeReport::default();
+        let mcp: BTreeMap<_, _> = merged
+            .mcp
+            .iter()
+            .filter(|(name, _)| options.includes(InstallTarget::Mcp, name))
+            .collect();
+        self.upgrade_mcp(mcp, &mut report)?;
+
+        let skills: BTreeMap<_, _> = merged
+            .skill
+            .iter()
+            .filter(|(name, _)| options.includes(InstallTarget::Skill, name))
+            .collect();
+        for (name, entry) in skills {
+            self.upgrade_skill(name, entry, &mut report)?;
+        }
+        Ok(report)
+    }
+
+    /// Re-resolve registry MCP servers and rewrite the configs that changed.
+    fn upgrade_mcp(
+        &self,
+        entries: BTreeMap<&String, &McpConfigEntry>,
+        report: &mut UpgradeReport,
+    ) -> anyhow::Result<()> {
+        let lockfile_service = self.ctx.lockfile_service();
+        let lockfile = lockfile_service.load()?;
+        let resolver = self.ctx.source_resolver()?;
+
+        let mut previous: BTreeMap<String, LockedMcpServer> = BTreeMap::new();
+        for (name, entry) in entries {
+            let Some(registry_part) = entry.source.strip_prefix("registry:") else {
+                continue;
+            };
+            let Some(locked) = lockfile.mcp_servers.get(name.as_str()) else {
+                report.warnings.push(not_installed("MCP server", name));
+                continue;
+            };
+
+            let mut upgrade = UpgradeEntry::new(InstallTarget::Mcp, name, locked.scope);
+            upgrade.constraint = locked.constraint.clone();
+            upgrade.from = locked.resolved_version.clone();
+            let version = Some(locked.constraint.as_str()).filter(|c| *c != "latest");
+            let resolved = match resolver.resolve_mcp_registry_version(registry_part, version) {
+                Ok(resolutions) => resolutions
+                    .and_then(|r| r.into_iter().next())
+                    .map(|r| r.plugin_version),
+                Err(err) => {
+                    report.entries.push(upgrade.failed(err));
+                    continue;
+                }
+            };
+            let Some(resolved) = resolved else {
+                report.warnings.push(format!(
+                    "MCP server '{}': registry does not publish versions; skipped",
+                    name
+                ));
+                continue;
+            };
+
+            upgrade.to = resolved.clone();
+            if resolved != locked.resolved_version {
+                let mut upgraded = locked.clone();
+                upgraded.resolved_version = resolved;
+                // A new release may ship a new image; sync pins it again
+                upgraded.checksum = None;
+                lockfile_service.add_mcp(name, upgraded)?;
+                previous.insert(name.clone(), locked.clone());
+                upgrade.status = UpgradeStatus::Upgraded;
+            }
+            report.entries.push(upgrade);
+        }
+
+        if previous.is_empty() {
+            return Ok(());
+        }
+
+        // Rewrite only the client configs holding an upgraded server
+        let names: BTreeSet<String> = previous.keys().cloned().collect();
+        let delivered = self.sync_command().redeliver_mcp(&names, false)?;
+        for synced in delivered {
+            let Some(upgrade) = report
+                .entries
+                .iter_mut()
+                .find(|e| e.kind == InstallTarget::Mcp && e.name == synced.name)
+            else {
+                continue;
+            };
+            upgrade.clients = synced.clients;
+            if let Some(error) = synced.error {
+                upgrade.status = UpgradeStatus::Failed;
+                upgrade.error = Some(error);
+                if let Some(locked) = previous.remove(&synced.name) {
+                    lockfile_service.add_mcp(&synced.name, locked)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Re-resolve a git skill against the remote's refs and redeliver it if
+    /// the commit moved.
+    fn upgrade_skill(
+        &self,
+        name: &str,
+        entry: &SkillConfigEntry,
+        report: &mut UpgradeReport,
+    ) -> anyhow::Result<()> {
+        let Some(locked) = self.ctx.lockfile_service().get_skill(name)? else {
+            report.warnings.push(not_installed("Skill", name));
+            return Ok(());
+        };
+
+        let mut upgrade = UpgradeEntry::new(InstallTarget::Skill, name, locked.scope);
+        upgrade.from = describe_commit(locked.git_ref.as_deref(), &locked.resolved_version);
+
+        let resolved = match self.resolve_skill(entry, &mut upgrade) {
+            Ok(Some(resolved)) => resolved,
+            Ok(None) => return Ok(()),
+            Err(err) => {
+                report.entries.push(upgrade.failed(err));
+                return Ok(());
+            }
+        };
+
+        upgrade.to = describe_commit(resolved.reference.as_deref(), &resolved.commit);
+        if is_same_commit(&resolved.commit, &locked) {
+            report.entries.push(upgrade);
+            return Ok(());
+        }
+
+        match self.deliver_skill(name, entry, &locked, &resolved, &upgrade.constraint) {
+            Ok((clients, explicit)) => {
+                upgrade.error = entry_error(&clients, explicit);
+                upgrade.status = if upgrade.error.is_some() {
+                    UpgradeStatus::Failed
+                } else {
+                    UpgradeStatus::Upgraded
+                };
+                upgrade.clients = clients;
+                report.entries.push(upgrade);
+            }
+            Err(err) => report.entries.push(upgrade.failed(err)),
+        }
+        Ok(())
+    }
+
+    /// Resolve a skill's constraint to a remote ref.
+    ///
+    /// Returns `None` for sources without versions (local paths).
+    fn resolve_skill(
+        &self,
+        entry: &SkillConfigEntry,
+        upgrade: &mut UpgradeEntry,
+    ) -> anyhow::Result<Option<ResolvedGitRef>> {
+        let resolver = self.ctx.source_resolver()?;
+        let ResolvedSource::Git(spec) = resolver.resolve(&entry.source)? else {
+            return Ok(None);
+        };
+
+        let (constraint, label) = skill_constraint(entry, &spec)?;
+        upgrade.constraint = label;
+
+        let refs = self.ctx.git_fetcher().list_remote_refs(&spec)?;
+        resolve_git_constraint(&constraint, &refs).map(Some)
+    }
+
+    /// Fetch the resolved ref and deliver it to every targeted client.
+    fn deliver_skill(
+        &self,
+        name: &str,
+        entry: &SkillConfigEntry,
+        locked: &LockedSkill,
+        resolved: &ResolvedGitRef,
+        constraint: &str,
+    ) -> anyhow::Result<(Vec<ClientInstallResult>, bool)> {
+        let client_ctx = self.ctx.client_context();
+        let registry = self.ctx.client_registry()?;
+        let policy = TargetingPolicy::new(entry.targets.clone(), entry.ignore_targets.clone());
+        let selection = policy.select_clients(
+            &registry,
+            ResourceKind::Skill,
+            ScopeRequest::Explicit(locked.scope),
+            RepoStatus::from_project_root(self.ctx.project_root()),
+        )?;
+
+        let orchestrator = self.create_orchestrator(locked.scope)?;
+        let mut refetch = true;
+        let mut clients = Vec::new();
+        for target in selection.clients {
+            let client_id = target.client.id().to_string();
+            let result = match target.resolution {
+                ScopeResolution::Apply(decision) => match orchestrator.upgrade_skill(
+                    target.client,
+                    &client_ctx,
+                    name,
+                    &entry.source,
+                    resolved.reference.as_deref(),
+                    constraint,
+                    &decision,
+                    refetch,
+                ) {
+                    Ok(_) => {
+                        refetch = false;
+                        ClientInstallResult {
+                            client_id,
+                            status: ClientInstallStatus::Applied,
+                            scope: Some(decision.scope),
+                            message: None,
+                        }
+                    }
+                    Err(err) => ClientInstallResult {
+                        client_id,
+                        status: ClientInstallStatus::Failed,
+                        scope: None,
+                        message: Some(format!("{:#}", err)),
+                    },
+                },
+                ScopeResolution::Skip { warning } => ClientInstallResult {
+                    client_id,
+                    status: ClientInstallStatus::Skipped,
+                    scope: None,
+                    message: Some(warning),
+                },
+            };
+            clients.push(result);
+        }
+        Ok((clients, selection.explicit))
+    }
+
+    fn sync_command(&self) -> SyncCommand {
+        SyncCommand::with_global_config_dir(
+            self.ctx.home_dir().to_path_buf(),
+            self.ctx.project_root().to_path_buf(),
+            self.ctx.state_dir().to_path_buf(),
+            self.ctx.global_config_dir().to_path_buf(),
+            self.ctx.link_mode(),
+        )
+    }
+
+    fn create_orchestrator(&self, scope: ConfigScope) -> anyhow::Result<InstallOrchestrator> {
+        Ok(InstallOrchestrator::new(
+            self.ctx.config_store(scope),
+            self.ctx.lockfile_service(),
+            self.ctx.skill_installer(),
+            self.ctx.source_resolver()?,
+            self.ctx.git_fetcher(),
+            self.ctx.link_mode(),
+        ))
+    }
+}
+
+impl UpgradeEntry {
+    fn new(kind: InstallTarget, name: &str, scope: ConfigScope) -> Self {
+        Self {
+            kind,
+            name: name.to_string(),
+            scope,
+            constraint: String::new(),
+            from: String::new(),
+            to: String::new(),
+            status: UpgradeStatus::UpToDate,
+            clients: Vec::new(),
+            error: None,
+        }
+    }
+
+    fn failed(mut self, err: anyhow::Error) -> Self {
+        self.status = UpgradeStatus::Failed;
+        self.error = Some(format!("{:#}", err));
+        self
+    }
+}
+
+/// The constraint a git skill is resolved against, and its display label.
+///
+/// Skills without a declared version follow the ref named in their source,
+/// or the remote HEAD.
+pub(super) fn skill_constraint(
+    entry: &SkillConfigEntry,
+    spec: &GitSpec,
+) -> anyhow::Result<(VersionConstraint, String)> {
+    let constraint = match (&entry.version, &spec.reference) {
+        (Some(version), _) => VersionResolver::parse_constraint(version)?,
+        (None, Some(reference)) if is_full_sha(reference) => {
+            VersionConstraint::GitSha(reference.clone())
+        }
+        (None, reference) => {
+            VersionConstraint::Branch(reference.clone().unwrap_or_else(|| "HEAD".to_string()))
+        }
+    };
+    let label = entry
+        .version
+        .clone()
+        .or_else(|| spec.reference.clone())
+        .unwrap_or_else(|| "HEAD".to_string());
+    Ok((constraint, label))
+}
+
+pub(super) fn not_installed(kind: &str, name: &str) -> String {
+    format!(
+        "{} '{}' is not installed; run `sift install` first",
+        kind, name
+    )
+}
+
+/// Render a commit for display, e.g. `v1.2.0 (abc1234)`.
+pub(super) fn describe_commit(reference: Option<&str>, commit: &str) -> String {
+    let short = &commit[..commit.len().min(7)];
+    match reference {
+        Some(reference) if !commit.starts_with(reference) => format!("{} ({})", reference, short),
+        _ => short.to_string(),
+    }
+}
+
+/// Whether `commit` and the locked version name the same commit, either
+/// being an abbreviation of the other. An empty side matches nothing.
+pub(super) fn is_same_commit(commit: &str, locked: &LockedSkill) -> bool {
+    let version = locked.resolved_version.as_str();
+    if commit.is_empty() || version.is_empty() {
+        return false;
+    }
+    version.starts_with(commit) || commit.starts_with(version)
+}
+
+fn is_full_sha(reference: &str) -> bool {
+    reference.len() == 40 && reference.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn locked_at(version: &str) -> LockedSkill {
+        LockedSkill::new(
+            "skill".to_string(),
+            version.to_string(),
+            "latest".to_string(),
+            "registry".to_string(),
+            ConfigScope::Global,
+        )
+    }
+
+    #[test]
+    fn test_is_same_commit_matches_abbreviations() {
+        let locked = locked_at("abc1234def");
+        assert!(is_same_commit("abc1234", &locked));
+        assert!(is_same_commit("abc1234def5678", &locked));
+        assert!(!is_same_commit("fff0000", &locked));
+    }
+
+    #[test]
+    fn test_is_same_commit_rejects_empty_sides() {
+        assert!(!is_same_commit("abc1234", &locked_at("")));
+        assert!(!is_same_commit("", &locked_at("abc1234")));
+    }
+}
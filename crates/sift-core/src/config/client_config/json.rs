@@ -1,4 +1,7 @@
 //! JSON serializer for client configuration files.
+//!
+//! Loading tolerates JSON with comments and trailing commas, as found in
+//! editor settings files such as Zed's `settings.json`.
 
 use std::path::Path;
 
@@ -18,8 +21,9 @@ impl ConfigSerializer for JsonSerializer {
         }
         let bytes = std::fs::read(path)
             .with_context(|| format!("Failed to read config file: {}", path.display()))?;
-        let value: Value =
-            serde_json::from_slice(&bytes).with_context(|| "Failed to parse JSON config")?;
+        let text = String::from_utf8_lossy(&bytes);
+        let value: Value = serde_json::from_str(&strip_jsonc(&text))
+            .with_context(|| "Failed to parse JSON config")?;
         match value {
             Value::Object(map) => Ok(map),
             _ => anyhow::bail!("Expected JSON object at root: {}", path.display()),
@@ -42,3 +46,68 @@ impl ConfigSerializer for JsonSerializer {
         ConfigFormat::Json
     }
 }
+
+/// Blank out comments and trailing commas so JSONC parses as plain JSON.
+///
+/// Removed characters are replaced with spaces (newlines are kept), so byte
+/// offsets and line numbers in parse errors still match the original text.
+pub(crate) fn strip_jsonc(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut out = bytes.to_vec();
+    let mut index = 0;
+    // Position of the last comma outside strings, cleared by any other token
+    let mut pending_comma: Option<usize> = None;
+
+    while index < bytes.len() {
+        match bytes[index] {
+            b'"' => {
+                pending_comma = None;
+                index += 1;
+                while index < bytes.len() && bytes[index] != b'"' {
+                    if bytes[index] == b'\\' {
+                        index += 1;
+                    }
+                    index += 1;
+                }
+                index += 1;
+            }
+            b'/' if bytes.get(index + 1) == Some(&b'/') => {
+                while index < bytes.len() && bytes[index] != b'\n' {
+                    out[index] = b' ';
+                    index += 1;
+                }
+            }
+            b'/' if bytes.get(index + 1) == Some(&b'*') => {
+                let end = text[index + 2..]
+                    .find("*/")
+                    .map(|pos| index + 2 + pos + 2)
+                    .unwrap_or(bytes.len());
+                for byte in &mut out[index..end] {
+                    if *byte != b'\n' {
+                        *byte = b' ';
+                    }
+                }
+                index = end;
+            }
+            b',' => {
+                pending_comma = Some(index);
+                index += 1;
+            }
+            b'}' | b']' => {
+                if let Some(comma) = pending_comma.take() {
+                    out[comma] = b' ';
+                }
+                index += 1;
+            }
+            byte if byte.is_ascii_whitespace() => index += 1,
+            _ => {
+                pending_comma = None;
+                index += 1;
+            }
+        }
+    }
+
+    // Comments are blanked byte by byte, so multi-byte characters in them
+    // become spaces and the result stays valid UTF-8
+    String::from_utf8_lossy(&out).into_owned()
+}
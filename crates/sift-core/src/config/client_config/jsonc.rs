@@ -0,0 +1,665 @@
+//! JSONC (JSON with comments) serializer for client configuration files.
+//!
+//! Editor settings files such as VS Code's `settings.json` and Zed's
+//! `settings.json` carry comments, trailing commas and the user's own
+//! formatting. Saving patches the existing text instead of re-rendering it:
+//! only members whose values changed are rewritten, so comments and
+//! formatting everywhere else stay byte-identical.
+
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde_json::{Map, Value};
+
+use super::json::strip_jsonc;
+use super::{ConfigFormat, ConfigSerializer};
+
+/// JSONC configuration file serializer.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct JsoncSerializer;
+
+impl ConfigSerializer for JsoncSerializer {
+    fn load(&self, path: &Path) -> Result<Map<String, Value>> {
+        if !path.exists() {
+            return Ok(Map::new());
+        }
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config file: {}", path.display()))?;
+        parse_jsonc(&text)
+            .with_context(|| format!("Failed to parse JSONC config: {}", path.display()))
+    }
+
+    fn save(&self, path: &Path, map: &Map<String, Value>) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).with_context(|| {
+                format!("Failed to create config directory: {}", parent.display())
+            })?;
+        }
+        let existing = if path.exists() {
+            std::fs::read_to_string(path)
+                .with_context(|| format!("Failed to read config file: {}", path.display()))?
+        } else {
+            String::new()
+        };
+        let content = update_jsonc(&existing, map)
+            .with_context(|| format!("Failed to update JSONC config: {}", path.display()))?;
+        std::fs::write(path, content)
+            .with_context(|| format!("Failed to write config file: {}", path.display()))?;
+        Ok(())
+    }
+
+    fn format(&self) -> ConfigFormat {
+        ConfigFormat::Jsonc
+    }
+}
+
+/// Parse JSONC text into a map. Blank text is an empty map.
+pub fn parse_jsonc(text: &str) -> Result<Map<String, Value>> {
+    if text.trim().is_empty() {
+        return Ok(Map::new());
+    }
+    let value: Value =
+        serde_json::from_str(&strip_jsonc(text)).context("Failed to parse JSONC config")?;
+    match value {
+        Value::Object(map) => Ok(map),
+        _ => anyhow::bail!("Expected JSON object at root"),
+    }
+}
+
+/// Rewrite JSONC text so it holds `desired`, touching only changed members.
+///
+/// Blank text is rendered as pretty JSON.
+pub fn update_jsonc(text: &str, desired: &Map<String, Value>) -> Result<String> {
+    if text.trim().is_empty() {
+        let mut rendered =
+            serde_json::to_string_pretty(desired).context("Failed to serialize JSON config")?;
+        rendered.push('\n');
+        return Ok(rendered);
+    }
+
+    let current = parse_jsonc(text)?;
+    let mut parser = Parser { text, pos: 0 };
+    parser.skip_trivia();
+    let root = parser.parse_value()?;
+    let Node::Object(object) = root else {
+        anyhow::bail!("Expected JSON object at root");
+    };
+
+    let mut edits = Vec::new();
+    patch_object(text, &object, &current, desired, &mut edits)?;
+
+    // Apply from the end so earlier offsets stay valid
+    edits.sort_by_key(|edit| std::cmp::Reverse(edit.start));
+    let mut output = text.to_string();
+    for edit in edits {
+        output.replace_range(edit.start..edit.end, &edit.text);
+    }
+    Ok(output)
+}
+
+/// A replacement of `text[start..end]`.
+#[derive(Debug)]
+struct Edit {
+    start: usize,
+    end: usize,
+    text: String,
+}
+
+#[derive(Debug)]
+enum Node {
+    Object(ObjectNode),
+    Other { start: usize, end: usize },
+}
+
+impl Node {
+    fn start(&self) -> usize {
+        match self {
+            Node::Object(object) => object.start,
+            Node::Other { start, .. } => *start,
+        }
+    }
+
+    fn end(&self) -> usize {
+        match self {
+            Node::Object(object) => object.end,
+            Node::Other { end, .. } => *end,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct ObjectNode {
+    /// Offset of `{`
+    start: usize,
+    /// Offset just past `}`
+    end: usize,
+    members: Vec<Member>,
+}
+
+#[derive(Debug)]
+struct Member {
+    key: String,
+    /// Offset of the key's opening quote
+    key_start: usize,
+    value: Node,
+    /// Offset of the comma following the value, if any
+    comma: Option<usize>,
+}
+
+fn patch_object(
+    text: &str,
+    object: &ObjectNode,
+    current: &Map<String, Value>,
+    desired: &Map<String, Value>,
+    edits: &mut Vec<Edit>,
+) -> Result<()> {
+    let added: Vec<(&String, &Value)> = desired
+        .iter()
+        .filter(|(key, _)| !current.contains_key(*key))
+        .collect();
+    let last_removed = object
+        .members
+        .last()
+        .is_some_and(|last| !desired.contains_key(&last.key));
+
+    // Removing the last member and appending after it would overlap, so
+    // re-render the object instead
+    if !added.is_empty() && last_removed {
+        edits.push(Edit {
+            start: object.start,
+            end: object.end,
+            text: render_value(
+                &Value::Object(desired.clone()),
+                &line_indent(text, object.start),
+            )?,
+        });
+        return Ok(());
+    }
+
+    let mut removed = Vec::new();
+    for (index, member) in object.members.iter().enumerate() {
+        // Duplicate keys resolve to the last occurrence, like serde_json
+        if object.members[index + 1..]
+            .iter()
+            .any(|later| later.key == member.key)
+        {
+            continue;
+        }
+        let Some(new_value) = desired.get(&member.key) else {
+            removed.push(index);
+            continue;
+        };
+        let old_value = current.get(&member.key);
+        if old_value == Some(new_value) {
+            continue;
+        }
+        match (&member.value, old_value, new_value) {
+            (Node::Object(child), Some(Value::Object(old)), Value::Object(new)) => {
+                patch_object(text, child, old, new, edits)?;
+            }
+            (value, _, _) => edits.push(Edit {
+                start: value.start(),
+                end: value.end(),
+                text: render_value(new_value, &line_indent(text, member.key_start))?,
+            }),
+        }
+    }
+
+    // Adjacent members are removed as one range so the edits never overlap
+    let mut runs: Vec<(usize, usize)> = Vec::new();
+    for index in removed {
+        match runs.last_mut() {
+            Some((_, last)) if *last + 1 == index => *last = index,
+            _ => runs.push((index, index)),
+        }
+    }
+    for (first, last) in runs {
+        edits.extend(remove_members(text, object, first, last));
+    }
+
+    if !added.is_empty() {
+        edits.push(append_members(text, object, &added)?);
+    }
+    Ok(())
+}
+
+/// Remove members `first..=last` along with their commas and, when they
+/// sit on their own lines, the surrounding indentation and line breaks.
+///
+/// Comments trailing a removed member on its line go with it; comments after
+/// a surviving member's comma stay.
+fn remove_members(text: &str, object: &ObjectNode, first: usize, last: usize) -> Vec<Edit> {
+    let members = &object.members;
+    let remove = |start, end| Edit {
+        start,
+        end,
+        text: String::new(),
+    };
+
+    if first == 0 && last + 1 == members.len() {
+        return vec![remove(object.start + 1, object.end - 1)];
+    }
+
+    let key_start = members[first].key_start;
+    let line_start = text[..key_start].rfind('\n').map_or(0, |i| i + 1);
+    let own_line = text[line_start..key_start].trim().is_empty();
+    let start = if own_line { line_start } else { key_start };
+
+    if let Some(comma) = members[last].comma {
+        let end = trailing_comment_end(text, comma + 1);
+        let line_break = ["\r\n", "\n"]
+            .into_iter()
+            .find(|newline| text[end..].starts_with(newline))
+            .map_or(0, str::len);
+        return vec![remove(start, end + line_break)];
+    }
+
+    // The last member has no trailing comma, so the surviving one before it
+    // gives up its comma instead
+    let end = trailing_comment_end(text, members[last].value.end());
+    let Some(comma) = members[first - 1].comma else {
+        return vec![remove(start, end)];
+    };
+    if text[comma + 1..key_start].trim().is_empty() {
+        return vec![remove(comma, end)];
+    }
+    // Keep what follows the comma, such as a comment, and remove the members
+    // from the end of the line before them
+    let start = if own_line {
+        text[..line_start - 1]
+            .strip_suffix('\r')
+            .map_or(line_start - 1, str::len)
+    } else {
+        start
+    };
+    vec![remove(comma, comma + 1), remove(start, end)]
+}
+
+/// Offset past whitespace and a `//` comment that follow `offset` on its
+/// line, stopping before the line break; `offset` if anything else follows.
+fn trailing_comment_end(text: &str, offset: usize) -> usize {
+    let rest = &text[offset..];
+    let line = &rest[..rest.find('\n').unwrap_or(rest.len())];
+    let trimmed = line.trim();
+    if trimmed.is_empty() || trimmed.starts_with("//") {
+        offset + line.trim_end_matches('\r').len()
+    } else {
+        offset
+    }
+}
+
+/// Append new members before the object's closing brace.
+fn append_members(text: &str, object: &ObjectNode, added: &[(&String, &Value)]) -> Result<Edit> {
+    let close = object.end - 1;
+    let brace_indent = line_indent(text, close);
+    let member_indent = match object.members.first() {
+        Some(first) => line_indent(text, first.key_start),
+        None => format!("{}  ", brace_indent),
+    };
+
+    let mut rendered = Vec::new();
+    for (key, value) in added {
+        rendered.push(format!(
+            "\n{}{}: {}",
+            member_indent,
+            serde_json::to_string(key)?,
+            render_value(value, &member_indent)?
+        ));
+    }
+
+    match object.members.last() {
+        Some(last) => {
+            let (position, prefix) = match last.comma {
+                Some(comma) => (comma + 1, ""),
+                None => (last.value.end(), ","),
+            };
+            Ok(Edit {
+                start: position,
+                end: position,
+                text: format!("{}{}", prefix, rendered.join(",")),
+            })
+        }
+        None => Ok(Edit {
+            start: object.start + 1,
+            end: close,
+            text: format!("{}\n{}", rendered.join(","), brace_indent),
+        }),
+    }
+}
+
+/// Pretty-print a value whose first line starts at `indent`.
+fn render_value(value: &Value, indent: &str) -> Result<String> {
+    let pretty = serde_json::to_string_pretty(value).context("Failed to serialize JSON config")?;
+    Ok(pretty.replace('\n', &format!("\n{}", indent)))
+}
+
+/// Leading whitespace of the line containing `offset`.
+fn line_indent(text: &str, offset: usize) -> String {
+    let line_start = text[..offset].rfind('\n').map_or(0, |i| i + 1);
+    text[line_start..]
+        .chars()
+        .take_while(|c| *c == ' ' || *c == '\t')
+        .collect()
+}
+
+/// Minimal JSONC parser recording the byte spans of object members.
+struct Parser<'a> {
+    text: &'a str,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.text.as_bytes().get(self.pos).copied()
+    }
+
+    /// Skip whitespace and comments.
+    fn skip_trivia(&mut self) {
+        loop {
+            let rest = &self.text[self.pos..];
+            let trimmed = rest.trim_start();
+            self.pos += rest.len() - trimmed.len();
+            if trimmed.starts_with("//") {
+                self.pos += trimmed.find('\n').unwrap_or(trimmed.len());
+            } else if let Some(comment) = trimmed.strip_prefix("/*") {
+                self.pos += comment.find("*/").map_or(trimmed.len(), |i| i + 4);
+            } else {
+                return;
+            }
+        }
+    }
+
+    fn expect(&mut self, byte: u8) -> Result<()> {
+        if self.peek() != Some(byte) {
+            anyhow::bail!(
+                "Expected '{}' at offset {} in JSONC config",
+                byte as char,
+                self.pos
+            );
+        }
+        self.pos += 1;
+        Ok(())
+    }
+
+    fn parse_value(&mut self) -> Result<Node> {
+        match self.peek() {
+            Some(b'{') => self.parse_object().map(Node::Object),
+            Some(b'[') => self.parse_array(),
+            Some(b'"') => {
+                let start = self.pos;
+                self.parse_string()?;
+                Ok(Node::Other {
+                    start,
+                    end: self.pos,
+                })
+            }
+            Some(_) => {
+                let start = self.pos;
+                let rest = &self.text[start..];
+                let len = rest
+                    .find(|c: char| c.is_whitespace() || matches!(c, ',' | '}' | ']' | '/'))
+                    .unwrap_or(rest.len());
+                if len == 0 {
+                    anyhow::bail!("Unexpected character at offset {} in JSONC config", start);
+                }
+                self.pos += len;
+                Ok(Node::Other {
+                    start,
+                    end: self.pos,
+                })
+            }
+            None => anyhow::bail!("Unexpected end of JSONC config"),
+        }
+    }
+
+    fn parse_object(&mut self) -> Result<ObjectNode> {
+        let start = self.pos;
+        self.expect(b'{')?;
+        let mut members = Vec::new();
+        loop {
+            self.skip_trivia();
+            if self.peek() == Some(b'}') {
+                self.pos += 1;
+                return Ok(ObjectNode {
+                    start,
+                    end: self.pos,
+                    members,
+                });
+            }
+            let key_start = self.pos;
+            let key = self.parse_string()?;
+            self.skip_trivia();
+            self.expect(b':')?;
+            self.skip_trivia();
+            let value = self.parse_value()?;
+            self.skip_trivia();
+            let comma = if self.peek() == Some(b',') {
+                self.pos += 1;
+                Some(self.pos - 1)
+            } else {
+                None
+            };
+            members.push(Member {
+                key,
+                key_start,
+                value,
+                comma,
+            });
+            if comma.is_none() {
+                self.skip_trivia();
+                if self.peek() != Some(b'}') {
+                    anyhow::bail!(
+                        "Expected ',' or '}}' at offset {} in JSONC config",
+                        self.pos
+                    );
+                }
+            }
+        }
+    }
+
+    fn parse_array(&mut self) -> Result<Node> {
+        let start = self.pos;
+        self.expect(b'[')?;
+        loop {
+            self.skip_trivia();
+            if self.peek() == Some(b']') {
+                self.pos += 1;
+                return Ok(Node::Other {
+                    start,
+                    end: self.pos,
+                });
+            }
+            self.parse_value()?;
+            self.skip_trivia();
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b']') => {}
+                _ => anyhow::bail!("Expected ',' or ']' at offset {} in JSONC config", self.pos),
+            }
+        }
+    }
+
+    fn parse_string(&mut self) -> Result<String> {
+        let start = self.pos;
+        self.expect(b'"')?;
+        let bytes = self.text.as_bytes();
+        while let Some(&byte) = bytes.get(self.pos) {
+            match byte {
+                b'\\' => self.pos += 2,
+                b'"' => {
+                    self.pos += 1;
+                    return serde_json::from_str(&self.text[start..self.pos])
+                        .context("Invalid string in JSONC config");
+                }
+                _ => self.pos += 1,
+            }
+        }
+        anyhow::bail!("Unterminated string in JSONC config")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const SETTINGS: &str = r#"// User settings
+{
+  "editor.fontSize": 14, // keep
+  /* servers managed below */
+  "amp.mcpServers": {
+    "user": {"command": "echo"},
+    "old": {"command": "old"}
+  },
+  "files.exclude": {
+    "**/.git": true,
+  },
+}
+"#;
+
+    fn desired(update: impl FnOnce(&mut Map<String, Value>)) -> Map<String, Value> {
+        let mut map = parse_jsonc(SETTINGS).unwrap();
+        update(&mut map);
+        map
+    }
+
+    #[test]
+    fn test_update_unchanged_is_identical() {
+        let map = parse_jsonc(SETTINGS).unwrap();
+        assert_eq!(update_jsonc(SETTINGS, &map).unwrap(), SETTINGS);
+    }
+
+    #[test]
+    fn test_update_replaces_changed_member() {
+        let map = desired(|m| {
+            m["amp.mcpServers"]["old"] = json!({"command": "new"});
+            m["editor.fontSize"] = json!([12, 14]);
+        });
+
+        let updated = update_jsonc(SETTINGS, &map).unwrap();
+
+        assert_eq!(
+            updated,
+            SETTINGS
+                .replace(r#"{"command": "old"}"#, r#"{"command": "new"}"#)
+                .replace("14, // keep", "[\n    12,\n    14\n  ], // keep")
+        );
+        assert_eq!(parse_jsonc(&updated).unwrap(), map);
+    }
+
+    #[test]
+    fn test_update_appends_and_removes_members() {
+        let map = desired(|m| {
+            let servers = m["amp.mcpServers"].as_object_mut().unwrap();
+            servers.remove("old");
+            servers.insert("added".to_string(), json!({"command": "npx"}));
+        });
+
+        let updated = update_jsonc(SETTINGS, &map).unwrap();
+
+        assert!(updated.starts_with("// User settings\n{\n  \"editor.fontSize\": 14, // keep\n"));
+        assert!(updated.contains("/* servers managed below */"));
+        assert!(updated.ends_with("  \"files.exclude\": {\n    \"**/.git\": true,\n  },\n}\n"));
+        assert_eq!(parse_jsonc(&updated).unwrap(), map);
+    }
+
+    const SERVERS: &str = r#"{
+  "servers": {
+    "user": {"command": "echo"},
+    "a": {"command": "a"}, // sift
+    "b": {"command": "b"}
+  }
+}
+"#;
+
+    fn without(text: &str, keys: &[&str]) -> Map<String, Value> {
+        let mut map = parse_jsonc(text).unwrap();
+        let servers = map["servers"].as_object_mut().unwrap();
+        for key in keys {
+            servers.remove(*key);
+        }
+        map
+    }
+
+    #[test]
+    fn test_update_removes_several_members() {
+        let map = without(SERVERS, &["a", "b"]);
+
+        let updated = update_jsonc(SERVERS, &map).unwrap();
+
+        assert_eq!(
+            updated,
+            "{\n  \"servers\": {\n    \"user\": {\"command\": \"echo\"}\n  }\n}\n"
+        );
+        assert_eq!(parse_jsonc(&updated).unwrap(), map);
+
+        let map = without(SERVERS, &["user", "a"]);
+        let updated = update_jsonc(SERVERS, &map).unwrap();
+        assert_eq!(
+            updated,
+            "{\n  \"servers\": {\n    \"b\": {\"command\": \"b\"}\n  }\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_update_removes_all_members() {
+        let map = without(SERVERS, &["user", "a", "b"]);
+
+        let updated = update_jsonc(SERVERS, &map).unwrap();
+
+        assert_eq!(updated, "{\n  \"servers\": {}\n}\n");
+        assert_eq!(parse_jsonc(&updated).unwrap(), map);
+    }
+
+    #[test]
+    fn test_update_keeps_comment_after_previous_member() {
+        let map = without(SERVERS, &["b"]);
+
+        let updated = update_jsonc(SERVERS, &map).unwrap();
+
+        assert_eq!(
+            updated,
+            SERVERS.replace(
+                "{\"command\": \"a\"}, // sift\n    \"b\": {\"command\": \"b\"}",
+                "{\"command\": \"a\"} // sift"
+            )
+        );
+        assert_eq!(parse_jsonc(&updated).unwrap(), map);
+
+        // Removing the commented member takes its comment along
+        let map = without(SERVERS, &["a"]);
+        let updated = update_jsonc(SERVERS, &map).unwrap();
+        assert!(!updated.contains("// sift"));
+        assert_eq!(parse_jsonc(&updated).unwrap(), map);
+    }
+
+    #[test]
+    fn test_update_inserts_missing_object() {
+        let text = "{\n  // comment\n  \"a\": 1\n}\n";
+        let mut map = parse_jsonc(text).unwrap();
+        map.insert("servers".to_string(), json!({"x": {"url": "https://x"}}));
+
+        let updated = update_jsonc(text, &map).unwrap();
+
+        assert!(updated.starts_with("{\n  // comment\n  \"a\": 1,\n  \"servers\": {"));
+        assert_eq!(parse_jsonc(&updated).unwrap(), map);
+    }
+
+    #[test]
+    fn test_update_fills_empty_object() {
+        let mut map = Map::new();
+        map.insert("servers".to_string(), json!({}));
+
+        let updated = update_jsonc("{}", &map).unwrap();
+
+        assert_eq!(updated, "{\n  \"servers\": {}\n}");
+        assert_eq!(parse_jsonc(&updated).unwrap(), map);
+    }
+
+    #[test]
+    fn test_update_blank_text_renders_json() {
+        let mut map = Map::new();
+        map.insert("a".to_string(), json!(1));
+        assert_eq!(update_jsonc("", &map).unwrap(), "{\n  \"a\": 1\n}\n");
+    }
+}
@@ -2,9 +2,10 @@
 //!
 //! This module handles reading and writing third-party client configuration files
 //! (e.g., `~/.codex/config.toml`, `claude_desktop_config.json`) with support for
-//! multiple formats (JSON, TOML) and ownership-based merge semantics.
+//! multiple formats (JSON, JSONC, TOML) and ownership-based merge semantics.
 
 mod json;
+mod jsonc;
 mod toml;
 
 use std::collections::HashMap;
@@ -13,16 +14,19 @@ use std::path::Path;
 use anyhow::{Context, Result};
 use serde_json::{Map, Value};
 
-use crate::config::ownership::{hash_json, merge_owned_map};
+use crate::config::ownership::{hash_entry, merge_owned_map_retaining};
 use crate::lockfile::LockfileService;
 
 pub use json::JsonSerializer;
+pub use jsonc::{JsoncSerializer, parse_jsonc, update_jsonc};
 pub use toml::TomlSerializer;
 
 /// Configuration file format
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ConfigFormat {
     Json,
+    /// JSON with comments, edited in place to preserve formatting
+    Jsonc,
     Toml,
 }
 
@@ -30,6 +34,9 @@ impl From<crate::client::McpConfigFormat> for ConfigFormat {
     fn from(format: crate::client::McpConfigFormat) -> Self {
         match format {
             crate::client::McpConfigFormat::Toml => ConfigFormat::Toml,
+            crate::client::McpConfigFormat::Jsonc | crate::client::McpConfigFormat::Zed => {
+                ConfigFormat::Jsonc
+            }
             // All other formats are JSON-based
             crate::client::McpConfigFormat::ClaudeDesktop
             | crate::client::McpConfigFormat::ClaudeCode
@@ -70,6 +77,7 @@ pub struct ManagedConfigResult {
 pub fn serializer_for_format(format: ConfigFormat) -> Box<dyn ConfigSerializer> {
     match format {
         ConfigFormat::Json => Box::new(JsonSerializer),
+        ConfigFormat::Jsonc => Box::new(JsoncSerializer),
         ConfigFormat::Toml => Box::new(TomlSerializer),
     }
 }
@@ -101,6 +109,55 @@ pub fn apply_managed_entries_in_path(
     lockfile_service: &LockfileService,
     force: bool,
     format: ConfigFormat,
+) -> Result<ManagedConfigResult> {
+    apply_managed_entries_preserving(
+        config_path,
+        path,
+        desired,
+        &[],
+        lockfile_service,
+        force,
+        format,
+    )
+}
+
+/// Apply managed entries, keeping the existing values of each entry's
+/// `preserved` fields (see [`ManagedConfigPlan::preserved_fields`]).
+///
+/// [`ManagedConfigPlan::preserved_fields`]: crate::client::ManagedConfigPlan::preserved_fields
+pub fn apply_managed_entries_preserving(
+    config_path: &Path,
+    path: &[&str],
+    desired: &Map<String, Value>,
+    preserved: &[String],
+    lockfile_service: &LockfileService,
+    force: bool,
+    format: ConfigFormat,
+) -> Result<ManagedConfigResult> {
+    apply_managed_entries_retaining(
+        config_path,
+        path,
+        desired,
+        preserved,
+        &[],
+        lockfile_service,
+        force,
+        format,
+    )
+}
+
+/// Apply managed entries, leaving the owned entries named in `retained` in
+/// place and still owned even though they are not desired.
+#[allow(clippy::too_many_arguments)]
+pub fn apply_managed_entries_retaining(
+    config_path: &Path,
+    path: &[&str],
+    desired: &Map<String, Value>,
+    preserved: &[String],
+    retained: &[String],
+    lockfile_service: &LockfileService,
+    force: bool,
+    format: ConfigFormat,
 ) -> Result<ManagedConfigResult> {
     if path.is_empty() {
         anyhow::bail!("Path for managed entries cannot be empty");
@@ -115,13 +172,21 @@ pub fn apply_managed_entries_in_path(
 
     let field_key = path.join(".");
     let ownership = lockfile_service.load_ownership(config_path, Some(&field_key))?;
-    let merged_field = merge_owned_map(&existing, desired, &ownership, force)?;
+    let merged_field =
+        merge_owned_map_retaining(&existing, desired, &ownership, preserved, retained, force)?;
 
     set_map_at_path(&mut root, path, merged_field.clone())?;
 
     let mut updated_ownership = HashMap::new();
     for (key, value) in desired {
-        updated_ownership.insert(key.clone(), hash_json(value));
+        updated_ownership.insert(key.clone(), hash_entry(value, preserved));
+    }
+    for key in retained {
+        if let Some(hash) = ownership.get(key) {
+            updated_ownership
+                .entry(key.clone())
+                .or_insert_with(|| hash.clone());
+        }
     }
 
     serializer
@@ -135,6 +200,39 @@ pub fn apply_managed_entries_in_path(
     })
 }
 
+/// Remove a single entry from the map at a nested path.
+///
+/// Leaves every other entry untouched and does not update ownership.
+/// Returns whether the entry existed.
+pub fn remove_entry_at_path(
+    config_path: &Path,
+    path: &[&str],
+    key: &str,
+    format: ConfigFormat,
+) -> Result<bool> {
+    if path.is_empty() {
+        anyhow::bail!("Path for managed entries cannot be empty");
+    }
+    if !config_path.exists() {
+        return Ok(false);
+    }
+
+    let serializer = serializer_for_format(format);
+    let mut root = serializer
+        .load(config_path)
+        .with_context(|| format!("Failed to load config: {}", config_path.display()))?;
+    let mut field = extract_map_at_path(&root, path)?;
+    if field.remove(key).is_none() {
+        return Ok(false);
+    }
+
+    set_map_at_path(&mut root, path, field)?;
+    serializer
+        .save(config_path, &root)
+        .with_context(|| format!("Failed to save config: {}", config_path.display()))?;
+    Ok(true)
+}
+
 /// Extract a nested map from a root map at the given path.
 fn extract_map_at_path(root: &Map<String, Value>, path: &[&str]) -> Result<Map<String, Value>> {
     let mut current = root;
@@ -251,6 +349,34 @@ mod tests {
         assert_eq!(original, loaded);
     }
 
+    #[test]
+    fn json_load_tolerates_comments_and_trailing_commas() {
+        let temp_dir = TempDir::new().expect("create temp dir");
+        let path = temp_dir.path().join("settings.json");
+        std::fs::write(
+            &path,
+            r#"// Zed settings
+{
+  /* theme */ "theme": "One Dark",
+  "url": "https://example.com/a//b", // trailing
+  "context_servers": {
+    "test": {"command": "echo", "args": ["/*not a comment*/",],},
+  },
+}
+"#,
+        )
+        .expect("write file");
+
+        let result = JsonSerializer.load(&path).expect("load should succeed");
+
+        assert_eq!(result["theme"], json!("One Dark"));
+        assert_eq!(result["url"], json!("https://example.com/a//b"));
+        assert_eq!(
+            result["context_servers"]["test"],
+            json!({"command": "echo", "args": ["/*not a comment*/"]})
+        );
+    }
+
     #[test]
     fn json_format_returns_json() {
         let serializer = JsonSerializer;
@@ -385,7 +511,37 @@ args = ["hello"]
         let json_serializer = serializer_for_format(ConfigFormat::Json);
         assert_eq!(json_serializer.format(), ConfigFormat::Json);
 
+        let jsonc_serializer = serializer_for_format(ConfigFormat::Jsonc);
+        assert_eq!(jsonc_serializer.format(), ConfigFormat::Jsonc);
+
         let toml_serializer = serializer_for_format(ConfigFormat::Toml);
         assert_eq!(toml_serializer.format(), ConfigFormat::Toml);
     }
+
+    // ===========================================
+    // Entry Removal Tests
+    // ===========================================
+
+    #[test]
+    fn remove_entry_at_path_keeps_other_entries() {
+        let temp = TempDir::new().expect("temp dir");
+        let path = temp.path().join("config.json");
+        std::fs::write(
+            &path,
+            r#"{"mcpServers":{"old":{"command":"a"},"user":{"command":"b"}},"other":1}"#,
+        )
+        .expect("write");
+
+        let removed = remove_entry_at_path(&path, &["mcpServers"], "old", ConfigFormat::Json)
+            .expect("remove");
+        assert!(removed);
+
+        let loaded = JsonSerializer.load(&path).expect("load");
+        assert_eq!(loaded["mcpServers"], json!({"user": {"command": "b"}}));
+        assert_eq!(loaded["other"], json!(1));
+
+        let removed_again = remove_entry_at_path(&path, &["mcpServers"], "old", ConfigFormat::Json)
+            .expect("remove again");
+        assert!(!removed_again);
+    }
 }
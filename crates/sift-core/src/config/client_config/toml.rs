@@ -1,4 +1,7 @@
 //! TOML serializer for client configuration files.
+//!
+//! Saving edits the existing document in place, so comments and formatting
+//! outside the changed keys survive.
 
 use std::path::Path;
 
@@ -6,6 +9,7 @@ use anyhow::{Context, Result};
 use serde_json::{Map, Value};
 
 use super::{ConfigFormat, ConfigSerializer};
+use crate::config::toml_document::update_toml_document;
 
 /// TOML configuration file serializer.
 #[derive(Debug, Default, Clone, Copy)]
@@ -32,9 +36,21 @@ impl ConfigSerializer for TomlSerializer {
             })?;
         }
 
-        let toml_value = json_map_to_toml(map)?;
-        let content =
-            toml::to_string_pretty(&toml_value).context("Failed to serialize TOML config")?;
+        let toml::Value::Table(desired) = json_map_to_toml(map)? else {
+            anyhow::bail!("Expected TOML table at root");
+        };
+        let existing = if path.exists() {
+            std::fs::read_to_string(path)
+                .with_context(|| format!("Failed to read config file: {}", path.display()))?
+        } else {
+            String::new()
+        };
+        let content = if existing.trim().is_empty() {
+            toml::to_string_pretty(&desired).context("Failed to serialize TOML config")?
+        } else {
+            update_toml_document(&existing, &desired)
+                .with_context(|| format!("Failed to update TOML config: {}", path.display()))?
+        };
 
         std::fs::write(path, content)
             .with_context(|| format!("Failed to write config file: {}", path.display()))?;
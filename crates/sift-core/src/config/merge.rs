@@ -146,6 +146,18 @@ fn merge_mcp_entry(base: &mut McpConfigEntry, overlay: McpConfigEntry) {
     if overlay.url.is_some() {
         base.url = overlay.url;
     }
+    if !overlay.volumes.is_empty() {
+        base.volumes = overlay.volumes;
+    }
+    if overlay.network.is_some() {
+        base.network = overlay.network;
+    }
+    if !overlay.always_allow.is_empty() {
+        base.always_allow = overlay.always_allow;
+    }
+    if overlay.oauth.is_some() {
+        base.oauth = overlay.oauth;
+    }
     // Deep merge headers
     for (key, value) in overlay.headers {
         base.headers.insert(key, value);
@@ -196,6 +208,30 @@ fn merge_client_entry(
     if overlay.capabilities.is_some() {
         base.capabilities = overlay.capabilities;
     }
+    if overlay.mcp_global.is_some() {
+        base.mcp_global = overlay.mcp_global;
+    }
+    if overlay.mcp_project.is_some() {
+        base.mcp_project = overlay.mcp_project;
+    }
+    if overlay.format.is_some() {
+        base.format = overlay.format;
+    }
+    if overlay.config_path.is_some() {
+        base.config_path = overlay.config_path;
+    }
+    if overlay.template.is_some() {
+        base.template = overlay.template;
+    }
+    if overlay.skills_global.is_some() {
+        base.skills_global = overlay.skills_global;
+    }
+    if overlay.skills_project.is_some() {
+        base.skills_project = overlay.skills_project;
+    }
+    if !overlay.profiles.is_empty() {
+        base.profiles = overlay.profiles;
+    }
 }
 
 /// Apply project-local override from global config
@@ -270,6 +306,7 @@ mod tests {
             args: vec![],
             url: None,
             headers: HashMap::new(),
+            oauth: None,
             targets: None,
             ignore_targets: None,
             env: HashMap::new(),
@@ -277,6 +314,9 @@ mod tests {
             reset_ignore_targets: false,
             reset_env: None,
             reset_env_all: false,
+            volumes: Vec::new(),
+            network: None,
+            always_allow: Vec::new(),
         }
     }
 
@@ -358,6 +398,7 @@ mod tests {
             args: vec!["--arg1".to_string()],
             url: None,
             headers: HashMap::new(),
+            oauth: None,
             targets: None,
             ignore_targets: None,
             env: {
@@ -369,6 +410,9 @@ mod tests {
             reset_ignore_targets: false,
             reset_env: None,
             reset_env_all: false,
+            volumes: Vec::new(),
+            network: None,
+            always_allow: Vec::new(),
         };
 
         let overlay = McpConfigEntry {
@@ -378,6 +422,7 @@ mod tests {
             args: vec!["--arg2".to_string()],
             url: None,
             headers: HashMap::new(),
+            oauth: None,
             targets: Some(vec!["claude-desktop".to_string()]),
             ignore_targets: None,
             env: {
@@ -389,6 +434,9 @@ mod tests {
             reset_ignore_targets: false,
             reset_env: None,
             reset_env_all: false,
+            volumes: Vec::new(),
+            network: None,
+            always_allow: Vec::new(),
         };
 
         merge_mcp_entry(&mut base, overlay);
@@ -468,6 +516,7 @@ mod tests {
             args: vec![],
             url: None,
             headers: HashMap::new(),
+            oauth: None,
             targets: None,
             ignore_targets: None,
             env: HashMap::new(),
@@ -475,6 +524,9 @@ mod tests {
             reset_ignore_targets: false,
             reset_env: None,
             reset_env_all: false,
+            volumes: Vec::new(),
+            network: None,
+            always_allow: Vec::new(),
         };
         let overlay = McpConfigEntry {
             transport: Some("http".to_string()),
@@ -483,6 +535,7 @@ mod tests {
             args: vec![],
             url: Some("https://example.com/mcp".to_string()),
             headers: HashMap::new(),
+            oauth: None,
             targets: None,
             ignore_targets: None,
             env: HashMap::new(),
@@ -490,6 +543,9 @@ mod tests {
             reset_ignore_targets: false,
             reset_env: None,
             reset_env_all: false,
+            volumes: Vec::new(),
+            network: None,
+            always_allow: Vec::new(),
         };
 
         // Should warn but allow
@@ -522,6 +578,7 @@ mod tests {
                 args: vec![],
                 url: None,
                 headers: HashMap::new(),
+                oauth: None,
                 targets: None,
                 ignore_targets: None,
                 env: {
@@ -533,6 +590,9 @@ mod tests {
                 reset_ignore_targets: false,
                 reset_env: None,
                 reset_env_all: false,
+                volumes: Vec::new(),
+                network: None,
+                always_allow: Vec::new(),
             },
         );
 
@@ -570,6 +630,7 @@ mod tests {
                 args: vec![],
                 url: None,
                 headers: HashMap::new(),
+                oauth: None,
                 targets: None,
                 ignore_targets: None,
                 env: HashMap::new(),
@@ -577,6 +638,9 @@ mod tests {
                 reset_ignore_targets: false,
                 reset_env: None,
                 reset_env_all: false,
+                volumes: Vec::new(),
+                network: None,
+                always_allow: Vec::new(),
             },
         );
 
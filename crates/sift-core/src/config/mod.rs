@@ -13,13 +13,14 @@ pub mod parser;
 pub mod paths;
 pub mod schema;
 pub mod store;
+pub mod toml_document;
 
 use std::path::PathBuf;
 
 // Re-export the new module types
 pub use managed_json::ManagedJsonResult;
 pub use merge::merge_configs;
-pub use parser::{parse_sift_toml, parse_sift_toml_str, to_toml};
+pub use parser::{parse_sift_toml, parse_sift_toml_str, to_toml, update_toml};
 pub use paths::config_path_for_scope;
 pub use schema::{
     ClientConfigEntry, McpConfigEntry, McpOverrideEntry, ProjectConfig, RegistryConfigEntry,
@@ -10,42 +10,86 @@ pub fn hash_json(value: &Value) -> String {
     blake3::hash(&bytes).to_hex().to_string()
 }
 
+/// Hash of an entry without its `preserved` fields.
+///
+/// Preserved fields are ones the client's own UI changes, such as a
+/// server's enabled state, so changing them does not make an entry
+/// user-modified.
+pub fn hash_entry(value: &Value, preserved: &[String]) -> String {
+    match value {
+        Value::Object(map) if !preserved.is_empty() => {
+            let mut map = map.clone();
+            for field in preserved {
+                map.remove(field);
+            }
+            hash_json(&Value::Object(map))
+        }
+        _ => hash_json(value),
+    }
+}
+
 pub fn merge_owned_map(
     existing: &Map<String, Value>,
     desired: &Map<String, Value>,
     ownership: &HashMap<String, String>,
     force: bool,
+) -> anyhow::Result<Map<String, Value>> {
+    merge_owned_map_preserving(existing, desired, ownership, &[], force)
+}
+
+/// Like [`merge_owned_map`], but updated entries keep the existing values of
+/// their `preserved` fields, and those fields are left out of ownership hashes.
+pub fn merge_owned_map_preserving(
+    existing: &Map<String, Value>,
+    desired: &Map<String, Value>,
+    ownership: &HashMap<String, String>,
+    preserved: &[String],
+    force: bool,
+) -> anyhow::Result<Map<String, Value>> {
+    merge_owned_map_retaining(existing, desired, ownership, preserved, &[], force)
+}
+
+/// Like [`merge_owned_map_preserving`], but owned entries named in `retained`
+/// are left as they are instead of being removed when not desired.
+pub fn merge_owned_map_retaining(
+    existing: &Map<String, Value>,
+    desired: &Map<String, Value>,
+    ownership: &HashMap<String, String>,
+    preserved: &[String],
+    retained: &[String],
+    force: bool,
 ) -> anyhow::Result<Map<String, Value>> {
     let mut merged = existing.clone();
 
     for (key, desired_value) in desired {
-        if let Some(expected_hash) = ownership.get(key) {
-            if let Some(existing_value) = existing.get(key) {
-                let existing_hash = hash_json(existing_value);
-                if existing_hash != *expected_hash && !force {
+        let Some(existing_value) = existing.get(key) else {
+            merged.insert(key.clone(), desired_value.clone());
+            continue;
+        };
+        match ownership.get(key) {
+            Some(expected_hash) => {
+                if hash_entry(existing_value, preserved) != *expected_hash && !force {
                     anyhow::bail!("Refusing to overwrite user-modified entry: {}", key);
                 }
-                merged.insert(key.clone(), desired_value.clone());
-            } else {
-                merged.insert(key.clone(), desired_value.clone());
             }
-        } else if existing.contains_key(key) {
-            if !force {
-                anyhow::bail!("Entry '{}' already exists and is not managed by Sift", key);
+            None => {
+                if !force {
+                    anyhow::bail!("Entry '{}' already exists and is not managed by Sift", key);
+                }
             }
-            merged.insert(key.clone(), desired_value.clone());
-        } else {
-            merged.insert(key.clone(), desired_value.clone());
         }
+        merged.insert(
+            key.clone(),
+            keep_preserved(desired_value, existing_value, preserved),
+        );
     }
 
     for (key, expected_hash) in ownership {
-        if desired.contains_key(key) {
+        if desired.contains_key(key) || retained.contains(key) {
             continue;
         }
         if let Some(existing_value) = existing.get(key) {
-            let existing_hash = hash_json(existing_value);
-            if existing_hash != *expected_hash && !force {
+            if hash_entry(existing_value, preserved) != *expected_hash && !force {
                 anyhow::bail!("Refusing to remove user-modified entry: {}", key);
             }
             merged.remove(key);
@@ -55,6 +99,20 @@ pub fn merge_owned_map(
     Ok(merged)
 }
 
+/// `desired` with the `preserved` fields `existing` has.
+fn keep_preserved(desired: &Value, existing: &Value, preserved: &[String]) -> Value {
+    let (Value::Object(desired), Value::Object(existing)) = (desired, existing) else {
+        return desired.clone();
+    };
+    let mut value = desired.clone();
+    for field in preserved {
+        if let Some(current) = existing.get(field) {
+            value.insert(field.clone(), current.clone());
+        }
+    }
+    Value::Object(value)
+}
+
 fn normalize_json(value: &Value) -> Value {
     match value {
         Value::Object(map) => {
@@ -80,6 +80,22 @@ pub fn to_toml(config: &SiftConfig) -> Result<String> {
     toml::to_string_pretty(config).with_context(|| "Failed to serialize configuration to TOML")
 }
 
+/// Update existing sift.toml content to hold a configuration.
+///
+/// Comments, ordering and formatting of unchanged entries are preserved.
+pub fn update_toml(existing: &str, config: &SiftConfig) -> Result<String> {
+    if existing.trim().is_empty() {
+        return to_toml(config);
+    }
+    // Diff the typed views so defaults the file leaves implicit don't count
+    // as changes
+    let current = toml::Table::try_from(parse_sift_toml_str(existing)?)
+        .with_context(|| "Failed to serialize configuration to TOML")?;
+    let desired = toml::Table::try_from(config)
+        .with_context(|| "Failed to serialize configuration to TOML")?;
+    super::toml_document::patch_toml_document(existing, &current, &desired)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -172,6 +188,7 @@ ignore_targets = ["vscode"]
                 args: vec!["--readonly".to_string()],
                 url: None,
                 headers: HashMap::new(),
+                oauth: None,
                 targets: None,
                 ignore_targets: None,
                 env: {
@@ -183,6 +200,9 @@ ignore_targets = ["vscode"]
                 reset_ignore_targets: false,
                 reset_env: None,
                 reset_env_all: false,
+                volumes: Vec::new(),
+                network: None,
+                always_allow: Vec::new(),
             },
         );
 
@@ -209,6 +229,36 @@ ignore_targets = ["vscode"]
         assert_eq!(parsed.skill["pdf"].source, original.skill["pdf"].source);
     }
 
+    #[test]
+    fn test_update_toml_adds_entry_with_minimal_diff() {
+        let existing = r#"# Team servers
+[mcp.postgres]
+source = "registry:postgres-mcp"
+reset_targets = false # explicit default
+"#;
+        let mut config = parse_sift_toml_str(existing).unwrap();
+        config.skill.insert(
+            "pdf".to_string(),
+            crate::config::schema::SkillConfigEntry {
+                source: "registry:anthropic/pdf".to_string(),
+                version: Some("^1.0".to_string()),
+                targets: None,
+                ignore_targets: None,
+                reset_version: false,
+            },
+        );
+
+        let updated = update_toml(existing, &config).unwrap();
+
+        assert_eq!(
+            updated,
+            format!(
+                "{}\n[skill.pdf]\nsource = \"registry:anthropic/pdf\"\nversion = \"^1.0\"\n",
+                existing
+            )
+        );
+    }
+
     #[test]
     fn test_parse_from_file() {
         let mut temp_file = NamedTempFile::new().unwrap();
@@ -14,11 +14,11 @@ use std::path::Path;
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct SiftConfig {
     /// MCP server configurations
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub mcp: HashMap<String, McpConfigEntry>,
 
     /// Skill configurations
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub skill: HashMap<String, SkillConfigEntry>,
 
     /// Global install link mode
@@ -26,11 +26,11 @@ pub struct SiftConfig {
     pub link_mode: Option<crate::fs::LinkMode>,
 
     /// Client configurations (valid in all scopes)
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub clients: HashMap<String, ClientConfigEntry>,
 
     /// Registry configurations
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub registry: HashMap<String, RegistryConfigEntry>,
 
     /// Project-local configuration (ONLY valid in global config)
@@ -45,7 +45,7 @@ pub struct McpConfigEntry {
     #[serde(default)]
     pub transport: Option<String>,
 
-    /// STDIO: Source: "registry:name" or "local:/path/to/server"
+    /// STDIO: Source: "registry:name", "local:/path/to/server" or "docker:image[:tag]"
     #[serde(default)]
     pub source: String,
 
@@ -54,7 +54,7 @@ pub struct McpConfigEntry {
     pub runtime: Option<String>,
 
     /// STDIO: Command arguments
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub args: Vec<String>,
 
     /// HTTP: Server URL
@@ -62,9 +62,13 @@ pub struct McpConfigEntry {
     pub url: Option<String>,
 
     /// HTTP: Static HTTP headers
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub headers: HashMap<String, String>,
 
+    /// HTTP: OAuth client settings (client id, scopes, callback port)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub oauth: Option<crate::mcp::OAuthConfig>,
+
     /// Target control (whitelist)
     #[serde(default)]
     pub targets: Option<Vec<String>>,
@@ -74,16 +78,28 @@ pub struct McpConfigEntry {
     pub ignore_targets: Option<Vec<String>>,
 
     /// Environment variables
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub env: HashMap<String, String>,
 
+    /// Docker runtime: volume mounts passed as `-v` (e.g. "/srv/data:/data:ro")
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub volumes: Vec<String>,
+
+    /// Docker runtime: network passed as `--network`
+    #[serde(default)]
+    pub network: Option<String>,
+
+    /// Tools the client may call without asking (Cline / Roo Code `alwaysAllow`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub always_allow: Vec<String>,
+
     // RESET FLAGS - for clearing inherited values
     /// Reset targets to None (clear inherited whitelist)
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "is_false")]
     pub reset_targets: bool,
 
     /// Reset ignore_targets to None (clear inherited blacklist)
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "is_false")]
     pub reset_ignore_targets: bool,
 
     /// Reset specific environment variables by key
@@ -91,7 +107,7 @@ pub struct McpConfigEntry {
     pub reset_env: Option<Vec<String>>,
 
     /// Reset all environment variables to empty
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "is_false")]
     pub reset_env_all: bool,
 }
 
@@ -126,6 +142,7 @@ impl TryFrom<McpConfigEntry> for crate::mcp::McpConfig {
             args: entry.args,
             url: entry.url,
             headers: entry.headers,
+            oauth: entry.oauth,
             targets: entry.targets,
             ignore_targets: entry.ignore_targets,
             env: entry.env,
@@ -153,7 +170,7 @@ pub struct SkillConfigEntry {
 
     // RESET FLAG
     /// Reset version to None (clear inherited version)
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "is_false")]
     pub reset_version: bool,
 }
 
@@ -198,6 +215,53 @@ pub struct ClientConfigEntry {
     /// Capabilities (optional, auto-detected for built-in clients)
     #[serde(default)]
     pub capabilities: Option<serde_json::Value>,
+
+    /// Global MCP config file of a custom client, relative to the home directory
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mcp_global: Option<String>,
+
+    /// Project MCP config file of a custom client, relative to the project root
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mcp_project: Option<String>,
+
+    /// Config file format of a custom client: "json", "jsonc" or "toml"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+
+    /// Path within the config file to the servers map (default: ["mcpServers"])
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub config_path: Option<Vec<String>>,
+
+    /// Entry shape of a custom client
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<crate::client::custom::EntryTemplate>,
+
+    /// Global skill directory of a custom client, relative to the home directory
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub skills_global: Option<String>,
+
+    /// Project skill directory of a custom client, relative to the project root
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub skills_project: Option<String>,
+
+    /// User profiles to deliver global MCP servers to (VS Code).
+    /// "Default" is the default profile and "*" selects every profile.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub profiles: Vec<String>,
+}
+
+impl ClientConfigEntry {
+    /// Whether this entry declares a custom client adapter.
+    pub fn is_custom(&self) -> bool {
+        self.mcp_global.is_some()
+            || self.mcp_project.is_some()
+            || self.skills_global.is_some()
+            || self.skills_project.is_some()
+    }
+}
+
+fn is_false(value: &bool) -> bool {
+    !*value
 }
 
 fn default_client_enabled() -> bool {
@@ -208,10 +272,35 @@ impl TryFrom<ClientConfigEntry> for crate::client::ClientConfig {
     type Error = anyhow::Error;
 
     fn try_from(entry: ClientConfigEntry) -> Result<Self, Self::Error> {
+        let custom = if entry.is_custom() {
+            Some(crate::client::custom::CustomClientSpec {
+                format: crate::client::custom::CustomClientSpec::parse_format(
+                    entry.format.as_deref().unwrap_or("json"),
+                )?,
+                config_path: entry
+                    .config_path
+                    .unwrap_or_else(|| vec!["mcpServers".to_string()]),
+                template: entry.template.unwrap_or_default(),
+                mcp_global: entry.mcp_global,
+                mcp_project: entry.mcp_project,
+                skills_global: entry.skills_global,
+                skills_project: entry.skills_project,
+            })
+        } else {
+            None
+        };
+        let capabilities = entry
+            .capabilities
+            .map(serde_json::from_value)
+            .transpose()
+            .context("Invalid client capabilities")?;
+
         Ok(crate::client::ClientConfig {
             enabled: entry.enabled,
             source: entry.source,
-            capabilities: None, // Would need JSON deserialization for full support
+            capabilities,
+            custom,
+            profiles: entry.profiles,
         })
     }
 }
@@ -268,20 +357,28 @@ pub struct ProjectConfig {
     pub path: std::path::PathBuf,
 
     /// Project-local MCP entries for this project
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub mcp: HashMap<String, McpConfigEntry>,
 
     /// Project-local skill entries for this project
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub skill: HashMap<String, SkillConfigEntry>,
 
     /// MCP server overrides for this project
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub mcp_overrides: HashMap<String, McpOverrideEntry>,
 
     /// Skill overrides for this project
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub skill_overrides: HashMap<String, SkillOverrideEntry>,
+
+    /// Values for `${VAR}` references in this project's MCP servers
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub vars: HashMap<String, String>,
+
+    /// Write resolved `secret:` references into this project's shared configs
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub allow_shared_secrets: bool,
 }
 
 /// MCP server override for project-local configuration
@@ -291,7 +388,7 @@ pub struct McpOverrideEntry {
     pub runtime: Option<String>,
 
     /// Override environment variables (merged with base)
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub env: HashMap<String, String>,
 }
 
@@ -431,11 +528,11 @@ impl SiftConfig {
 
         // Validate each client config
         for (name, entry) in &self.clients {
-            // Warn about unimplemented capabilities override
-            if entry.capabilities.is_some() {
+            // Built-in adapters report their own capabilities
+            if entry.capabilities.is_some() && !entry.is_custom() {
                 eprintln!(
-                    "Warning: Client '{}' has capabilities override, but this is currently ignored. \
-                     The capabilities field is not yet supported and will be removed from the final config.",
+                    "Warning: Client '{}' has capabilities override, but this is only used by \
+                     custom clients that declare a config file or skill directory.",
                     name
                 );
             }
@@ -498,6 +595,13 @@ impl SiftConfig {
 
         None
     }
+
+    /// Project-local `${VAR}` values for the project at `path`.
+    pub fn project_variables(&self, path: &Path) -> HashMap<String, String> {
+        self.get_project_config(path)
+            .map(|(_, project)| project.vars.clone())
+            .unwrap_or_default()
+    }
 }
 
 #[cfg(test)]
@@ -533,6 +637,7 @@ mod tests {
             args: vec!["--readonly".to_string()],
             url: None,
             headers: HashMap::new(),
+            oauth: None,
             targets: Some(vec!["claude-desktop".to_string()]),
             ignore_targets: None,
             env: {
@@ -544,6 +649,9 @@ mod tests {
             reset_ignore_targets: false,
             reset_env: None,
             reset_env_all: false,
+            volumes: Vec::new(),
+            network: None,
+            always_allow: Vec::new(),
         };
 
         let config: crate::mcp::McpConfig = entry.try_into().unwrap();
@@ -634,6 +742,7 @@ mod tests {
                 args: vec![],
                 url: None,
                 headers: HashMap::new(),
+                oauth: None,
                 targets: None,
                 ignore_targets: None,
                 env: HashMap::new(),
@@ -641,6 +750,9 @@ mod tests {
                 reset_ignore_targets: false,
                 reset_env: None,
                 reset_env_all: false,
+                volumes: Vec::new(),
+                network: None,
+                always_allow: Vec::new(),
             },
         );
 
@@ -660,6 +772,7 @@ mod tests {
                 args: vec![],
                 url: None,
                 headers: HashMap::new(),
+                oauth: None,
                 targets: Some(vec!["target".to_string()]),
                 ignore_targets: Some(vec!["ignore".to_string()]), // Both set - should fail
                 env: HashMap::new(),
@@ -667,12 +780,44 @@ mod tests {
                 reset_ignore_targets: false,
                 reset_env: None,
                 reset_env_all: false,
+                volumes: Vec::new(),
+                network: None,
+                always_allow: Vec::new(),
             },
         );
 
         assert!(config.validate().is_err());
     }
 
+    #[test]
+    fn test_parse_http_oauth_settings() {
+        let mut config: SiftConfig = toml::from_str(
+            r#"
+[mcp.linear]
+transport = "streamable-http"
+url = "https://mcp.linear.app/mcp"
+oauth = { client_id = "sift", scopes = ["read", "write"], callback_port = 8765 }
+
+[mcp.local]
+source = "local:server"
+runtime = "shell"
+oauth = { client_id = "sift" }
+"#,
+        )
+        .unwrap();
+
+        let oauth = config.mcp["linear"].oauth.as_ref().unwrap();
+        assert_eq!(oauth.client_id.as_deref(), Some("sift"));
+        assert_eq!(oauth.scopes, vec!["read", "write"]);
+        assert_eq!(oauth.callback_port, Some(8765));
+
+        let err = config.validate().unwrap_err();
+        assert!(format!("{:#}", err).contains("only supported for http transport"));
+
+        config.mcp.remove("local");
+        assert!(config.validate().is_ok());
+    }
+
     #[test]
     fn test_validate_rejects_invalid_project_override_runtime() {
         let mut config = SiftConfig::new();
@@ -53,8 +53,18 @@ impl ConfigStore {
         parser::parse_sift_toml(&self.config_path)
     }
 
+    /// Save the config, editing an existing file in place so unchanged
+    /// entries keep their comments and formatting.
     pub fn save(&self, config: &SiftConfig) -> anyhow::Result<()> {
-        let content = parser::to_toml(config).context("Failed to serialize config to TOML")?;
+        let existing = if self.config_path.exists() {
+            std::fs::read_to_string(&self.config_path).with_context(|| {
+                format!("Failed to read config file: {}", self.config_path.display())
+            })?
+        } else {
+            String::new()
+        };
+        let content =
+            parser::update_toml(&existing, config).context("Failed to serialize config to TOML")?;
         if let Some(parent) = self.config_path.parent() {
             std::fs::create_dir_all(parent).with_context(|| {
                 format!("Failed to create config directory: {}", parent.display())
@@ -0,0 +1,219 @@
+//! Format-preserving TOML document updates.
+//!
+//! Both `sift.toml` and client configs such as `~/.codex/config.toml` are
+//! edited by hand and reviewed in diffs. Instead of re-rendering the whole
+//! file, the existing document is patched with `toml_edit`: only keys whose
+//! values changed are rewritten, so comments, key order and formatting
+//! everywhere else are kept.
+
+use anyhow::{Context, Result};
+use toml_edit::{Array, DocumentMut, InlineTable, Item, Table, TableLike, Value};
+
+/// Rewrite TOML text so it holds `desired`, touching only changed keys.
+///
+/// Blank text is rendered from scratch.
+pub fn update_toml_document(text: &str, desired: &toml::Table) -> Result<String> {
+    let current: toml::Table = if text.trim().is_empty() {
+        toml::Table::new()
+    } else {
+        toml::from_str(text).context("Failed to parse existing TOML")?
+    };
+    patch_toml_document(text, &current, desired)
+}
+
+/// Rewrite TOML text from `current` to `desired`.
+///
+/// `current` is what the text holds as seen by the caller's model. Keys in
+/// the text that the model does not know about (`current` lacks them) are
+/// left alone, so re-saving a typed config does not drop values the user
+/// spelled out explicitly.
+pub fn patch_toml_document(
+    text: &str,
+    current: &toml::Table,
+    desired: &toml::Table,
+) -> Result<String> {
+    let mut document: DocumentMut = text.parse().context("Failed to parse existing TOML")?;
+    patch_table(document.as_table_mut(), current, desired, false);
+    Ok(document.to_string())
+}
+
+fn patch_table(
+    table: &mut dyn TableLike,
+    current: &toml::Table,
+    desired: &toml::Table,
+    inline: bool,
+) {
+    let removed: Vec<String> = table
+        .iter()
+        .map(|(key, _)| key.to_string())
+        .filter(|key| current.contains_key(key) && !desired.contains_key(key))
+        .collect();
+    for key in removed {
+        table.remove(&key);
+    }
+
+    for (key, new_value) in desired {
+        let old_value = current.get(key);
+        if old_value == Some(new_value) {
+            continue;
+        }
+        if let (Some(toml::Value::Table(old)), toml::Value::Table(new)) = (old_value, new_value)
+            && let Some(existing) = table.get_mut(key)
+        {
+            let child_inline = existing.is_inline_table();
+            if let Some(child) = existing.as_table_like_mut() {
+                patch_table(child, old, new, child_inline);
+                if child_inline {
+                    // Inline tables are one value; normalize their spacing
+                    child.fmt();
+                }
+                continue;
+            }
+        }
+
+        let mut item = to_item(new_value);
+        if inline && let Ok(value) = item.clone().into_value() {
+            item = Item::Value(value);
+        }
+        // Keep comments attached to a replaced value
+        if let (Some(Item::Value(old)), Item::Value(new)) = (table.get(key), &mut item) {
+            *new.decor_mut() = old.decor().clone();
+        }
+        table.insert(key, item);
+    }
+}
+
+/// Convert a value for insertion: non-empty tables become `[table]`
+/// sections, everything else an inline value.
+fn to_item(value: &toml::Value) -> Item {
+    match value {
+        toml::Value::Table(entries) if !entries.is_empty() => {
+            let mut table = Table::new();
+            // A table of tables only needs headers for its children
+            table.set_implicit(entries.values().all(|v| matches!(v, toml::Value::Table(_))));
+            for (key, value) in entries {
+                table.insert(key, to_item(value));
+            }
+            Item::Table(table)
+        }
+        _ => Item::Value(to_value(value)),
+    }
+}
+
+fn to_value(value: &toml::Value) -> Value {
+    match value {
+        toml::Value::String(s) => Value::from(s.as_str()),
+        toml::Value::Integer(i) => Value::from(*i),
+        toml::Value::Float(f) => Value::from(*f),
+        toml::Value::Boolean(b) => Value::from(*b),
+        toml::Value::Datetime(dt) => Value::from(*dt),
+        toml::Value::Array(items) => Value::Array(items.iter().map(to_value).collect::<Array>()),
+        toml::Value::Table(entries) => Value::InlineTable(
+            entries
+                .iter()
+                .map(|(key, value)| (key.clone(), to_value(value)))
+                .collect::<InlineTable>(),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = r#"# Team MCP servers
+[mcp.postgres]
+source = "registry:postgres-mcp" # pinned by ops
+runtime = "docker"
+args = ["--readonly"]
+
+# Docs skill
+[skill.pdf]
+source = "registry:anthropic/pdf"
+version = "^1.0"
+"#;
+
+    fn desired(update: impl FnOnce(&mut toml::Table)) -> toml::Table {
+        let mut table: toml::Table = toml::from_str(CONFIG).unwrap();
+        update(&mut table);
+        table
+    }
+
+    #[test]
+    fn test_update_unchanged_is_identical() {
+        let table: toml::Table = toml::from_str(CONFIG).unwrap();
+        assert_eq!(update_toml_document(CONFIG, &table).unwrap(), CONFIG);
+    }
+
+    #[test]
+    fn test_update_adds_table_with_minimal_diff() {
+        let table = desired(|t| {
+            let mut entry = toml::Table::new();
+            entry.insert("source".into(), "registry:fetch".into());
+            entry.insert("args".into(), toml::Value::Array(vec!["-v".into()]));
+            t["mcp"]
+                .as_table_mut()
+                .unwrap()
+                .insert("fetch".into(), entry.into());
+        });
+
+        let updated = update_toml_document(CONFIG, &table).unwrap();
+
+        // The new table lands next to its siblings; everything else is kept
+        assert_eq!(
+            updated,
+            CONFIG.replace(
+                "# Docs skill",
+                "[mcp.fetch]\nargs = [\"-v\"]\nsource = \"registry:fetch\"\n\n# Docs skill"
+            )
+        );
+        assert_eq!(toml::from_str::<toml::Table>(&updated).unwrap(), table);
+    }
+
+    #[test]
+    fn test_update_replaces_value_and_keeps_comment() {
+        let table = desired(|t| {
+            t["mcp"]["postgres"]
+                .as_table_mut()
+                .unwrap()
+                .insert("source".into(), "registry:pg".into());
+        });
+
+        let updated = update_toml_document(CONFIG, &table).unwrap();
+
+        assert_eq!(
+            updated,
+            CONFIG.replace("registry:postgres-mcp", "registry:pg")
+        );
+    }
+
+    #[test]
+    fn test_update_removes_table() {
+        let table = desired(|t| {
+            t.remove("skill");
+        });
+
+        let updated = update_toml_document(CONFIG, &table).unwrap();
+
+        assert!(!updated.contains("[skill.pdf]"));
+        assert!(updated.starts_with("# Team MCP servers\n[mcp.postgres]\n"));
+        assert_eq!(toml::from_str::<toml::Table>(&updated).unwrap(), table);
+    }
+
+    #[test]
+    fn test_update_inline_table_stays_inline() {
+        let text = "[mcp_servers.ctx]\ncommand = \"npx\"\nenv = { A = \"1\" }\n";
+        let mut table: toml::Table = toml::from_str(text).unwrap();
+        table["mcp_servers"]["ctx"]["env"]
+            .as_table_mut()
+            .unwrap()
+            .insert("B".into(), "2".into());
+
+        let updated = update_toml_document(text, &table).unwrap();
+
+        assert_eq!(
+            updated,
+            "[mcp_servers.ctx]\ncommand = \"npx\"\nenv = { A = \"1\", B = \"2\" }\n"
+        );
+    }
+}
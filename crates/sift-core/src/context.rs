@@ -85,7 +85,11 @@ impl AppContext {
 
     /// Get a ClientContext for adapter calls.
     pub fn client_context(&self) -> ClientContext {
-        ClientContext::new(self.home_dir.clone(), self.project_root.clone())
+        ClientContext::for_project(
+            self.home_dir.clone(),
+            self.project_root.clone(),
+            &self.global_config_dir,
+        )
     }
 
     /// Get a LockfileService for the current project.
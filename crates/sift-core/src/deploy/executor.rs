@@ -7,6 +7,7 @@ use anyhow::Context;
 use crate::client::{ClientAdapter, ClientContext, PathRoot};
 use crate::config::client_config::{self, ConfigFormat};
 use crate::lockfile::LockfileService;
+use crate::mcp::expand::expand_servers;
 use crate::mcp::spec::McpResolvedServer;
 use crate::types::ConfigScope;
 
@@ -25,24 +26,27 @@ pub fn deploy_mcp_to_client(
     lockfile: &LockfileService,
     force: bool,
 ) -> anyhow::Result<DeployReport> {
-    let plan = client.plan_mcp(ctx, scope, servers)?;
-    let config_path = resolve_plan_path(ctx, plan.root, &plan.relative_path)?;
-    let path: Vec<&str> = plan.config_path.iter().map(|s| s.as_str()).collect();
-    let format: ConfigFormat = plan.format.into();
+    let expanded = expand_servers(client, ctx, scope, servers, force)?;
+    for plan in client.plan_mcp_all(ctx, scope, &expanded.servers)? {
+        let config_path = resolve_plan_path(ctx, plan.root, &plan.relative_path)?;
+        let path: Vec<&str> = plan.config_path.iter().map(|s| s.as_str()).collect();
+        let format: ConfigFormat = plan.format.into();
 
-    client_config::apply_managed_entries_in_path(
-        &config_path,
-        &path,
-        &plan.entries,
-        lockfile,
-        force,
-        format,
-    )
-    .with_context(|| format!("Failed to apply config to {}", config_path.display()))?;
+        client_config::apply_managed_entries_preserving(
+            &config_path,
+            &path,
+            &plan.entries,
+            &plan.preserved_fields,
+            lockfile,
+            force,
+            format,
+        )
+        .with_context(|| format!("Failed to apply config to {}", config_path.display()))?;
+    }
 
     Ok(DeployReport {
         applied: true,
-        warnings: Vec::new(),
+        warnings: expanded.warnings,
     })
 }
 
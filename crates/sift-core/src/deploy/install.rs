@@ -7,14 +7,18 @@ use anyhow::Context;
 use crate::client::{ClientAdapter, ClientContext, PathRoot};
 use crate::config::client_config::{self, ConfigFormat};
 use crate::config::{ConfigStore, McpConfigEntry, SkillConfigEntry};
-use crate::deploy::scope::ScopeResolution;
+use crate::deploy::scope::{ScopeDecision, ScopeResolution};
 use crate::deploy::service::{InstallOutcome, InstallService};
 use crate::fs::{LinkMode, remove_path_if_exists};
 use crate::git::{FetchResult, GitFetcher, ensure_git_exclude};
 use crate::lockfile::LockfileService;
 use crate::lockfile::{LockedMcpServer, ResolvedOrigin};
+use crate::mcp::expand::expand_servers;
+use crate::mcp::spec::McpResolvedServer;
+use crate::runtime::image_tag;
 use crate::skills::installer::{GitSkillMetadata, SkillInstallResult, SkillInstaller};
 use crate::source::{RegistryMetadata, ResolvedSource, SourceResolver};
+use crate::types::ConfigScope;
 
 #[derive(Debug, Clone)]
 pub struct InstallReport {
@@ -27,10 +31,12 @@ pub struct InstallReport {
 pub struct InstallMcpRequest<'a> {
     pub name: &'a str,
     pub entry: McpConfigEntry,
-    pub servers: &'a [crate::mcp::spec::McpResolvedServer],
+    pub servers: &'a [McpResolvedServer],
     pub resolution: ScopeResolution,
     pub force: bool,
     pub declared_version: Option<&'a str>,
+    pub resolved_version: Option<&'a str>,
+    pub checksum: Option<&'a str>,
 }
 
 #[derive(Debug)]
@@ -98,7 +104,14 @@ impl InstallOrchestrator {
             ScopeResolution::Skip { warning } => {
                 let entry = req.entry.clone();
                 let outcome = self.install.install_mcp(req.name, req.entry, req.force)?;
-                self.update_mcp_lockfile(req.name, &entry, req.declared_version)?;
+                self.update_mcp_lockfile(
+                    req.name,
+                    &entry,
+                    req.declared_version,
+                    req.resolved_version,
+                    req.checksum,
+                    req.servers,
+                )?;
                 Ok(InstallReport {
                     outcome,
                     warnings: vec![warning],
@@ -110,7 +123,14 @@ impl InstallOrchestrator {
                 if !should_deploy {
                     let entry = req.entry.clone();
                     let outcome = self.install.install_mcp(req.name, req.entry, req.force)?;
-                    self.update_mcp_lockfile(req.name, &entry, req.declared_version)?;
+                    self.update_mcp_lockfile(
+                        req.name,
+                        &entry,
+                        req.declared_version,
+                        req.resolved_version,
+                        req.checksum,
+                        req.servers,
+                    )?;
                     return Ok(InstallReport {
                         outcome,
                         warnings: vec![format!(
@@ -122,57 +142,122 @@ impl InstallOrchestrator {
                 }
                 let entry = req.entry.clone();
                 let outcome = self.install.install_mcp(req.name, req.entry, req.force)?;
-                let plan = client.plan_mcp(ctx, decision.scope, req.servers)?;
-                let config_path = resolve_plan_path(ctx, plan.root, &plan.relative_path)?;
-                let path: Vec<&str> = plan.config_path.iter().map(|s| s.as_str()).collect();
-                let format: ConfigFormat = plan.format.into();
-                client_config::apply_managed_entries_in_path(
-                    &config_path,
-                    &path,
-                    &plan.entries,
-                    &self.lockfile_service,
-                    req.force,
-                    format,
-                )
-                .with_context(|| format!("Failed to apply MCP config for {}", req.name))?;
-                self.update_mcp_lockfile(req.name, &entry, req.declared_version)?;
+                let retained = self.other_mcp_entries(req.name)?;
+                let warnings = self
+                    .apply_mcp_servers(
+                        client,
+                        ctx,
+                        decision.scope,
+                        req.servers,
+                        &retained,
+                        req.force,
+                    )
+                    .with_context(|| format!("Failed to apply MCP config for {}", req.name))?;
+                self.update_mcp_lockfile(
+                    req.name,
+                    &entry,
+                    req.declared_version,
+                    req.resolved_version,
+                    req.checksum,
+                    req.servers,
+                )?;
 
                 Ok(InstallReport {
                     outcome,
-                    warnings: Vec::new(),
+                    warnings,
                     applied: true,
                 })
             }
         }
     }
 
-    fn update_mcp_lockfile(
+    /// Write a set of MCP servers into a client's config at the given scope.
+    ///
+    /// Does not touch sift.toml. Sift-owned entries in the target config that
+    /// are neither part of `servers` nor named in `retained` are removed, so
+    /// sync passes the full set. Returns warnings from expanding `${VAR}`
+    /// references.
+    pub fn apply_mcp_servers(
+        &self,
+        client: &dyn ClientAdapter,
+        ctx: &ClientContext,
+        scope: ConfigScope,
+        servers: &[McpResolvedServer],
+        retained: &[String],
+        force: bool,
+    ) -> anyhow::Result<Vec<String>> {
+        let expanded = expand_servers(client, ctx, scope, servers, force)?;
+        for plan in client.plan_mcp_all(ctx, scope, &expanded.servers)? {
+            let config_path = resolve_plan_path(ctx, plan.root, &plan.relative_path)?;
+            let path: Vec<&str> = plan.config_path.iter().map(|s| s.as_str()).collect();
+            let format: ConfigFormat = plan.format.into();
+            client_config::apply_managed_entries_retaining(
+                &config_path,
+                &path,
+                &plan.entries,
+                &plan.preserved_fields,
+                retained,
+                &self.lockfile_service,
+                force,
+                format,
+            )?;
+        }
+        Ok(expanded.warnings)
+    }
+
+    /// Client config entries of every locked MCP server other than `name`.
+    ///
+    /// Installing one server leaves these in place; only sync writes the
+    /// complete set.
+    fn other_mcp_entries(&self, name: &str) -> anyhow::Result<Vec<String>> {
+        let lockfile = self.lockfile_service.load()?;
+        Ok(lockfile
+            .mcp_servers
+            .values()
+            .filter(|locked| locked.name != name)
+            .flat_map(LockedMcpServer::entry_names)
+            .collect())
+    }
+
+    /// Record an MCP server in the lockfile at this orchestrator's scope.
+    ///
+    /// `resolved_version` is the version the registry published; registries
+    /// without version information record the constraint itself. Docker
+    /// images record their tag as the constraint and `checksum` as the digest
+    /// the tag resolved to. The names of `servers` are recorded so prune can
+    /// find every client config entry the server was delivered as.
+    pub fn update_mcp_lockfile(
         &self,
         name: &str,
         entry: &McpConfigEntry,
         declared_version: Option<&str>,
+        resolved_version: Option<&str>,
+        checksum: Option<&str>,
+        servers: &[McpResolvedServer],
     ) -> anyhow::Result<()> {
-        let is_registry = entry.source.starts_with("registry:");
-        let constraint = if is_registry {
-            declared_version.unwrap_or("latest")
+        let (constraint, resolved_version, registry) = if entry.source.starts_with("registry:") {
+            let constraint = declared_version.unwrap_or("latest");
+            (
+                constraint,
+                resolved_version.unwrap_or(constraint),
+                entry.source.clone(),
+            )
+        } else if let Some(image) = entry.source.strip_prefix("docker:") {
+            let tag = image_tag(image).or(declared_version).unwrap_or("latest");
+            (tag, tag, entry.source.clone())
         } else {
-            "unmanaged"
-        };
-        // TODO: Registry resolution is not implemented yet; resolved_version is a placeholder.
-        let resolved_version = if is_registry { "todo" } else { "unmanaged" };
-        let registry = if is_registry {
-            entry.source.clone()
-        } else {
-            "local".to_string()
+            ("unmanaged", "unmanaged", "local".to_string())
         };
 
-        let locked = LockedMcpServer::new(
+        let mut locked = LockedMcpServer::new(
             name.to_string(),
             resolved_version.to_string(),
             constraint.to_string(),
             registry,
             self.install.config_store().scope(),
         );
+        locked.checksum = checksum.map(str::to_string);
+        let locked = locked.with_entries(servers.iter().map(|s| s.name.clone()).collect());
 
         self.lockfile_service.add_mcp(name, locked)
     }
@@ -230,40 +315,7 @@ impl InstallOrchestrator {
                 }
                 let prepared = self.prepare_skill_source(name, source, force)?;
                 let outcome = self.install.install_skill(name, entry, force)?;
-
-                let mut plan = client.plan_skill(ctx, decision.scope)?;
-                if decision.use_git_exclude {
-                    plan.use_git_exclude = true;
-                }
-
-                let root = resolve_plan_path(ctx, plan.root, &plan.relative_path)?;
-                let dst_dir = root.join(name);
-
-                if force {
-                    self.cleanup_skill_delivery(&dst_dir, name)?;
-                }
-
-                if plan.use_git_exclude {
-                    let rel = plan.relative_path.to_string_lossy();
-                    ensure_git_exclude(&ctx.project_root, rel.as_ref())
-                        .context("Failed to update git exclude")?;
-                }
-
-                let allow_symlink = client.capabilities().supports_symlinked_skills;
-                let install = self.skill_installer.install(
-                    name,
-                    &prepared.cache_dir,
-                    &dst_dir,
-                    self.link_mode,
-                    force,
-                    allow_symlink,
-                    &prepared.resolved_version,
-                    &prepared.constraint,
-                    &prepared.registry,
-                    decision.scope,
-                    prepared.git_metadata,
-                    prepared.origin,
-                )?;
+                let install = self.deliver_skill(client, ctx, name, prepared, &decision, force)?;
 
                 Ok(SkillInstallReport {
                     outcome,
@@ -275,6 +327,98 @@ impl InstallOrchestrator {
         }
     }
 
+    /// Fetch and deliver a skill to a client without touching sift.toml.
+    ///
+    /// Used by sync, where the entry is already declared in config.
+    pub fn sync_skill(
+        &self,
+        client: &dyn ClientAdapter,
+        ctx: &ClientContext,
+        name: &str,
+        source: &str,
+        decision: &ScopeDecision,
+        force: bool,
+    ) -> anyhow::Result<SkillInstallResult> {
+        let prepared = self.prepare_skill_source(name, source, force)?;
+        self.deliver_skill(client, ctx, name, prepared, decision, force)
+    }
+
+    /// Re-fetch a git skill at a resolved ref and deliver it to a client.
+    ///
+    /// `reference` replaces the ref from the source (`None` follows the
+    /// remote HEAD) and `constraint` is recorded in the lockfile. Only the
+    /// first client of an upgrade needs `refetch`; later clients reuse the
+    /// refreshed cache.
+    #[allow(clippy::too_many_arguments)]
+    pub fn upgrade_skill(
+        &self,
+        client: &dyn ClientAdapter,
+        ctx: &ClientContext,
+        name: &str,
+        source: &str,
+        reference: Option<&str>,
+        constraint: &str,
+        decision: &ScopeDecision,
+        refetch: bool,
+    ) -> anyhow::Result<SkillInstallResult> {
+        let (resolved_source, registry_metadata) =
+            self.source_resolver.resolve_with_metadata(source)?;
+        let ResolvedSource::Git(mut spec) = resolved_source else {
+            anyhow::bail!("Skill '{}' is not sourced from git", name);
+        };
+        spec.reference = reference.map(str::to_string);
+
+        let mut prepared =
+            self.prepare_git_source(name, source, &spec, registry_metadata, refetch)?;
+        prepared.constraint = constraint.to_string();
+        self.deliver_skill(client, ctx, name, prepared, decision, true)
+    }
+
+    /// Link a prepared skill source into the client's skill directory.
+    fn deliver_skill(
+        &self,
+        client: &dyn ClientAdapter,
+        ctx: &ClientContext,
+        name: &str,
+        prepared: PreparedSkillSource,
+        decision: &ScopeDecision,
+        force: bool,
+    ) -> anyhow::Result<SkillInstallResult> {
+        let mut plan = client.plan_skill(ctx, decision.scope)?;
+        if decision.use_git_exclude {
+            plan.use_git_exclude = true;
+        }
+
+        let root = resolve_plan_path(ctx, plan.root, &plan.relative_path)?;
+        let dst_dir = root.join(name);
+
+        if force {
+            self.cleanup_skill_delivery(&dst_dir, name)?;
+        }
+
+        if plan.use_git_exclude {
+            let rel = plan.relative_path.to_string_lossy();
+            ensure_git_exclude(&ctx.project_root, rel.as_ref())
+                .context("Failed to update git exclude")?;
+        }
+
+        let allow_symlink = client.capabilities().supports_symlinked_skills;
+        self.skill_installer.install(
+            name,
+            &prepared.cache_dir,
+            &dst_dir,
+            self.link_mode,
+            force,
+            allow_symlink,
+            &prepared.resolved_version,
+            &prepared.constraint,
+            &prepared.registry,
+            decision.scope,
+            prepared.git_metadata,
+            prepared.origin,
+        )
+    }
+
     /// Prepare a skill source for installation.
     ///
     /// Resolves the source string, checks lockfile for cached versions,
@@ -289,8 +433,25 @@ impl InstallOrchestrator {
             self.source_resolver.resolve_with_metadata(source)?;
 
         match resolved_source {
-            ResolvedSource::Git(spec) => {
-                self.prepare_git_source(name, source, &spec, registry_metadata, force)
+            ResolvedSource::Git(mut spec) => {
+                // Keep a ref pinned by `sift upgrade` when the source names none
+                let pinned = self
+                    .lockfile_service
+                    .get_skill(name)?
+                    .filter(|locked| locked.git_repo.as_deref() == Some(spec.repo_url.as_str()))
+                    .filter(|_| spec.reference.is_none())
+                    .and_then(|locked| Some((locked.git_ref?, locked.constraint)));
+                let constraint = pinned.as_ref().map(|(_, c)| c.clone());
+                if let Some((reference, _)) = pinned {
+                    spec.reference = Some(reference);
+                }
+
+                let mut prepared =
+                    self.prepare_git_source(name, source, &spec, registry_metadata, force)?;
+                if let Some(constraint) = constraint {
+                    prepared.constraint = constraint;
+                }
+                Ok(prepared)
             }
             ResolvedSource::Local(spec) => {
                 if !spec.path.exists() {
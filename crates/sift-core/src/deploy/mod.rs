@@ -13,5 +13,5 @@ pub use scope::{
     resolve_scope,
 };
 pub use service::{InstallOutcome, InstallService, UninstallOutcome, UninstallService};
-pub use targeting::TargetingPolicy;
+pub use targeting::{ClientTarget, TargetSelection, TargetingPolicy};
 pub use uninstall::{UninstallOrchestrator, UninstallReport};
@@ -1,5 +1,12 @@
 //! Client targeting policy for selective deployment.
 
+use crate::client::ClientAdapter;
+use crate::client::registry::ClientRegistry;
+use crate::deploy::scope::{
+    RepoStatus, ResourceKind, ScopeRequest, ScopeResolution, ScopeSupport, resolve_scope,
+};
+use crate::types::ConfigScope;
+
 /// Policy for determining which clients to deploy to.
 #[derive(Debug, Clone, Default)]
 pub struct TargetingPolicy {
@@ -27,4 +34,136 @@ impl TargetingPolicy {
         }
         true
     }
+
+    /// Whether the policy names clients explicitly (whitelist).
+    ///
+    /// Explicit targets are fail-fast; implicit targets are best-effort.
+    pub fn is_explicit(&self) -> bool {
+        self.targets.as_ref().is_some_and(|t| !t.is_empty())
+    }
+
+    /// Resolve which clients receive a resource and at which scope.
+    ///
+    /// One scope is chosen for the whole install: the requested scope, or for
+    /// `Auto` the scope picked by the first matching client. Every matching
+    /// client is then resolved against that scope. With explicit targets an
+    /// unknown client or unsupported scope is an error; otherwise such clients
+    /// are returned as `Skip` with a warning. Clients disabled in sift.toml
+    /// are always returned as `Skip`.
+    pub fn select_clients<'a>(
+        &self,
+        registry: &'a ClientRegistry,
+        resource: ResourceKind,
+        request: ScopeRequest,
+        repo: RepoStatus,
+    ) -> anyhow::Result<TargetSelection<'a>> {
+        let explicit = self.is_explicit();
+        if let Some(targets) = self.targets.as_ref().filter(|_| explicit) {
+            for target in targets {
+                if registry.get(target).is_none() {
+                    anyhow::bail!(
+                        "Unknown target client '{}'. Known clients: {}",
+                        target,
+                        registry.client_ids().join(", ")
+                    );
+                }
+            }
+        }
+
+        let targets = self.targets.as_deref().filter(|_| explicit);
+        let candidates = registry.applicable_clients(targets, self.ignore_targets.as_deref());
+
+        let requested_scope = match request {
+            ScopeRequest::Explicit(scope) => Some(scope),
+            ScopeRequest::Auto => candidates.iter().find_map(|client| {
+                if !registry.is_enabled(client.id()) {
+                    return None;
+                }
+                match resolve_scope(resource, request, support_for(*client, resource), repo) {
+                    Ok(ScopeResolution::Apply(decision)) => Some(decision.scope),
+                    _ => None,
+                }
+            }),
+        };
+
+        let mut clients = Vec::with_capacity(candidates.len());
+        for client in candidates {
+            if !registry.is_enabled(client.id()) {
+                clients.push(ClientTarget {
+                    client,
+                    resolution: ScopeResolution::Skip {
+                        warning: format!(
+                            "Skipping deployment to '{}': client is disabled in sift.toml",
+                            client.id()
+                        ),
+                    },
+                });
+                continue;
+            }
+            let resolution = match requested_scope {
+                Some(scope) => match resolve_scope(
+                    resource,
+                    ScopeRequest::Explicit(scope),
+                    support_for(client, resource),
+                    repo,
+                ) {
+                    Ok(resolution) => resolution,
+                    Err(err) if explicit => {
+                        return Err(err.context(format!("Target client '{}'", client.id())));
+                    }
+                    Err(err) => ScopeResolution::Skip {
+                        warning: format!("Skipping deployment to '{}': {}", client.id(), err),
+                    },
+                },
+                None => ScopeResolution::Skip {
+                    warning: format!(
+                        "Skipping deployment to '{}': client does not support any requested scopes",
+                        client.id()
+                    ),
+                },
+            };
+            clients.push(ClientTarget { client, resolution });
+        }
+
+        let config_scope = clients
+            .iter()
+            .find_map(|t| match &t.resolution {
+                ScopeResolution::Apply(decision) => Some(decision.scope),
+                ScopeResolution::Skip { .. } => None,
+            })
+            .or(requested_scope)
+            .unwrap_or(ConfigScope::PerProjectShared);
+
+        Ok(TargetSelection {
+            config_scope,
+            explicit,
+            clients,
+        })
+    }
+}
+
+/// A client selected for deployment with its scope resolution.
+#[derive(Debug)]
+pub struct ClientTarget<'a> {
+    pub client: &'a dyn ClientAdapter,
+    pub resolution: ScopeResolution,
+}
+
+/// Result of [`TargetingPolicy::select_clients`].
+#[derive(Debug)]
+pub struct TargetSelection<'a> {
+    /// Scope of the sift.toml the entry is recorded in
+    pub config_scope: ConfigScope,
+    /// Whether targets were named explicitly (fail-fast)
+    pub explicit: bool,
+    /// Matching clients in registry order
+    pub clients: Vec<ClientTarget<'a>>,
+}
+
+fn support_for(client: &dyn ClientAdapter, resource: ResourceKind) -> ScopeSupport {
+    let capabilities = client.capabilities();
+    match resource {
+        ResourceKind::Mcp => capabilities.mcp,
+        ResourceKind::Skill => capabilities.skills,
+    }
 }
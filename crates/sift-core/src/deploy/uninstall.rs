@@ -5,9 +5,10 @@ use std::collections::HashMap;
 use anyhow::Context;
 use serde_json::{Map, Value};
 
-use crate::client::{ClientAdapter, ClientContext};
+use crate::client::{ClientAdapter, ClientContext, ManagedJsonPlan};
 use crate::config::ConfigStore;
 use crate::config::client_config::{self, ConfigFormat};
+use crate::config::ownership::hash_entry;
 use crate::deploy::install::resolve_plan_path;
 use crate::deploy::service::{UninstallOutcome, UninstallService};
 use crate::fs::remove_path_if_exists;
@@ -37,29 +38,39 @@ impl UninstallOrchestrator {
         self.uninstall.config_store()
     }
 
+    /// Remove an MCP server from sift.toml, the lockfile and, when `client`
+    /// is given, its client config.
     pub fn uninstall_mcp(
         &self,
-        client: &dyn ClientAdapter,
+        client: Option<&dyn ClientAdapter>,
         ctx: &ClientContext,
         name: &str,
     ) -> anyhow::Result<UninstallReport> {
         let outcome = self.uninstall.remove_mcp(name)?;
         let mut warnings = Vec::new();
-        let managed_changed = self.remove_managed_mcp_entry(client, ctx, name, &mut warnings)?;
+        let managed_changed = match client {
+            Some(client) => self.remove_managed_mcp_entry(client, ctx, name, &mut warnings)?,
+            None => false,
+        };
         let lockfile_removed = self.lockfile_service.remove_mcp(name)?;
         let changed =
             matches!(outcome, UninstallOutcome::Changed) || managed_changed || lockfile_removed;
         Ok(UninstallReport { changed, warnings })
     }
 
+    /// Remove a skill from sift.toml, the lockfile and, when `client` is
+    /// given, its delivery directory.
     pub fn uninstall_skill(
         &self,
-        client: &dyn ClientAdapter,
+        client: Option<&dyn ClientAdapter>,
         ctx: &ClientContext,
         name: &str,
     ) -> anyhow::Result<UninstallReport> {
         let outcome = self.uninstall.remove_skill(name)?;
-        let removed_dir = self.remove_skill_dir(client, ctx, name)?;
+        let removed_dir = match client {
+            Some(client) => self.remove_skill_dir(client, ctx, name)?,
+            None => false,
+        };
         let lockfile_removed = self.lockfile_service.remove_skill(name)?;
         let changed =
             matches!(outcome, UninstallOutcome::Changed) || removed_dir || lockfile_removed;
@@ -77,7 +88,20 @@ impl UninstallOrchestrator {
         warnings: &mut Vec<String>,
     ) -> anyhow::Result<bool> {
         let scope = self.uninstall.config_store().scope();
-        let plan = client.plan_mcp(ctx, scope, &[])?;
+        let mut removed = false;
+        for plan in client.plan_mcp_all(ctx, scope, &[])? {
+            removed |= self.remove_managed_mcp_entry_in(&plan, ctx, name, warnings)?;
+        }
+        Ok(removed)
+    }
+
+    fn remove_managed_mcp_entry_in(
+        &self,
+        plan: &ManagedJsonPlan,
+        ctx: &ClientContext,
+        name: &str,
+        warnings: &mut Vec<String>,
+    ) -> anyhow::Result<bool> {
         let config_path = resolve_plan_path(ctx, plan.root, &plan.relative_path)?;
         let path: Vec<&str> = plan.config_path.iter().map(|s| s.as_str()).collect();
         let ownership_key = plan.config_path.join(".");
@@ -88,7 +112,7 @@ impl UninstallOrchestrator {
             return Ok(false);
         }
 
-        let format: ConfigFormat = plan.format.into();
+        let format: ConfigFormat = plan.format.clone().into();
         let existing = client_config::read_map_at_path(&config_path, &path, format)?;
         let has_entry = existing.contains_key(name);
         let is_owned = ownership.contains_key(name);
@@ -106,10 +130,11 @@ impl UninstallOrchestrator {
             return Ok(false);
         }
 
-        client_config::apply_managed_entries_in_path(
+        client_config::apply_managed_entries_preserving(
             &config_path,
             &path,
             &desired,
+            &plan.preserved_fields,
             &self.lockfile_service,
             false,
             format,
@@ -119,6 +144,83 @@ impl UninstallOrchestrator {
         Ok(is_owned)
     }
 
+    /// Remove an orphaned MCP server's entries from a client config without
+    /// touching sift.toml.
+    ///
+    /// `names` are the client config entries the server was delivered as (see
+    /// [`crate::lockfile::LockedMcpServer::entry_names`]); each is looked up in
+    /// the config's ownership records. Only entries Sift owns are removed. An
+    /// owned entry the user edited since Sift wrote it is left in place and
+    /// released from Sift ownership.
+    pub fn prune_mcp(
+        &self,
+        client: &dyn ClientAdapter,
+        ctx: &ClientContext,
+        names: &[String],
+        warnings: &mut Vec<String>,
+    ) -> anyhow::Result<bool> {
+        let scope = self.uninstall.config_store().scope();
+        let mut removed = false;
+        for plan in client.plan_mcp_all(ctx, scope, &[])? {
+            removed |= self.prune_mcp_in(&plan, ctx, names, warnings)?;
+        }
+        Ok(removed)
+    }
+
+    fn prune_mcp_in(
+        &self,
+        plan: &ManagedJsonPlan,
+        ctx: &ClientContext,
+        names: &[String],
+        warnings: &mut Vec<String>,
+    ) -> anyhow::Result<bool> {
+        let config_path = resolve_plan_path(ctx, plan.root, &plan.relative_path)?;
+        let path: Vec<&str> = plan.config_path.iter().map(|s| s.as_str()).collect();
+        let ownership_key = plan.config_path.join(".");
+        let mut ownership = self
+            .lockfile_service
+            .load_ownership(&config_path, Some(&ownership_key))?;
+        let owned: Vec<(&String, String)> = names
+            .iter()
+            .filter_map(|name| ownership.remove(name).map(|hash| (name, hash)))
+            .collect();
+        if owned.is_empty() {
+            return Ok(false);
+        }
+
+        let format: ConfigFormat = plan.format.clone().into();
+        let existing = client_config::read_map_at_path(&config_path, &path, format)?;
+        let mut removed = false;
+        for (name, expected_hash) in owned {
+            match existing.get(name) {
+                Some(value) if hash_entry(value, &plan.preserved_fields) != expected_hash => {
+                    warnings.push(format!(
+                        "'{}' in {} was modified outside Sift; leaving it in place",
+                        name,
+                        config_path.display()
+                    ));
+                }
+                Some(_) => {
+                    removed |=
+                        client_config::remove_entry_at_path(&config_path, &path, name, format)
+                            .with_context(|| {
+                                format!("Failed to remove MCP '{}' from client config", name)
+                            })?;
+                }
+                None => {}
+            }
+        }
+
+        self.lockfile_service
+            .save_ownership(&config_path, Some(&ownership_key), &ownership)?;
+        Ok(removed)
+    }
+
+    /// Drop the lockfile record for an MCP server.
+    pub fn forget_mcp(&self, name: &str) -> anyhow::Result<bool> {
+        self.lockfile_service.remove_mcp(name)
+    }
+
     fn remove_skill_dir(
         &self,
         client: &dyn ClientAdapter,
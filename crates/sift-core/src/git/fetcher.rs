@@ -23,6 +23,15 @@ pub struct FetchResult {
     pub commit_sha: String,
 }
 
+/// A ref advertised by a remote repository.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteRef {
+    /// Full ref name (e.g., "refs/tags/v1.0.0", "refs/heads/main", "HEAD")
+    pub name: String,
+    /// Commit the ref points to (annotated tags are peeled)
+    pub commit: String,
+}
+
 /// Fetches git repositories and exports subdirectories.
 #[derive(Debug)]
 pub struct GitFetcher {
@@ -117,6 +126,22 @@ impl GitFetcher {
         })
     }
 
+    /// List the branches, tags and HEAD advertised by the remote.
+    ///
+    /// Queries the remote directly so the result reflects its current state
+    /// rather than the local bare repo cache.
+    pub fn list_remote_refs(&self, spec: &GitSpec) -> anyhow::Result<Vec<RemoteRef>> {
+        let output = Self::git_command()
+            .args(["ls-remote", &spec.repo_url])
+            .output()
+            .with_context(|| format!("Failed to run git ls-remote {}", spec.repo_url))?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("git ls-remote {} failed: {}", spec.repo_url, stderr.trim());
+        }
+        Ok(parse_ls_remote(&String::from_utf8_lossy(&output.stdout)))
+    }
+
     /// Read a file from a git repository without full checkout.
     ///
     /// Useful for reading marketplace.json or other metadata files.
@@ -251,10 +276,10 @@ impl GitFetcher {
                 Some(bare_dir),
                 &["fetch", "--filter=blob:none", "origin", reference],
             )?;
-            return Self::git_rev_parse(Some(bare_dir), "FETCH_HEAD");
+            return Self::git_rev_parse(Some(bare_dir), "FETCH_HEAD^{commit}");
         }
 
-        match Self::git_rev_parse(Some(bare_dir), reference) {
+        match Self::git_rev_parse(Some(bare_dir), &format!("{}^{{commit}}", reference)) {
             Ok(commit) => Ok(commit),
             Err(err) => {
                 if spec.reference.is_some() {
@@ -262,7 +287,7 @@ impl GitFetcher {
                         Some(bare_dir),
                         &["fetch", "--filter=blob:none", "origin", reference],
                     )?;
-                    Self::git_rev_parse(Some(bare_dir), "FETCH_HEAD")
+                    Self::git_rev_parse(Some(bare_dir), "FETCH_HEAD^{commit}")
                 } else {
                     Err(err)
                 }
@@ -469,3 +494,25 @@ impl GitFetcher {
         cmd
     }
 }
+
+/// Parse `git ls-remote` output, replacing annotated tag objects with the
+/// commits they point to.
+fn parse_ls_remote(output: &str) -> Vec<RemoteRef> {
+    let mut refs: Vec<RemoteRef> = Vec::new();
+    for line in output.lines() {
+        let Some((commit, name)) = line.split_once('\t') else {
+            continue;
+        };
+        if let Some(tag) = name.strip_suffix("^{}") {
+            if let Some(existing) = refs.iter_mut().find(|r| r.name == tag) {
+                existing.commit = commit.to_string();
+            }
+            continue;
+        }
+        refs.push(RemoteRef {
+            name: name.to_string(),
+            commit: commit.to_string(),
+        });
+    }
+    refs
+}
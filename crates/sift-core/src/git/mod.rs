@@ -10,7 +10,7 @@ mod fetcher;
 mod spec;
 
 pub use exclude::ensure_git_exclude;
-pub use fetcher::{FetchResult, GitFetcher};
+pub use fetcher::{FetchResult, GitFetcher, RemoteRef};
 pub use spec::GitSpec;
 
 #[cfg(test)]
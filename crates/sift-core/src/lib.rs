@@ -15,6 +15,7 @@ pub mod mcp;
 pub mod mcpb;
 pub mod registry;
 pub mod runtime;
+pub mod secrets;
 pub mod skills;
 pub mod source;
 pub mod status;
@@ -87,6 +87,10 @@ impl Default for Lockfile {
     }
 }
 
+/// Version early releases recorded for registry MCP servers before they
+/// resolved one. Lockfiles written then may still contain it.
+pub const PLACEHOLDER_VERSION: &str = "todo";
+
 /// A locked MCP server with resolved version
 ///
 /// MCP servers only need version tracking, no install state.
@@ -113,6 +117,11 @@ pub struct LockedMcpServer {
 
     /// Checksum for verification (optional)
     pub checksum: Option<String>,
+
+    /// Client config entries this server was delivered as, when they differ
+    /// from its name (a registry plugin can publish several servers)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub entries: Vec<String>,
 }
 
 impl LockedMcpServer {
@@ -132,6 +141,7 @@ impl LockedMcpServer {
             scope,
             origin: None,
             checksum: None,
+            entries: Vec::new(),
         }
     }
 
@@ -146,6 +156,32 @@ impl LockedMcpServer {
         self.checksum = Some(checksum);
         self
     }
+
+    /// Set the client config entries, if they differ from the server name
+    pub fn with_entries(mut self, entries: Vec<String>) -> Self {
+        self.entries = if entries.len() == 1 && entries[0] == self.name {
+            Vec::new()
+        } else {
+            entries
+        };
+        self
+    }
+
+    /// The exact version to deliver, unless the lock records none (the
+    /// `latest` constraint, an unmanaged source, or [`PLACEHOLDER_VERSION`]).
+    pub fn pinned_version(&self) -> Option<&str> {
+        let version = self.resolved_version.as_str();
+        (!matches!(version, "" | "latest" | "unmanaged" | PLACEHOLDER_VERSION)).then_some(version)
+    }
+
+    /// Names of the client config entries this server was delivered as.
+    pub fn entry_names(&self) -> Vec<String> {
+        if self.entries.is_empty() {
+            vec![self.name.clone()]
+        } else {
+            self.entries.clone()
+        }
+    }
 }
 
 /// Registry/source metadata for a resolved entry.
@@ -340,6 +376,24 @@ mod tests {
         assert_eq!(retrieved.resolved_version, "1.2.3");
     }
 
+    #[test]
+    fn test_locked_mcp_server_pinned_version() {
+        let locked = |version: &str| {
+            LockedMcpServer::new(
+                "test".to_string(),
+                version.to_string(),
+                "latest".to_string(),
+                "registry:official".to_string(),
+                ConfigScope::Global,
+            )
+        };
+
+        assert_eq!(locked("1.2.3").pinned_version(), Some("1.2.3"));
+        for unpinned in ["", "latest", "unmanaged", PLACEHOLDER_VERSION] {
+            assert_eq!(locked(unpinned).pinned_version(), None);
+        }
+    }
+
     #[test]
     fn test_locked_mcp_server_with_checksum() {
         let server = LockedMcpServer::new(
@@ -1,14 +1,17 @@
 //! MCP server builder for constructing resolved server specifications.
 //!
 //! Extracted from InstallCommand to handle the complex logic of building
-//! McpResolvedServer instances from various sources (HTTP, MCPB, registry, npm-style).
+//! McpResolvedServer instances from various sources (HTTP, MCPB, registry,
+//! Docker, npm-style).
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use crate::config::McpConfigEntry;
-use crate::source::SourceResolver;
+use crate::mcpb::{McpbBundle, McpbFetcher, McpbManifest};
+use crate::runtime::{self, DockerRuntimeRequest, RuntimeKind, RuntimeRequest};
+use crate::source::{RegistryEntryNotFound, SourceResolver};
 
-use super::spec::McpResolvedServer;
+use super::spec::{MachineLaunch, McpResolvedServer, McpTransport};
 
 /// Default runtime for MCP servers when not specified
 pub const DEFAULT_RUNTIME: &str = "shell";
@@ -22,13 +25,17 @@ pub const DEFAULT_VERSION: &str = "latest";
 /// - HTTP transport servers (direct URL configuration)
 /// - MCPB bundle sources (downloaded and extracted archives)
 /// - Registry sources (resolved via marketplace adapters)
+/// - Docker images (`docker:` sources or the docker runtime)
 /// - Shell runtime servers (local commands)
+/// - Python packages (uvx, or pipx when uv is missing)
 /// - npm-style fallback (name@version pattern)
 pub struct McpServerBuilder<'a> {
-    /// State directory for MCPB cache
+    /// State directory for MCPB and launcher caches
     state_dir: &'a Path,
     /// Source resolver for registry lookups
     source_resolver: Option<SourceResolver>,
+    /// Launcher for Python packages
+    python_runtime: RuntimeKind,
 }
 
 impl<'a> McpServerBuilder<'a> {
@@ -37,6 +44,7 @@ impl<'a> McpServerBuilder<'a> {
         Self {
             state_dir,
             source_resolver: None,
+            python_runtime: runtime::python_package_kind(),
         }
     }
 
@@ -46,6 +54,12 @@ impl<'a> McpServerBuilder<'a> {
         self
     }
 
+    /// Override the detected Python package launcher (uvx or pipx).
+    pub fn with_python_runtime(mut self, kind: RuntimeKind) -> Self {
+        self.python_runtime = kind;
+        self
+    }
+
     /// Build resolved server specifications from a config entry.
     ///
     /// Dispatches to the appropriate handler based on transport type and source prefix.
@@ -56,6 +70,51 @@ impl<'a> McpServerBuilder<'a> {
         entry: &McpConfigEntry,
         version: Option<&str>,
         force: bool,
+    ) -> anyhow::Result<Vec<McpResolvedServer>> {
+        self.build_with_version(name, source, entry, version, force)
+            .map(|(servers, _)| servers)
+    }
+
+    /// Build resolved server specifications and report the registry version used.
+    ///
+    /// The version is `None` unless a registry published the server.
+    pub fn build_with_version(
+        &self,
+        name: &str,
+        source: &str,
+        entry: &McpConfigEntry,
+        version: Option<&str>,
+        force: bool,
+    ) -> anyhow::Result<(Vec<McpResolvedServer>, Option<String>)> {
+        // Registry source - resolve from marketplace
+        let (mut servers, resolved_version) = if let Some(registry_part) =
+            source.strip_prefix("registry:")
+            && entry.transport.as_deref() != Some("http")
+        {
+            self.build_from_registry(name, registry_part, entry, version, force)?
+        } else {
+            (
+                self.build_unversioned(name, source, entry, version, force)?,
+                None,
+            )
+        };
+
+        for server in &mut servers {
+            server.always_allow = entry.always_allow.clone();
+            if server.transport == McpTransport::Http {
+                server.oauth = entry.oauth.clone();
+            }
+        }
+        Ok((servers, resolved_version))
+    }
+
+    fn build_unversioned(
+        &self,
+        name: &str,
+        source: &str,
+        entry: &McpConfigEntry,
+        version: Option<&str>,
+        force: bool,
     ) -> anyhow::Result<Vec<McpResolvedServer>> {
         // HTTP transport - direct URL configuration
         if entry.transport.as_deref() == Some("http") {
@@ -67,9 +126,13 @@ impl<'a> McpServerBuilder<'a> {
             return self.build_from_mcpb(name, mcpb_url, entry, force);
         }
 
-        // Registry source - resolve from marketplace
-        if let Some(registry_part) = source.strip_prefix("registry:") {
-            return self.build_from_registry(name, registry_part, entry, version, force);
+        // Docker image
+        if let Some(image) = source.strip_prefix("docker:") {
+            return Ok(self.build_docker(name, image, entry, version));
+        }
+        if entry.runtime.as_deref() == Some("docker") {
+            let image = source.strip_prefix("local:").unwrap_or(source);
+            return Ok(self.build_docker(name, image, entry, version));
         }
 
         // Shell runtime - local command
@@ -117,6 +180,31 @@ impl<'a> McpServerBuilder<'a> {
         )])
     }
 
+    /// Build a Docker runtime server.
+    ///
+    /// The version becomes the image tag unless the image already has one.
+    fn build_docker(
+        &self,
+        name: &str,
+        image: &str,
+        entry: &McpConfigEntry,
+        version: Option<&str>,
+    ) -> Vec<McpResolvedServer> {
+        let spec = runtime::resolve_docker(&DockerRuntimeRequest {
+            image: runtime::image_reference(image, version.unwrap_or(DEFAULT_VERSION)),
+            env: entry.env.clone(),
+            volumes: entry.volumes.clone(),
+            network: entry.network.clone(),
+            extra_args: entry.args.clone(),
+        });
+        vec![McpResolvedServer::stdio(
+            name.to_string(),
+            spec.command,
+            spec.args,
+            spec.env,
+        )]
+    }
+
     /// Build servers from an MCPB bundle.
     ///
     /// Downloads the bundle, extracts it, parses manifest.json, and converts
@@ -128,15 +216,9 @@ impl<'a> McpServerBuilder<'a> {
         entry: &McpConfigEntry,
         force: bool,
     ) -> anyhow::Result<Vec<McpResolvedServer>> {
-        use crate::mcpb::{McpbFetcher, manifest_to_server};
+        use crate::mcpb::manifest_to_server;
 
-        let fetcher = McpbFetcher::new(self.state_dir.join("cache"));
-
-        // Block on async fetch using tokio runtime
-        let runtime = tokio::runtime::Runtime::new()
-            .map_err(|e| anyhow::anyhow!("Failed to create tokio runtime: {}", e))?;
-
-        let bundle = runtime.block_on(fetcher.fetch(url, force))?;
+        let bundle = self.fetch_mcpb(url, force)?;
 
         // Convert manifest to resolved server
         let mut server = manifest_to_server(name, &bundle.manifest, &bundle.extract_dir)?;
@@ -152,12 +234,68 @@ impl<'a> McpServerBuilder<'a> {
         Ok(vec![server])
     }
 
+    fn fetch_mcpb(&self, url: &str, force: bool) -> anyhow::Result<McpbBundle> {
+        let fetcher = McpbFetcher::new(self.state_dir.join("cache"));
+
+        // Block on async fetch using tokio runtime
+        let runtime = tokio::runtime::Runtime::new()
+            .map_err(|e| anyhow::anyhow!("Failed to create tokio runtime: {}", e))?;
+
+        runtime.block_on(fetcher.fetch(url, force))
+    }
+
+    /// MCPB manifests behind a config entry, paired with the server name
+    /// each one builds.
+    ///
+    /// Covers `mcpb:` sources and registry plugins that publish a bundle.
+    /// Bundles are read from the cache that [`build`](Self::build) fills.
+    pub fn bundle_manifests(
+        &self,
+        name: &str,
+        source: &str,
+        entry: &McpConfigEntry,
+        version: Option<&str>,
+    ) -> anyhow::Result<Vec<(String, McpbManifest)>> {
+        if let Some(url) = source.strip_prefix("mcpb:") {
+            return Ok(vec![(
+                name.to_string(),
+                self.fetch_mcpb(url, false)?.manifest,
+            )]);
+        }
+
+        let (Some(registry_part), Some(resolver)) =
+            (source.strip_prefix("registry:"), &self.source_resolver)
+        else {
+            return Ok(Vec::new());
+        };
+        if entry.transport.as_deref() == Some("http") {
+            return Ok(Vec::new());
+        }
+        // Same fallback as the build: unresolvable plugins use npm-style servers
+        let Ok(Some(resolutions)) = resolver.resolve_mcp_registry_version(registry_part, version)
+        else {
+            return Ok(Vec::new());
+        };
+
+        let mut manifests = Vec::new();
+        for resolution in resolutions {
+            if let Some(url) = resolution.mcp_config.source.strip_prefix("mcpb:") {
+                let bundle = self.fetch_mcpb(url, false)?;
+                manifests.push((resolution.plugin_name, bundle.manifest));
+            }
+        }
+        Ok(manifests)
+    }
+
     /// Build servers from a registry source.
     ///
     /// Resolves the plugin from the marketplace registry, extracts mcpServers,
     /// and handles MCPB bundles or other transport types.
     ///
-    /// Falls back to npm-style resolution if registry resolution fails.
+    /// Falls back to npm-style resolution when no registry is configured, or
+    /// the registry does not list the entry or lists it without MCP servers.
+    /// Other resolution failures,
+    /// such as network or parse errors, are returned.
     fn build_from_registry(
         &self,
         name: &str,
@@ -165,22 +303,29 @@ impl<'a> McpServerBuilder<'a> {
         entry: &McpConfigEntry,
         version: Option<&str>,
         force: bool,
-    ) -> anyhow::Result<Vec<McpResolvedServer>> {
+    ) -> anyhow::Result<(Vec<McpResolvedServer>, Option<String>)> {
         let resolver = match &self.source_resolver {
-            Some(r) => r,
-            None => return self.build_npm_fallback(name, version, entry),
+            Some(r) if r.has_registries() => r,
+            // No registry to ask
+            _ => return Ok((self.build_npm_fallback(name, version, entry)?, None)),
         };
 
         // Try to resolve the MCP config from the registry
-        let resolutions = match resolver.resolve_mcp_registry(registry_part) {
+        let resolutions = match resolver.resolve_mcp_registry_version(registry_part, version) {
             Ok(Some(resolutions)) if !resolutions.is_empty() => resolutions,
             Ok(Some(_)) | Ok(None) => {
                 // Plugin found but has no mcpServers - fall back to npm-style
-                return self.build_npm_fallback(name, version, entry);
+                return Ok((self.build_npm_fallback(name, version, entry)?, None));
+            }
+            Err(err) if err.downcast_ref::<RegistryEntryNotFound>().is_some() => {
+                // Not in the registry - fall back to npm-style
+                return Ok((self.build_npm_fallback(name, version, entry)?, None));
             }
-            Err(_) => {
-                // Registry resolution failed - fall back to npm-style
-                return self.build_npm_fallback(name, version, entry);
+            Err(err) => {
+                return Err(err.context(format!(
+                    "Failed to resolve '{}' from registry '{}'",
+                    name, registry_part
+                )));
             }
         };
 
@@ -188,6 +333,7 @@ impl<'a> McpServerBuilder<'a> {
             anyhow::bail!("Plugin '{}' has no MCP servers defined", name);
         }
 
+        let resolved_version = resolutions.first().map(|r| r.plugin_version.clone());
         let mut servers = Vec::new();
         for resolution in resolutions {
             let mcp_source = &resolution.mcp_config.source;
@@ -218,22 +364,46 @@ impl<'a> McpServerBuilder<'a> {
                             .to_string()
                     });
 
+                let mut command = command;
                 let mut args = resolution.mcp_config.args.clone();
                 args.extend(entry.args.clone());
 
                 let mut env = resolution.mcp_config.env.clone();
                 env.extend(entry.env.clone());
 
-                servers.push(McpResolvedServer::stdio(
-                    resolution.plugin_name.clone(),
-                    command,
-                    args,
-                    env,
-                ));
+                let mut machine_launch = None;
+                if command == "uvx" {
+                    let mut launch = MachineLaunch::default();
+                    if self.python_runtime == RuntimeKind::Pipx
+                        && let Some(pipx_args) = runtime::uvx_to_pipx(&args)
+                    {
+                        command = "pipx".to_string();
+                        launch.uvx_args = Some(std::mem::replace(&mut args, pipx_args));
+                    }
+                    if let Some((key, dir)) =
+                        runtime::python_cache_env(self.python_runtime, &self.python_cache_dir())
+                        && !env.contains_key(&key)
+                    {
+                        env.insert(key.clone(), dir);
+                        launch.cache_env.push(key);
+                    }
+                    machine_launch = Some(launch);
+                }
+
+                if command == "docker" {
+                    let options =
+                        runtime::run_options(env.keys(), &entry.volumes, entry.network.as_deref());
+                    runtime::insert_run_options(&mut args, options);
+                }
+
+                let mut server =
+                    McpResolvedServer::stdio(resolution.plugin_name.clone(), command, args, env);
+                server.machine_launch = machine_launch;
+                servers.push(server);
             }
         }
 
-        Ok(servers)
+        Ok((servers, resolved_version))
     }
 
     /// Build servers using npm-style name@version resolution.
@@ -246,6 +416,9 @@ impl<'a> McpServerBuilder<'a> {
         entry: &McpConfigEntry,
     ) -> anyhow::Result<Vec<McpResolvedServer>> {
         let runtime = entry.runtime.as_deref().unwrap_or(DEFAULT_RUNTIME);
+        if runtime == "python" {
+            return self.build_python_package(name, version, entry);
+        }
         let command = runtime.to_string();
 
         let resolved_version = version.unwrap_or(DEFAULT_VERSION);
@@ -260,6 +433,80 @@ impl<'a> McpServerBuilder<'a> {
         )])
     }
 
+    /// Build a Python package server launched by uvx or pipx.
+    ///
+    /// The launcher and its cache directory depend on this machine, so they
+    /// are recorded as [`MachineLaunch`] for shared configs to undo.
+    fn build_python_package(
+        &self,
+        name: &str,
+        version: Option<&str>,
+        entry: &McpConfigEntry,
+    ) -> anyhow::Result<Vec<McpResolvedServer>> {
+        let version = version.unwrap_or(DEFAULT_VERSION);
+        let spec = runtime::resolve_runtime(&RuntimeRequest {
+            kind: self.python_runtime,
+            package: name.to_string(),
+            version: version.to_string(),
+            cache_dir: self.python_cache_dir(),
+            extra_args: entry.args.clone(),
+        })?;
+        let launch = MachineLaunch {
+            cache_env: spec
+                .env
+                .keys()
+                .filter(|key| !entry.env.contains_key(*key))
+                .cloned()
+                .collect(),
+            uvx_args: (spec.command != "uvx").then(|| {
+                let mut args = vec![runtime::python_requirement(name, version)];
+                args.extend(entry.args.clone());
+                args
+            }),
+        };
+        let mut env = spec.env;
+        env.extend(entry.env.clone());
+        let mut server = McpResolvedServer::stdio(name.to_string(), spec.command, spec.args, env);
+        server.machine_launch = Some(launch);
+        Ok(vec![server])
+    }
+
+    /// Cache directory for the Python package launcher.
+    fn python_cache_dir(&self) -> PathBuf {
+        let launcher = match self.python_runtime {
+            RuntimeKind::Pipx => "pipx",
+            _ => "uv",
+        };
+        self.state_dir.join("cache").join(launcher)
+    }
+
+    /// Pin the Docker image of a built server to a digest.
+    ///
+    /// Uses `locked` when given, otherwise asks Docker for the digest of the
+    /// image tag. Returns the digest, or `None` when the entry is not a
+    /// single Docker server.
+    pub fn pin_docker_image(
+        servers: &mut [McpResolvedServer],
+        locked: Option<&str>,
+    ) -> anyhow::Result<Option<String>> {
+        let [server] = servers else {
+            return Ok(None);
+        };
+        if server.command.as_deref() != Some("docker") {
+            return Ok(None);
+        }
+        let Some(index) = runtime::image_position(&server.args) else {
+            return Ok(None);
+        };
+        let image = &server.args[index];
+        let digest = match locked {
+            Some(digest) => digest.to_string(),
+            None => runtime::resolve_image_digest(image)?,
+        };
+        server.args[index] = runtime::pin_image(image, &digest);
+        Ok(Some(digest))
+    }
+
     /// Extract command from a registry source string.
     ///
     /// Registry sources can include a command suffix after a second colon:
@@ -293,6 +540,7 @@ mod tests {
             args: Vec::new(),
             url: None,
             headers: HashMap::new(),
+            oauth: None,
             targets: None,
             ignore_targets: None,
             env: HashMap::new(),
@@ -300,6 +548,9 @@ mod tests {
             reset_ignore_targets: false,
             reset_env: None,
             reset_env_all: false,
+            volumes: Vec::new(),
+            network: None,
+            always_allow: Vec::new(),
         }
     }
 
@@ -333,6 +584,49 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_build_carries_always_allow() {
+        let temp = TempDir::new().unwrap();
+        let builder = McpServerBuilder::new(temp.path());
+
+        let mut entry = create_test_entry();
+        entry.transport = Some("http".to_string());
+        entry.url = Some("https://example.com/api".to_string());
+        entry.always_allow = vec!["read_file".to_string()];
+
+        let servers = builder
+            .build("my-server", "registry:test", &entry, None, false)
+            .unwrap();
+
+        assert_eq!(servers[0].always_allow, vec!["read_file"]);
+    }
+
+    #[test]
+    fn test_build_http_carries_oauth() {
+        let temp = TempDir::new().unwrap();
+        let builder = McpServerBuilder::new(temp.path());
+
+        let mut entry = create_test_entry();
+        entry.transport = Some("http".to_string());
+        entry.url = Some("https://example.com/api".to_string());
+        entry.oauth = Some(crate::mcp::OAuthConfig {
+            client_id: Some("sift".to_string()),
+            ..Default::default()
+        });
+
+        let servers = builder
+            .build("my-server", "registry:test", &entry, None, false)
+            .unwrap();
+
+        assert_eq!(
+            servers[0]
+                .oauth
+                .as_ref()
+                .and_then(|o| o.client_id.as_deref()),
+            Some("sift")
+        );
+    }
+
     #[test]
     fn test_build_http_transport_requires_url() {
         let temp = TempDir::new().unwrap();
@@ -531,6 +825,169 @@ mod tests {
         );
     }
 
+    // =========================================================================
+    // Python Package Tests
+    // =========================================================================
+
+    #[test]
+    fn test_build_python_package_with_uvx() {
+        let temp = TempDir::new().unwrap();
+        let builder = McpServerBuilder::new(temp.path()).with_python_runtime(RuntimeKind::Python);
+
+        let mut entry = create_test_entry();
+        entry.runtime = Some("python".to_string());
+
+        let servers = builder
+            .build(
+                "mcp-server-fetch",
+                "registry:fetch",
+                &entry,
+                Some("1.0"),
+                false,
+            )
+            .unwrap();
+
+        assert_eq!(servers[0].command, Some("uvx".to_string()));
+        assert_eq!(servers[0].args, vec!["mcp-server-fetch==1.0"]);
+        assert_eq!(
+            servers[0].env.get("UV_CACHE_DIR"),
+            Some(
+                &temp
+                    .path()
+                    .join("cache")
+                    .join("uv")
+                    .to_string_lossy()
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn test_build_python_package_falls_back_to_pipx() {
+        let temp = TempDir::new().unwrap();
+        let builder = McpServerBuilder::new(temp.path()).with_python_runtime(RuntimeKind::Pipx);
+
+        let mut entry = create_test_entry();
+        entry.runtime = Some("python".to_string());
+
+        let servers = builder
+            .build("mcp-server-fetch", "registry:fetch", &entry, None, false)
+            .unwrap();
+
+        assert_eq!(servers[0].command, Some("pipx".to_string()));
+        assert_eq!(
+            servers[0].args,
+            vec!["run", "--spec", "mcp-server-fetch", "mcp-server-fetch"]
+        );
+        assert!(servers[0].env.contains_key("PIPX_HOME"));
+    }
+
+    #[test]
+    fn test_python_package_portable_launch_is_uvx_without_cache() {
+        let temp = TempDir::new().unwrap();
+        let builder = McpServerBuilder::new(temp.path()).with_python_runtime(RuntimeKind::Pipx);
+
+        let mut entry = create_test_entry();
+        entry.runtime = Some("python".to_string());
+        entry.args = vec!["--verbose".to_string()];
+        entry.env.insert("LOG".to_string(), "debug".to_string());
+
+        let mut servers = builder
+            .build(
+                "mcp-server-fetch",
+                "registry:fetch",
+                &entry,
+                Some("1.0"),
+                false,
+            )
+            .unwrap();
+        servers[0].make_portable();
+
+        assert_eq!(servers[0].command, Some("uvx".to_string()));
+        assert_eq!(servers[0].args, vec!["mcp-server-fetch==1.0", "--verbose"]);
+        assert_eq!(
+            servers[0].env,
+            HashMap::from([("LOG".to_string(), "debug".to_string())])
+        );
+    }
+
+    // =========================================================================
+    // Docker Runtime Tests
+    // =========================================================================
+
+    #[test]
+    fn test_build_docker_source() {
+        let temp = TempDir::new().unwrap();
+        let builder = McpServerBuilder::new(temp.path());
+
+        let mut entry = create_test_entry();
+        entry.source = "docker:ghcr.io/acme/mcp".to_string();
+        entry.volumes = vec!["/srv/data:/data".to_string()];
+        entry.network = Some("none".to_string());
+        entry.env.insert("TOKEN".to_string(), "secret".to_string());
+
+        let servers = builder
+            .build(
+                "acme",
+                "docker:ghcr.io/acme/mcp",
+                &entry,
+                Some("1.2"),
+                false,
+            )
+            .unwrap();
+
+        assert_eq!(servers[0].command, Some("docker".to_string()));
+        assert_eq!(
+            servers[0].args,
+            vec![
+                "run",
+                "-i",
+                "--rm",
+                "--network",
+                "none",
+                "-v",
+                "/srv/data:/data",
+                "-e",
+                "TOKEN",
+                "ghcr.io/acme/mcp:1.2",
+            ]
+        );
+        assert_eq!(servers[0].env.get("TOKEN"), Some(&"secret".to_string()));
+    }
+
+    #[test]
+    fn test_pin_docker_image_uses_locked_digest() {
+        let temp = TempDir::new().unwrap();
+        let builder = McpServerBuilder::new(temp.path());
+
+        let mut entry = create_test_entry();
+        entry.runtime = Some("docker".to_string());
+
+        let mut servers = builder
+            .build("acme", "local:acme/mcp:2", &entry, None, false)
+            .unwrap();
+        let digest = McpServerBuilder::pin_docker_image(&mut servers, Some("sha256:abc")).unwrap();
+
+        assert_eq!(digest.as_deref(), Some("sha256:abc"));
+        assert_eq!(servers[0].args.last().unwrap(), "acme/mcp:2@sha256:abc");
+    }
+
+    #[test]
+    fn test_pin_docker_image_ignores_other_runtimes() {
+        let temp = TempDir::new().unwrap();
+        let builder = McpServerBuilder::new(temp.path());
+
+        let mut servers = builder
+            .build("pkg", "registry:test", &create_test_entry(), None, false)
+            .unwrap();
+
+        assert_eq!(
+            McpServerBuilder::pin_docker_image(&mut servers, Some("sha256:abc")).unwrap(),
+            None
+        );
+        assert_eq!(servers[0].args, vec!["pkg@latest"]);
+    }
+
     // =========================================================================
     // Constants Tests
     // =========================================================================
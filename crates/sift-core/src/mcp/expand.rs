@@ -0,0 +1,500 @@
+//! `${VAR}` references in MCP server env, headers, args and url.
+//!
+//! Project `sift.toml` files are committed, so secrets are written as
+//! references instead of values:
+//!
+//! ```toml
+//! [mcp.github]
+//! transport = "http"
+//! url = "https://api.githubcopilot.com/mcp/"
+//! headers = { Authorization = "Bearer ${GITHUB_TOKEN}" }
+//! ```
+//!
+//! Clients that expand environment variables themselves get the reference
+//! in their own syntax (see [`EnvExpansion`]). For the rest, Sift resolves
+//! it when rendering, looking in the project-local `vars` table of the
+//! global config, then the process environment, then the project's `.env`
+//! file. `${VAR:-default}` falls back to `default` when `VAR` is unset.
+//!
+//! `${user_config.KEY}` references in an MCPB bundle's command, args and env
+//! take the default the bundle's manifest gives the option; options without
+//! one are written as-is with a warning.
+//!
+//! `env` and `headers` values may also be `secret:<provider>/<path>`
+//! references (see [`crate::secrets`]). No client can resolve those, so
+//! they are always looked up here. Writing a resolved secret to a shared
+//! project config is refused unless forced or allowed for the project with
+//! `allow_shared_secrets` in the global config.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use anyhow::Context;
+
+use crate::client::{ClientAdapter, ClientContext, EnvExpansion};
+use crate::mcp::spec::{McpResolvedServer, McpTransport};
+use crate::types::ConfigScope;
+
+/// Servers ready for planning, with anything worth telling the user.
+#[derive(Debug, Clone)]
+pub struct ExpandedServers {
+    pub servers: Vec<McpResolvedServer>,
+    pub warnings: Vec<String>,
+}
+
+/// Values available to `${VAR}` references.
+#[derive(Debug, Clone, Default)]
+pub struct Variables {
+    local: HashMap<String, String>,
+    dotenv: HashMap<String, String>,
+}
+
+impl Variables {
+    /// Collect the project-local variables from `ctx` and the project's `.env`.
+    pub fn load(ctx: &ClientContext) -> anyhow::Result<Self> {
+        Ok(Self {
+            local: ctx.variables.clone(),
+            dotenv: read_dotenv(&ctx.project_root.join(".env"))?,
+        })
+    }
+
+    /// Look up a variable: project-local, then process env, then `.env`.
+    pub fn get(&self, name: &str) -> Option<String> {
+        self.local
+            .get(name)
+            .cloned()
+            .or_else(|| std::env::var(name).ok())
+            .or_else(|| self.dotenv.get(name).cloned())
+    }
+}
+
+/// Rewrite `${VAR}` references in `servers` for `client`.
+///
+/// Also resolves `secret:` references in `env` and `headers`; for a shared
+/// (committed) project config that fails unless `force` is set or the
+/// context allows shared secrets. Warns about unset variables, about values
+/// that would be written in plain text to a shared project config, and about
+/// HTTP headers and OAuth settings the client has no place for. Shared project
+/// configs get the portable launch of each server (see
+/// [`McpResolvedServer::make_portable`]).
+pub fn expand_servers(
+    client: &dyn ClientAdapter,
+    ctx: &ClientContext,
+    scope: ConfigScope,
+    servers: &[McpResolvedServer],
+    force: bool,
+) -> anyhow::Result<ExpandedServers> {
+    let capabilities = client.capabilities();
+    let shared = (scope == ConfigScope::PerProjectShared).then(|| SharedOutput {
+        client_id: client.id(),
+        allow_secrets: force || ctx.allow_shared_secrets,
+    });
+    let mut expanded = expand(ctx, capabilities.env_expansion, shared, servers)?;
+    if !capabilities.supports_http_headers {
+        for server in &mut expanded.servers {
+            if server.transport != McpTransport::Http || server.headers.is_empty() {
+                continue;
+            }
+            server.headers.clear();
+            expanded.warnings.push(format!(
+                "'{}': {} has no place for HTTP headers; they are not written",
+                server.name,
+                client.id()
+            ));
+        }
+    }
+    for server in servers {
+        let Some(oauth) = &server.oauth else {
+            continue;
+        };
+        let ignored: Vec<&str> = oauth
+            .fields()
+            .into_iter()
+            .filter(|field| !capabilities.supported_oauth.contains(*field))
+            .collect();
+        if ignored.is_empty() {
+            continue;
+        }
+        if capabilities.supported_oauth.is_empty() {
+            expanded.warnings.push(format!(
+                "'{}': {} has no OAuth client settings; oauth is not written and the \
+                 client falls back to its own sign-in",
+                server.name,
+                client.id()
+            ));
+        } else {
+            expanded.warnings.push(format!(
+                "'{}': {} does not support oauth.{}; it is not written",
+                server.name,
+                client.id(),
+                ignored.join(", oauth.")
+            ));
+        }
+    }
+    Ok(expanded)
+}
+
+/// Resolve every reference in `servers`, as needed to run them directly.
+pub fn resolve_servers(
+    ctx: &ClientContext,
+    servers: &[McpResolvedServer],
+) -> anyhow::Result<ExpandedServers> {
+    expand(ctx, EnvExpansion::None, None, servers)
+}
+
+/// Output that goes to a shared project file.
+#[derive(Clone, Copy)]
+struct SharedOutput<'a> {
+    client_id: &'a str,
+    /// Resolved `secret:` references may be written
+    allow_secrets: bool,
+}
+
+fn expand(
+    ctx: &ClientContext,
+    expansion: EnvExpansion,
+    shared: Option<SharedOutput<'_>>,
+    servers: &[McpResolvedServer],
+) -> anyhow::Result<ExpandedServers> {
+    let variables = Variables::load(ctx)?;
+
+    let mut expanded = Vec::with_capacity(servers.len());
+    let mut warnings = Vec::new();
+    for server in servers {
+        let mut expander = Expander {
+            expansion,
+            variables: &variables,
+            resolved: Vec::new(),
+            missing: Vec::new(),
+        };
+        let mut server = server.clone();
+        if shared.is_some() {
+            server.make_portable();
+        }
+        for key in resolve_user_config(&mut server) {
+            warnings.push(format!(
+                "'{}': bundle option '{}' has no value or default; ${{user_config.{}}} is \
+                 written as-is",
+                server.name, key, key
+            ));
+        }
+        server.command = server.command.map(|c| expander.expand(&c));
+        server.args = server.args.iter().map(|a| expander.expand(a)).collect();
+        server.url = server.url.map(|u| expander.expand(&u));
+        for (field, values) in [("env", &mut server.env), ("header", &mut server.headers)] {
+            for (key, value) in values.iter_mut() {
+                if let Some(secret) = ctx.secrets.resolve(value).with_context(|| {
+                    format!("Failed to render {} '{}' of '{}'", field, key, server.name)
+                })? {
+                    if let Some(shared) = shared {
+                        if !shared.allow_secrets {
+                            anyhow::bail!(
+                                "'{}': {} '{}' ({}) would be written in plain text to {}'s \
+                                 project config, which is usually committed. Install to a \
+                                 local scope, pass --force, or set allow_shared_secrets for \
+                                 this project in the global config",
+                                server.name,
+                                field,
+                                key,
+                                value,
+                                shared.client_id
+                            );
+                        }
+                        warnings.push(format!(
+                            "'{}': {} '{}' ({}) is written in plain text to {}'s project config, \
+                             which is usually committed",
+                            server.name, field, key, value, shared.client_id
+                        ));
+                    }
+                    *value = secret;
+                    continue;
+                }
+                if let Some(SharedOutput { client_id, .. }) = shared
+                    && !value.contains("${")
+                    && looks_secret(key)
+                    && !value.is_empty()
+                {
+                    warnings.push(format!(
+                        "'{}': {} '{}' is written in plain text to {}'s project config, \
+                         which is usually committed; use a ${{VAR}} reference instead",
+                        server.name, field, key, client_id
+                    ));
+                }
+                *value = expander.expand(value);
+            }
+        }
+
+        for name in dedup(expander.missing) {
+            warnings.push(format!(
+                "'{}': variable '{}' is not set; the reference is written as-is",
+                server.name, name
+            ));
+        }
+        if let Some(SharedOutput { client_id, .. }) = shared {
+            for name in dedup(expander.resolved) {
+                warnings.push(format!(
+                    "'{}': the value of '{}' is written in plain text to {}'s project config, \
+                     which is usually committed",
+                    server.name, name, client_id
+                ));
+            }
+        }
+        expanded.push(server);
+    }
+
+    Ok(ExpandedServers {
+        servers: expanded,
+        warnings,
+    })
+}
+
+struct Expander<'a> {
+    expansion: EnvExpansion,
+    variables: &'a Variables,
+    /// Variables whose values were written into the output
+    resolved: Vec<String>,
+    /// Variables that could not be resolved
+    missing: Vec<String>,
+}
+
+impl Expander<'_> {
+    fn expand(&mut self, text: &str) -> String {
+        replace_references(text, |name, default| match (self.expansion, default) {
+            (EnvExpansion::Braces, _) => None,
+            (EnvExpansion::EnvPrefix, None) => Some(format!("${{env:{}}}", name)),
+            // No native fallback syntax, so defaults are resolved here
+            (EnvExpansion::EnvPrefix, Some(default)) | (EnvExpansion::None, Some(default)) => {
+                Some(self.resolve(name).unwrap_or_else(|| default.to_string()))
+            }
+            (EnvExpansion::None, None) => {
+                let value = self.resolve(name);
+                if value.is_none() {
+                    self.missing.push(name.to_string());
+                }
+                value
+            }
+        })
+    }
+
+    fn resolve(&mut self, name: &str) -> Option<String> {
+        let value = self.variables.get(name)?;
+        self.resolved.push(name.to_string());
+        Some(value)
+    }
+}
+
+/// Replace `${user_config.KEY}` references in a server's command, args and
+/// env with the option values it carries. Returns the keys left unresolved.
+fn resolve_user_config(server: &mut McpResolvedServer) -> Vec<String> {
+    const PREFIX: &str = "${user_config.";
+    let options = &server.user_config;
+    let mut unset = Vec::new();
+    let mut resolve = |text: &str| {
+        let mut output = String::with_capacity(text.len());
+        let mut rest = text;
+        while let Some(start) = rest.find(PREFIX) {
+            let Some(len) = rest[start..].find('}') else {
+                break;
+            };
+            let key = &rest[start + PREFIX.len()..start + len];
+            output.push_str(&rest[..start]);
+            match options.get(key) {
+                Some(Some(value)) => output.push_str(value),
+                _ => {
+                    unset.push(key.to_string());
+                    output.push_str(&rest[start..=start + len]);
+                }
+            }
+            rest = &rest[start + len + 1..];
+        }
+        output.push_str(rest);
+        output
+    };
+    let command = server.command.as_deref().map(&mut resolve);
+    let args = server.args.iter().map(|a| resolve(a)).collect();
+    let env = server
+        .env
+        .iter()
+        .map(|(key, value)| (key.clone(), resolve(value)))
+        .collect();
+    server.command = command;
+    server.args = args;
+    server.env = env;
+    dedup(unset)
+}
+
+/// Replace each `${NAME}` or `${NAME:-default}` in `text` with what `f`
+/// returns for it. References `f` declines, and anything that is not a
+/// plain variable name (e.g. `${env:NAME}`), are kept as written.
+fn replace_references(
+    text: &str,
+    mut f: impl FnMut(&str, Option<&str>) -> Option<String>,
+) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("${") {
+        output.push_str(&rest[..start]);
+        let Some(len) = rest[start..].find('}') else {
+            rest = &rest[start..];
+            break;
+        };
+        let reference = &rest[start..start + len + 1];
+        let body = &reference[2..reference.len() - 1];
+        let (name, default) = match body.split_once(":-") {
+            Some((name, default)) => (name, Some(default)),
+            None => (body, None),
+        };
+        let replacement = is_variable_name(name).then(|| f(name, default)).flatten();
+        output.push_str(replacement.as_deref().unwrap_or(reference));
+        rest = &rest[start + len + 1..];
+    }
+    output.push_str(rest);
+    output
+}
+
+fn is_variable_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Whether an env var or header name usually carries a credential.
+fn looks_secret(key: &str) -> bool {
+    const MARKERS: &[&str] = &[
+        "TOKEN",
+        "SECRET",
+        "PASSWORD",
+        "PASSWD",
+        "API_KEY",
+        "APIKEY",
+        "ACCESS_KEY",
+        "PRIVATE_KEY",
+        "CREDENTIAL",
+        "AUTHORIZATION",
+    ];
+    let key = key.to_ascii_uppercase().replace('-', "_");
+    MARKERS.iter().any(|marker| key.contains(marker))
+}
+
+fn dedup(mut names: Vec<String>) -> Vec<String> {
+    names.sort();
+    names.dedup();
+    names
+}
+
+/// Parse a `.env` file of `KEY=value` lines. A missing file is empty.
+fn read_dotenv(path: &Path) -> anyhow::Result<HashMap<String, String>> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(HashMap::new()),
+        Err(err) => {
+            return Err(err).with_context(|| format!("Failed to read {}", path.display()));
+        }
+    };
+    Ok(parse_dotenv(&content))
+}
+
+fn parse_dotenv(content: &str) -> HashMap<String, String> {
+    let mut vars = HashMap::new();
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let value = value.trim();
+        let value = ['"', '\'']
+            .iter()
+            .find_map(|quote| {
+                value
+                    .strip_prefix(*quote)
+                    .and_then(|v| v.strip_suffix(*quote))
+            })
+            .unwrap_or(value);
+        vars.insert(key.trim().to_string(), value.to_string());
+    }
+    vars
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn variables(pairs: &[(&str, &str)]) -> Variables {
+        Variables {
+            local: pairs
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            dotenv: HashMap::new(),
+        }
+    }
+
+    fn expand(expansion: EnvExpansion, vars: &Variables, text: &str) -> (String, Vec<String>) {
+        let mut expander = Expander {
+            expansion,
+            variables: vars,
+            resolved: Vec::new(),
+            missing: Vec::new(),
+        };
+        let output = expander.expand(text);
+        (output, expander.missing)
+    }
+
+    #[test]
+    fn test_expand_resolves_for_clients_without_expansion() {
+        let vars = variables(&[("SIFT_TEST_TOKEN", "abc")]);
+
+        let (output, missing) = expand(
+            EnvExpansion::None,
+            &vars,
+            "Bearer ${SIFT_TEST_TOKEN} ${SIFT_TEST_UNSET} ${SIFT_TEST_UNSET:-x}",
+        );
+
+        assert_eq!(output, "Bearer abc ${SIFT_TEST_UNSET} x");
+        assert_eq!(missing, vec!["SIFT_TEST_UNSET"]);
+    }
+
+    #[test]
+    fn test_expand_passes_references_through_native_syntax() {
+        let vars = variables(&[("SIFT_TEST_TOKEN", "abc")]);
+        let text = "${SIFT_TEST_TOKEN}/${SIFT_TEST_UNSET:-x}";
+
+        assert_eq!(expand(EnvExpansion::Braces, &vars, text).0, text);
+        assert_eq!(
+            expand(EnvExpansion::EnvPrefix, &vars, text).0,
+            "${env:SIFT_TEST_TOKEN}/x"
+        );
+    }
+
+    #[test]
+    fn test_expand_keeps_non_variable_references() {
+        let vars = variables(&[]);
+        let text = "${env:HOME} ${user_config.key} ${ unterminated";
+
+        assert_eq!(expand(EnvExpansion::None, &vars, text).0, text);
+    }
+
+    #[test]
+    fn test_parse_dotenv() {
+        let vars = parse_dotenv("# comment\nexport A=1\nB = \"two words\"\nC='3'\n\nnot a pair\n");
+
+        assert_eq!(vars.get("A").map(String::as_str), Some("1"));
+        assert_eq!(vars.get("B").map(String::as_str), Some("two words"));
+        assert_eq!(vars.get("C").map(String::as_str), Some("3"));
+        assert_eq!(vars.len(), 3);
+    }
+
+    #[test]
+    fn test_looks_secret() {
+        assert!(looks_secret("GITHUB_TOKEN"));
+        assert!(looks_secret("X-Api-Key"));
+        assert!(looks_secret("Authorization"));
+        assert!(!looks_secret("LOG_LEVEL"));
+    }
+}
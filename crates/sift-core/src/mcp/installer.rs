@@ -118,6 +118,8 @@ impl<'a> McpInstaller<'a> {
         )
         .with_context(|| format!("Failed to deploy MCP '{}' to client", request.name))?;
 
+        warnings.extend(deploy_report.warnings);
+
         // 6. Update lockfile
         self.update_lockfile(&request)?;
 
@@ -140,13 +142,14 @@ impl<'a> McpInstaller<'a> {
 
     fn update_lockfile(&self, request: &McpInstallRequest) -> anyhow::Result<()> {
         let lockfile = self.ctx.lockfile_service();
+        let constraint = request
+            .version
+            .clone()
+            .unwrap_or_else(|| "latest".to_string());
         let locked = LockedMcpServer::new(
             request.name.clone(),
-            "todo".to_string(), // TODO: proper version resolution
-            request
-                .version
-                .clone()
-                .unwrap_or_else(|| "latest".to_string()),
+            constraint.clone(),
+            constraint,
             request.entry.source.clone(),
             self.scope,
         );
@@ -1,7 +1,9 @@
 //! MCP (Model Context Protocol) server management
 
 pub mod builder;
+pub mod expand;
 pub mod installer;
+pub mod probe;
 pub mod schema;
 pub mod spec;
 
@@ -9,7 +11,7 @@ use serde::{Deserialize, Serialize};
 
 // Re-export the new schema types
 pub use builder::{DEFAULT_RUNTIME, DEFAULT_VERSION, McpServerBuilder};
-pub use schema::{McpConfig, McpConfigOverride, RuntimeType, TransportType};
+pub use schema::{McpConfig, McpConfigOverride, OAuthConfig, RuntimeType, TransportType};
 pub use spec::{McpResolvedServer, McpTransport};
 
 /// Represents an MCP server configuration
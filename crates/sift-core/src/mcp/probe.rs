@@ -0,0 +1,653 @@
+//! Health check and inspection for MCP servers.
+//!
+//! A probe starts a resolved server the way a client would (or connects to
+//! its HTTP URL), performs the `initialize` handshake and lists its tools.
+//! It catches broken runtimes, missing packages and bad credentials before
+//! an agent runs into them. [`inspect_server`] runs the same exchange but
+//! keeps the tools, prompts and resources the server lists.
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::Path;
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::time::{Duration, Instant};
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use crate::mcp::spec::{McpResolvedServer, McpTransport};
+
+/// Protocol version offered in `initialize`.
+pub const PROTOCOL_VERSION: &str = "2025-06-18";
+
+/// Default time a server gets to answer the whole probe.
+pub const DEFAULT_PROBE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// What a server reported during a successful probe.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProbeResult {
+    /// `serverInfo.name`
+    pub server_name: Option<String>,
+    /// `serverInfo.version`
+    pub server_version: Option<String>,
+    /// Protocol version the server agreed to
+    pub protocol_version: Option<String>,
+    /// Number of tools from `tools/list`
+    pub tool_count: usize,
+    /// Time from launch (or first request) to the `initialize` response
+    pub startup_ms: u64,
+}
+
+/// A tool from `tools/list`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolInfo {
+    pub name: String,
+    pub description: Option<String>,
+    /// JSON Schema for the tool's arguments (`inputSchema`)
+    pub input_schema: Value,
+}
+
+/// A prompt from `prompts/list`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PromptInfo {
+    pub name: String,
+    pub description: Option<String>,
+    /// Argument names, in the order the server lists them
+    pub arguments: Vec<String>,
+}
+
+/// A resource from `resources/list`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResourceInfo {
+    pub uri: String,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub mime_type: Option<String>,
+}
+
+/// Everything a server listed during an inspection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerInventory {
+    /// Handshake details, as for a probe
+    pub probe: ProbeResult,
+    pub tools: Vec<ToolInfo>,
+    /// Empty unless the server advertises the `prompts` capability
+    pub prompts: Vec<PromptInfo>,
+    /// Empty unless the server advertises the `resources` capability
+    pub resources: Vec<ResourceInfo>,
+}
+
+/// Start `server` and run the `initialize` + `tools/list` exchange.
+///
+/// Stdio servers run in `cwd` and are killed afterwards. The whole exchange
+/// must finish within `timeout`.
+pub fn probe_server(
+    server: &McpResolvedServer,
+    cwd: &Path,
+    timeout: Duration,
+) -> anyhow::Result<ProbeResult> {
+    connect(server, cwd, timeout, false).map(|inventory| inventory.probe)
+}
+
+/// Start `server` and list its tools, prompts and resources.
+///
+/// Prompts and resources are only requested when the server advertises them
+/// in its `initialize` capabilities. Runs under the same rules as
+/// [`probe_server`].
+pub fn inspect_server(
+    server: &McpResolvedServer,
+    cwd: &Path,
+    timeout: Duration,
+) -> anyhow::Result<ServerInventory> {
+    connect(server, cwd, timeout, true)
+}
+
+fn connect(
+    server: &McpResolvedServer,
+    cwd: &Path,
+    timeout: Duration,
+    full: bool,
+) -> anyhow::Result<ServerInventory> {
+    let started = Instant::now();
+    let deadline = started + timeout;
+    match server.transport {
+        McpTransport::Stdio => {
+            let mut transport = StdioTransport::spawn(server, cwd)?;
+            let result = session(&mut transport, started, deadline, full);
+            transport.finish(result)
+        }
+        McpTransport::Http => {
+            let mut transport = HttpTransport::new(server)?;
+            session(&mut transport, started, deadline, full)
+        }
+    }
+}
+
+/// One JSON-RPC connection to a server.
+trait Transport {
+    /// Send a request and wait for the response with the same id.
+    fn request(&mut self, id: u64, message: &Value, deadline: Instant) -> anyhow::Result<Value>;
+
+    /// Send a notification.
+    fn notify(&mut self, message: &Value, deadline: Instant) -> anyhow::Result<()>;
+}
+
+fn session(
+    transport: &mut dyn Transport,
+    started: Instant,
+    deadline: Instant,
+    full: bool,
+) -> anyhow::Result<ServerInventory> {
+    let init = call(
+        transport,
+        1,
+        "initialize",
+        json!({
+            "protocolVersion": PROTOCOL_VERSION,
+            "capabilities": {},
+            "clientInfo": {"name": "sift", "version": env!("CARGO_PKG_VERSION")},
+        }),
+        deadline,
+    )?;
+    let startup = started.elapsed();
+    transport.notify(
+        &json!({"jsonrpc": "2.0", "method": "notifications/initialized"}),
+        deadline,
+    )?;
+
+    let mut id = 2;
+    let tools = list_all(transport, "tools/list", "tools", &mut id, deadline)?;
+    let offers = |capability: &str| {
+        full && init
+            .get("capabilities")
+            .and_then(|c| c.get(capability))
+            .is_some()
+    };
+    let prompts = if offers("prompts") {
+        list_all(transport, "prompts/list", "prompts", &mut id, deadline)?
+    } else {
+        Vec::new()
+    };
+    let resources = if offers("resources") {
+        list_all(transport, "resources/list", "resources", &mut id, deadline)?
+    } else {
+        Vec::new()
+    };
+
+    let info = init.get("serverInfo");
+    Ok(ServerInventory {
+        probe: ProbeResult {
+            server_name: text(info.and_then(|i| i.get("name"))),
+            server_version: text(info.and_then(|i| i.get("version"))),
+            protocol_version: text(init.get("protocolVersion")),
+            tool_count: tools.len(),
+            startup_ms: startup.as_millis() as u64,
+        },
+        tools: tools
+            .iter()
+            .map(|tool| ToolInfo {
+                name: text(tool.get("name")).unwrap_or_default(),
+                description: text(tool.get("description")),
+                input_schema: tool.get("inputSchema").cloned().unwrap_or(Value::Null),
+            })
+            .collect(),
+        prompts: prompts
+            .iter()
+            .map(|prompt| PromptInfo {
+                name: text(prompt.get("name")).unwrap_or_default(),
+                description: text(prompt.get("description")),
+                arguments: prompt
+                    .get("arguments")
+                    .and_then(Value::as_array)
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|arg| text(arg.get("name")))
+                    .collect(),
+            })
+            .collect(),
+        resources: resources
+            .iter()
+            .map(|resource| ResourceInfo {
+                uri: text(resource.get("uri")).unwrap_or_default(),
+                name: text(resource.get("name")),
+                description: text(resource.get("description")),
+                mime_type: text(resource.get("mimeType")),
+            })
+            .collect(),
+    })
+}
+
+/// Call a paginated `*/list` method and collect the `key` array of every page.
+fn list_all(
+    transport: &mut dyn Transport,
+    method: &str,
+    key: &str,
+    id: &mut u64,
+    deadline: Instant,
+) -> anyhow::Result<Vec<Value>> {
+    let mut items = Vec::new();
+    let mut cursor: Option<String> = None;
+    loop {
+        let params = match &cursor {
+            Some(cursor) => json!({"cursor": cursor}),
+            None => json!({}),
+        };
+        let page = call(transport, *id, method, params, deadline)?;
+        *id += 1;
+        if let Some(Value::Array(page_items)) = page.get(key) {
+            items.extend(page_items.iter().cloned());
+        }
+        cursor = text(page.get("nextCursor"));
+        if cursor.is_none() {
+            return Ok(items);
+        }
+    }
+}
+
+fn text(value: Option<&Value>) -> Option<String> {
+    value.and_then(Value::as_str).map(str::to_string)
+}
+
+/// Send `method` and return its result, turning JSON-RPC errors into errors.
+fn call(
+    transport: &mut dyn Transport,
+    id: u64,
+    method: &str,
+    params: Value,
+    deadline: Instant,
+) -> anyhow::Result<Value> {
+    let message = json!({"jsonrpc": "2.0", "id": id, "method": method, "params": params});
+    let response = transport
+        .request(id, &message, deadline)
+        .with_context(|| format!("{} failed", method))?;
+    if let Some(error) = response.get("error") {
+        anyhow::bail!(
+            "{} failed: {} (code {})",
+            method,
+            error
+                .get("message")
+                .and_then(Value::as_str)
+                .unwrap_or("unknown error"),
+            error.get("code").unwrap_or(&Value::Null)
+        );
+    }
+    Ok(response.get("result").cloned().unwrap_or(Value::Null))
+}
+
+/// Whether `message` is the response to request `id`.
+fn is_response(message: &Value, id: u64) -> bool {
+    message.get("id").and_then(Value::as_u64) == Some(id)
+        && (message.get("result").is_some() || message.get("error").is_some())
+}
+
+fn remaining(deadline: Instant) -> anyhow::Result<Duration> {
+    deadline
+        .checked_duration_since(Instant::now())
+        .filter(|d| !d.is_zero())
+        .ok_or_else(|| anyhow::anyhow!("timed out"))
+}
+
+/// Newline-delimited JSON-RPC over a child process's stdin/stdout.
+struct StdioTransport {
+    child: Child,
+    stdin: ChildStdin,
+    stdout: Receiver<String>,
+    stderr: Receiver<String>,
+}
+
+impl StdioTransport {
+    fn spawn(server: &McpResolvedServer, cwd: &Path) -> anyhow::Result<Self> {
+        let command = server
+            .command
+            .as_deref()
+            .filter(|c| !c.is_empty())
+            .ok_or_else(|| anyhow::anyhow!("Server '{}' has no command", server.name))?;
+        let mut child = Command::new(command)
+            .args(&server.args)
+            .envs(&server.env)
+            .current_dir(cwd)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .with_context(|| format!("Failed to start '{}'", command))?;
+
+        let stdin = child.stdin.take().expect("stdin is piped");
+        let stdout = read_lines(child.stdout.take().expect("stdout is piped"));
+        let stderr = read_lines(child.stderr.take().expect("stderr is piped"));
+        Ok(Self {
+            child,
+            stdin,
+            stdout,
+            stderr,
+        })
+    }
+
+    fn send(&mut self, message: &Value) -> anyhow::Result<()> {
+        writeln!(self.stdin, "{}", message)
+            .and_then(|_| self.stdin.flush())
+            .context("Server closed its input")
+    }
+
+    /// Stop the server, adding what it printed to stderr to any error.
+    fn finish<T>(mut self, result: anyhow::Result<T>) -> anyhow::Result<T> {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+        result.map_err(|err| {
+            // Readers may stay blocked on pipes held by grandchildren, so
+            // only wait briefly for what the server printed
+            let grace = Instant::now() + Duration::from_millis(200);
+            let mut lines = Vec::new();
+            while let Ok(line) = self
+                .stderr
+                .recv_timeout(grace.saturating_duration_since(Instant::now()))
+            {
+                lines.push(line);
+            }
+            let tail = lines[lines.len().saturating_sub(10)..].join("\n");
+            if tail.trim().is_empty() {
+                err
+            } else {
+                err.context(format!("Server stderr:\n{}", tail.trim_end()))
+            }
+        })
+    }
+}
+
+impl Transport for StdioTransport {
+    fn request(&mut self, id: u64, message: &Value, deadline: Instant) -> anyhow::Result<Value> {
+        self.send(message)?;
+        loop {
+            let line = match self.stdout.recv_timeout(remaining(deadline)?) {
+                Ok(line) => line,
+                Err(RecvTimeoutError::Timeout) => anyhow::bail!("timed out"),
+                Err(RecvTimeoutError::Disconnected) => {
+                    let status = self.child.try_wait().ok().flatten();
+                    match status {
+                        Some(status) => anyhow::bail!("server exited ({})", status),
+                        None => anyhow::bail!("server closed its output"),
+                    }
+                }
+            };
+            // Servers may log to stdout or send notifications first
+            if let Ok(response) = serde_json::from_str::<Value>(&line)
+                && is_response(&response, id)
+            {
+                return Ok(response);
+            }
+        }
+    }
+
+    fn notify(&mut self, message: &Value, _deadline: Instant) -> anyhow::Result<()> {
+        self.send(message)
+    }
+}
+
+fn read_lines(pipe: impl Read + Send + 'static) -> Receiver<String> {
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        for line in BufReader::new(pipe).lines() {
+            let Ok(line) = line else { break };
+            if tx.send(line).is_err() {
+                break;
+            }
+        }
+    });
+    rx
+}
+
+/// Streamable HTTP: each message is a POST answered with JSON or an SSE stream.
+struct HttpTransport {
+    runtime: tokio::runtime::Runtime,
+    client: reqwest::Client,
+    url: String,
+    headers: reqwest::header::HeaderMap,
+    session_id: Option<String>,
+}
+
+impl HttpTransport {
+    fn new(server: &McpResolvedServer) -> anyhow::Result<Self> {
+        let url = server
+            .url
+            .clone()
+            .filter(|u| !u.is_empty())
+            .ok_or_else(|| anyhow::anyhow!("Server '{}' has no URL", server.name))?;
+        let mut headers = reqwest::header::HeaderMap::new();
+        for (name, value) in &server.headers {
+            headers.insert(
+                reqwest::header::HeaderName::from_bytes(name.as_bytes())
+                    .with_context(|| format!("Invalid header name '{}'", name))?,
+                reqwest::header::HeaderValue::from_str(value)
+                    .with_context(|| format!("Invalid value for header '{}'", name))?,
+            );
+        }
+        Ok(Self {
+            runtime: tokio::runtime::Runtime::new()
+                .context("Failed to create runtime for HTTP probe")?,
+            client: reqwest::Client::new(),
+            url,
+            headers,
+            session_id: None,
+        })
+    }
+
+    /// POST `message` and return the response with `id`, if any is expected.
+    fn post(
+        &mut self,
+        message: &Value,
+        id: Option<u64>,
+        deadline: Instant,
+    ) -> anyhow::Result<Option<Value>> {
+        let mut request = self
+            .client
+            .post(&self.url)
+            .headers(self.headers.clone())
+            .header(
+                reqwest::header::ACCEPT,
+                "application/json, text/event-stream",
+            )
+            .timeout(remaining(deadline)?)
+            .json(message);
+        if let Some(session_id) = &self.session_id {
+            request = request
+                .header("Mcp-Session-Id", session_id)
+                .header("MCP-Protocol-Version", PROTOCOL_VERSION);
+        }
+
+        self.runtime.block_on(async {
+            let mut response = request.send().await.map_err(describe_http_error)?;
+            let status = response.status();
+            if !status.is_success() {
+                let body = response.text().await.unwrap_or_default();
+                anyhow::bail!("HTTP {}: {}", status, body.trim());
+            }
+            if let Some(session_id) = response
+                .headers()
+                .get("mcp-session-id")
+                .and_then(|v| v.to_str().ok())
+            {
+                self.session_id = Some(session_id.to_string());
+            }
+            let Some(id) = id else {
+                return Ok(None);
+            };
+
+            let is_stream = response
+                .headers()
+                .get(reqwest::header::CONTENT_TYPE)
+                .and_then(|v| v.to_str().ok())
+                .is_some_and(|v| v.starts_with("text/event-stream"));
+            if !is_stream {
+                let body: Value = response.json().await.context("Response is not JSON-RPC")?;
+                return Ok(Some(body));
+            }
+
+            // Read events until the response arrives; the stream may stay open
+            let mut buffer = String::new();
+            while let Some(chunk) = response.chunk().await.map_err(describe_http_error)? {
+                buffer.push_str(&String::from_utf8_lossy(&chunk));
+                while let Some(end) = buffer.find("\n\n") {
+                    let event: String = buffer.drain(..end + 2).collect();
+                    if let Some(message) = parse_sse_event(&event)
+                        && is_response(&message, id)
+                    {
+                        return Ok(Some(message));
+                    }
+                }
+            }
+            anyhow::bail!("stream ended without a response")
+        })
+    }
+}
+
+impl Transport for HttpTransport {
+    fn request(&mut self, id: u64, message: &Value, deadline: Instant) -> anyhow::Result<Value> {
+        self.post(message, Some(id), deadline)?
+            .ok_or_else(|| anyhow::anyhow!("no response"))
+    }
+
+    fn notify(&mut self, message: &Value, deadline: Instant) -> anyhow::Result<()> {
+        self.post(message, None, deadline).map(|_| ())
+    }
+}
+
+fn describe_http_error(err: reqwest::Error) -> anyhow::Error {
+    if err.is_timeout() {
+        anyhow::anyhow!("timed out")
+    } else {
+        anyhow::Error::new(err)
+    }
+}
+
+/// The JSON payload of one SSE event, joining its `data:` lines.
+fn parse_sse_event(event: &str) -> Option<Value> {
+    let data: Vec<&str> = event
+        .lines()
+        .filter_map(|line| line.strip_prefix("data:"))
+        .map(|data| data.strip_prefix(' ').unwrap_or(data))
+        .collect();
+    if data.is_empty() {
+        return None;
+    }
+    serde_json::from_str(&data.join("\n")).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    /// A shell script that answers like a minimal MCP server.
+    const FAKE_SERVER: &str = r#"
+echo "starting up" >&2
+echo "not json"
+while IFS= read -r line; do
+  case "$line" in
+    *'"initialize"'*) echo '{"jsonrpc":"2.0","id":1,"result":{"protocolVersion":"2025-06-18","serverInfo":{"name":"fake","version":"1.2.3"},"capabilities":{"tools":{}}}}' ;;
+    *'"cursor"'*) echo '{"jsonrpc":"2.0","id":3,"result":{"tools":[{"name":"c"}]}}' ;;
+    *'"tools/list"'*) echo '{"jsonrpc":"2.0","id":2,"result":{"tools":[{"name":"a"},{"name":"b"}],"nextCursor":"p2"}}' ;;
+  esac
+done
+"#;
+
+    /// Answers by echoing the request id; offers prompts but not resources.
+    const DOCS_SERVER: &str = r#"
+while IFS= read -r line; do
+  id=$(printf '%s' "$line" | sed -n 's/.*"id":\([0-9]*\).*/\1/p')
+  case "$line" in
+    *'"initialize"'*) result='{"protocolVersion":"2025-06-18","serverInfo":{"name":"docs"},"capabilities":{"tools":{},"prompts":{}}}' ;;
+    *'"tools/list"'*) result='{"tools":[{"name":"search","description":"Search the docs","inputSchema":{"type":"object","properties":{"query":{"type":"string"}}}}]}' ;;
+    *'"prompts/list"'*) result='{"prompts":[{"name":"summarize","arguments":[{"name":"topic","required":true}]}]}' ;;
+    *) continue ;;
+  esac
+  echo "{\"jsonrpc\":\"2.0\",\"id\":$id,\"result\":$result}"
+done
+"#;
+
+    fn shell_server(script: &str) -> McpResolvedServer {
+        McpResolvedServer::stdio(
+            "fake".to_string(),
+            "sh".to_string(),
+            vec!["-c".to_string(), script.to_string()],
+            HashMap::new(),
+        )
+    }
+
+    #[test]
+    fn test_probe_stdio_server() {
+        let temp = TempDir::new().unwrap();
+
+        let result = probe_server(
+            &shell_server(FAKE_SERVER),
+            temp.path(),
+            Duration::from_secs(10),
+        )
+        .unwrap();
+
+        assert_eq!(result.server_name.as_deref(), Some("fake"));
+        assert_eq!(result.server_version.as_deref(), Some("1.2.3"));
+        assert_eq!(result.protocol_version.as_deref(), Some(PROTOCOL_VERSION));
+        assert_eq!(result.tool_count, 3);
+    }
+
+    #[test]
+    fn test_inspect_lists_advertised_capabilities() {
+        let temp = TempDir::new().unwrap();
+
+        let inventory = inspect_server(
+            &shell_server(DOCS_SERVER),
+            temp.path(),
+            Duration::from_secs(10),
+        )
+        .unwrap();
+
+        assert_eq!(inventory.probe.server_name.as_deref(), Some("docs"));
+        assert_eq!(inventory.tools.len(), 1);
+        assert_eq!(inventory.tools[0].name, "search");
+        assert_eq!(
+            inventory.tools[0].description.as_deref(),
+            Some("Search the docs")
+        );
+        assert_eq!(
+            inventory.tools[0].input_schema["properties"]["query"]["type"],
+            "string"
+        );
+        assert_eq!(inventory.prompts.len(), 1);
+        assert_eq!(inventory.prompts[0].arguments, vec!["topic"]);
+        // resources/list is never sent, or the server would leave it unanswered
+        assert!(inventory.resources.is_empty());
+    }
+
+    #[test]
+    fn test_probe_reports_exit_with_stderr() {
+        let temp = TempDir::new().unwrap();
+        let server = shell_server("echo 'Cannot find module x' >&2; exit 3");
+
+        let err = probe_server(&server, temp.path(), Duration::from_secs(10)).unwrap_err();
+        let message = format!("{:#}", err);
+
+        assert!(message.contains("initialize failed"), "{message}");
+        assert!(message.contains("Cannot find module x"), "{message}");
+    }
+
+    #[test]
+    fn test_probe_times_out() {
+        let temp = TempDir::new().unwrap();
+        let server = shell_server("sleep 5");
+
+        let err = probe_server(&server, temp.path(), Duration::from_millis(200)).unwrap_err();
+
+        assert!(format!("{:#}", err).contains("timed out"));
+    }
+
+    #[test]
+    fn test_parse_sse_event() {
+        let event =
+            "event: message\ndata: {\"jsonrpc\":\"2.0\",\ndata: \"id\":1,\"result\":{}}\n\n";
+        let message = parse_sse_event(event).unwrap();
+        assert!(is_response(&message, 1));
+        assert!(parse_sse_event(": keep-alive\n\n").is_none());
+    }
+}
@@ -12,7 +12,8 @@ pub enum TransportType {
     /// Command-based execution (default)
     #[default]
     Stdio,
-    /// HTTP-based MCP server
+    /// Streamable HTTP MCP server
+    #[serde(alias = "streamable-http")]
     Http,
 }
 
@@ -22,12 +23,57 @@ impl TryFrom<&str> for TransportType {
     fn try_from(value: &str) -> Result<Self, Self::Error> {
         match value.to_lowercase().as_str() {
             "stdio" => Ok(TransportType::Stdio),
-            "http" => Ok(TransportType::Http),
-            _ => anyhow::bail!("Invalid transport: '{}'. Valid values: stdio, http", value),
+            "http" | "streamable-http" => Ok(TransportType::Http),
+            _ => anyhow::bail!(
+                "Invalid transport: '{}'. Valid values: stdio, http (or streamable-http)",
+                value
+            ),
         }
     }
 }
 
+/// OAuth client settings for an HTTP server.
+///
+/// The client still runs the authorization flow itself; these settings are
+/// written into its config where it has a place for them. Without them,
+/// clients fall back to dynamic client registration.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OAuthConfig {
+    /// Pre-registered OAuth client id
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_id: Option<String>,
+
+    /// Scopes to request
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub scopes: Vec<String>,
+
+    /// Fixed localhost port for the redirect callback
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub callback_port: Option<u16>,
+}
+
+impl OAuthConfig {
+    /// Setting names as they appear in `supported_oauth` capabilities
+    pub const CLIENT_ID: &'static str = "client_id";
+    pub const SCOPES: &'static str = "scopes";
+    pub const CALLBACK_PORT: &'static str = "callback_port";
+
+    /// Names of the settings that have a value.
+    pub fn fields(&self) -> Vec<&'static str> {
+        let mut fields = Vec::new();
+        if self.client_id.is_some() {
+            fields.push(Self::CLIENT_ID);
+        }
+        if !self.scopes.is_empty() {
+            fields.push(Self::SCOPES);
+        }
+        if self.callback_port.is_some() {
+            fields.push(Self::CALLBACK_PORT);
+        }
+        fields
+    }
+}
+
 /// Complete MCP server configuration from sift.toml
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct McpConfig {
@@ -55,6 +101,10 @@ pub struct McpConfig {
     #[serde(default)]
     pub headers: HashMap<String, String>,
 
+    /// HTTP: OAuth client settings
+    #[serde(default)]
+    pub oauth: Option<OAuthConfig>,
+
     /// Target control (whitelist) - applies to all transports
     #[serde(default)]
     pub targets: Option<Vec<String>>,
@@ -149,6 +199,9 @@ impl McpConfig {
         if !other.headers.is_empty() {
             self.headers = other.headers;
         }
+        if other.oauth.is_some() {
+            self.oauth = other.oauth;
+        }
 
         // Common fields
         if other.targets.is_some() {
@@ -187,9 +240,10 @@ impl McpConfig {
                     && !self.source.starts_with("local:")
                     && !self.source.starts_with("github:")
                     && !self.source.starts_with("git:")
+                    && !self.source.starts_with("docker:")
                 {
                     anyhow::bail!(
-                        "Invalid source format for stdio transport: must be 'registry:name', 'local:/path', 'github:org/repo', or 'git:url'"
+                        "Invalid source format for stdio transport: must be 'registry:name', 'local:/path', 'github:org/repo', 'git:url', or 'docker:image'"
                     );
                 }
             }
@@ -201,6 +255,15 @@ impl McpConfig {
             }
         }
 
+        if let Some(oauth) = &self.oauth {
+            if self.transport != TransportType::Http {
+                anyhow::bail!("'oauth' is only supported for http transport");
+            }
+            if oauth.callback_port == Some(0) {
+                anyhow::bail!("oauth.callback_port must be a port between 1 and 65535");
+            }
+        }
+
         Ok(())
     }
 
@@ -256,6 +319,7 @@ mod tests {
             args: vec!["--readonly".to_string()],
             url: None,
             headers: HashMap::new(),
+            oauth: None,
             targets: Some(vec!["claude-desktop".to_string()]),
             ignore_targets: None,
             env: HashMap::new(),
@@ -272,6 +336,7 @@ mod tests {
             args: vec![],
             url: None,
             headers: HashMap::new(),
+            oauth: None,
             targets: Some(vec!["claude-desktop".to_string()]),
             ignore_targets: Some(vec!["vscode".to_string()]),
             env: HashMap::new(),
@@ -288,6 +353,7 @@ mod tests {
             args: vec![],
             url: None,
             headers: HashMap::new(),
+            oauth: None,
             targets: Some(vec!["claude-desktop".to_string()]),
             ignore_targets: None,
             env: HashMap::new(),
@@ -305,6 +371,7 @@ mod tests {
             args: vec![],
             url: None,
             headers: HashMap::new(),
+            oauth: None,
             targets: None,
             ignore_targets: Some(vec!["vscode".to_string()]),
             env: HashMap::new(),
@@ -322,6 +389,7 @@ mod tests {
             args: vec!["--arg1".to_string()],
             url: None,
             headers: HashMap::new(),
+            oauth: None,
             targets: None,
             ignore_targets: None,
             env: {
@@ -338,6 +406,7 @@ mod tests {
             args: vec!["--arg2".to_string()],
             url: None,
             headers: HashMap::new(),
+            oauth: None,
             targets: Some(vec!["claude-desktop".to_string()]),
             ignore_targets: None,
             env: {
@@ -369,6 +438,7 @@ mod tests {
             args: vec![],
             url: None,
             headers: HashMap::new(),
+            oauth: None,
             targets: None,
             ignore_targets: None,
             env: {
@@ -411,6 +481,7 @@ mod tests {
                 map.insert("Authorization".to_string(), "Bearer token".to_string());
                 map
             },
+            oauth: None,
             targets: None,
             ignore_targets: None,
             env: HashMap::new(),
@@ -427,6 +498,7 @@ mod tests {
             args: vec![],
             url: None,
             headers: HashMap::new(),
+            oauth: None,
             targets: None,
             ignore_targets: None,
             env: HashMap::new(),
@@ -443,6 +515,7 @@ mod tests {
             args: vec![],
             url: None,
             headers: HashMap::new(),
+            oauth: None,
             targets: None,
             ignore_targets: None,
             env: HashMap::new(),
@@ -450,6 +523,23 @@ mod tests {
         assert!(config.validate().is_err());
     }
 
+    #[test]
+    fn test_validate_stdio_config_docker_source() {
+        let config = McpConfig {
+            transport: TransportType::Stdio,
+            source: "docker:ghcr.io/acme/mcp:1.0".to_string(),
+            runtime: RuntimeType::Docker,
+            args: vec![],
+            url: None,
+            headers: HashMap::new(),
+            oauth: None,
+            targets: None,
+            ignore_targets: None,
+            env: HashMap::new(),
+        };
+        assert!(config.validate().is_ok());
+    }
+
     #[test]
     fn test_merge_http_config() {
         let mut base = McpConfig {
@@ -459,6 +549,7 @@ mod tests {
             args: vec![],
             url: Some("https://example.com".to_string()),
             headers: HashMap::new(),
+            oauth: None,
             targets: None,
             ignore_targets: None,
             env: HashMap::new(),
@@ -475,6 +566,7 @@ mod tests {
                 map.insert("X-Header".to_string(), "value".to_string());
                 map
             },
+            oauth: None,
             targets: Some(vec!["claude-desktop".to_string()]),
             ignore_targets: None,
             env: {
@@ -2,6 +2,8 @@
 
 use std::collections::HashMap;
 
+use super::OAuthConfig;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum McpTransport {
     Stdio,
@@ -17,6 +19,27 @@ pub struct McpResolvedServer {
     pub env: HashMap<String, String>,
     pub url: Option<String>,
     pub headers: HashMap<String, String>,
+    /// OAuth client settings for HTTP servers
+    pub oauth: Option<OAuthConfig>,
+    /// Tools pre-approved for clients that support auto-approval
+    pub always_allow: Vec<String>,
+    /// Launch details Sift adapted to this machine
+    pub machine_launch: Option<MachineLaunch>,
+    /// Options an MCPB bundle declares in `user_config`, with their default
+    /// (`None` if the manifest gives none)
+    pub user_config: HashMap<String, Option<String>>,
+}
+
+/// How Sift adapted a Python package launch to the installing machine.
+///
+/// Shared project configs are used on other machines, so they get the
+/// portable launch instead (see [`McpResolvedServer::make_portable`]).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MachineLaunch {
+    /// `env` keys Sift set to its own launcher cache directories
+    pub cache_env: Vec<String>,
+    /// The `uvx` arguments, when the launch was rewritten for pipx
+    pub uvx_args: Option<Vec<String>>,
 }
 
 impl McpResolvedServer {
@@ -34,6 +57,10 @@ impl McpResolvedServer {
             env,
             url: None,
             headers: HashMap::new(),
+            oauth: None,
+            always_allow: Vec::new(),
+            machine_launch: None,
+            user_config: HashMap::new(),
         }
     }
 
@@ -46,6 +73,25 @@ impl McpResolvedServer {
             env: HashMap::new(),
             url: Some(url),
             headers,
+            oauth: None,
+            always_allow: Vec::new(),
+            machine_launch: None,
+            user_config: HashMap::new(),
+        }
+    }
+
+    /// Drop the machine-specific launch details: Sift's launcher cache
+    /// directories, and a pipx fallback in favour of the `uvx` launch.
+    pub fn make_portable(&mut self) {
+        let Some(launch) = self.machine_launch.take() else {
+            return;
+        };
+        for key in &launch.cache_env {
+            self.env.remove(key);
+        }
+        if let Some(args) = launch.uvx_args {
+            self.command = Some("uvx".to_string());
+            self.args = args;
         }
     }
 }
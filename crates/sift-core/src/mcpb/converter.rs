@@ -60,12 +60,22 @@ pub fn manifest_to_server(
         }
     }
 
-    Ok(McpResolvedServer::stdio(
-        name.to_string(),
-        command,
-        args,
-        env,
-    ))
+    let mut server = McpResolvedServer::stdio(name.to_string(), command, args, env);
+    server.user_config = manifest
+        .user_config
+        .iter()
+        .map(|(key, option)| {
+            let default = option.default.as_ref().and_then(|value| match value {
+                serde_json::Value::String(s) => Some(s.clone()),
+                serde_json::Value::Number(_) | serde_json::Value::Bool(_) => {
+                    Some(value.to_string())
+                }
+                _ => None,
+            });
+            (key.clone(), default)
+        })
+        .collect();
+    Ok(server)
 }
 
 /// Derive mcp_config from server type and entry point when not explicitly provided.
@@ -369,6 +379,12 @@ mod tests {
             Some(&"${HOME}/Documents".to_string())
         );
         assert!(!server.env.contains_key("API_KEY"));
+
+        assert_eq!(
+            server.user_config.get("workspace"),
+            Some(&Some("${HOME}/Documents".to_string()))
+        );
+        assert_eq!(server.user_config.get("api_key"), Some(&None));
     }
 
     // =========================================================================
@@ -470,6 +470,7 @@ impl MarketplaceAdapter {
                 args: vec![],
                 url: None,
                 headers: std::collections::HashMap::new(),
+                oauth: None,
                 targets: None,
                 ignore_targets: None,
                 env: std::collections::HashMap::new(),
@@ -484,6 +485,7 @@ impl MarketplaceAdapter {
             args: vec![],
             url: Some(url_str.to_string()),
             headers: std::collections::HashMap::new(),
+            oauth: None,
             targets: None,
             ignore_targets: None,
             env: std::collections::HashMap::new(),
@@ -511,6 +513,7 @@ impl MarketplaceAdapter {
                     args: vec![],
                     url: Some(url_str.to_string()),
                     headers: Self::parse_headers_field(server_config),
+                    oauth: None,
                     targets: None,
                     ignore_targets: None,
                     env: Self::parse_env_field(server_config),
@@ -539,6 +542,7 @@ impl MarketplaceAdapter {
                     args,
                     url: None,
                     headers: Self::parse_headers_field(server_config),
+                    oauth: None,
                     targets: None,
                     ignore_targets: None,
                     env: Self::parse_env_field(server_config),
@@ -1,7 +1,513 @@
+//! Native Sift registry client.
+//!
+//! A Sift registry is a static HTTP endpoint serving a single JSON index at
+//! `<url>/index.json`. The index describes every package the registry
+//! provides, the versions it publishes, and for each version the MCP server
+//! and/or skill it delivers:
+//!
+//! ```json
+//! {
+//!   "schema_version": 1,
+//!   "packages": {
+//!     "postgres": {
+//!       "description": "PostgreSQL MCP server",
+//!       "default_runtime": "node",
+//!       "latest": "1.2.3",
+//!       "versions": {
+//!         "1.2.3": {
+//!           "mcp": {
+//!             "package": "@acme/postgres-mcp",
+//!             "env": { "DATABASE_URL": "" }
+//!           }
+//!         }
+//!       }
+//!     },
+//!     "pdf": {
+//!       "versions": {
+//!         "0.4.0": {
+//!           "skill": { "source": "github:acme/skills", "ref": "v0.4.0", "path": "pdf" }
+//!         }
+//!       }
+//!     }
+//!   }
+//! }
+//! ```
+//!
+//! MCP entries are either HTTP (`url`/`headers`), an MCPB bundle (`mcpb`),
+//! an explicit `command`/`args`, or a runtime `package` that is launched via
+//! the package's runtime (`npx`, `bunx`, `uvx` or `docker run`). Skill entries
+//! point at a git repository; a `skills` list turns the package into a group
+//! that expands into one skill per path.
+
+use std::collections::{BTreeMap, HashMap};
+use std::time::Duration;
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::mcp::{McpConfig, RuntimeType, TransportType};
 use crate::registry::RegistryCapabilities;
+use crate::runtime::{DockerRuntimeRequest, image_reference, resolve_docker};
+
+/// Schema version of the index format understood by this client.
+pub const INDEX_SCHEMA_VERSION: u32 = 1;
+
+/// File name of the index, relative to the registry URL.
+pub const INDEX_FILE: &str = "index.json";
+
+/// Request timeout when fetching the registry index.
+const FETCH_TIMEOUT: Duration = Duration::from_secs(30);
 
 pub fn capabilities() -> RegistryCapabilities {
     RegistryCapabilities {
         supports_version_pinning: true,
     }
 }
+
+/// Top-level Sift registry index document.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SiftRegistryIndex {
+    /// Index format version
+    #[serde(default = "default_schema_version")]
+    pub schema_version: u32,
+    /// Packages keyed by name
+    #[serde(default)]
+    pub packages: BTreeMap<String, SiftPackage>,
+}
+
+fn default_schema_version() -> u32 {
+    INDEX_SCHEMA_VERSION
+}
+
+/// Package metadata and published versions.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SiftPackage {
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub homepage: Option<String>,
+    /// Runtime used for MCP entries that do not declare one
+    #[serde(default)]
+    pub default_runtime: Option<RuntimeType>,
+    /// Version returned for `latest` (defaults to the highest stable semver)
+    #[serde(default)]
+    pub latest: Option<String>,
+    /// Published versions keyed by version string
+    #[serde(default)]
+    pub versions: BTreeMap<String, SiftPackageVersion>,
+}
+
+/// Contents of a single published version.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SiftPackageVersion {
+    #[serde(default)]
+    pub mcp: Option<SiftMcpEntry>,
+    #[serde(default)]
+    pub skill: Option<SiftSkillEntry>,
+}
+
+/// MCP server definition for a package version.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SiftMcpEntry {
+    #[serde(default)]
+    pub transport: Option<TransportType>,
+    /// Overrides the package `default_runtime`
+    #[serde(default)]
+    pub runtime: Option<RuntimeType>,
+    /// Runtime package (npm/PyPI package or Docker image)
+    #[serde(default)]
+    pub package: Option<String>,
+    /// Explicit command (takes precedence over `package`)
+    #[serde(default)]
+    pub command: Option<String>,
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+    /// HTTP: Server URL
+    #[serde(default)]
+    pub url: Option<String>,
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+    /// MCPB bundle URL
+    #[serde(default)]
+    pub mcpb: Option<String>,
+}
+
+/// Skill definition for a package version.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SiftSkillEntry {
+    /// Git source (`github:org/repo` or `git:url`)
+    pub source: String,
+    /// Git reference (tag, branch or commit)
+    #[serde(default, rename = "ref")]
+    pub reference: Option<String>,
+    /// Skill directory within the repository
+    #[serde(default)]
+    pub path: Option<String>,
+    /// Skill directories for group packages
+    #[serde(default)]
+    pub skills: Vec<String>,
+}
+
+impl SiftRegistryIndex {
+    /// Parse an index document.
+    pub fn parse(content: &str) -> anyhow::Result<Self> {
+        let index: Self =
+            serde_json::from_str(content).context("Failed to parse Sift registry index")?;
+        if index.schema_version > INDEX_SCHEMA_VERSION {
+            anyhow::bail!(
+                "Unsupported Sift registry index schema version {} (supported: {})",
+                index.schema_version,
+                INDEX_SCHEMA_VERSION
+            );
+        }
+        Ok(index)
+    }
+
+    /// Look up a package by name.
+    pub fn find_package(&self, name: &str) -> Option<&SiftPackage> {
+        self.packages.get(name)
+    }
+}
+
+impl SiftPackage {
+    /// Select a published version matching `requested`.
+    ///
+    /// `None` and `"latest"` select the `latest` field or the highest stable
+    /// semver.
+    /// Otherwise an exact version key is preferred, then the highest version
+    /// satisfying the string as a semver requirement (e.g. `^1.2`).
+    pub fn select_version(
+        &self,
+        requested: Option<&str>,
+    ) -> anyhow::Result<(&str, &SiftPackageVersion)> {
+        let requested = requested.filter(|v| !v.is_empty() && *v != "latest");
+
+        let Some(requested) = requested else {
+            if let Some(latest) = &self.latest {
+                return self
+                    .versions
+                    .get_key_value(latest)
+                    .map(|(k, v)| (k.as_str(), v))
+                    .ok_or_else(|| {
+                        anyhow::anyhow!("Latest version '{}' is not published", latest)
+                    });
+            }
+            return self
+                .highest_matching(|v| v.pre.is_empty())
+                .or_else(|| self.highest_matching(|_| true))
+                .or_else(|| {
+                    self.versions
+                        .iter()
+                        .next_back()
+                        .map(|(k, v)| (k.as_str(), v))
+                })
+                .ok_or_else(|| anyhow::anyhow!("Package has no published versions"));
+        };
+
+        if let Some((key, version)) = self.versions.get_key_value(requested) {
+            return Ok((key.as_str(), version));
+        }
+
+        let req = semver::VersionReq::parse(requested)
+            .with_context(|| format!("Version '{}' is not published", requested))?;
+        self.highest_matching(|v| req.matches(v))
+            .ok_or_else(|| anyhow::anyhow!("No published version satisfies '{}'", requested))
+    }
+
+    fn highest_matching(
+        &self,
+        predicate: impl Fn(&semver::Version) -> bool,
+    ) -> Option<(&str, &SiftPackageVersion)> {
+        self.versions
+            .iter()
+            .filter_map(|(key, version)| {
+                let parsed = semver::Version::parse(key.trim_start_matches('v')).ok()?;
+                predicate(&parsed).then_some((parsed, key.as_str(), version))
+            })
+            .max_by(|a, b| a.0.cmp(&b.0))
+            .map(|(_, key, version)| (key, version))
+    }
+}
+
+impl SiftMcpEntry {
+    /// Convert to an `McpConfig` for the builder.
+    ///
+    /// `source` is the registry source string; for stdio entries the command
+    /// is appended as `<source>:<command>` so the builder can recover it.
+    pub fn to_mcp_config(
+        &self,
+        source: &str,
+        version: &str,
+        default_runtime: Option<RuntimeType>,
+    ) -> anyhow::Result<McpConfig> {
+        let runtime = self
+            .runtime
+            .or(default_runtime)
+            .unwrap_or(RuntimeType::Node);
+
+        if let Some(url) = &self.mcpb {
+            let source =
+                crate::mcpb::normalize_mcpb_source(url).unwrap_or_else(|| format!("mcpb:{}", url));
+            return Ok(McpConfig {
+                transport: TransportType::Stdio,
+                source,
+                runtime,
+                args: self.args.clone(),
+                url: None,
+                headers: HashMap::new(),
+                oauth: None,
+                targets: None,
+                ignore_targets: None,
+                env: self.env.clone(),
+            });
+        }
+
+        if self.transport == Some(TransportType::Http) || self.url.is_some() {
+            let url = self
+                .url
+                .clone()
+                .ok_or_else(|| anyhow::anyhow!("HTTP entry in {} is missing 'url'", source))?;
+            return Ok(McpConfig {
+                transport: TransportType::Http,
+                source: source.to_string(),
+                runtime,
+                args: Vec::new(),
+                url: Some(url),
+                headers: self.headers.clone(),
+                oauth: None,
+                targets: None,
+                ignore_targets: None,
+                env: self.env.clone(),
+            });
+        }
+
+        let (command, mut args) = if let Some(command) = &self.command {
+            (command.clone(), Vec::new())
+        } else if let Some(package) = &self.package {
+            runtime_launch(runtime, package, version)?
+        } else {
+            anyhow::bail!(
+                "MCP entry in {} must define 'command', 'package', 'url' or 'mcpb'",
+                source
+            );
+        };
+        args.extend(self.args.iter().cloned());
+
+        Ok(McpConfig {
+            transport: TransportType::Stdio,
+            source: format!("{}:{}", source, command),
+            runtime,
+            args,
+            url: None,
+            headers: HashMap::new(),
+            oauth: None,
+            targets: None,
+            ignore_targets: None,
+            env: self.env.clone(),
+        })
+    }
+}
+
+/// Build the launcher command and args for a runtime package.
+fn runtime_launch(
+    runtime: RuntimeType,
+    package: &str,
+    version: &str,
+) -> anyhow::Result<(String, Vec<String>)> {
+    let launch = match runtime {
+        RuntimeType::Node => (
+            "npx".to_string(),
+            vec!["-y".to_string(), format!("{}@{}", package, version)],
+        ),
+        RuntimeType::Bun => ("bunx".to_string(), vec![format!("{}@{}", package, version)]),
+        RuntimeType::Python => ("uvx".to_string(), vec![format!("{}=={}", package, version)]),
+        RuntimeType::Docker => {
+            let spec = resolve_docker(&DockerRuntimeRequest {
+                image: image_reference(package, version),
+                ..Default::default()
+            });
+            (spec.command, spec.args)
+        }
+        RuntimeType::Shell => {
+            anyhow::bail!("Shell runtime entries must define 'command' instead of 'package'")
+        }
+    };
+    Ok(launch)
+}
+
+/// Blocking HTTP client for a Sift registry.
+#[derive(Debug, Clone)]
+pub struct SiftRegistryClient {
+    base_url: Url,
+}
+
+impl SiftRegistryClient {
+    pub fn new(base_url: Url) -> Self {
+        Self { base_url }
+    }
+
+    /// URL of the registry index.
+    pub fn index_url(&self) -> anyhow::Result<Url> {
+        let mut base = self.base_url.clone();
+        if !base.path().ends_with('/') {
+            let path = format!("{}/", base.path());
+            base.set_path(&path);
+        }
+        base.join(INDEX_FILE)
+            .with_context(|| format!("Invalid Sift registry URL: {}", self.base_url))
+    }
+
+    /// Fetch and parse the registry index.
+    pub fn fetch_index(&self) -> anyhow::Result<SiftRegistryIndex> {
+        let url = self.index_url()?;
+
+        // Block on async fetch using tokio runtime
+        let runtime = tokio::runtime::Runtime::new()
+            .map_err(|e| anyhow::anyhow!("Failed to create tokio runtime: {}", e))?;
+        let content = runtime.block_on(async {
+            let client = reqwest::Client::builder()
+                .timeout(FETCH_TIMEOUT)
+                .build()
+                .context("Failed to build HTTP client")?;
+            let response = client
+                .get(url.clone())
+                .send()
+                .await
+                .with_context(|| format!("Failed to fetch Sift registry index from {}", url))?;
+            if !response.status().is_success() {
+                anyhow::bail!(
+                    "Failed to fetch Sift registry index: HTTP {} from {}",
+                    response.status(),
+                    url
+                );
+            }
+            response
+                .text()
+                .await
+                .with_context(|| format!("Failed to read response body from {}", url))
+        })?;
+
+        SiftRegistryIndex::parse(&content)
+            .with_context(|| format!("Invalid Sift registry index at {}", url))
+    }
+}
+
+/// Split a `name@version` package reference.
+///
+/// Leading `@` (scoped npm-style names) is not treated as a separator.
+pub fn split_package_version(input: &str) -> (&str, Option<&str>) {
+    match input.rfind('@') {
+        Some(pos) if pos > 0 && pos + 1 < input.len() => (&input[..pos], Some(&input[pos + 1..])),
+        _ => (input, None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_index() -> SiftRegistryIndex {
+        SiftRegistryIndex::parse(
+            r#"{
+                "schema_version": 1,
+                "packages": {
+                    "postgres": {
+                        "default_runtime": "node",
+                        "versions": {
+                            "1.2.3": { "mcp": { "package": "@acme/postgres-mcp" } },
+                            "1.10.0": { "mcp": { "package": "@acme/postgres-mcp" } },
+                            "2.0.0-beta.1": { "mcp": { "package": "@acme/postgres-mcp" } }
+                        }
+                    }
+                }
+            }"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn select_latest_uses_highest_stable_semver() {
+        let index = sample_index();
+        let package = index.find_package("postgres").unwrap();
+        let (version, _) = package.select_version(None).unwrap();
+        assert_eq!(version, "1.10.0");
+    }
+
+    #[test]
+    fn select_latest_prefers_declared_latest() {
+        let mut index = sample_index();
+        let package = index.packages.get_mut("postgres").unwrap();
+        package.latest = Some("1.10.0".to_string());
+        let (version, _) = package.select_version(Some("latest")).unwrap();
+        assert_eq!(version, "1.10.0");
+    }
+
+    #[test]
+    fn select_exact_and_requirement() {
+        let index = sample_index();
+        let package = index.find_package("postgres").unwrap();
+        assert_eq!(package.select_version(Some("1.2.3")).unwrap().0, "1.2.3");
+        assert_eq!(package.select_version(Some("^1.2")).unwrap().0, "1.10.0");
+        assert!(package.select_version(Some("3.0.0")).is_err());
+    }
+
+    #[test]
+    fn rejects_newer_schema_version() {
+        let err = SiftRegistryIndex::parse(r#"{"schema_version": 99}"#).unwrap_err();
+        assert!(err.to_string().contains("schema version"));
+    }
+
+    #[test]
+    fn package_entry_maps_to_runtime_launcher() {
+        let entry = SiftMcpEntry {
+            package: Some("pg-mcp".to_string()),
+            ..Default::default()
+        };
+        let config = entry
+            .to_mcp_config("registry:acme/postgres", "1.2.3", Some(RuntimeType::Python))
+            .unwrap();
+        assert_eq!(config.source, "registry:acme/postgres:uvx");
+        assert_eq!(config.args, vec!["pg-mcp==1.2.3"]);
+        assert_eq!(config.runtime, RuntimeType::Python);
+    }
+
+    #[test]
+    fn http_entry_maps_to_http_config() {
+        let entry = SiftMcpEntry {
+            url: Some("https://mcp.example.com".to_string()),
+            ..Default::default()
+        };
+        let config = entry
+            .to_mcp_config("registry:acme/x", "1.0.0", None)
+            .unwrap();
+        assert_eq!(config.transport, TransportType::Http);
+        assert_eq!(config.url.as_deref(), Some("https://mcp.example.com"));
+    }
+
+    #[test]
+    fn index_url_appends_index_file() {
+        let client =
+            SiftRegistryClient::new(Url::parse("https://registry.example.com/v1").unwrap());
+        assert_eq!(
+            client.index_url().unwrap().as_str(),
+            "https://registry.example.com/v1/index.json"
+        );
+    }
+
+    #[test]
+    fn split_package_version_handles_scoped_names() {
+        assert_eq!(
+            split_package_version("postgres@1.2.3"),
+            ("postgres", Some("1.2.3"))
+        );
+        assert_eq!(split_package_version("@acme/tool"), ("@acme/tool", None));
+        assert_eq!(
+            split_package_version("@acme/tool@0.4.0"),
+            ("@acme/tool", Some("0.4.0"))
+        );
+        assert_eq!(split_package_version("postgres"), ("postgres", None));
+    }
+}
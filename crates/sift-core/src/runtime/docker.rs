@@ -0,0 +1,376 @@
+//! Docker runtime resolution for MCP servers.
+//!
+//! Docker servers run as `docker run -i --rm [options] <image> [args...]`.
+//! Environment variables are forwarded by name (`-e KEY`) so their values
+//! stay in the client config's `env` map instead of the command line.
+//!
+//! Image tags are mutable, so installs pin the image to the digest it
+//! resolved to (`image:tag@sha256:...`) and record the digest in the
+//! lockfile.
+
+use std::collections::HashMap;
+use std::process::Command;
+
+use anyhow::Context;
+
+use super::RunnerSpec;
+
+/// `docker run` flags that consume the following argument.
+const VALUE_FLAGS: &[&str] = &[
+    "-e",
+    "--env",
+    "--env-file",
+    "-v",
+    "--volume",
+    "--mount",
+    "--network",
+    "--net",
+    "--name",
+    "-p",
+    "--publish",
+    "-w",
+    "--workdir",
+    "-u",
+    "--user",
+    "--entrypoint",
+    "--platform",
+    "--pull",
+    "-l",
+    "--label",
+    "-h",
+    "--hostname",
+    "--add-host",
+    "-m",
+    "--memory",
+    "--cpus",
+];
+
+/// Docker runtime request
+#[derive(Debug, Clone, Default)]
+pub struct DockerRuntimeRequest {
+    /// Image reference, e.g. `ghcr.io/acme/mcp:1.2.3`
+    pub image: String,
+    /// Environment variables forwarded into the container
+    pub env: HashMap<String, String>,
+    /// Volume mounts passed as `-v`
+    pub volumes: Vec<String>,
+    /// Network passed as `--network`
+    pub network: Option<String>,
+    /// Arguments passed to the container entrypoint
+    pub extra_args: Vec<String>,
+}
+
+/// Resolve a Docker runtime request into a RunnerSpec
+pub fn resolve_docker(request: &DockerRuntimeRequest) -> RunnerSpec {
+    let mut args = vec!["run".to_string(), "-i".to_string(), "--rm".to_string()];
+    args.extend(run_options(
+        request.env.keys(),
+        &request.volumes,
+        request.network.as_deref(),
+    ));
+    args.push(request.image.clone());
+    args.extend(request.extra_args.clone());
+
+    RunnerSpec {
+        command: "docker".to_string(),
+        args,
+        env: request.env.clone(),
+    }
+}
+
+/// Build the `docker run` options for a network, volumes and env keys.
+///
+/// Env keys are sorted so rendered configs are stable.
+pub fn run_options<'a>(
+    env_keys: impl IntoIterator<Item = &'a String>,
+    volumes: &[String],
+    network: Option<&str>,
+) -> Vec<String> {
+    let mut options = Vec::new();
+    if let Some(network) = network {
+        options.push("--network".to_string());
+        options.push(network.to_string());
+    }
+    for volume in volumes {
+        options.push("-v".to_string());
+        options.push(volume.clone());
+    }
+    let mut keys: Vec<&String> = env_keys.into_iter().collect();
+    keys.sort();
+    for key in keys {
+        options.push("-e".to_string());
+        options.push(key.clone());
+    }
+    options
+}
+
+/// Insert `options` before the image in existing `docker run` args.
+///
+/// Returns `false` when the args contain no image to insert before.
+pub fn insert_run_options(args: &mut Vec<String>, options: Vec<String>) -> bool {
+    let Some(index) = image_position(args) else {
+        return false;
+    };
+    args.splice(index..index, options);
+    true
+}
+
+/// Position of the image argument in `docker run` args.
+pub fn image_position(args: &[String]) -> Option<usize> {
+    let run = args.iter().position(|a| a == "run")?;
+    let mut index = run + 1;
+    while index < args.len() {
+        let arg = &args[index];
+        if !arg.starts_with('-') {
+            return Some(index);
+        }
+        if !arg.contains('=') && VALUE_FLAGS.contains(&arg.as_str()) {
+            index += 1;
+        }
+        index += 1;
+    }
+    None
+}
+
+/// Combine an image with a version, keeping any tag or digest it already has.
+///
+/// `sha256:` versions are treated as digests.
+pub fn image_reference(image: &str, version: &str) -> String {
+    if image_digest(image).is_some() || image_tag(image).is_some() {
+        image.to_string()
+    } else if version.starts_with("sha256:") {
+        format!("{}@{}", image, version)
+    } else {
+        format!("{}:{}", image, version)
+    }
+}
+
+/// Tag of an image reference, if it has one.
+pub fn image_tag(image: &str) -> Option<&str> {
+    let name = image.split('@').next().unwrap_or(image);
+    // A colon before the last slash is a registry port, not a tag
+    let last = name.rsplit('/').next().unwrap_or(name);
+    last.split_once(':').map(|(_, tag)| tag)
+}
+
+/// Digest of an image reference, if it is pinned.
+pub fn image_digest(image: &str) -> Option<&str> {
+    image.split_once('@').map(|(_, digest)| digest)
+}
+
+/// Pin an image reference to a digest, replacing any digest it had.
+pub fn pin_image(image: &str, digest: &str) -> String {
+    let name = image.split('@').next().unwrap_or(image);
+    format!("{}@{}", name, digest)
+}
+
+/// Resolve an image reference to its registry digest.
+///
+/// Asks the registry what the tag points to now, so a stale local copy of a
+/// mutable tag is not pinned. Without `docker buildx`, pulls the tag and
+/// reads the digest of the refreshed local image.
+pub fn resolve_image_digest(image: &str) -> anyhow::Result<String> {
+    if let Some(digest) = image_digest(image) {
+        return Ok(digest.to_string());
+    }
+    if let Some(digest) = registry_digest(image) {
+        return Ok(digest);
+    }
+
+    let output = Command::new("docker")
+        .args(["pull", "--quiet", image])
+        .output()
+        .context("Failed to run docker")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "docker pull {} failed: {}",
+            image,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    inspect_digest(image)?
+        .ok_or_else(|| anyhow::anyhow!("Image '{}' has no registry digest", image))
+}
+
+/// Digest the registry serves for `image`, via `docker buildx imagetools`.
+fn registry_digest(image: &str) -> Option<String> {
+    let output = Command::new("docker")
+        .args([
+            "buildx",
+            "imagetools",
+            "inspect",
+            "--format",
+            "{{.Manifest.Digest}}",
+            image,
+        ])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    parse_manifest_digest(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Pick the digest from `docker buildx imagetools inspect` output.
+fn parse_manifest_digest(output: &str) -> Option<String> {
+    output
+        .lines()
+        .map(str::trim)
+        .find(|line| line.starts_with("sha256:"))
+        .map(str::to_string)
+}
+
+fn inspect_digest(image: &str) -> anyhow::Result<Option<String>> {
+    let output = Command::new("docker")
+        .args([
+            "image",
+            "inspect",
+            "--format",
+            "{{range .RepoDigests}}{{println .}}{{end}}",
+            image,
+        ])
+        .output()
+        .context("Failed to run docker")?;
+    if !output.status.success() {
+        return Ok(None);
+    }
+    Ok(parse_repo_digests(
+        &String::from_utf8_lossy(&output.stdout),
+        image,
+    ))
+}
+
+/// Pick the digest for `image` from `docker image inspect` RepoDigests.
+///
+/// Falls back to the first digest when none matches the repository, since
+/// Docker may list the image under a normalized name.
+fn parse_repo_digests(output: &str, image: &str) -> Option<String> {
+    let repository = match image_tag(image) {
+        Some(tag) => image.strip_suffix(tag)?.trim_end_matches(':'),
+        None => image,
+    };
+    let digests: Vec<(&str, &str)> = output
+        .lines()
+        .filter_map(|line| line.trim().split_once('@'))
+        .collect();
+    digests
+        .iter()
+        .find(|(repo, _)| *repo == repository)
+        .or_else(|| digests.first())
+        .map(|(_, digest)| digest.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(args: &[&str]) -> Vec<String> {
+        args.iter().map(|a| a.to_string()).collect()
+    }
+
+    #[test]
+    fn test_resolve_docker_orders_options_before_image() {
+        let mut env = HashMap::new();
+        env.insert("TOKEN".to_string(), "secret".to_string());
+        env.insert("API_URL".to_string(), "https://api".to_string());
+        let request = DockerRuntimeRequest {
+            image: "ghcr.io/acme/mcp:1.0".to_string(),
+            env,
+            volumes: vec!["./data:/data:ro".to_string()],
+            network: Some("host".to_string()),
+            extra_args: vec!["--stdio".to_string()],
+        };
+
+        let spec = resolve_docker(&request);
+
+        assert_eq!(spec.command, "docker");
+        assert_eq!(
+            spec.args,
+            strings(&[
+                "run",
+                "-i",
+                "--rm",
+                "--network",
+                "host",
+                "-v",
+                "./data:/data:ro",
+                "-e",
+                "API_URL",
+                "-e",
+                "TOKEN",
+                "ghcr.io/acme/mcp:1.0",
+                "--stdio",
+            ])
+        );
+        // Values stay out of the command line
+        assert_eq!(spec.env.get("TOKEN"), Some(&"secret".to_string()));
+    }
+
+    #[test]
+    fn test_image_position_skips_flag_values() {
+        let args = strings(&["run", "-i", "--rm", "-e", "KEY", "--name=mcp", "img", "x"]);
+        assert_eq!(image_position(&args), Some(6));
+        assert_eq!(image_position(&strings(&["run", "-i"])), None);
+    }
+
+    #[test]
+    fn test_insert_run_options_before_image() {
+        let mut args = strings(&["run", "-i", "--rm", "acme/mcp:1", "--flag"]);
+        assert!(insert_run_options(&mut args, strings(&["-v", "/a:/b"])));
+        assert_eq!(
+            args,
+            strings(&["run", "-i", "--rm", "-v", "/a:/b", "acme/mcp:1", "--flag"])
+        );
+    }
+
+    #[test]
+    fn test_image_reference_keeps_existing_tag() {
+        assert_eq!(image_reference("acme/mcp:2", "1.0"), "acme/mcp:2");
+        assert_eq!(image_reference("acme/mcp", "1.0"), "acme/mcp:1.0");
+        assert_eq!(
+            image_reference("localhost:5000/mcp", "1.0"),
+            "localhost:5000/mcp:1.0"
+        );
+        assert_eq!(
+            image_reference("acme/mcp", "sha256:abc"),
+            "acme/mcp@sha256:abc"
+        );
+    }
+
+    #[test]
+    fn test_pin_image_replaces_digest() {
+        assert_eq!(
+            pin_image("acme/mcp:1.0", "sha256:abc"),
+            "acme/mcp:1.0@sha256:abc"
+        );
+        assert_eq!(
+            pin_image("acme/mcp:1.0@sha256:old", "sha256:new"),
+            "acme/mcp:1.0@sha256:new"
+        );
+        assert_eq!(image_digest("acme/mcp@sha256:abc"), Some("sha256:abc"));
+        assert_eq!(image_tag("acme/mcp:1.0@sha256:abc"), Some("1.0"));
+    }
+
+    #[test]
+    fn test_parse_repo_digests_matches_repository() {
+        let output = "mirror.local/acme/mcp@sha256:111\nacme/mcp@sha256:222\n";
+        assert_eq!(
+            parse_repo_digests(output, "acme/mcp:1.0"),
+            Some("sha256:222".to_string())
+        );
+        assert_eq!(
+            parse_repo_digests("docker.io/library/postgres@sha256:333\n", "postgres"),
+            Some("sha256:333".to_string())
+        );
+        assert_eq!(parse_repo_digests("", "acme/mcp"), None);
+    }
+
+    #[test]
+    fn test_parse_manifest_digest() {
+        assert_eq!(
+            parse_manifest_digest("sha256:444\n"),
+            Some("sha256:444".to_string())
+        );
+        assert_eq!(parse_manifest_digest("\n"), None);
+    }
+}
@@ -21,13 +21,31 @@
 //! | `python`         | Python           | python   |
 //! | `uv`             | Python           | uv       |
 //! | `binary`         | Shell            | direct   |
+//!
+//! ## Docker
+//!
+//! Docker servers resolve to `docker run -i --rm` with volume, network and
+//! env options placed before the image; see [`resolve_docker`].
+//!
+//! ## Python packages
+//!
+//! PyPI packages resolve to `uvx <pkg>==<version>` (`RuntimeKind::Python`)
+//! or `pipx run` (`RuntimeKind::Pipx`) with an isolated cache directory;
+//! see [`python_package_kind`].
 
+mod docker;
 mod mcpb_resolver;
+mod python_package;
 
 use std::collections::HashMap;
 use std::path::PathBuf;
 
+pub use docker::{
+    DockerRuntimeRequest, image_digest, image_position, image_reference, image_tag,
+    insert_run_options, pin_image, resolve_docker, resolve_image_digest, run_options,
+};
 pub use mcpb_resolver::{McpbRuntimeRequest, resolve_mcpb};
+pub use python_package::{python_cache_env, python_package_kind, python_requirement, uvx_to_pipx};
 
 /// High-level runtime kind for MCP servers
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -36,6 +54,7 @@ pub enum RuntimeKind {
     Npx,
     Docker,
     Python,
+    Pipx,
     Shell,
 }
 
@@ -95,8 +114,13 @@ pub fn resolve_runtime(request: &RuntimeRequest) -> anyhow::Result<RunnerSpec> {
     match request.kind {
         RuntimeKind::Bunx => resolve_bunx(request),
         RuntimeKind::Npx => resolve_npx(request),
-        RuntimeKind::Docker => anyhow::bail!("Docker runtime resolution is not implemented yet"),
-        RuntimeKind::Python => anyhow::bail!("Python runtime resolution is not implemented yet"),
+        RuntimeKind::Docker => Ok(resolve_docker(&DockerRuntimeRequest {
+            image: image_reference(&request.package, &request.version),
+            extra_args: request.extra_args.clone(),
+            ..Default::default()
+        })),
+        RuntimeKind::Python => Ok(python_package::resolve_uvx(request)),
+        RuntimeKind::Pipx => Ok(python_package::resolve_pipx(request)),
         RuntimeKind::Shell => anyhow::bail!("Shell runtime resolution is not implemented yet"),
     }
 }
@@ -0,0 +1,175 @@
+//! Python package runtime resolution for MCP servers.
+//!
+//! PyPI packages run through `uvx <pkg>==<version>`, or through
+//! `pipx run --spec <pkg>==<version> <pkg>` when uv is not installed. Each
+//! launcher gets its own cache directory, like the npx/bunx launchers.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use super::{RunnerSpec, RuntimeKind, RuntimeRequest};
+
+/// Pick the Python package launcher available on this machine.
+///
+/// Prefers uvx; falls back to pipx only when uvx is missing and pipx is
+/// present, so an environment without either still renders uvx.
+pub fn python_package_kind() -> RuntimeKind {
+    if !on_path("uvx") && on_path("pipx") {
+        RuntimeKind::Pipx
+    } else {
+        RuntimeKind::Python
+    }
+}
+
+/// Requirement specifier for a package at a version.
+///
+/// `latest` leaves the version open; versions starting with a comparison
+/// operator are passed through as specifiers.
+pub fn python_requirement(package: &str, version: &str) -> String {
+    if version.is_empty() || version == "latest" {
+        package.to_string()
+    } else if version.starts_with(['=', '<', '>', '!', '~']) {
+        format!("{}{}", package, version)
+    } else {
+        format!("{}=={}", package, version)
+    }
+}
+
+/// Cache environment variable for a Python package launcher.
+pub fn python_cache_env(kind: RuntimeKind, cache_dir: &Path) -> Option<(String, String)> {
+    let key = match kind {
+        RuntimeKind::Python => "UV_CACHE_DIR",
+        RuntimeKind::Pipx => "PIPX_HOME",
+        _ => return None,
+    };
+    Some((key.to_string(), cache_dir.to_string_lossy().to_string()))
+}
+
+/// Rewrite a `uvx <requirement> [args...]` launch for pipx.
+pub fn uvx_to_pipx(args: &[String]) -> Option<Vec<String>> {
+    let (requirement, rest) = args.split_first()?;
+    let mut pipx = vec![
+        "run".to_string(),
+        "--spec".to_string(),
+        requirement.clone(),
+        app_name(requirement).to_string(),
+    ];
+    pipx.extend(rest.iter().cloned());
+    Some(pipx)
+}
+
+pub(super) fn resolve_uvx(request: &RuntimeRequest) -> RunnerSpec {
+    let mut args = vec![python_requirement(&request.package, &request.version)];
+    args.extend(request.extra_args.clone());
+
+    RunnerSpec {
+        command: "uvx".to_string(),
+        args,
+        env: cache_env(RuntimeKind::Python, &request.cache_dir),
+    }
+}
+
+pub(super) fn resolve_pipx(request: &RuntimeRequest) -> RunnerSpec {
+    let mut args = vec![
+        "run".to_string(),
+        "--spec".to_string(),
+        python_requirement(&request.package, &request.version),
+        app_name(&request.package).to_string(),
+    ];
+    args.extend(request.extra_args.clone());
+
+    RunnerSpec {
+        command: "pipx".to_string(),
+        args,
+        env: cache_env(RuntimeKind::Pipx, &request.cache_dir),
+    }
+}
+
+fn cache_env(kind: RuntimeKind, cache_dir: &Path) -> HashMap<String, String> {
+    python_cache_env(kind, cache_dir).into_iter().collect()
+}
+
+/// Executable name for a requirement: the package without extras or version.
+fn app_name(requirement: &str) -> &str {
+    requirement
+        .split(['=', '<', '>', '!', '~', '[', ';', ' '])
+        .next()
+        .unwrap_or(requirement)
+}
+
+fn on_path(program: &str) -> bool {
+    let Some(path) = std::env::var_os("PATH") else {
+        return false;
+    };
+    std::env::split_paths(&path).any(|dir| {
+        dir.join(program).is_file()
+            || (cfg!(windows) && dir.join(format!("{}.exe", program)).is_file())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn request(package: &str, version: &str) -> RuntimeRequest {
+        RuntimeRequest {
+            kind: RuntimeKind::Python,
+            package: package.to_string(),
+            version: version.to_string(),
+            cache_dir: PathBuf::from("/tmp/sift-cache"),
+            extra_args: vec!["--readonly".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_python_requirement() {
+        assert_eq!(python_requirement("pkg", "1.2.3"), "pkg==1.2.3");
+        assert_eq!(python_requirement("pkg", "latest"), "pkg");
+        assert_eq!(python_requirement("pkg", ">=1.2"), "pkg>=1.2");
+        assert_eq!(python_requirement("pkg", "~=1.2"), "pkg~=1.2");
+    }
+
+    #[test]
+    fn test_resolve_uvx_isolates_cache() {
+        let spec = resolve_uvx(&request("mcp-server-fetch", "2025.1.0"));
+
+        assert_eq!(spec.command, "uvx");
+        assert_eq!(spec.args, vec!["mcp-server-fetch==2025.1.0", "--readonly"]);
+        assert_eq!(
+            spec.env.get("UV_CACHE_DIR"),
+            Some(&"/tmp/sift-cache".to_string())
+        );
+    }
+
+    #[test]
+    fn test_resolve_pipx_runs_spec() {
+        let spec = resolve_pipx(&request("mcp-server-fetch[cli]", "1.0"));
+
+        assert_eq!(spec.command, "pipx");
+        assert_eq!(
+            spec.args,
+            vec![
+                "run",
+                "--spec",
+                "mcp-server-fetch[cli]==1.0",
+                "mcp-server-fetch",
+                "--readonly"
+            ]
+        );
+        assert_eq!(
+            spec.env.get("PIPX_HOME"),
+            Some(&"/tmp/sift-cache".to_string())
+        );
+    }
+
+    #[test]
+    fn test_uvx_to_pipx() {
+        let args = vec!["pg-mcp==1.2.3".to_string(), "--dsn".to_string()];
+        assert_eq!(
+            uvx_to_pipx(&args).unwrap(),
+            vec!["run", "--spec", "pg-mcp==1.2.3", "pg-mcp", "--dsn"]
+        );
+        assert_eq!(uvx_to_pipx(&[]), None);
+    }
+}
@@ -0,0 +1,130 @@
+//! Secret providers backed by external CLI tools.
+
+use std::process::{Command, Stdio};
+
+use super::SecretProvider;
+
+/// Looks secrets up by running a command.
+///
+/// `{path}` in the arguments is replaced by the path from the reference, and
+/// the command's standard output (without the trailing newline) is the
+/// secret.
+#[derive(Debug, Clone)]
+pub struct CommandProvider {
+    id: String,
+    program: String,
+    args: Vec<String>,
+    first_line: bool,
+}
+
+impl CommandProvider {
+    pub fn new(
+        id: impl Into<String>,
+        program: impl Into<String>,
+        args: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            program: program.into(),
+            args: args.into_iter().map(Into::into).collect(),
+            first_line: false,
+        }
+    }
+
+    /// Keep only the first line of output.
+    pub fn with_first_line(mut self) -> Self {
+        self.first_line = true;
+        self
+    }
+
+    /// `secret:pass/<name>` runs `pass show <name>`.
+    pub fn pass() -> Self {
+        Self::new("pass", "pass", ["show", "{path}"]).with_first_line()
+    }
+
+    /// `secret:op/<vault>/<item>/<field>` runs `op read op://<vault>/<item>/<field>`.
+    pub fn one_password() -> Self {
+        Self::new("op", "op", ["read", "--no-newline", "op://{path}"])
+    }
+
+    /// `secret:keyring/<account>` reads the `sift` service entry for
+    /// `<account>` from the OS keychain.
+    pub fn keyring() -> Self {
+        if cfg!(target_os = "macos") {
+            Self::new(
+                "keyring",
+                "security",
+                ["find-generic-password", "-s", "sift", "-a", "{path}", "-w"],
+            )
+        } else {
+            Self::new(
+                "keyring",
+                "secret-tool",
+                ["lookup", "service", "sift", "account", "{path}"],
+            )
+        }
+    }
+}
+
+impl SecretProvider for CommandProvider {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn get(&self, path: &str) -> anyhow::Result<String> {
+        let output = Command::new(&self.program)
+            .args(self.args.iter().map(|arg| arg.replace("{path}", path)))
+            .stdin(Stdio::null())
+            .output()
+            .map_err(|err| match err.kind() {
+                std::io::ErrorKind::NotFound => {
+                    anyhow::anyhow!("'{}' is not installed or not on PATH", self.program)
+                }
+                _ => anyhow::Error::new(err).context(format!("Failed to run '{}'", self.program)),
+            })?;
+        if !output.status.success() {
+            anyhow::bail!(
+                "'{}' exited with {}: {}",
+                self.program,
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+
+        let stdout = String::from_utf8(output.stdout)
+            .map_err(|_| anyhow::anyhow!("'{}' printed a non-UTF-8 secret", self.program))?;
+        let secret = if self.first_line {
+            stdout.lines().next().unwrap_or_default()
+        } else {
+            stdout.trim_end_matches(['\r', '\n'])
+        };
+        if secret.is_empty() {
+            anyhow::bail!("'{}' returned an empty secret for '{}'", self.program, path);
+        }
+        Ok(secret.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_command_provider_reads_stdout() {
+        let provider = CommandProvider::new("stub", "printf", ["%s\\nsecond line\\n", "{path}"]);
+        assert_eq!(provider.get("db/url").unwrap(), "db/url\nsecond line");
+
+        let provider = provider.with_first_line();
+        assert_eq!(provider.get("db/url").unwrap(), "db/url");
+    }
+
+    #[test]
+    fn test_command_provider_reports_failures() {
+        let missing = CommandProvider::new("stub", "sift-test-no-such-tool", ["{path}"]);
+        let err = missing.get("x").unwrap_err();
+        assert!(err.to_string().contains("not installed"));
+
+        let failing = CommandProvider::new("stub", "false", Vec::<String>::new());
+        assert!(failing.get("x").unwrap_err().to_string().contains("exited"));
+    }
+}
@@ -0,0 +1,283 @@
+//! Encrypted secret file in Sift's global config directory.
+//!
+//! Secrets live in `secrets.enc`, a ChaCha20-Poly1305 encrypted JSON map,
+//! next to the random 256-bit key in `secrets.key`. Both files are created
+//! on the first write with owner-only permissions. Keeping the key in a
+//! separate file means the secret file itself is safe to back up or sync.
+
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use ring::aead::{Aad, CHACHA20_POLY1305, LessSafeKey, NONCE_LEN, Nonce, UnboundKey};
+use ring::rand::{SecureRandom, SystemRandom};
+
+use super::SecretProvider;
+
+const MAGIC: &[u8] = b"SIFTSEC1";
+const KEY_LEN: usize = 32;
+
+/// Secret provider `file`, backed by `secrets.enc` and `secrets.key`.
+#[derive(Debug, Clone)]
+pub struct FileSecretStore {
+    path: PathBuf,
+    key_path: PathBuf,
+}
+
+impl FileSecretStore {
+    /// Store in `dir` (normally `~/.config/sift`).
+    pub fn new(dir: &Path) -> Self {
+        Self {
+            path: dir.join("secrets.enc"),
+            key_path: dir.join("secrets.key"),
+        }
+    }
+
+    /// Store in the default global config directory.
+    pub fn with_defaults() -> anyhow::Result<Self> {
+        let dir = match dirs::config_dir() {
+            Some(dir) => dir.join("sift"),
+            None => dirs::home_dir()
+                .ok_or_else(|| anyhow::anyhow!("Could not determine home directory"))?
+                .join(".config")
+                .join("sift"),
+        };
+        Ok(Self::new(&dir))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Names of the stored secrets, sorted.
+    pub fn names(&self) -> anyhow::Result<Vec<String>> {
+        Ok(self.load()?.into_keys().collect())
+    }
+
+    /// Store `value` under `name`, replacing any previous value.
+    pub fn set(&self, name: &str, value: &str) -> anyhow::Result<()> {
+        if name.is_empty() {
+            anyhow::bail!("Secret name must not be empty");
+        }
+        let mut secrets = self.load()?;
+        secrets.insert(name.to_string(), value.to_string());
+        self.save(&secrets)
+    }
+
+    /// Remove `name`. Returns false if it was not stored.
+    pub fn remove(&self, name: &str) -> anyhow::Result<bool> {
+        let mut secrets = self.load()?;
+        if secrets.remove(name).is_none() {
+            return Ok(false);
+        }
+        self.save(&secrets)?;
+        Ok(true)
+    }
+
+    fn load(&self) -> anyhow::Result<BTreeMap<String, String>> {
+        if !self.path.exists() {
+            return Ok(BTreeMap::new());
+        }
+        let data = std::fs::read(&self.path)
+            .with_context(|| format!("Failed to read {}", self.path.display()))?;
+        let key = self.read_key()?;
+
+        let invalid = || anyhow::anyhow!("{} is not a Sift secret file", self.path.display());
+        let rest = data.strip_prefix(MAGIC).ok_or_else(invalid)?;
+        if rest.len() < NONCE_LEN {
+            return Err(invalid());
+        }
+        let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+        let nonce = Nonce::try_assume_unique_for_key(nonce).map_err(|_| invalid())?;
+        let mut buffer = ciphertext.to_vec();
+        let plaintext = key
+            .open_in_place(nonce, Aad::from(MAGIC), &mut buffer)
+            .map_err(|_| {
+                anyhow::anyhow!(
+                    "Failed to decrypt {}: wrong key in {} or corrupted file",
+                    self.path.display(),
+                    self.key_path.display()
+                )
+            })?;
+        serde_json::from_slice(plaintext)
+            .with_context(|| format!("Failed to parse {}", self.path.display()))
+    }
+
+    fn save(&self, secrets: &BTreeMap<String, String>) -> anyhow::Result<()> {
+        let key = if self.key_path.exists() {
+            self.read_key()?
+        } else {
+            self.create_key()?
+        };
+
+        let mut nonce = [0u8; NONCE_LEN];
+        SystemRandom::new()
+            .fill(&mut nonce)
+            .map_err(|_| anyhow::anyhow!("Failed to generate a nonce"))?;
+        let mut buffer = serde_json::to_vec(secrets)?;
+        key.seal_in_place_append_tag(
+            Nonce::assume_unique_for_key(nonce),
+            Aad::from(MAGIC),
+            &mut buffer,
+        )
+        .map_err(|_| anyhow::anyhow!("Failed to encrypt secrets"))?;
+
+        let mut data = Vec::with_capacity(MAGIC.len() + NONCE_LEN + buffer.len());
+        data.extend_from_slice(MAGIC);
+        data.extend_from_slice(&nonce);
+        data.extend_from_slice(&buffer);
+        write_private(&self.path, &data)
+    }
+
+    fn read_key(&self) -> anyhow::Result<LessSafeKey> {
+        let bytes = std::fs::read(&self.key_path)
+            .with_context(|| format!("Failed to read secret key {}", self.key_path.display()))?;
+        if bytes.len() != KEY_LEN {
+            anyhow::bail!(
+                "Secret key {} is not {} bytes",
+                self.key_path.display(),
+                KEY_LEN
+            );
+        }
+        to_key(&bytes)
+    }
+
+    fn create_key(&self) -> anyhow::Result<LessSafeKey> {
+        let mut bytes = [0u8; KEY_LEN];
+        SystemRandom::new()
+            .fill(&mut bytes)
+            .map_err(|_| anyhow::anyhow!("Failed to generate a secret key"))?;
+        write_private(&self.key_path, &bytes)?;
+        to_key(&bytes)
+    }
+}
+
+impl SecretProvider for FileSecretStore {
+    fn id(&self) -> &str {
+        "file"
+    }
+
+    fn get(&self, path: &str) -> anyhow::Result<String> {
+        self.load()?.remove(path).ok_or_else(|| {
+            anyhow::anyhow!(
+                "Secret '{}' not found in {}. Add it with `sift secret set {}`",
+                path,
+                self.path.display(),
+                path
+            )
+        })
+    }
+}
+
+fn to_key(bytes: &[u8]) -> anyhow::Result<LessSafeKey> {
+    let key = UnboundKey::new(&CHACHA20_POLY1305, bytes)
+        .map_err(|_| anyhow::anyhow!("Invalid secret key"))?;
+    Ok(LessSafeKey::new(key))
+}
+
+/// Write a file readable only by its owner.
+///
+/// The data goes to a temporary file that is created with owner-only
+/// permissions and then renamed over `path`, so it is never readable by
+/// others, even briefly.
+fn write_private(path: &Path, data: &[u8]) -> anyhow::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    let mut tmp_name = path.file_name().unwrap_or_default().to_os_string();
+    tmp_name.push(".tmp");
+    let tmp = path.with_file_name(tmp_name);
+    // A leftover temporary file may have other permissions; start afresh
+    match std::fs::remove_file(&tmp) {
+        Err(err) if err.kind() != std::io::ErrorKind::NotFound => {
+            return Err(err).with_context(|| format!("Failed to remove {}", tmp.display()));
+        }
+        _ => {}
+    }
+
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options
+        .open(&tmp)
+        .with_context(|| format!("Failed to create {}", tmp.display()))?;
+    file.write_all(data)
+        .and_then(|()| file.sync_all())
+        .with_context(|| format!("Failed to write {}", tmp.display()))?;
+    drop(file);
+    std::fs::rename(&tmp, path).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_file_store_round_trip() {
+        let temp = TempDir::new().unwrap();
+        let store = FileSecretStore::new(temp.path());
+        assert!(store.names().unwrap().is_empty());
+
+        store.set("db/url", "postgres://user:pw@db/app").unwrap();
+        store.set("github", "ghp_123").unwrap();
+
+        assert_eq!(store.get("db/url").unwrap(), "postgres://user:pw@db/app");
+        assert_eq!(store.names().unwrap(), vec!["db/url", "github"]);
+        assert!(store.get("missing").is_err());
+
+        // The file holds ciphertext only
+        let raw = std::fs::read(store.path()).unwrap();
+        assert!(!String::from_utf8_lossy(&raw).contains("ghp_123"));
+
+        assert!(store.remove("github").unwrap());
+        assert!(!store.remove("github").unwrap());
+        assert_eq!(store.names().unwrap(), vec!["db/url"]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_file_store_files_are_owner_only() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp = TempDir::new().unwrap();
+        let store = FileSecretStore::new(temp.path());
+        // A stale temporary file with open permissions is not reused
+        std::fs::write(temp.path().join("secrets.enc.tmp"), b"stale").unwrap();
+        std::fs::set_permissions(
+            temp.path().join("secrets.enc.tmp"),
+            std::fs::Permissions::from_mode(0o644),
+        )
+        .unwrap();
+
+        store.set("token", "abc").unwrap();
+
+        for name in ["secrets.enc", "secrets.key"] {
+            let mode = std::fs::metadata(temp.path().join(name))
+                .unwrap()
+                .permissions()
+                .mode();
+            assert_eq!(mode & 0o777, 0o600, "{} should be owner-only", name);
+        }
+        assert!(!temp.path().join("secrets.enc.tmp").exists());
+        assert_eq!(store.get("token").unwrap(), "abc");
+    }
+
+    #[test]
+    fn test_file_store_rejects_wrong_key() {
+        let temp = TempDir::new().unwrap();
+        let store = FileSecretStore::new(temp.path());
+        store.set("token", "abc").unwrap();
+
+        std::fs::write(temp.path().join("secrets.key"), [7u8; KEY_LEN]).unwrap();
+
+        let err = store.get("token").unwrap_err();
+        assert!(err.to_string().contains("Failed to decrypt"));
+    }
+}
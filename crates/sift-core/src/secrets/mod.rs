@@ -0,0 +1,129 @@
+//! Secret references in MCP server `env` and `headers`.
+//!
+//! A value of the form `secret:<provider>/<path>` is looked up through a
+//! [`SecretProvider`] when a client config is written, so `sift.toml` (and
+//! the project-local overrides in the global config) never hold the secret
+//! itself:
+//!
+//! ```toml
+//! [mcp.postgres]
+//! source = "registry:postgres-mcp"
+//! env = { DATABASE_URL = "secret:pass/db/url" }
+//! ```
+//!
+//! Built-in providers are `file` (Sift's encrypted secret file), `pass`,
+//! `op` (1Password CLI) and `keyring` (the OS keychain).
+
+pub mod command;
+pub mod file;
+
+use std::path::Path;
+use std::sync::Arc;
+
+use anyhow::Context;
+
+pub use command::CommandProvider;
+pub use file::FileSecretStore;
+
+/// Prefix that marks a value as a secret reference.
+pub const SECRET_PREFIX: &str = "secret:";
+
+/// A backend that can look up secrets by path.
+pub trait SecretProvider: Send + Sync + std::fmt::Debug {
+    /// Identifier used in references (`secret:<id>/<path>`)
+    fn id(&self) -> &str;
+
+    /// Fetch the secret stored at `path`.
+    fn get(&self, path: &str) -> anyhow::Result<String>;
+}
+
+/// Split `secret:<provider>/<path>` into provider and path.
+pub fn parse_reference(value: &str) -> Option<(&str, &str)> {
+    let (provider, path) = value.strip_prefix(SECRET_PREFIX)?.split_once('/')?;
+    (!provider.is_empty() && !path.is_empty()).then_some((provider, path))
+}
+
+/// The secret providers available when rendering client configs.
+#[derive(Debug, Clone, Default)]
+pub struct SecretProviders {
+    providers: Vec<Arc<dyn SecretProvider>>,
+}
+
+impl SecretProviders {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Built-in providers, with the secret file kept in `global_config_dir`.
+    pub fn with_defaults(global_config_dir: &Path) -> Self {
+        let mut providers = Self::new();
+        providers.register(Arc::new(FileSecretStore::new(global_config_dir)));
+        providers.register(Arc::new(CommandProvider::pass()));
+        providers.register(Arc::new(CommandProvider::one_password()));
+        providers.register(Arc::new(CommandProvider::keyring()));
+        providers
+    }
+
+    /// Register a provider, replacing any provider with the same id.
+    pub fn register(&mut self, provider: Arc<dyn SecretProvider>) {
+        self.providers.retain(|p| p.id() != provider.id());
+        self.providers.push(provider);
+    }
+
+    pub fn get(&self, id: &str) -> Option<&dyn SecretProvider> {
+        self.providers
+            .iter()
+            .find(|p| p.id() == id)
+            .map(|p| p.as_ref())
+    }
+
+    /// Resolve `value` if it is a secret reference; `None` otherwise.
+    pub fn resolve(&self, value: &str) -> anyhow::Result<Option<String>> {
+        let Some((id, path)) = parse_reference(value) else {
+            return Ok(None);
+        };
+        let provider = self.get(id).ok_or_else(|| {
+            let known: Vec<&str> = self.providers.iter().map(|p| p.id()).collect();
+            anyhow::anyhow!(
+                "Unknown secret provider '{}' in '{}'. Known providers: {}",
+                id,
+                value,
+                known.join(", ")
+            )
+        })?;
+        provider
+            .get(path)
+            .with_context(|| format!("Failed to resolve secret '{}'", value))
+            .map(Some)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_reference() {
+        assert_eq!(
+            parse_reference("secret:pass/db/url"),
+            Some(("pass", "db/url"))
+        );
+        assert_eq!(parse_reference("secret:pass"), None);
+        assert_eq!(parse_reference("secret:/db"), None);
+        assert_eq!(parse_reference("plain"), None);
+    }
+
+    #[test]
+    fn test_resolve_uses_registered_provider() {
+        let mut providers = SecretProviders::new();
+        providers.register(Arc::new(CommandProvider::new("stub", "echo", ["{path}"])));
+
+        assert_eq!(
+            providers.resolve("secret:stub/db/url").unwrap().as_deref(),
+            Some("db/url")
+        );
+        assert_eq!(providers.resolve("postgres://localhost").unwrap(), None);
+        let err = providers.resolve("secret:vault/db").unwrap_err();
+        assert!(err.to_string().contains("Unknown secret provider 'vault'"));
+    }
+}
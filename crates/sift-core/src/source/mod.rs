@@ -10,9 +10,9 @@ mod resolver;
 mod spec;
 
 pub use resolver::{
-    McpRegistryResolution, RegistryMetadata, RegistryResolution, ResolvedInput, SourceResolver,
-    derive_name_from_git_source, derive_name_from_path, is_git_like, is_local_path,
-    normalize_git_source,
+    McpRegistryResolution, RegistryEntryNotFound, RegistryMetadata, RegistryResolution,
+    ResolvedInput, SourceResolver, derive_name_from_git_source, derive_name_from_path, is_git_like,
+    is_local_path, normalize_git_source,
 };
 pub use spec::{LocalSpec, ResolvedSource};
 
@@ -9,6 +9,9 @@ use anyhow::Context;
 use crate::git::{GitFetcher, GitSpec};
 use crate::mcpb::{derive_name_from_mcpb_url, is_mcpb_url, normalize_mcpb_source};
 use crate::registry::marketplace::MarketplaceAdapter;
+use crate::registry::sift::{
+    SiftRegistryClient, SiftRegistryIndex, SiftSkillEntry, split_package_version,
+};
 use crate::registry::{RegistryConfig, RegistryType};
 
 use super::spec::{LocalSpec, McpbSpec, ResolvedSource};
@@ -31,6 +34,25 @@ pub struct ResolvedInput {
     pub warnings: Vec<String>,
 }
 
+/// A registry that was reached but does not list the requested entry.
+///
+/// Returned inside the `anyhow::Error` of MCP registry resolution so callers
+/// can tell an absent entry from network, auth or parse failures.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegistryEntryNotFound {
+    /// "Plugin" for marketplaces, "Package" for Sift registries
+    pub kind: &'static str,
+    pub name: String,
+}
+
+impl std::fmt::Display for RegistryEntryNotFound {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} not found in registry: {}", self.kind, self.name)
+    }
+}
+
+impl std::error::Error for RegistryEntryNotFound {}
+
 /// Metadata about a registry resolution.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RegistryMetadata {
@@ -211,7 +233,8 @@ impl SourceResolver {
                 }
             }
             RegistryType::Sift => {
-                anyhow::bail!("Sift registry resolution not yet implemented")
+                let index = self.fetch_sift_index(config)?;
+                self.resolve_sift_skill(&index, &registry_key, skill_name)
             }
         }
     }
@@ -509,7 +532,8 @@ impl SourceResolver {
                 self.expand_nested_marketplace(config, &registry_key, skill_name)
             }
             RegistryType::Sift => {
-                anyhow::bail!("Sift registry resolution not yet implemented")
+                let index = self.fetch_sift_index(config)?;
+                self.expand_sift_skill(&index, &registry_key, skill_name)
             }
         }
     }
@@ -779,6 +803,19 @@ impl SourceResolver {
     pub fn resolve_mcp_registry(
         &self,
         registry_part: &str,
+    ) -> anyhow::Result<Option<Vec<McpRegistryResolution>>> {
+        self.resolve_mcp_registry_version(registry_part, None)
+    }
+
+    /// Resolve an MCP server from a registry source string at a requested version.
+    ///
+    /// The version is only honored by registries that support version pinning
+    /// (`type = "sift"`); it may also be given inline as `registry:key/name@1.2.3`.
+    /// A registry without the entry fails with [`RegistryEntryNotFound`].
+    pub fn resolve_mcp_registry_version(
+        &self,
+        registry_part: &str,
+        version: Option<&str>,
     ) -> anyhow::Result<Option<Vec<McpRegistryResolution>>> {
         let (registry_key, plugin_name) = self.parse_registry_source(registry_part)?;
 
@@ -809,7 +846,10 @@ impl SourceResolver {
                 let manifest = MarketplaceAdapter::parse(&manifest_content)?;
                 let plugin =
                     MarketplaceAdapter::find_plugin(&manifest, plugin_name).ok_or_else(|| {
-                        anyhow::anyhow!("Plugin not found in registry: {}", plugin_name)
+                        RegistryEntryNotFound {
+                            kind: "Plugin",
+                            name: plugin_name.to_string(),
+                        }
                     })?;
 
                 let resolutions = self.resolve_mcp_from_plugin(plugin, &registry_key)?;
@@ -820,11 +860,178 @@ impl SourceResolver {
                 }
             }
             RegistryType::Sift => {
-                anyhow::bail!("Sift registry MCP resolution not yet implemented")
+                self.resolve_sift_mcp(config, &registry_key, plugin_name, version)
             }
         }
     }
 
+    /// Fetch the index of a Sift registry.
+    fn fetch_sift_index(&self, config: &RegistryConfig) -> anyhow::Result<SiftRegistryIndex> {
+        let url = config
+            .url
+            .clone()
+            .ok_or_else(|| anyhow::anyhow!("Sift registry missing url field"))?;
+        SiftRegistryClient::new(url).fetch_index()
+    }
+
+    /// Resolve a skill from a Sift registry.
+    ///
+    /// Accepts `name`, `name@version` and `group/child` references.
+    fn resolve_sift_skill(
+        &self,
+        index: &SiftRegistryIndex,
+        registry_key: &str,
+        reference: &str,
+    ) -> anyhow::Result<RegistryResolution> {
+        let (name, requested) = split_package_version(reference);
+
+        if index.find_package(name).is_none()
+            && let Some((parent, child)) = name.split_once('/')
+        {
+            return self
+                .expand_sift_skill(index, registry_key, &with_version(parent, requested))?
+                .into_iter()
+                .find(|r| r.metadata.aliases.first().map(String::as_str) == Some(child))
+                .ok_or_else(|| {
+                    anyhow::anyhow!("Package '{}' does not contain skill '{}'", parent, child)
+                });
+        }
+
+        let package = index
+            .find_package(name)
+            .ok_or_else(|| anyhow::anyhow!("Package not found in registry: {}", name))?;
+        let (version, published) = package.select_version(requested)?;
+        let skill = published.skill.as_ref().ok_or_else(|| {
+            anyhow::anyhow!("Package '{}@{}' does not provide a skill", name, version)
+        })?;
+
+        let subdir = skill.path.clone().or_else(|| skill.skills.first().cloned());
+        Ok(RegistryResolution {
+            git_spec: sift_skill_git_spec(skill, subdir.as_deref())?,
+            metadata: RegistryMetadata {
+                original_source: format!("registry:{}/{}", registry_key, name),
+                registry_key: registry_key.to_string(),
+                skill_name: name.to_string(),
+                marketplace_version: version.to_string(),
+                aliases: vec![name.to_string()],
+                parent_plugin: None,
+                is_group: false,
+            },
+        })
+    }
+
+    /// Resolve a skill from a Sift registry, expanding group packages.
+    ///
+    /// A package whose skill entry lists more than one path in `skills` expands
+    /// into a group alias followed by one resolution per path.
+    fn expand_sift_skill(
+        &self,
+        index: &SiftRegistryIndex,
+        registry_key: &str,
+        reference: &str,
+    ) -> anyhow::Result<Vec<RegistryResolution>> {
+        let (name, requested) = split_package_version(reference);
+        let Some(package) = index.find_package(name) else {
+            // May be a `group/child` reference
+            return Ok(vec![self.resolve_sift_skill(
+                index,
+                registry_key,
+                reference,
+            )?]);
+        };
+        let (version, published) = package.select_version(requested)?;
+        let skill = published.skill.as_ref().ok_or_else(|| {
+            anyhow::anyhow!("Package '{}@{}' does not provide a skill", name, version)
+        })?;
+
+        if skill.skills.len() <= 1 {
+            return Ok(vec![self.resolve_sift_skill(
+                index,
+                registry_key,
+                reference,
+            )?]);
+        }
+
+        let mut resolutions = vec![RegistryResolution {
+            git_spec: sift_skill_git_spec(skill, skill.path.as_deref())?,
+            metadata: RegistryMetadata {
+                original_source: format!("registry:{}/{}", registry_key, name),
+                registry_key: registry_key.to_string(),
+                skill_name: name.to_string(),
+                marketplace_version: version.to_string(),
+                aliases: vec![name.to_string()],
+                parent_plugin: None,
+                is_group: true,
+            },
+        }];
+
+        for skill_path in &skill.skills {
+            let trimmed = skill_path.trim_start_matches("./").trim_end_matches('/');
+            let short_name = trimmed
+                .split('/')
+                .next_back()
+                .unwrap_or(trimmed)
+                .to_string();
+            let canonical_name = format!("{}/{}", name, short_name);
+            let subdir = match &skill.path {
+                Some(base) => format!("{}/{}", base.trim_end_matches('/'), trimmed),
+                None => trimmed.to_string(),
+            };
+
+            resolutions.push(RegistryResolution {
+                git_spec: sift_skill_git_spec(skill, Some(&subdir))?,
+                metadata: RegistryMetadata {
+                    original_source: format!("registry:{}/{}", registry_key, canonical_name),
+                    registry_key: registry_key.to_string(),
+                    skill_name: canonical_name.clone(),
+                    marketplace_version: version.to_string(),
+                    aliases: vec![short_name, canonical_name],
+                    parent_plugin: Some(name.to_string()),
+                    is_group: false,
+                },
+            });
+        }
+
+        Ok(resolutions)
+    }
+
+    /// Resolve an MCP server from a Sift registry.
+    ///
+    /// Returns `None` if the selected version does not provide an MCP server.
+    fn resolve_sift_mcp(
+        &self,
+        config: &RegistryConfig,
+        registry_key: &str,
+        reference: &str,
+        version: Option<&str>,
+    ) -> anyhow::Result<Option<Vec<McpRegistryResolution>>> {
+        let (name, inline_version) = split_package_version(reference);
+        let requested = inline_version.or(version);
+
+        let index = self.fetch_sift_index(config)?;
+        let package = index
+            .find_package(name)
+            .ok_or_else(|| RegistryEntryNotFound {
+                kind: "Package",
+                name: name.to_string(),
+            })?;
+        let (resolved_version, published) = package.select_version(requested)?;
+
+        let Some(mcp) = &published.mcp else {
+            return Ok(None);
+        };
+
+        let source = format!("registry:{}/{}", registry_key, name);
+        let mcp_config = mcp.to_mcp_config(&source, resolved_version, package.default_runtime)?;
+
+        Ok(Some(vec![McpRegistryResolution {
+            mcp_config,
+            registry_key: registry_key.to_string(),
+            plugin_name: name.to_string(),
+            plugin_version: resolved_version.to_string(),
+        }]))
+    }
+
     /// Detect name collisions across plugin aliases.
     ///
     /// Returns an error if the same alias maps to different plugins.
@@ -1026,6 +1233,11 @@ impl SourceResolver {
         )
     }
 
+    /// Whether any registry is configured.
+    pub fn has_registries(&self) -> bool {
+        !self.registries.is_empty()
+    }
+
     /// Get project root path (for testing and external access).
     pub fn project_root(&self) -> &Path {
         &self.project_root
@@ -1078,6 +1290,36 @@ impl SourceResolver {
     }
 }
 
+/// Build the git spec for a Sift registry skill entry.
+fn sift_skill_git_spec(skill: &SiftSkillEntry, subdir: Option<&str>) -> anyhow::Result<GitSpec> {
+    if !skill.source.starts_with("github:") && !skill.source.starts_with("git:") {
+        anyhow::bail!(
+            "Unsupported skill source in Sift registry: {} (expected 'github:' or 'git:')",
+            skill.source
+        );
+    }
+    let mut spec = GitSpec::parse(&skill.source)?;
+    if let Some(reference) = &skill.reference {
+        spec.reference = Some(reference.clone());
+    }
+    if let Some(subdir) = subdir {
+        let subdir = subdir.trim_start_matches("./").trim_matches('/');
+        spec.subdir = Some(match spec.subdir.take() {
+            Some(base) => format!("{}/{}", base.trim_end_matches('/'), subdir),
+            None => subdir.to_string(),
+        });
+    }
+    Ok(spec)
+}
+
+/// Re-attach an optional version to a package name.
+fn with_version(name: &str, version: Option<&str>) -> String {
+    match version {
+        Some(v) => format!("{}@{}", name, v),
+        None => name.to_string(),
+    }
+}
+
 // ============================================================================
 // Public Helper Functions
 // ============================================================================
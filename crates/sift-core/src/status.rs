@@ -11,13 +11,7 @@ use std::path::{Path, PathBuf};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
-use crate::client::amp::AmpClient;
-use crate::client::claude_code::ClaudeCodeClient;
-use crate::client::codex::CodexClient;
-use crate::client::droid::DroidClient;
-use crate::client::gemini_cli::GeminiCliClient;
-use crate::client::opencode::OpenCodeClient;
-use crate::client::vscode::VsCodeClient;
+use crate::client::registry::ClientRegistry;
 use crate::client::{ClientAdapter, ClientContext, PathRoot};
 use crate::config::SiftConfig;
 use crate::config::client_config::{ConfigFormat, serializer_for_format};
@@ -139,6 +133,8 @@ impl SkillStatus {
 pub struct ClientStatus {
     pub id: String,
     pub enabled: bool,
+    /// Whether the client's config directory exists for this user
+    pub installed: bool,
     pub mcp_scopes: Vec<ConfigScope>,
     pub skill_scopes: Vec<ConfigScope>,
     pub supports_symlinks: bool,
@@ -345,6 +341,18 @@ pub fn verify_mcp_deployment(
     json_path: &[&str],
     entry_name: &str,
     ownership: &HashMap<String, String>,
+) -> DeploymentIntegrity {
+    verify_mcp_deployment_preserving(config_content, json_path, entry_name, ownership, &[])
+}
+
+/// Like [`verify_mcp_deployment`], ignoring `preserved` fields the client's
+/// own UI changes.
+pub fn verify_mcp_deployment_preserving(
+    config_content: &Value,
+    json_path: &[&str],
+    entry_name: &str,
+    ownership: &HashMap<String, String>,
+    preserved: &[String],
 ) -> DeploymentIntegrity {
     // Check if we have ownership record
     let expected_hash = match ownership.get(entry_name) {
@@ -366,7 +374,7 @@ pub fn verify_mcp_deployment(
         None => return DeploymentIntegrity::Missing,
     };
 
-    let actual_hash = crate::config::ownership::hash_json(entry_value);
+    let actual_hash = crate::config::ownership::hash_entry(entry_value, preserved);
     if actual_hash == *expected_hash {
         DeploymentIntegrity::Ok
     } else {
@@ -485,23 +493,13 @@ pub fn collect_status_with_paths(
     let lockfile_service =
         LockfileService::new(state_dir.to_path_buf(), Some(project_root.to_path_buf()));
 
-    // 4. Get registered clients
-    let clients: Vec<Box<dyn ClientAdapter>> = vec![
-        Box::new(AmpClient::new()),
-        Box::new(ClaudeCodeClient::new()),
-        Box::new(CodexClient::new()),
-        Box::new(DroidClient::new()),
-        Box::new(GeminiCliClient::new()),
-        Box::new(OpenCodeClient::new()),
-        Box::new(VsCodeClient::new()),
-    ];
+    // 4. Get registered clients, applying the [clients] table
+    let client_registry = ClientRegistry::with_config_clients(&merged_config.clients)?;
+    let clients = client_registry.all();
 
     // 5. Build client context
     let home_dir = dirs::home_dir().unwrap_or_else(|| PathBuf::from("/"));
-    let ctx = ClientContext {
-        home_dir,
-        project_root: project_root.to_path_buf(),
-    };
+    let ctx = ClientContext::new(home_dir, project_root.to_path_buf());
 
     // 6. Collect skill statuses
     let mut skills = Vec::new();
@@ -535,7 +533,10 @@ pub fn collect_status_with_paths(
         // Collect per-client deployment status if verify
         let mut deployments = Vec::new();
         if verify {
-            for client in &clients {
+            for client in clients
+                .iter()
+                .filter(|c| client_registry.is_enabled(c.id()))
+            {
                 // Check each scope the client supports
                 let caps = client.capabilities();
                 let skill_client_scopes = [
@@ -628,7 +629,10 @@ pub fn collect_status_with_paths(
         // Collect per-client deployment status if verify
         let mut deployments = Vec::new();
         if verify {
-            for client in &clients {
+            for client in clients
+                .iter()
+                .filter(|c| client_registry.is_enabled(c.id()))
+            {
                 // Check each scope the client supports
                 let caps = client.capabilities();
                 let mcp_client_scopes = [
@@ -647,8 +651,9 @@ pub fn collect_status_with_paths(
                         continue;
                     }
 
-                    // Get the config path for this client/scope combination
-                    if let Ok(plan) = client.plan_mcp(&ctx, scope, &[]) {
+                    // Get the config paths for this client/scope combination
+                    let plans = client.plan_mcp_all(&ctx, scope, &[]).unwrap_or_default();
+                    for plan in plans {
                         let config_file_path =
                             resolve_plan_path(&ctx, plan.root, &plan.relative_path);
 
@@ -670,7 +675,13 @@ pub fn collect_status_with_paths(
                             match serializer.load(&config_file_path) {
                                 Ok(map) => {
                                     let json = Value::Object(map);
-                                    verify_mcp_deployment(&json, &config_path, name, &ownership)
+                                    verify_mcp_deployment_preserving(
+                                        &json,
+                                        &config_path,
+                                        name,
+                                        &ownership,
+                                        &plan.preserved_fields,
+                                    )
                                 }
                                 Err(_) => DeploymentIntegrity::NotDeployed,
                             }
@@ -754,7 +765,7 @@ pub fn collect_status_with_paths(
 
     // 9. Collect client statuses
     let mut client_statuses = Vec::new();
-    for client in &clients {
+    for client in clients {
         let caps = client.capabilities();
         let mut mcp_client_scopes = Vec::new();
         let mut skill_client_scopes = Vec::new();
@@ -782,16 +793,10 @@ pub fn collect_status_with_paths(
             crate::client::SkillDeliveryMode::None => "None".to_string(),
         };
 
-        // Check enabled status from config
-        let enabled = merged_config
-            .clients
-            .get(client.id())
-            .map(|c| c.enabled)
-            .unwrap_or(true);
-
         client_statuses.push(ClientStatus {
             id: client.id().to_string(),
-            enabled,
+            enabled: client_registry.is_enabled(client.id()),
+            installed: detect_installed(client.as_ref(), &ctx),
             mcp_scopes: mcp_client_scopes,
             skill_scopes: skill_client_scopes,
             supports_symlinks: caps.supports_symlinked_skills,
@@ -823,6 +828,26 @@ pub fn collect_status_with_paths(
     })
 }
 
+/// Detect whether a client is installed for the current user.
+///
+/// A client counts as installed when the directory holding its global MCP
+/// config or skills exists. Clients without a dedicated global directory
+/// (e.g. a config file directly in the home directory) cannot be detected
+/// and are reported as installed.
+pub fn detect_installed(client: &dyn ClientAdapter, ctx: &ClientContext) -> bool {
+    let mut dirs = Vec::new();
+    if let Ok(plan) = client.plan_mcp(ctx, ConfigScope::Global, &[]) {
+        dirs.extend(plan.relative_path.parent().map(Path::to_path_buf));
+    }
+    if let Ok(plan) = client.plan_skill(ctx, ConfigScope::Global)
+        && plan.root == PathRoot::User
+    {
+        dirs.extend(plan.relative_path.parent().map(Path::to_path_buf));
+    }
+    dirs.retain(|dir| !dir.as_os_str().is_empty());
+    dirs.is_empty() || dirs.iter().any(|dir| ctx.home_dir.join(dir).is_dir())
+}
+
 fn resolve_plan_path(ctx: &ClientContext, root: PathRoot, relative: &Path) -> PathBuf {
     let base = match root {
         PathRoot::User => &ctx.home_dir,
@@ -1134,6 +1159,18 @@ enabled = false
         );
     }
 
+    #[test]
+    fn test_detect_installed_checks_config_dir() {
+        let home = tempdir().unwrap();
+        let ctx = ClientContext::new(home.path().to_path_buf(), PathBuf::from("/work/project"));
+        let cursor = crate::client::cursor::CursorClient::new();
+
+        assert!(!detect_installed(&cursor, &ctx));
+
+        std::fs::create_dir_all(home.path().join(".cursor")).unwrap();
+        assert!(detect_installed(&cursor, &ctx));
+    }
+
     #[test]
     fn test_mcp_status_uses_toml_config_for_codex() {
         let _home_lock = HOME_LOCK.lock().expect("lock home mutex");
@@ -12,3 +12,14 @@ pub enum ConfigScope {
     /// Per-project, shared (e.g., checked into version control).
     PerProjectShared,
 }
+
+impl ConfigScope {
+    /// Lowercase label used in CLI output, as accepted by `--scope`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ConfigScope::Global => "global",
+            ConfigScope::PerProjectShared => "shared",
+            ConfigScope::PerProjectLocal => "local",
+        }
+    }
+}
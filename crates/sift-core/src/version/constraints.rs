@@ -42,16 +42,38 @@ impl VersionResolver {
         anyhow::bail!("Invalid version constraint: {}", input)
     }
 
-    /// Resolve a constraint to a specific version.
+    /// Resolve a constraint against the versions a source publishes.
     ///
-    /// This is a stub implementation. In a real system, this would:
-    /// - Query the registry for available versions
-    /// - Resolve Git branches to specific commits
-    /// - Check Docker tags
-    pub fn resolve(_constraint: &VersionConstraint, _registry: &str) -> anyhow::Result<String> {
-        // TODO: Implement actual resolution.
-        // For now, return a placeholder.
-        Ok("resolved-placeholder".to_string())
+    /// `available` holds version strings as published (tags may carry a `v`
+    /// prefix); the matching entry is returned unchanged. `Latest` picks the
+    /// highest stable version and falls back to prereleases. Branch and SHA
+    /// constraints do not select from a version list and are rejected.
+    pub fn resolve(constraint: &VersionConstraint, available: &[String]) -> anyhow::Result<String> {
+        let mut parsed = available
+            .iter()
+            .filter_map(|raw| Some((parse_version(raw)?, raw)));
+
+        let selected = match constraint {
+            VersionConstraint::Latest => parsed
+                .clone()
+                .filter(|(v, _)| v.pre.is_empty())
+                .max_by(|a, b| a.0.cmp(&b.0))
+                .or_else(|| parsed.max_by(|a, b| a.0.cmp(&b.0))),
+            VersionConstraint::Semver(req) => parsed
+                .filter(|(v, _)| req.matches(v))
+                .max_by(|a, b| a.0.cmp(&b.0)),
+            VersionConstraint::Exact(version) => parsed.find(|(v, _)| v == version),
+            VersionConstraint::Branch(_) | VersionConstraint::GitSha(_) => {
+                anyhow::bail!(
+                    "Constraint '{}' does not select from published versions",
+                    constraint.as_str()
+                )
+            }
+        };
+
+        selected
+            .map(|(_, raw)| raw.clone())
+            .ok_or_else(|| anyhow::anyhow!("No version satisfies '{}'", constraint.as_str()))
     }
 
     /// Compare two version strings to determine which is newer.
@@ -70,6 +92,11 @@ impl VersionResolver {
     }
 }
 
+/// Parse a published version, tolerating a leading `v` (e.g. `v1.2.0`).
+pub fn parse_version(raw: &str) -> Option<semver::Version> {
+    semver::Version::parse(raw.strip_prefix('v').unwrap_or(raw)).ok()
+}
+
 /// Version constraint types.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum VersionConstraint {
@@ -219,6 +246,49 @@ mod tests {
         assert!(constraint.satisfies(&full_sha[..7]).unwrap());
     }
 
+    fn versions(list: &[&str]) -> Vec<String> {
+        list.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn test_resolve_latest_prefers_stable() {
+        let available = versions(&["v1.0.0", "v1.2.0", "v2.0.0-rc.1", "nightly"]);
+        let resolved = VersionResolver::resolve(&VersionConstraint::Latest, &available).unwrap();
+        assert_eq!(resolved, "v1.2.0");
+    }
+
+    #[test]
+    fn test_resolve_latest_falls_back_to_prerelease() {
+        let available = versions(&["0.1.0-alpha", "0.1.0-beta"]);
+        let resolved = VersionResolver::resolve(&VersionConstraint::Latest, &available).unwrap();
+        assert_eq!(resolved, "0.1.0-beta");
+    }
+
+    #[test]
+    fn test_resolve_semver_picks_highest_match() {
+        let available = versions(&["1.0.0", "1.4.2", "1.10.0", "2.0.0"]);
+        let constraint = VersionResolver::parse_constraint("^1.0").unwrap();
+        let resolved = VersionResolver::resolve(&constraint, &available).unwrap();
+        assert_eq!(resolved, "1.10.0");
+    }
+
+    #[test]
+    fn test_resolve_exact_matches_prefixed_tag() {
+        let available = versions(&["v1.2.3", "v1.2.4"]);
+        let constraint = VersionResolver::parse_constraint("1.2.3").unwrap();
+        let resolved = VersionResolver::resolve(&constraint, &available).unwrap();
+        assert_eq!(resolved, "v1.2.3");
+    }
+
+    #[test]
+    fn test_resolve_without_match_errors() {
+        let available = versions(&["1.0.0"]);
+        let constraint = VersionResolver::parse_constraint("^2").unwrap();
+        assert!(VersionResolver::resolve(&constraint, &available).is_err());
+        let branch = VersionResolver::parse_constraint("branch:main").unwrap();
+        assert!(VersionResolver::resolve(&branch, &available).is_err());
+    }
+
     #[test]
     fn test_compare_versions_semver() {
         use std::cmp::Ordering;
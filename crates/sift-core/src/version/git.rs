@@ -1,9 +1,74 @@
-//! Git helpers for resolving subdirectory versions.
+//! Git helpers for resolving subdirectory versions and ref constraints.
 
 use std::path::Path;
 
 use git2::{DiffOptions, Repository};
 
+use crate::git::RemoteRef;
+use crate::version::{VersionConstraint, VersionResolver, parse_version};
+
+/// A constraint resolved against the refs a remote advertises.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvedGitRef {
+    /// Ref to fetch (tag, branch or SHA); `None` follows the remote HEAD
+    pub reference: Option<String>,
+    /// Commit the ref points to
+    pub commit: String,
+}
+
+/// Resolve a version constraint against a remote's refs.
+///
+/// Semver and `latest` constraints select among tags; `latest` follows the
+/// remote HEAD when no tag parses as a version. `branch:` constraints also
+/// accept a tag name or `HEAD`. SHA constraints stay pinned.
+pub fn resolve_git_constraint(
+    constraint: &VersionConstraint,
+    refs: &[RemoteRef],
+) -> anyhow::Result<ResolvedGitRef> {
+    let tags: Vec<String> = refs
+        .iter()
+        .filter_map(|r| r.name.strip_prefix("refs/tags/"))
+        .map(str::to_string)
+        .collect();
+
+    match constraint {
+        VersionConstraint::Branch(name) => {
+            let found = refs
+                .iter()
+                .find(|r| r.name == format!("refs/heads/{}", name))
+                .or_else(|| {
+                    refs.iter()
+                        .find(|r| r.name == format!("refs/tags/{}", name))
+                })
+                .or_else(|| refs.iter().find(|r| r.name == *name))
+                .ok_or_else(|| anyhow::anyhow!("Ref '{}' not found on remote", name))?;
+            Ok(ResolvedGitRef {
+                reference: (name != "HEAD").then(|| name.clone()),
+                commit: found.commit.clone(),
+            })
+        }
+        VersionConstraint::GitSha(sha) => Ok(ResolvedGitRef {
+            reference: Some(sha.clone()),
+            commit: sha.clone(),
+        }),
+        VersionConstraint::Latest if !tags.iter().any(|t| parse_version(t).is_some()) => {
+            resolve_git_constraint(&VersionConstraint::Branch("HEAD".to_string()), refs)
+        }
+        VersionConstraint::Latest | VersionConstraint::Semver(_) | VersionConstraint::Exact(_) => {
+            let tag = VersionResolver::resolve(constraint, &tags)?;
+            let commit = refs
+                .iter()
+                .find(|r| r.name == format!("refs/tags/{}", tag))
+                .map(|r| r.commit.clone())
+                .ok_or_else(|| anyhow::anyhow!("Tag '{}' not found on remote", tag))?;
+            Ok(ResolvedGitRef {
+                reference: Some(tag),
+                commit,
+            })
+        }
+    }
+}
+
 pub fn resolve_subdir_commit(
     repo_path: &Path,
     reference: &str,
@@ -50,3 +115,71 @@ fn commit_touches_path(
     let diff = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), Some(&mut diff_opts))?;
     Ok(diff.deltas().len() > 0)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn remote_ref(name: &str, commit: &str) -> RemoteRef {
+        RemoteRef {
+            name: name.to_string(),
+            commit: commit.to_string(),
+        }
+    }
+
+    fn sample_refs() -> Vec<RemoteRef> {
+        vec![
+            remote_ref("HEAD", "c3"),
+            remote_ref("refs/heads/main", "c3"),
+            remote_ref("refs/heads/dev", "c4"),
+            remote_ref("refs/tags/v1.0.0", "c1"),
+            remote_ref("refs/tags/v1.1.0", "c2"),
+            remote_ref("refs/tags/v2.0.0-rc.1", "c3"),
+        ]
+    }
+
+    #[test]
+    fn latest_selects_highest_stable_tag() {
+        let resolved = resolve_git_constraint(&VersionConstraint::Latest, &sample_refs()).unwrap();
+        assert_eq!(resolved.reference.as_deref(), Some("v1.1.0"));
+        assert_eq!(resolved.commit, "c2");
+    }
+
+    #[test]
+    fn latest_without_version_tags_follows_head() {
+        let refs = vec![
+            remote_ref("HEAD", "c9"),
+            remote_ref("refs/heads/main", "c9"),
+        ];
+        let resolved = resolve_git_constraint(&VersionConstraint::Latest, &refs).unwrap();
+        assert_eq!(resolved.reference, None);
+        assert_eq!(resolved.commit, "c9");
+    }
+
+    #[test]
+    fn semver_selects_matching_tag() {
+        let constraint = VersionResolver::parse_constraint("~1.0").unwrap();
+        let resolved = resolve_git_constraint(&constraint, &sample_refs()).unwrap();
+        assert_eq!(resolved.reference.as_deref(), Some("v1.0.0"));
+        assert_eq!(resolved.commit, "c1");
+    }
+
+    #[test]
+    fn branch_resolves_head_of_branch() {
+        let constraint = VersionResolver::parse_constraint("branch:dev").unwrap();
+        let resolved = resolve_git_constraint(&constraint, &sample_refs()).unwrap();
+        assert_eq!(resolved.reference.as_deref(), Some("dev"));
+        assert_eq!(resolved.commit, "c4");
+
+        let missing = VersionResolver::parse_constraint("branch:gone").unwrap();
+        assert!(resolve_git_constraint(&missing, &sample_refs()).is_err());
+    }
+
+    #[test]
+    fn sha_stays_pinned() {
+        let sha = "a".repeat(40);
+        let constraint = VersionResolver::parse_constraint(&sha).unwrap();
+        let resolved = resolve_git_constraint(&constraint, &sample_refs()).unwrap();
+        assert_eq!(resolved.commit, sha);
+    }
+}
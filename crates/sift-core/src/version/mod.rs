@@ -3,4 +3,4 @@
 pub mod constraints;
 pub mod git;
 
-pub use constraints::{VersionConstraint, VersionResolver};
+pub use constraints::{VersionConstraint, VersionResolver, parse_version};
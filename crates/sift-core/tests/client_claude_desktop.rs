@@ -0,0 +1,116 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use serde_json::json;
+
+use sift_core::client::claude_desktop::ClaudeDesktopClient;
+use sift_core::client::{ClientAdapter, ClientContext, PathRoot};
+use sift_core::mcp::spec::McpResolvedServer;
+use sift_core::types::ConfigScope;
+
+fn context() -> ClientContext {
+    ClientContext::new(PathBuf::from("/home/dev"), PathBuf::from("/work/project"))
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn claude_desktop_global_config_path_on_linux() {
+    let client = ClaudeDesktopClient::new();
+
+    let plan = client
+        .plan_mcp(&context(), ConfigScope::Global, &[])
+        .unwrap();
+
+    assert_eq!(plan.root, PathRoot::User);
+    assert_eq!(
+        context().home_dir.join(&plan.relative_path),
+        PathBuf::from("/home/dev/.config/Claude/claude_desktop_config.json")
+    );
+    assert_eq!(plan.config_path, vec!["mcpServers"]);
+}
+
+#[test]
+fn claude_desktop_is_global_only() {
+    let client = ClaudeDesktopClient::new();
+    let caps = client.capabilities();
+
+    assert!(caps.mcp.global);
+    assert!(!caps.mcp.project);
+    assert!(!caps.mcp.local);
+    assert!(!caps.skills.global);
+    assert!(
+        client
+            .plan_mcp(&context(), ConfigScope::PerProjectShared, &[])
+            .is_err()
+    );
+    assert!(client.plan_skill(&context(), ConfigScope::Global).is_err());
+}
+
+#[test]
+fn claude_desktop_renders_mcpb_variables() {
+    let client = ClaudeDesktopClient::new();
+
+    // Bundle converter output with ${__dirname} resolved
+    let mut env = HashMap::new();
+    env.insert("WORKSPACE".to_string(), "${HOME}/notes".to_string());
+    let servers = vec![McpResolvedServer::stdio(
+        "notes".to_string(),
+        "node".to_string(),
+        vec![
+            "/state/cache/mcpb/notes/server/index.js".to_string(),
+            "--out=${DOCUMENTS}".to_string(),
+        ],
+        env,
+    )];
+
+    let plan = client
+        .plan_mcp(&context(), ConfigScope::Global, &servers)
+        .unwrap();
+
+    let documents = PathBuf::from("/home/dev").join("Documents");
+    assert_eq!(
+        plan.entries["notes"],
+        json!({
+            "command": "node",
+            "args": [
+                "/state/cache/mcpb/notes/server/index.js",
+                format!("--out={}", documents.display()),
+            ],
+            "env": {
+                "WORKSPACE": format!("{}/notes", PathBuf::from("/home/dev").display())
+            }
+        })
+    );
+}
+
+#[test]
+fn claude_desktop_bridges_http_servers() {
+    let client = ClaudeDesktopClient::new();
+
+    let mut headers = HashMap::new();
+    headers.insert("Authorization".to_string(), "Bearer token".to_string());
+    let servers = vec![McpResolvedServer::http(
+        "remote".to_string(),
+        "https://api.example.com/mcp".to_string(),
+        headers,
+    )];
+
+    let plan = client
+        .plan_mcp(&context(), ConfigScope::Global, &servers)
+        .unwrap();
+
+    assert_eq!(
+        plan.entries["remote"],
+        json!({
+            "command": "npx",
+            "args": [
+                "-y",
+                "mcp-remote",
+                "https://api.example.com/mcp",
+                "--header",
+                "Authorization:${MCP_HEADER_AUTHORIZATION}"
+            ],
+            "env": {"MCP_HEADER_AUTHORIZATION": "Bearer token"}
+        })
+    );
+}
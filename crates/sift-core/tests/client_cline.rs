@@ -0,0 +1,180 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use serde_json::json;
+
+use sift_core::client::cline::ClineClient;
+use sift_core::client::{ClientAdapter, ClientContext, PathRoot};
+use sift_core::deploy::executor::deploy_mcp_to_client;
+use sift_core::lockfile::LockfileService;
+use sift_core::mcp::spec::McpResolvedServer;
+use sift_core::types::ConfigScope;
+use tempfile::TempDir;
+
+fn context() -> ClientContext {
+    ClientContext::new(PathBuf::from("/home/dev"), PathBuf::from("/work/project"))
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn cline_settings_in_vscode_global_storage() {
+    let client = ClineClient::new();
+
+    let plan = client
+        .plan_mcp(&context(), ConfigScope::Global, &[])
+        .unwrap();
+
+    assert_eq!(plan.root, PathRoot::User);
+    assert_eq!(
+        plan.relative_path,
+        PathBuf::from(
+            ".config/Code/User/globalStorage/saoudrizwan.claude-dev/settings/cline_mcp_settings.json"
+        )
+    );
+    assert_eq!(plan.config_path, vec!["mcpServers"]);
+    assert!(
+        client
+            .plan_mcp(&context(), ConfigScope::PerProjectShared, &[])
+            .is_err()
+    );
+}
+
+#[test]
+fn cline_renders_always_allow() {
+    let client = ClineClient::new();
+
+    let mut server = McpResolvedServer::stdio(
+        "files".to_string(),
+        "npx".to_string(),
+        vec!["-y".to_string(), "files-mcp".to_string()],
+        HashMap::new(),
+    );
+    server.always_allow = vec!["read_file".to_string(), "list_dir".to_string()];
+    let remote = McpResolvedServer::http(
+        "remote".to_string(),
+        "https://api.example.com/mcp".to_string(),
+        HashMap::new(),
+    );
+
+    let plan = client
+        .plan_mcp(&context(), ConfigScope::Global, &[server, remote])
+        .unwrap();
+
+    assert_eq!(
+        plan.entries["files"],
+        json!({
+            "command": "npx",
+            "args": ["-y", "files-mcp"],
+            "env": {},
+            "disabled": false,
+            "alwaysAllow": ["read_file", "list_dir"]
+        })
+    );
+    assert_eq!(
+        plan.entries["remote"],
+        json!({
+            "type": "streamableHttp",
+            "url": "https://api.example.com/mcp",
+            "disabled": false,
+            "alwaysAllow": []
+        })
+    );
+}
+
+#[test]
+fn cline_keeps_toggles_made_in_the_extension() {
+    let temp = TempDir::new().unwrap();
+    let home = temp.path().join("home");
+    let project = temp.path().join("project");
+    let ctx = ClientContext::new(home.clone(), project.clone());
+    let lockfile = LockfileService::new(temp.path().join("state"), Some(project));
+    let client = ClineClient::new();
+    let mut server = McpResolvedServer::stdio(
+        "files".to_string(),
+        "npx".to_string(),
+        vec!["files-mcp".to_string()],
+        HashMap::new(),
+    );
+    server.always_allow = vec!["read_file".to_string()];
+    let deploy = |servers: &[McpResolvedServer]| {
+        deploy_mcp_to_client(
+            &client,
+            &ctx,
+            ConfigScope::Global,
+            servers,
+            &lockfile,
+            false,
+        )
+    };
+    deploy(std::slice::from_ref(&server)).unwrap();
+
+    // The user turns the server off in the panel
+    let path = home.join(ClineClient::settings_relative_path());
+    let mut settings: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    settings["mcpServers"]["files"]["disabled"] = json!(true);
+    std::fs::write(&path, settings.to_string()).unwrap();
+
+    // A later sync with changed args still applies and keeps the toggles
+    server.args = vec!["files-mcp@2".to_string()];
+    deploy(std::slice::from_ref(&server)).unwrap();
+
+    let settings: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    assert_eq!(
+        settings["mcpServers"]["files"],
+        json!({
+            "command": "npx",
+            "args": ["files-mcp@2"],
+            "env": {},
+            "disabled": true,
+            "alwaysAllow": ["read_file"]
+        })
+    );
+
+    // Removing it is not blocked by the toggles either
+    deploy(&[]).unwrap();
+    let settings: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    assert!(settings["mcpServers"].get("files").is_none());
+}
+
+#[test]
+fn cline_follows_always_allow_changes_from_sift_toml() {
+    let temp = TempDir::new().unwrap();
+    let home = temp.path().join("home");
+    let project = temp.path().join("project");
+    let ctx = ClientContext::new(home.clone(), project.clone());
+    let lockfile = LockfileService::new(temp.path().join("state"), Some(project));
+    let client = ClineClient::new();
+    let mut server = McpResolvedServer::stdio(
+        "files".to_string(),
+        "npx".to_string(),
+        vec!["files-mcp".to_string()],
+        HashMap::new(),
+    );
+    server.always_allow = vec!["read_file".to_string(), "list_dir".to_string()];
+    let deploy = |servers: &[McpResolvedServer]| {
+        deploy_mcp_to_client(
+            &client,
+            &ctx,
+            ConfigScope::Global,
+            servers,
+            &lockfile,
+            false,
+        )
+    };
+    deploy(std::slice::from_ref(&server)).unwrap();
+
+    // The team drops one pre-approved tool and adds another
+    server.always_allow = vec!["read_file".to_string(), "search".to_string()];
+    deploy(std::slice::from_ref(&server)).unwrap();
+
+    let path = home.join(ClineClient::settings_relative_path());
+    let settings: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    assert_eq!(
+        settings["mcpServers"]["files"]["alwaysAllow"],
+        json!(["read_file", "search"])
+    );
+}
@@ -0,0 +1,107 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use serde_json::json;
+
+use sift_core::client::cursor::CursorClient;
+use sift_core::client::{ClientAdapter, ClientContext, PathRoot};
+use sift_core::mcp::spec::McpResolvedServer;
+use sift_core::types::ConfigScope;
+
+fn context() -> ClientContext {
+    ClientContext::new(PathBuf::from("/home/dev"), PathBuf::from("/work/project"))
+}
+
+#[test]
+fn cursor_skill_paths() {
+    let client = CursorClient::new();
+
+    let global = client.plan_skill(&context(), ConfigScope::Global).unwrap();
+    assert_eq!(global.root, PathRoot::User);
+    assert_eq!(global.relative_path, PathBuf::from(".cursor/skills"));
+
+    let project = client
+        .plan_skill(&context(), ConfigScope::PerProjectShared)
+        .unwrap();
+    assert_eq!(project.root, PathRoot::Project);
+    assert_eq!(project.relative_path, PathBuf::from(".cursor/skills"));
+}
+
+#[test]
+fn cursor_capabilities() {
+    let caps = CursorClient::new().capabilities();
+
+    assert!(caps.mcp.global);
+    assert!(caps.mcp.project);
+    assert!(!caps.mcp.local);
+    assert!(caps.supported_transports.contains("stdio"));
+    assert!(caps.supported_transports.contains("http"));
+    assert!(caps.supports_http_headers);
+}
+
+#[test]
+fn cursor_mcp_config_paths() {
+    let client = CursorClient::new();
+
+    let global = client
+        .plan_mcp(&context(), ConfigScope::Global, &[])
+        .unwrap();
+    assert_eq!(global.root, PathRoot::User);
+    assert_eq!(global.relative_path, PathBuf::from(".cursor/mcp.json"));
+    assert_eq!(global.config_path, vec!["mcpServers"]);
+
+    let project = client
+        .plan_mcp(&context(), ConfigScope::PerProjectShared, &[])
+        .unwrap();
+    assert_eq!(project.root, PathRoot::Project);
+    assert_eq!(project.relative_path, PathBuf::from(".cursor/mcp.json"));
+
+    assert!(
+        client
+            .plan_mcp(&context(), ConfigScope::PerProjectLocal, &[])
+            .is_err()
+    );
+}
+
+#[test]
+fn cursor_project_mcp_config() {
+    let client = CursorClient::new();
+
+    let mut env = HashMap::new();
+    env.insert("TOKEN".to_string(), "secret".to_string());
+
+    let mut headers = HashMap::new();
+    headers.insert("Authorization".to_string(), "Bearer token".to_string());
+
+    let servers = vec![
+        McpResolvedServer::stdio(
+            "local".to_string(),
+            "npx".to_string(),
+            vec!["pkg@1.2.3".to_string()],
+            env,
+        ),
+        McpResolvedServer::http(
+            "remote".to_string(),
+            "https://api.example.com/mcp".to_string(),
+            headers,
+        ),
+    ];
+
+    let plan = client
+        .plan_mcp(&context(), ConfigScope::PerProjectShared, &servers)
+        .unwrap();
+
+    let expected = json!({
+        "local": {
+            "command": "npx",
+            "args": ["pkg@1.2.3"],
+            "env": {"TOKEN": "secret"}
+        },
+        "remote": {
+            "url": "https://api.example.com/mcp",
+            "headers": {"Authorization": "Bearer token"}
+        }
+    });
+
+    assert_eq!(serde_json::Value::Object(plan.entries), expected);
+}
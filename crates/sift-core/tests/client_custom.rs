@@ -0,0 +1,207 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use serde_json::json;
+
+use sift_core::client::custom::{CustomClient, CustomClientSpec, EntryTemplate};
+use sift_core::client::{
+    ClientAdapter, ClientContext, McpConfigFormat, PathRoot, SkillDeliveryMode,
+};
+use sift_core::mcp::OAuthConfig;
+use sift_core::mcp::spec::McpResolvedServer;
+use sift_core::types::ConfigScope;
+
+fn context() -> ClientContext {
+    ClientContext::new(PathBuf::from("/home/dev"), PathBuf::from("/work/project"))
+}
+
+fn spec() -> CustomClientSpec {
+    CustomClientSpec {
+        mcp_global: Some("~/.acme/mcp.json".to_string()),
+        mcp_project: None,
+        format: McpConfigFormat::Generic,
+        config_path: vec!["mcpServers".to_string()],
+        template: EntryTemplate::default(),
+        skills_global: Some("~/.acme/skills".to_string()),
+        skills_project: Some(".acme/skills".to_string()),
+    }
+}
+
+#[test]
+fn custom_client_capabilities_follow_declared_paths() {
+    let client = CustomClient::new("acme", spec());
+    let caps = client.capabilities();
+
+    assert_eq!(client.id(), "acme");
+    assert!(caps.mcp.global);
+    assert!(!caps.mcp.project);
+    assert!(caps.skills.global);
+    assert!(caps.skills.project);
+    assert!(matches!(
+        caps.skill_delivery,
+        SkillDeliveryMode::Filesystem { ref global_path, .. } if global_path == "~/.acme/skills"
+    ));
+    assert!(
+        client
+            .plan_mcp(&context(), ConfigScope::PerProjectShared, &[])
+            .is_err()
+    );
+}
+
+#[test]
+fn custom_client_default_template() {
+    let client = CustomClient::new("acme", spec());
+
+    let mut headers = HashMap::new();
+    headers.insert("Authorization".to_string(), "Bearer ${TOKEN}".to_string());
+    let servers = vec![
+        McpResolvedServer::stdio(
+            "local".to_string(),
+            "npx".to_string(),
+            vec!["-y".to_string(), "server".to_string()],
+            HashMap::new(),
+        ),
+        McpResolvedServer::http(
+            "remote".to_string(),
+            "https://api.example.com/mcp".to_string(),
+            headers,
+        ),
+    ];
+
+    let plan = client
+        .plan_mcp(&context(), ConfigScope::Global, &servers)
+        .unwrap();
+
+    assert_eq!(plan.root, PathRoot::User);
+    assert_eq!(plan.relative_path, PathBuf::from(".acme/mcp.json"));
+    assert_eq!(plan.config_path, vec!["mcpServers"]);
+    assert_eq!(
+        plan.entries["local"],
+        json!({"command": "npx", "args": ["-y", "server"], "env": {}})
+    );
+    assert_eq!(
+        plan.entries["remote"],
+        json!({
+            "url": "https://api.example.com/mcp",
+            "headers": {"Authorization": "Bearer ${TOKEN}"}
+        })
+    );
+}
+
+#[test]
+fn custom_client_renders_entry_template() {
+    let mut spec = spec();
+    spec.format = McpConfigFormat::Toml;
+    spec.config_path = vec!["agent".to_string(), "servers".to_string()];
+    spec.template.stdio = Some(json!({
+        "exec": ["${command}", "--name=${name}"],
+        "argv": "${args}",
+        "enabled": true,
+    }));
+    let client = CustomClient::new("acme", spec);
+
+    let servers = vec![McpResolvedServer::stdio(
+        "ctx".to_string(),
+        "uvx".to_string(),
+        vec!["ctx-server".to_string()],
+        HashMap::new(),
+    )];
+    let plan = client
+        .plan_mcp(&context(), ConfigScope::Global, &servers)
+        .unwrap();
+
+    assert_eq!(plan.format, McpConfigFormat::Toml);
+    assert_eq!(
+        plan.entries["ctx"],
+        json!({"exec": ["uvx", "--name=ctx"], "argv": ["ctx-server"], "enabled": true})
+    );
+}
+
+#[test]
+fn custom_client_renders_oauth_placeholders() {
+    let mut spec = spec();
+    spec.template.http = Some(json!({
+        "url": "${url}",
+        "auth": {"client": "${oauth_client_id}", "scopes": "${oauth_scopes}"},
+    }));
+    let client = CustomClient::new("acme", spec);
+
+    let supported = client.capabilities().supported_oauth;
+    assert!(supported.contains("client_id"));
+    assert!(supported.contains("scopes"));
+    assert!(!supported.contains("callback_port"));
+
+    let mut with_oauth = McpResolvedServer::http(
+        "linear".to_string(),
+        "https://mcp.linear.app/mcp".to_string(),
+        HashMap::new(),
+    );
+    with_oauth.oauth = Some(OAuthConfig {
+        client_id: Some("sift".to_string()),
+        ..Default::default()
+    });
+    let plain = McpResolvedServer::http(
+        "plain".to_string(),
+        "https://example.com/mcp".to_string(),
+        HashMap::new(),
+    );
+    let plan = client
+        .plan_mcp(&context(), ConfigScope::Global, &[with_oauth, plain])
+        .unwrap();
+
+    // Unset settings are left out rather than written as null
+    assert_eq!(
+        plan.entries["linear"],
+        json!({"url": "https://mcp.linear.app/mcp", "auth": {"client": "sift"}})
+    );
+    assert_eq!(
+        plan.entries["plain"],
+        json!({"url": "https://example.com/mcp", "auth": {}})
+    );
+}
+
+#[test]
+fn custom_client_keeps_literal_nulls() {
+    let mut spec = spec();
+    spec.template.http = Some(json!({
+        "url": "${url}",
+        "proxy": null,
+        "client": "${oauth_client_id}",
+    }));
+    let client = CustomClient::new("acme", spec);
+
+    let servers = vec![McpResolvedServer::http(
+        "remote".to_string(),
+        "https://example.com/mcp".to_string(),
+        HashMap::new(),
+    )];
+    let plan = client
+        .plan_mcp(&context(), ConfigScope::Global, &servers)
+        .unwrap();
+
+    assert_eq!(
+        plan.entries["remote"],
+        json!({"url": "https://example.com/mcp", "proxy": null})
+    );
+}
+
+#[test]
+fn custom_client_skill_paths() {
+    let client = CustomClient::new("acme", spec());
+
+    let global = client.plan_skill(&context(), ConfigScope::Global).unwrap();
+    assert_eq!(global.root, PathRoot::User);
+    assert_eq!(global.relative_path, PathBuf::from(".acme/skills"));
+
+    let project = client
+        .plan_skill(&context(), ConfigScope::PerProjectShared)
+        .unwrap();
+    assert_eq!(project.root, PathRoot::Project);
+    assert_eq!(project.relative_path, PathBuf::from(".acme/skills"));
+
+    assert!(
+        client
+            .plan_skill(&context(), ConfigScope::PerProjectLocal)
+            .is_err()
+    );
+}
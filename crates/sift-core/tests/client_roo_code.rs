@@ -0,0 +1,57 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use serde_json::json;
+
+use sift_core::client::roo_code::RooCodeClient;
+use sift_core::client::{ClientAdapter, ClientContext, PathRoot};
+use sift_core::mcp::spec::McpResolvedServer;
+use sift_core::types::ConfigScope;
+
+fn context() -> ClientContext {
+    ClientContext::new(PathBuf::from("/home/dev"), PathBuf::from("/work/project"))
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn roo_code_global_settings_in_vscode_global_storage() {
+    let plan = RooCodeClient::new()
+        .plan_mcp(&context(), ConfigScope::Global, &[])
+        .unwrap();
+
+    assert_eq!(plan.root, PathRoot::User);
+    assert_eq!(
+        plan.relative_path,
+        PathBuf::from(
+            ".config/Code/User/globalStorage/rooveterinaryinc.roo-cline/settings/mcp_settings.json"
+        )
+    );
+}
+
+#[test]
+fn roo_code_project_config_renders_always_allow() {
+    let mut server = McpResolvedServer::http(
+        "remote".to_string(),
+        "https://api.example.com/mcp".to_string(),
+        HashMap::from([("Authorization".to_string(), "Bearer token".to_string())]),
+    );
+    server.always_allow = vec!["search".to_string()];
+
+    let plan = RooCodeClient::new()
+        .plan_mcp(&context(), ConfigScope::PerProjectShared, &[server])
+        .unwrap();
+
+    assert_eq!(plan.root, PathRoot::Project);
+    assert_eq!(plan.relative_path, PathBuf::from(".roo/mcp.json"));
+    assert_eq!(plan.config_path, vec!["mcpServers"]);
+    assert_eq!(
+        plan.entries["remote"],
+        json!({
+            "type": "streamable-http",
+            "url": "https://api.example.com/mcp",
+            "headers": {"Authorization": "Bearer token"},
+            "disabled": false,
+            "alwaysAllow": ["search"]
+        })
+    );
+}
@@ -0,0 +1,129 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use serde_json::json;
+use tempfile::TempDir;
+
+use sift_core::client::vscode::VsCodeClient;
+use sift_core::client::{ClientAdapter, ClientContext, PathRoot};
+use sift_core::deploy::executor::deploy_mcp_to_client;
+use sift_core::lockfile::LockfileService;
+use sift_core::mcp::spec::McpResolvedServer;
+use sift_core::types::ConfigScope;
+
+fn context(home: &Path) -> ClientContext {
+    ClientContext::new(home.to_path_buf(), PathBuf::from("/work/project"))
+}
+
+/// Register named profiles the way VS Code does in `storage.json`.
+fn write_profiles(home: &Path, profiles: &[(&str, &str)]) {
+    let storage = home
+        .join(VsCodeClient::user_dir_relative_path())
+        .join("globalStorage/storage.json");
+    std::fs::create_dir_all(storage.parent().unwrap()).unwrap();
+    let profiles: Vec<_> = profiles
+        .iter()
+        .map(|(name, location)| json!({"name": name, "location": location}))
+        .collect();
+    std::fs::write(
+        storage,
+        serde_json::to_string(&json!({"userDataProfiles": profiles})).unwrap(),
+    )
+    .unwrap();
+}
+
+#[test]
+fn vscode_global_uses_default_profile() {
+    let home = TempDir::new().unwrap();
+    let client = VsCodeClient::new();
+
+    let plan = client
+        .plan_mcp(&context(home.path()), ConfigScope::Global, &[])
+        .unwrap();
+
+    assert_eq!(plan.root, PathRoot::User);
+    assert_eq!(
+        plan.relative_path,
+        VsCodeClient::user_dir_relative_path().join("mcp.json")
+    );
+    assert_eq!(plan.config_path, vec!["servers"]);
+}
+
+#[test]
+fn vscode_selects_named_profiles() {
+    let home = TempDir::new().unwrap();
+    write_profiles(home.path(), &[("Work", "-6f1a2b"), ("Demo", "3c4d5e")]);
+    let user_dir = VsCodeClient::user_dir_relative_path();
+
+    let client = VsCodeClient::new().with_profiles(vec!["Default".into(), "Work".into()]);
+    let paths: Vec<_> = client
+        .plan_mcp_all(&context(home.path()), ConfigScope::Global, &[])
+        .unwrap()
+        .into_iter()
+        .map(|plan| plan.relative_path)
+        .collect();
+    assert_eq!(
+        paths,
+        vec![
+            user_dir.join("mcp.json"),
+            user_dir.join("profiles/-6f1a2b/mcp.json"),
+        ]
+    );
+
+    let all = VsCodeClient::new().with_profiles(vec!["*".into()]);
+    let plans = all
+        .plan_mcp_all(&context(home.path()), ConfigScope::Global, &[])
+        .unwrap();
+    assert_eq!(plans.len(), 3);
+
+    let err = VsCodeClient::new()
+        .with_profiles(vec!["Missing".into()])
+        .plan_mcp_all(&context(home.path()), ConfigScope::Global, &[])
+        .unwrap_err();
+    assert!(
+        err.to_string().contains("Default, Work, Demo"),
+        "got: {err:#}"
+    );
+}
+
+#[test]
+fn vscode_deploys_to_every_selected_profile() {
+    let temp = TempDir::new().unwrap();
+    let home = temp.path().join("home");
+    write_profiles(&home, &[("Work", "-6f1a2b")]);
+    let ctx = context(&home);
+    let lockfile = LockfileService::new(temp.path().join("state"), None);
+    let client = VsCodeClient::new().with_profiles(vec!["Default".into(), "Work".into()]);
+
+    let servers = vec![McpResolvedServer::stdio(
+        "ctx".to_string(),
+        "npx".to_string(),
+        vec!["ctx-server".to_string()],
+        HashMap::new(),
+    )];
+    deploy_mcp_to_client(
+        &client,
+        &ctx,
+        ConfigScope::Global,
+        &servers,
+        &lockfile,
+        false,
+    )
+    .unwrap();
+
+    let user_dir = home.join(VsCodeClient::user_dir_relative_path());
+    for config in [
+        user_dir.join("mcp.json"),
+        user_dir.join("profiles/-6f1a2b/mcp.json"),
+    ] {
+        let content: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&config).unwrap()).unwrap();
+        assert_eq!(content["servers"]["ctx"]["command"], "npx");
+        let ownership = lockfile.load_ownership(&config, Some("servers")).unwrap();
+        assert!(
+            ownership.contains_key("ctx"),
+            "{} not owned",
+            config.display()
+        );
+    }
+}
@@ -0,0 +1,72 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use serde_json::json;
+
+use sift_core::client::windsurf::WindsurfClient;
+use sift_core::client::{ClientAdapter, ClientContext, PathRoot};
+use sift_core::mcp::spec::McpResolvedServer;
+use sift_core::types::ConfigScope;
+
+fn context() -> ClientContext {
+    ClientContext::new(PathBuf::from("/home/dev"), PathBuf::from("/work/project"))
+}
+
+#[test]
+fn windsurf_is_global_only() {
+    let client = WindsurfClient::new();
+    let caps = client.capabilities();
+
+    assert!(caps.mcp.global);
+    assert!(!caps.mcp.project);
+    assert!(!caps.mcp.local);
+    assert!(!caps.skills.global);
+    assert!(
+        client
+            .plan_mcp(&context(), ConfigScope::PerProjectShared, &[])
+            .is_err()
+    );
+    assert!(client.plan_skill(&context(), ConfigScope::Global).is_err());
+}
+
+#[test]
+fn windsurf_global_mcp_config() {
+    let client = WindsurfClient::new();
+
+    let mut headers = HashMap::new();
+    headers.insert("Authorization".to_string(), "Bearer token".to_string());
+    let servers = vec![
+        McpResolvedServer::stdio(
+            "local".to_string(),
+            "npx".to_string(),
+            vec!["pkg@1.2.3".to_string()],
+            HashMap::new(),
+        ),
+        McpResolvedServer::http(
+            "remote".to_string(),
+            "https://api.example.com/mcp".to_string(),
+            headers,
+        ),
+    ];
+
+    let plan = client
+        .plan_mcp(&context(), ConfigScope::Global, &servers)
+        .unwrap();
+
+    assert_eq!(plan.root, PathRoot::User);
+    assert_eq!(
+        plan.relative_path,
+        PathBuf::from(".codeium/windsurf/mcp_config.json")
+    );
+    assert_eq!(plan.config_path, vec!["mcpServers"]);
+    assert_eq!(
+        serde_json::Value::Object(plan.entries),
+        json!({
+            "local": {"command": "npx", "args": ["pkg@1.2.3"], "env": {}},
+            "remote": {
+                "serverUrl": "https://api.example.com/mcp",
+                "headers": {"Authorization": "Bearer token"}
+            }
+        })
+    );
+}
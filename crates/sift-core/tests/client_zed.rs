@@ -0,0 +1,139 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use serde_json::json;
+use tempfile::TempDir;
+
+use sift_core::client::zed::ZedClient;
+use sift_core::client::{ClientAdapter, ClientContext, PathRoot};
+use sift_core::config::client_config::{self, ConfigFormat};
+use sift_core::lockfile::LockfileService;
+use sift_core::mcp::spec::McpResolvedServer;
+use sift_core::types::ConfigScope;
+
+fn context() -> ClientContext {
+    ClientContext::new(PathBuf::from("/home/dev"), PathBuf::from("/work/project"))
+}
+
+#[test]
+fn zed_renders_context_servers() {
+    let client = ZedClient::new();
+
+    let mut headers = HashMap::new();
+    headers.insert("Authorization".to_string(), "Bearer token".to_string());
+    let servers = vec![
+        McpResolvedServer::stdio(
+            "local".to_string(),
+            "npx".to_string(),
+            vec!["pkg@1.2.3".to_string()],
+            HashMap::new(),
+        ),
+        McpResolvedServer::http(
+            "remote".to_string(),
+            "https://api.example.com/mcp".to_string(),
+            headers,
+        ),
+    ];
+
+    let plan = client
+        .plan_mcp(&context(), ConfigScope::PerProjectShared, &servers)
+        .unwrap();
+
+    assert_eq!(plan.root, PathRoot::Project);
+    assert_eq!(plan.relative_path, PathBuf::from(".zed/settings.json"));
+    assert_eq!(plan.config_path, vec!["context_servers"]);
+    assert_eq!(
+        serde_json::Value::Object(plan.entries),
+        json!({
+            "local": {
+                "source": "custom",
+                "command": "npx",
+                "args": ["pkg@1.2.3"],
+                "env": {}
+            },
+            "remote": {
+                "source": "custom",
+                "url": "https://api.example.com/mcp",
+                "headers": {"Authorization": "Bearer token"}
+            }
+        })
+    );
+}
+
+#[test]
+#[cfg(not(target_os = "windows"))]
+fn zed_global_settings_path() {
+    let client = ZedClient::new();
+
+    let plan = client
+        .plan_mcp(&context(), ConfigScope::Global, &[])
+        .unwrap();
+
+    assert_eq!(plan.root, PathRoot::User);
+    assert_eq!(
+        plan.relative_path,
+        PathBuf::from(".config/zed/settings.json")
+    );
+    assert!(
+        client
+            .plan_mcp(&context(), ConfigScope::PerProjectLocal, &[])
+            .is_err()
+    );
+}
+
+#[test]
+fn zed_settings_keep_comments_when_adding_entries() {
+    let temp = TempDir::new().unwrap();
+    let settings = temp.path().join(".zed/settings.json");
+    std::fs::create_dir_all(settings.parent().unwrap()).unwrap();
+    std::fs::write(
+        &settings,
+        r#"// Project settings
+{
+  "tab_size": 2, // two spaces
+  "context_servers": {
+    "user-server": {"source": "custom", "command": "user-cmd", "args": []},
+  },
+}
+"#,
+    )
+    .unwrap();
+
+    let plan = ZedClient::new()
+        .plan_mcp(
+            &ClientContext::new(temp.path().join("home"), temp.path().to_path_buf()),
+            ConfigScope::PerProjectShared,
+            &[McpResolvedServer::stdio(
+                "managed".to_string(),
+                "npx".to_string(),
+                vec![],
+                HashMap::new(),
+            )],
+        )
+        .unwrap();
+    let lockfile_service = LockfileService::new(temp.path().join("state"), None);
+    let path: Vec<&str> = plan.config_path.iter().map(String::as_str).collect();
+
+    let result = client_config::apply_managed_entries_in_path(
+        &settings,
+        &path,
+        &plan.entries,
+        &lockfile_service,
+        false,
+        ConfigFormat::from(plan.format),
+    )
+    .unwrap();
+
+    assert_eq!(result.merged["tab_size"], json!(2));
+    let servers = result.merged["context_servers"].as_object().unwrap();
+    assert!(servers.contains_key("user-server"));
+    assert!(servers.contains_key("managed"));
+
+    // Only the managed subtree is rewritten; comments elsewhere survive
+    let saved = std::fs::read_to_string(&settings).unwrap();
+    assert!(saved.starts_with("// Project settings\n{\n  \"tab_size\": 2, // two spaces\n"));
+    assert!(
+        saved
+            .contains(r#""user-server": {"source": "custom", "command": "user-cmd", "args": []},"#)
+    );
+}
@@ -10,10 +10,10 @@ use std::path::PathBuf;
 use tempfile::TempDir;
 
 fn create_test_context(temp_dir: &TempDir) -> ClientContext {
-    ClientContext {
-        home_dir: temp_dir.path().to_path_buf(),
-        project_root: temp_dir.path().join("project"),
-    }
+    ClientContext::new(
+        temp_dir.path().to_path_buf(),
+        temp_dir.path().join("project"),
+    )
 }
 
 #[test]
@@ -38,6 +38,10 @@ fn codex_plan_mcp_returns_toml_format() {
         env: std::collections::HashMap::new(),
         url: None,
         headers: std::collections::HashMap::new(),
+        oauth: None,
+        always_allow: Vec::new(),
+        machine_launch: None,
+        user_config: std::collections::HashMap::new(),
     }];
 
     let plan = client
@@ -95,6 +99,31 @@ fn toml_serializer_writes_codex_format() {
     assert_eq!(root, loaded);
 }
 
+#[test]
+fn toml_serializer_keeps_user_comments() {
+    let temp_dir = TempDir::new().expect("create temp dir");
+    let config_path = temp_dir.path().join("config.toml");
+    let original = r#"# Codex settings
+model = "o3" # preferred model
+
+[mcp_servers.user]
+command = "user-cmd"
+"#;
+    std::fs::write(&config_path, original).expect("write file");
+
+    let serializer = TomlSerializer;
+    let mut root = serializer.load(&config_path).expect("load");
+    root["mcp_servers"].as_object_mut().unwrap().insert(
+        "context7".to_string(),
+        json!({"command": "npx", "args": ["-y"]}),
+    );
+    serializer.save(&config_path, &root).expect("save");
+
+    let content = std::fs::read_to_string(&config_path).expect("read file");
+    assert!(content.starts_with(original));
+    assert!(content.ends_with("\n[mcp_servers.context7]\nargs = [\"-y\"]\ncommand = \"npx\"\n"));
+}
+
 #[test]
 fn config_format_converts_from_mcp_config_format() {
     assert_eq!(
@@ -113,6 +142,14 @@ fn config_format_converts_from_mcp_config_format() {
         ConfigFormat::from(McpConfigFormat::ClaudeDesktop),
         ConfigFormat::Json
     );
+    assert_eq!(
+        ConfigFormat::from(McpConfigFormat::Jsonc),
+        ConfigFormat::Jsonc
+    );
+    assert_eq!(
+        ConfigFormat::from(McpConfigFormat::Zed),
+        ConfigFormat::Jsonc
+    );
 }
 
 #[test]
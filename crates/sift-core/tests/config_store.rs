@@ -40,6 +40,7 @@ fn save_then_load_roundtrip() {
             args: vec!["--flag".to_string()],
             url: None,
             headers: HashMap::new(),
+            oauth: None,
             targets: None,
             ignore_targets: None,
             env: HashMap::new(),
@@ -47,6 +48,9 @@ fn save_then_load_roundtrip() {
             reset_ignore_targets: false,
             reset_env: None,
             reset_env_all: false,
+            volumes: Vec::new(),
+            network: None,
+            always_allow: Vec::new(),
         },
     );
 
@@ -55,3 +59,35 @@ fn save_then_load_roundtrip() {
 
     assert!(loaded.mcp.contains_key("test"));
 }
+
+#[test]
+fn save_keeps_comments_and_formatting() {
+    let temp = TempDir::new().unwrap();
+    let project = temp.path().join("project");
+    let store = ConfigStore::from_paths(
+        ConfigScope::PerProjectShared,
+        temp.path().join("config"),
+        project.clone(),
+    );
+    let original = r#"# Shared team config
+
+[mcp.postgres]
+source = "registry:postgres-mcp"   # reviewed by ops
+args = ["--readonly"]
+
+[skill.pdf]
+source = "registry:anthropic/pdf"
+"#;
+    std::fs::create_dir_all(store.config_path().parent().unwrap()).unwrap();
+    std::fs::write(store.config_path(), original).unwrap();
+
+    let mut config = store.load().unwrap();
+    config.skill.remove("pdf");
+    store.save(&config).unwrap();
+
+    let saved = std::fs::read_to_string(store.config_path()).unwrap();
+    assert_eq!(
+        saved,
+        "# Shared team config\n\n[mcp.postgres]\nsource = \"registry:postgres-mcp\"   # reviewed by ops\nargs = [\"--readonly\"]\n"
+    );
+}
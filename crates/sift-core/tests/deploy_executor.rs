@@ -1,12 +1,21 @@
 //! Tests for the deploy executor module.
 
 use std::collections::HashMap;
+use std::sync::Arc;
 
-use sift_core::client::ClientContext;
+use serde_json::Value;
 use sift_core::client::claude_code::ClaudeCodeClient;
+use sift_core::client::claude_desktop::ClaudeDesktopClient;
+use sift_core::client::cursor::CursorClient;
+use sift_core::client::custom::{CustomClient, CustomClientSpec, EntryTemplate};
+use sift_core::client::gemini_cli::GeminiCliClient;
+use sift_core::client::vscode::VsCodeClient;
+use sift_core::client::{ClientAdapter, ClientContext, McpConfigFormat};
 use sift_core::deploy::executor::deploy_mcp_to_client;
 use sift_core::lockfile::LockfileService;
+use sift_core::mcp::OAuthConfig;
 use sift_core::mcp::spec::McpResolvedServer;
+use sift_core::secrets::{CommandProvider, SecretProviders};
 use sift_core::types::ConfigScope;
 use tempfile::TempDir;
 
@@ -42,3 +51,522 @@ fn deploy_mcp_to_client_writes_config_file() {
     assert!(report.applied);
     assert!(project.join(".mcp.json").exists());
 }
+
+fn read_json(path: &std::path::Path) -> Value {
+    serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+}
+
+#[test]
+fn deploy_mcp_to_client_expands_variable_references_per_client() {
+    let temp = TempDir::new().unwrap();
+    let home = temp.path().join("home");
+    let project = temp.path().join("project");
+    std::fs::create_dir_all(&home).unwrap();
+    std::fs::create_dir_all(&project).unwrap();
+    std::fs::write(project.join(".env"), "SIFT_TEST_DOTENV_REGION=eu\n").unwrap();
+
+    let ctx = ClientContext::new(home, project.clone()).with_variables(HashMap::from([(
+        "SIFT_TEST_LOCAL_TOKEN".to_string(),
+        "s3cret".to_string(),
+    )]));
+    let lockfile = LockfileService::new(temp.path().join("state"), Some(project.clone()));
+    let servers = vec![McpResolvedServer::http(
+        "api".to_string(),
+        "https://${SIFT_TEST_DOTENV_REGION}.example.com/mcp".to_string(),
+        HashMap::from([(
+            "Authorization".to_string(),
+            "Bearer ${SIFT_TEST_LOCAL_TOKEN}".to_string(),
+        )]),
+    )];
+    let scope = ConfigScope::PerProjectShared;
+
+    // Claude Code expands ${VAR} itself
+    let report = deploy_mcp_to_client(
+        &ClaudeCodeClient::new(),
+        &ctx,
+        scope,
+        &servers,
+        &lockfile,
+        false,
+    )
+    .unwrap();
+    let entry = &read_json(&project.join(".mcp.json"))["mcpServers"]["api"];
+    assert_eq!(
+        entry["headers"]["Authorization"],
+        "Bearer ${SIFT_TEST_LOCAL_TOKEN}"
+    );
+    assert!(report.warnings.is_empty());
+
+    // Cursor expands ${env:VAR}
+    let report = deploy_mcp_to_client(
+        &CursorClient::new(),
+        &ctx,
+        scope,
+        &servers,
+        &lockfile,
+        false,
+    )
+    .unwrap();
+    let entry = &read_json(&project.join(".cursor/mcp.json"))["mcpServers"]["api"];
+    assert_eq!(
+        entry["headers"]["Authorization"],
+        "Bearer ${env:SIFT_TEST_LOCAL_TOKEN}"
+    );
+    assert_eq!(
+        entry["url"],
+        "https://${env:SIFT_TEST_DOTENV_REGION}.example.com/mcp"
+    );
+    assert!(report.warnings.is_empty());
+
+    // Gemini CLI expands nothing: values are resolved and flagged
+    let report = deploy_mcp_to_client(
+        &GeminiCliClient::new(),
+        &ctx,
+        scope,
+        &servers,
+        &lockfile,
+        false,
+    )
+    .unwrap();
+    let entry = &read_json(&project.join(".gemini/settings.json"))["mcpServers"]["api"];
+    assert_eq!(entry["headers"]["Authorization"], "Bearer s3cret");
+    assert_eq!(entry["httpUrl"], "https://eu.example.com/mcp");
+    assert_eq!(report.warnings.len(), 2);
+    assert!(
+        report
+            .warnings
+            .iter()
+            .any(|w| w.contains("SIFT_TEST_LOCAL_TOKEN") && w.contains("plain text"))
+    );
+}
+
+#[test]
+fn deploy_mcp_to_client_flags_literal_secrets_in_shared_config() {
+    let temp = TempDir::new().unwrap();
+    let home = temp.path().join("home");
+    let project = temp.path().join("project");
+    std::fs::create_dir_all(&home).unwrap();
+    std::fs::create_dir_all(&project).unwrap();
+
+    let ctx = ClientContext::new(home, project.clone());
+    let lockfile = LockfileService::new(temp.path().join("state"), Some(project.clone()));
+    let servers = vec![McpResolvedServer::stdio(
+        "db".to_string(),
+        "npx".to_string(),
+        vec!["db-mcp".to_string()],
+        HashMap::from([
+            ("DB_PASSWORD".to_string(), "hunter2".to_string()),
+            ("LOG_LEVEL".to_string(), "debug".to_string()),
+        ]),
+    )];
+    let client = ClaudeCodeClient::new();
+
+    let report = deploy_mcp_to_client(
+        &client,
+        &ctx,
+        ConfigScope::PerProjectShared,
+        &servers,
+        &lockfile,
+        false,
+    )
+    .unwrap();
+    assert_eq!(report.warnings.len(), 1);
+    assert!(report.warnings[0].contains("DB_PASSWORD"));
+
+    // Local scope is private to the user
+    let report = deploy_mcp_to_client(
+        &client,
+        &ctx,
+        ConfigScope::PerProjectLocal,
+        &servers,
+        &lockfile,
+        false,
+    )
+    .unwrap();
+    assert!(report.warnings.is_empty());
+}
+
+#[test]
+fn deploy_mcp_to_client_resolves_secret_references() {
+    let temp = TempDir::new().unwrap();
+    let home = temp.path().join("home");
+    let project = temp.path().join("project");
+    std::fs::create_dir_all(&home).unwrap();
+    std::fs::create_dir_all(&project).unwrap();
+
+    let mut secrets = SecretProviders::new();
+    secrets.register(Arc::new(CommandProvider::new(
+        "stub",
+        "printf",
+        ["value-of-%s", "{path}"],
+    )));
+    let ctx = ClientContext::new(home.clone(), project.clone()).with_secrets(secrets);
+    let lockfile = LockfileService::new(temp.path().join("state"), Some(project.clone()));
+    let servers = vec![McpResolvedServer::stdio(
+        "db".to_string(),
+        "npx".to_string(),
+        vec!["db-mcp".to_string()],
+        HashMap::from([("DATABASE_URL".to_string(), "secret:stub/db/url".to_string())]),
+    )];
+    let client = ClaudeCodeClient::new();
+
+    let report = deploy_mcp_to_client(
+        &client,
+        &ctx,
+        ConfigScope::PerProjectLocal,
+        &servers,
+        &lockfile,
+        false,
+    )
+    .unwrap();
+    let config = read_json(&home.join(".claude.json"));
+    let project_key = project.to_string_lossy();
+    assert_eq!(
+        config["projects"][project_key.as_ref()]["mcpServers"]["db"]["env"]["DATABASE_URL"],
+        "value-of-db/url"
+    );
+    assert!(report.warnings.is_empty());
+
+    // A shared file is usually committed, so the resolved secret is refused
+    let err = deploy_mcp_to_client(
+        &client,
+        &ctx,
+        ConfigScope::PerProjectShared,
+        &servers,
+        &lockfile,
+        false,
+    )
+    .unwrap_err();
+    assert!(format!("{:#}", err).contains("secret:stub/db/url"));
+    assert!(format!("{:#}", err).contains("allow_shared_secrets"));
+    assert!(!project.join(".mcp.json").exists());
+
+    // Forcing writes it, with a warning
+    let report = deploy_mcp_to_client(
+        &client,
+        &ctx,
+        ConfigScope::PerProjectShared,
+        &servers,
+        &lockfile,
+        true,
+    )
+    .unwrap();
+    assert_eq!(report.warnings.len(), 1);
+    assert!(report.warnings[0].contains("secret:stub/db/url"));
+
+    // Unknown providers fail the render instead of writing the reference
+    let mut servers = servers;
+    servers[0]
+        .env
+        .insert("API_KEY".to_string(), "secret:vault/api".to_string());
+    let err = deploy_mcp_to_client(
+        &client,
+        &ctx,
+        ConfigScope::PerProjectLocal,
+        &servers,
+        &lockfile,
+        false,
+    )
+    .unwrap_err();
+    assert!(format!("{:#}", err).contains("Unknown secret provider 'vault'"));
+}
+
+#[test]
+fn deploy_mcp_to_client_writes_shared_secrets_when_project_opts_in() {
+    let temp = TempDir::new().unwrap();
+    let home = temp.path().join("home");
+    let project = temp.path().join("project");
+    let global_config = temp.path().join("config");
+    std::fs::create_dir_all(&home).unwrap();
+    std::fs::create_dir_all(&project).unwrap();
+    std::fs::create_dir_all(&global_config).unwrap();
+    std::fs::write(
+        global_config.join("sift.toml"),
+        format!(
+            "[projects.\"{}\"]\nallow_shared_secrets = true\n",
+            project.display()
+        ),
+    )
+    .unwrap();
+
+    let ctx = ClientContext::for_project(home.clone(), project.clone(), &global_config);
+    assert!(ctx.allow_shared_secrets);
+    let mut secrets = SecretProviders::new();
+    secrets.register(Arc::new(CommandProvider::new(
+        "stub",
+        "printf",
+        ["value-of-%s", "{path}"],
+    )));
+    let ctx = ctx.with_secrets(secrets);
+    let lockfile = LockfileService::new(temp.path().join("state"), Some(project.clone()));
+    let servers = vec![McpResolvedServer::stdio(
+        "db".to_string(),
+        "npx".to_string(),
+        vec!["db-mcp".to_string()],
+        HashMap::from([("DATABASE_URL".to_string(), "secret:stub/db/url".to_string())]),
+    )];
+
+    let report = deploy_mcp_to_client(
+        &ClaudeCodeClient::new(),
+        &ctx,
+        ConfigScope::PerProjectShared,
+        &servers,
+        &lockfile,
+        false,
+    )
+    .unwrap();
+
+    let config = read_json(&project.join(".mcp.json"));
+    assert_eq!(
+        config["mcpServers"]["db"]["env"]["DATABASE_URL"],
+        "value-of-db/url"
+    );
+    assert_eq!(report.warnings.len(), 1);
+    assert!(report.warnings[0].contains("plain text"));
+}
+
+#[test]
+fn deploy_mcp_to_client_renders_oauth_settings_per_client() {
+    let temp = TempDir::new().unwrap();
+    let home = temp.path().join("home");
+    let project = temp.path().join("project");
+    std::fs::create_dir_all(&home).unwrap();
+    std::fs::create_dir_all(&project).unwrap();
+
+    let ctx = ClientContext::new(home, project.clone());
+    let lockfile = LockfileService::new(temp.path().join("state"), Some(project.clone()));
+    let mut server = McpResolvedServer::http(
+        "linear".to_string(),
+        "https://mcp.linear.app/mcp".to_string(),
+        HashMap::new(),
+    );
+    server.oauth = Some(OAuthConfig {
+        client_id: Some("sift-test-client".to_string()),
+        scopes: vec!["read".to_string(), "write".to_string()],
+        callback_port: Some(8765),
+    });
+    let servers = vec![server];
+    let scope = ConfigScope::PerProjectShared;
+
+    // Claude Code takes the client id and callback port, but not scopes
+    let report = deploy_mcp_to_client(
+        &ClaudeCodeClient::new(),
+        &ctx,
+        scope,
+        &servers,
+        &lockfile,
+        false,
+    )
+    .unwrap();
+    let entry = &read_json(&project.join(".mcp.json"))["mcpServers"]["linear"];
+    assert_eq!(entry["oauth"]["clientId"], "sift-test-client");
+    assert_eq!(entry["oauth"]["callbackPort"], 8765);
+    assert_eq!(report.warnings.len(), 1);
+    assert!(report.warnings[0].contains("does not support oauth.scopes"));
+
+    // Gemini CLI takes everything
+    let report = deploy_mcp_to_client(
+        &GeminiCliClient::new(),
+        &ctx,
+        scope,
+        &servers,
+        &lockfile,
+        false,
+    )
+    .unwrap();
+    let entry = &read_json(&project.join(".gemini/settings.json"))["mcpServers"]["linear"];
+    assert_eq!(entry["oauth"]["enabled"], true);
+    assert_eq!(entry["oauth"]["clientId"], "sift-test-client");
+    assert_eq!(
+        entry["oauth"]["scopes"],
+        serde_json::json!(["read", "write"])
+    );
+    assert_eq!(
+        entry["oauth"]["redirectUri"],
+        "http://localhost:8765/oauth/callback"
+    );
+    assert!(report.warnings.is_empty());
+
+    // Cursor has no callback port
+    let report = deploy_mcp_to_client(
+        &CursorClient::new(),
+        &ctx,
+        scope,
+        &servers,
+        &lockfile,
+        false,
+    )
+    .unwrap();
+    let entry = &read_json(&project.join(".cursor/mcp.json"))["mcpServers"]["linear"];
+    assert_eq!(entry["auth"]["CLIENT_ID"], "sift-test-client");
+    assert_eq!(
+        entry["auth"]["scopes"],
+        serde_json::json!(["read", "write"])
+    );
+    assert!(report.warnings[0].contains("oauth.callback_port"));
+
+    // VS Code has no OAuth client settings at all
+    let report = deploy_mcp_to_client(
+        &VsCodeClient::new(),
+        &ctx,
+        scope,
+        &servers,
+        &lockfile,
+        false,
+    )
+    .unwrap();
+    let entry = &read_json(&project.join(".vscode/mcp.json"))["servers"]["linear"];
+    assert!(entry.get("oauth").is_none());
+    assert_eq!(report.warnings.len(), 1);
+    assert!(report.warnings[0].contains("no OAuth client settings"));
+}
+
+#[test]
+fn deploy_mcp_to_client_warns_when_headers_are_dropped() {
+    let temp = TempDir::new().unwrap();
+    let home = temp.path().join("home");
+    let project = temp.path().join("project");
+    std::fs::create_dir_all(&home).unwrap();
+    std::fs::create_dir_all(&project).unwrap();
+
+    let ctx = ClientContext::new(home.clone(), project.clone());
+    let lockfile = LockfileService::new(temp.path().join("state"), Some(project));
+    let client = CustomClient::new(
+        "acme",
+        CustomClientSpec {
+            mcp_global: Some("~/.acme/mcp.json".to_string()),
+            mcp_project: None,
+            format: McpConfigFormat::Generic,
+            config_path: vec!["mcpServers".to_string()],
+            template: EntryTemplate {
+                stdio: None,
+                http: Some(serde_json::json!({"url": "${url}"})),
+            },
+            skills_global: None,
+            skills_project: None,
+        },
+    );
+    assert!(!client.capabilities().supports_http_headers);
+
+    let mut headers = HashMap::new();
+    headers.insert("X-Team".to_string(), "platform".to_string());
+    let servers = vec![McpResolvedServer::http(
+        "remote".to_string(),
+        "https://example.com/mcp".to_string(),
+        headers,
+    )];
+
+    let report = deploy_mcp_to_client(
+        &client,
+        &ctx,
+        ConfigScope::Global,
+        &servers,
+        &lockfile,
+        false,
+    )
+    .unwrap();
+    let entry = &read_json(&home.join(".acme/mcp.json"))["mcpServers"]["remote"];
+    assert_eq!(
+        entry,
+        &serde_json::json!({"url": "https://example.com/mcp"})
+    );
+    assert_eq!(report.warnings.len(), 1);
+    assert!(report.warnings[0].contains("no place for HTTP headers"));
+}
+
+#[test]
+fn deploy_mcp_to_client_bridges_headers_to_claude_desktop() {
+    let temp = TempDir::new().unwrap();
+    let home = temp.path().join("home");
+    let project = temp.path().join("project");
+    std::fs::create_dir_all(&home).unwrap();
+    std::fs::create_dir_all(&project).unwrap();
+
+    let ctx = ClientContext::new(home.clone(), project.clone());
+    let lockfile = LockfileService::new(temp.path().join("state"), Some(project));
+    let client = ClaudeDesktopClient::new();
+
+    let mut headers = HashMap::new();
+    headers.insert("Authorization".to_string(), "Bearer token".to_string());
+    let servers = vec![McpResolvedServer::http(
+        "remote".to_string(),
+        "https://example.com/mcp".to_string(),
+        headers,
+    )];
+
+    let report = deploy_mcp_to_client(
+        &client,
+        &ctx,
+        ConfigScope::Global,
+        &servers,
+        &lockfile,
+        false,
+    )
+    .unwrap();
+    assert!(report.warnings.is_empty(), "{:?}", report.warnings);
+
+    let config = read_json(&home.join(ClaudeDesktopClient::config_relative_path()));
+    let entry = &config["mcpServers"]["remote"];
+    assert_eq!(
+        entry["args"],
+        serde_json::json!([
+            "-y",
+            "mcp-remote",
+            "https://example.com/mcp",
+            "--header",
+            "Authorization:${MCP_HEADER_AUTHORIZATION}"
+        ])
+    );
+    assert_eq!(entry["env"]["MCP_HEADER_AUTHORIZATION"], "Bearer token");
+}
+
+#[test]
+fn deploy_mcp_to_client_resolves_bundle_options_from_the_manifest() {
+    let temp = TempDir::new().unwrap();
+    let home = temp.path().join("home");
+    let project = temp.path().join("project");
+    std::fs::create_dir_all(&home).unwrap();
+    std::fs::create_dir_all(&project).unwrap();
+
+    let ctx = ClientContext::new(home.clone(), project.clone());
+    let lockfile = LockfileService::new(temp.path().join("state"), Some(project));
+    let mut env = HashMap::new();
+    env.insert("API_KEY".to_string(), "${user_config.api_key}".to_string());
+    // Not an option value, even though the name matches
+    env.insert("MODE".to_string(), "fast".to_string());
+    let mut server = McpResolvedServer::stdio(
+        "notes".to_string(),
+        "node".to_string(),
+        vec![
+            "index.js".to_string(),
+            "--verbose=${user_config.verbose}".to_string(),
+            "--mode=${user_config.mode}".to_string(),
+        ],
+        env,
+    );
+    server.user_config = HashMap::from([
+        ("verbose".to_string(), Some("true".to_string())),
+        ("mode".to_string(), Some("safe".to_string())),
+        ("api_key".to_string(), None),
+    ]);
+
+    let report = deploy_mcp_to_client(
+        &ClaudeDesktopClient::new(),
+        &ctx,
+        ConfigScope::Global,
+        &[server],
+        &lockfile,
+        false,
+    )
+    .unwrap();
+
+    let config = read_json(&home.join(ClaudeDesktopClient::config_relative_path()));
+    let entry = &config["mcpServers"]["notes"];
+    assert_eq!(
+        entry["args"],
+        serde_json::json!(["index.js", "--verbose=true", "--mode=safe"])
+    );
+    assert_eq!(entry["env"]["API_KEY"], "${user_config.api_key}");
+    assert_eq!(report.warnings.len(), 1);
+    assert!(report.warnings[0].contains("'api_key' has no value or default"));
+}
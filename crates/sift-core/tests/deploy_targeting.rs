@@ -1,6 +1,9 @@
 //! Tests for client targeting policy.
 
+use sift_core::client::registry::ClientRegistry;
+use sift_core::deploy::scope::{RepoStatus, ResourceKind, ScopeRequest, ScopeResolution};
 use sift_core::deploy::targeting::TargetingPolicy;
+use sift_core::types::ConfigScope;
 
 #[test]
 fn targeting_empty_allows_all() {
@@ -35,3 +38,127 @@ fn targeting_whitelist_takes_precedence() {
     assert!(policy.should_deploy_to("amp"));
     assert!(!policy.should_deploy_to("claude-code"));
 }
+
+#[test]
+fn select_clients_implicit_skips_unsupported_scope() {
+    let registry = ClientRegistry::with_default_clients();
+    let policy = TargetingPolicy::new(None, None);
+
+    let selection = policy
+        .select_clients(
+            &registry,
+            ResourceKind::Mcp,
+            ScopeRequest::Explicit(ConfigScope::PerProjectShared),
+            RepoStatus::NotGit,
+        )
+        .expect("implicit targets should be best-effort");
+
+    assert!(!selection.explicit);
+    assert_eq!(selection.config_scope, ConfigScope::PerProjectShared);
+    assert_eq!(selection.clients.len(), registry.all().len());
+
+    let codex = selection
+        .clients
+        .iter()
+        .find(|t| t.client.id() == "codex")
+        .expect("codex should be selected");
+    assert!(matches!(codex.resolution, ScopeResolution::Skip { .. }));
+
+    let windsurf = selection
+        .clients
+        .iter()
+        .find(|t| t.client.id() == "windsurf")
+        .expect("windsurf should be selected");
+    let ScopeResolution::Skip { warning } = &windsurf.resolution else {
+        panic!("windsurf should be skipped for project scope");
+    };
+    assert!(warning.contains("'windsurf'"), "warning = {warning}");
+
+    let vscode = selection
+        .clients
+        .iter()
+        .find(|t| t.client.id() == "vscode")
+        .expect("vscode should be selected");
+    assert!(matches!(vscode.resolution, ScopeResolution::Apply(_)));
+}
+
+#[test]
+fn select_clients_respects_ignore_targets() {
+    let registry = ClientRegistry::with_default_clients();
+    let policy = TargetingPolicy::new(None, Some(vec!["vscode".into()]));
+
+    let selection = policy
+        .select_clients(
+            &registry,
+            ResourceKind::Mcp,
+            ScopeRequest::Auto,
+            RepoStatus::NotGit,
+        )
+        .expect("selection should succeed");
+
+    assert!(selection.clients.iter().all(|t| t.client.id() != "vscode"));
+}
+
+#[test]
+fn select_clients_explicit_fails_on_unsupported_scope() {
+    let registry = ClientRegistry::with_default_clients();
+    let policy = TargetingPolicy::new(Some(vec!["codex".into()]), None);
+
+    let err = policy
+        .select_clients(
+            &registry,
+            ResourceKind::Mcp,
+            ScopeRequest::Explicit(ConfigScope::PerProjectShared),
+            RepoStatus::NotGit,
+        )
+        .expect_err("explicit targets should fail fast");
+
+    assert!(err.to_string().contains("codex"), "got: {err:#}");
+}
+
+#[test]
+fn select_clients_explicit_rejects_unknown_client() {
+    let registry = ClientRegistry::with_default_clients();
+    let policy = TargetingPolicy::new(Some(vec!["nope".into()]), None);
+
+    let err = policy
+        .select_clients(
+            &registry,
+            ResourceKind::Skill,
+            ScopeRequest::Auto,
+            RepoStatus::NotGit,
+        )
+        .expect_err("unknown target should error");
+
+    assert!(err.to_string().contains("Unknown target client 'nope'"));
+}
+
+#[test]
+fn select_clients_skips_disabled_clients() {
+    let mut registry = ClientRegistry::with_default_clients();
+    registry.disable("cursor");
+
+    for policy in [
+        TargetingPolicy::new(None, None),
+        TargetingPolicy::new(Some(vec!["cursor".into(), "vscode".into()]), None),
+    ] {
+        let selection = policy
+            .select_clients(
+                &registry,
+                ResourceKind::Mcp,
+                ScopeRequest::Explicit(ConfigScope::PerProjectShared),
+                RepoStatus::NotGit,
+            )
+            .expect("disabled clients should not fail selection");
+
+        let cursor = selection
+            .clients
+            .iter()
+            .find(|t| t.client.id() == "cursor")
+            .expect("cursor should be reported");
+        let ScopeResolution::Skip { warning } = &cursor.resolution else {
+            panic!("disabled cursor should be skipped");
+        };
+        assert!(warning.contains("disabled"), "warning = {warning}");
+    }
+}
@@ -0,0 +1,175 @@
+//! Integration tests for the doctor command
+
+use std::path::PathBuf;
+
+use tempfile::TempDir;
+
+use sift_core::commands::{
+    DiagnosticCategory, DiagnosticSeverity, DoctorCommand, DoctorOptions, DoctorReport,
+};
+use sift_core::fs::LinkMode;
+use sift_core::git::GitSpec;
+use sift_core::lockfile::LockfileStore;
+
+mod support;
+use support::http::StaticHttpServer;
+
+struct Paths {
+    home: PathBuf,
+    project: PathBuf,
+    state: PathBuf,
+    config: PathBuf,
+}
+
+fn setup() -> (TempDir, Paths) {
+    let temp = TempDir::new().expect("Failed to create temp dir");
+    let paths = Paths {
+        home: temp.path().join("home"),
+        project: temp.path().join("project"),
+        state: temp.path().join("state"),
+        config: temp.path().join("config"),
+    };
+    for dir in [&paths.home, &paths.project, &paths.state, &paths.config] {
+        std::fs::create_dir_all(dir).expect("Failed to create dir");
+    }
+    (temp, paths)
+}
+
+fn run_doctor(paths: &Paths, options: &DoctorOptions) -> DoctorReport {
+    DoctorCommand::with_global_config_dir(
+        paths.home.clone(),
+        paths.project.clone(),
+        paths.state.clone(),
+        paths.config.clone(),
+        LinkMode::Copy,
+    )
+    .execute(options)
+    .expect("Doctor should produce a report")
+}
+
+#[test]
+fn doctor_reports_every_category_on_clean_environment() {
+    let (_temp, paths) = setup();
+
+    let report = run_doctor(&paths, &DoctorOptions::new());
+
+    for category in [
+        DiagnosticCategory::Runtime,
+        DiagnosticCategory::Git,
+        DiagnosticCategory::Permissions,
+        DiagnosticCategory::Lockfile,
+    ] {
+        assert!(
+            !report.category(category).is_empty(),
+            "missing {:?} diagnostics",
+            category
+        );
+    }
+    let permissions = report.category(DiagnosticCategory::Permissions);
+    assert_eq!(permissions[0].severity, DiagnosticSeverity::Ok);
+}
+
+#[test]
+fn doctor_flags_unparseable_lockfile() {
+    let (_temp, paths) = setup();
+    let locks = paths.state.join("locks");
+    std::fs::create_dir_all(&locks).unwrap();
+    let key = LockfileStore::project_key(&paths.project);
+    std::fs::write(locks.join(format!("{key}.lock.json")), "{ not json").unwrap();
+
+    let report = run_doctor(&paths, &DoctorOptions::new().with_offline(true));
+
+    assert!(report.has_errors());
+    let lockfile = report.category(DiagnosticCategory::Lockfile);
+    assert_eq!(lockfile[0].severity, DiagnosticSeverity::Error);
+    assert!(lockfile[0].fix.as_deref().unwrap().contains("sift install"));
+}
+
+#[test]
+fn doctor_warns_about_declared_entries_not_installed() {
+    let (_temp, paths) = setup();
+    std::fs::write(
+        paths.project.join("sift.toml"),
+        "[mcp.echo]\nsource = \"local:echo\"\nruntime = \"shell\"\n",
+    )
+    .unwrap();
+
+    let report = run_doctor(&paths, &DoctorOptions::new().with_offline(true));
+
+    let lockfile = report.category(DiagnosticCategory::Lockfile);
+    assert_eq!(lockfile.len(), 1);
+    assert_eq!(lockfile[0].subject, "echo");
+    assert_eq!(lockfile[0].severity, DiagnosticSeverity::Warning);
+}
+
+#[test]
+fn doctor_warns_about_unreferenced_bare_repos() {
+    let (_temp, paths) = setup();
+    let source = "git:https://example.com/acme/skills.git";
+    std::fs::write(
+        paths.project.join("sift.toml"),
+        format!("[skill.demo]\nsource = \"{source}\"\n"),
+    )
+    .unwrap();
+    let used = GitSpec::parse(source).unwrap().bare_repo_dir(&paths.state);
+    let stale = paths.state.join("git").join("0123abcd.git");
+    std::fs::create_dir_all(&used).unwrap();
+    std::fs::create_dir_all(&stale).unwrap();
+
+    let report = run_doctor(&paths, &DoctorOptions::new().with_offline(true));
+
+    let cache = report.category(DiagnosticCategory::Cache);
+    assert_eq!(cache.len(), 1);
+    assert_eq!(cache[0].severity, DiagnosticSeverity::Warning);
+    assert_eq!(cache[0].subject, stale.display().to_string());
+}
+
+#[test]
+fn doctor_skips_bare_repo_advice_when_a_lockfile_is_unreadable() {
+    let (_temp, paths) = setup();
+    let locks = paths.state.join("locks");
+    std::fs::create_dir_all(&locks).unwrap();
+    let other = locks.join("other-project.lock.json");
+    std::fs::write(&other, "{ not json").unwrap();
+    std::fs::create_dir_all(paths.state.join("git").join("0123abcd.git")).unwrap();
+
+    let report = run_doctor(&paths, &DoctorOptions::new().with_offline(true));
+
+    let lockfile = report.category(DiagnosticCategory::Lockfile);
+    assert!(lockfile.iter().any(|d| {
+        d.subject == other.display().to_string() && d.severity == DiagnosticSeverity::Error
+    }));
+    let cache = report.category(DiagnosticCategory::Cache);
+    assert_eq!(cache.len(), 1);
+    assert_eq!(cache[0].subject, "git cache");
+    assert!(cache[0].message.contains("could not be read"));
+}
+
+#[test]
+fn doctor_checks_registry_reachability() {
+    let (_temp, paths) = setup();
+    let server = StaticHttpServer::serve(vec![(
+        "/good/index.json",
+        r#"{ "schema_version": 1, "packages": {} }"#.to_string(),
+    )]);
+    std::fs::write(
+        paths.config.join("sift.toml"),
+        format!(
+            "[registry.good]\ntype = \"sift\"\nurl = \"{0}/good\"\n\n\
+             [registry.missing]\ntype = \"sift\"\nurl = \"{0}/missing\"\n",
+            server.base_url()
+        ),
+    )
+    .unwrap();
+
+    let report = run_doctor(&paths, &DoctorOptions::new());
+    let registries = report.category(DiagnosticCategory::Registry);
+    assert_eq!(registries.len(), 2);
+    let good = registries.iter().find(|d| d.subject == "good").unwrap();
+    assert_eq!(good.severity, DiagnosticSeverity::Ok);
+    let missing = registries.iter().find(|d| d.subject == "missing").unwrap();
+    assert_eq!(missing.severity, DiagnosticSeverity::Error);
+
+    let offline = run_doctor(&paths, &DoctorOptions::new().with_offline(true));
+    assert!(offline.category(DiagnosticCategory::Registry).is_empty());
+}
@@ -55,6 +55,7 @@ fn install_mcp_updates_config_and_writes_project_file() {
         args: vec!["--flag".to_string()],
         url: None,
         headers: HashMap::new(),
+        oauth: None,
         targets: None,
         ignore_targets: None,
         env: HashMap::new(),
@@ -62,6 +63,9 @@ fn install_mcp_updates_config_and_writes_project_file() {
         reset_ignore_targets: false,
         reset_env: None,
         reset_env_all: false,
+        volumes: Vec::new(),
+        network: None,
+        always_allow: Vec::new(),
     };
 
     let servers = vec![McpResolvedServer::stdio(
@@ -90,6 +94,8 @@ fn install_mcp_updates_config_and_writes_project_file() {
                 resolution,
                 force: false,
                 declared_version: None,
+                resolved_version: None,
+                checksum: None,
             },
         )
         .unwrap();
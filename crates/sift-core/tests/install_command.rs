@@ -5,7 +5,7 @@ use std::path::Path;
 use tempfile::TempDir;
 use url::Url;
 
-use sift_core::commands::{InstallCommand, InstallOptions};
+use sift_core::commands::{ClientInstallStatus, InstallCommand, InstallOptions};
 use sift_core::fs::LinkMode;
 use sift_core::types::ConfigScope;
 use toml::Value;
@@ -134,8 +134,39 @@ fn install_mcp_writes_lockfile() {
         .get("postgres")
         .expect("MCP server should be locked");
     assert_eq!(locked.constraint, "1.2.3");
-    // TODO: Registry resolution is not implemented yet; resolved_version is placeholder.
-    assert_eq!(locked.resolved_version, "todo");
+    // Without registry version information the constraint is recorded as resolved.
+    assert_eq!(locked.resolved_version, "1.2.3");
+}
+
+#[test]
+fn install_mcp_keeps_previously_installed_servers() {
+    let (temp, cmd) = setup_isolated_install_command();
+
+    for name in ["first", "second"] {
+        let opts = InstallOptions::mcp(name)
+            .with_command(["echo", name])
+            .with_scope(ConfigScope::PerProjectShared);
+        cmd.execute(&opts).expect("Install should succeed");
+    }
+
+    let project = temp.path().join("project");
+    for (path, key) in [
+        (project.join(".mcp.json"), "mcpServers"),
+        (project.join(".vscode").join("mcp.json"), "servers"),
+    ] {
+        let content = std::fs::read_to_string(&path).expect("Should read client config");
+        let json: serde_json::Value = serde_json::from_str(&content).expect("Should parse JSON");
+        assert!(
+            json[key]["first"].is_object(),
+            "{} lost first",
+            path.display()
+        );
+        assert!(
+            json[key]["second"].is_object(),
+            "{} lost second",
+            path.display()
+        );
+    }
 }
 
 #[test]
@@ -633,12 +664,16 @@ source = "git:{}"
 #[test]
 fn install_mcp_registry_without_version_support_ignores_version() {
     let (temp, cmd) = setup_isolated_install_command();
+    let marketplace_url = create_marketplace_repo(temp.path(), &[]);
     let global_config_path = temp.path().join("config").join("sift.toml");
-    let global_config = r#"
+    let global_config = format!(
+        r#"
 [registry.company]
 type = "claude-marketplace"
-source = "github:company/plugins"
-"#;
+source = "git:{}"
+"#,
+        marketplace_url.trim_end_matches('/')
+    );
     std::fs::write(&global_config_path, global_config).expect("Failed to write global config");
 
     let opts = InstallOptions::mcp("demo-mcp")
@@ -1375,3 +1410,71 @@ source = "git:{}"
         "resolved_version should be a commit SHA"
     );
 }
+
+#[test]
+fn install_mcp_fans_out_to_all_supporting_clients() {
+    let (temp, cmd) = setup_isolated_install_command();
+    let project_root = temp.path().join("project");
+
+    let opts = InstallOptions::mcp("postgres")
+        .with_source("registry:postgres-mcp")
+        .with_scope(ConfigScope::PerProjectShared);
+
+    let report = cmd.execute(&opts).expect("Install should succeed");
+    assert!(report.applied);
+
+    assert!(project_root.join(".mcp.json").exists());
+    assert!(project_root.join(".vscode").join("mcp.json").exists());
+
+    let applied: Vec<_> = report
+        .clients
+        .iter()
+        .filter(|c| c.status == ClientInstallStatus::Applied)
+        .map(|c| c.client_id.as_str())
+        .collect();
+    assert!(applied.contains(&"claude-code"));
+    assert!(applied.contains(&"vscode"));
+
+    let codex = report
+        .clients
+        .iter()
+        .find(|c| c.client_id == "codex")
+        .expect("codex should be reported");
+    assert_eq!(codex.status, ClientInstallStatus::Skipped);
+    assert!(report.warnings.iter().any(|w| w.contains("codex")));
+}
+
+#[test]
+fn install_mcp_with_targets_only_deploys_to_targets() {
+    let (temp, cmd) = setup_isolated_install_command();
+    let project_root = temp.path().join("project");
+
+    let opts = InstallOptions::mcp("postgres")
+        .with_source("registry:postgres-mcp")
+        .with_scope(ConfigScope::PerProjectShared)
+        .with_targets(["vscode"]);
+
+    let report = cmd.execute(&opts).expect("Install should succeed");
+
+    assert!(project_root.join(".vscode").join("mcp.json").exists());
+    assert!(!project_root.join(".mcp.json").exists());
+    assert_eq!(report.clients.len(), 1);
+    assert_eq!(report.clients[0].client_id, "vscode");
+    assert_eq!(report.clients[0].status, ClientInstallStatus::Applied);
+}
+
+#[test]
+fn install_mcp_explicit_target_without_scope_support_fails() {
+    let (temp, cmd) = setup_isolated_install_command();
+
+    let opts = InstallOptions::mcp("postgres")
+        .with_source("registry:postgres-mcp")
+        .with_scope(ConfigScope::PerProjectShared)
+        .with_targets(["codex"]);
+
+    let err = cmd
+        .execute(&opts)
+        .expect_err("Explicit target without scope support should fail");
+    assert!(format!("{err:#}").contains("codex"));
+    assert!(!temp.path().join("project").join("sift.toml").exists());
+}
@@ -105,6 +105,7 @@ fn install_mcp_adds_entry() {
         args: vec!["--flag".to_string()],
         url: None,
         headers: HashMap::new(),
+        oauth: None,
         targets: None,
         ignore_targets: None,
         env: HashMap::new(),
@@ -112,6 +113,9 @@ fn install_mcp_adds_entry() {
         reset_ignore_targets: false,
         reset_env: None,
         reset_env_all: false,
+        volumes: Vec::new(),
+        network: None,
+        always_allow: Vec::new(),
     };
 
     let outcome = service.install_mcp("demo-mcp", entry, false).unwrap();
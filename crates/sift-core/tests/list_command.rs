@@ -0,0 +1,154 @@
+//! Integration tests for the list command
+
+use std::path::PathBuf;
+
+use tempfile::TempDir;
+
+use sift_core::commands::{
+    InstallTarget, ListCommand, ListOptions, ListReport, SyncCommand, SyncOptions,
+};
+use sift_core::fs::LinkMode;
+use sift_core::types::ConfigScope;
+
+struct Paths {
+    home: PathBuf,
+    project: PathBuf,
+    state: PathBuf,
+    config: PathBuf,
+}
+
+fn setup() -> (TempDir, Paths) {
+    let temp = TempDir::new().expect("Failed to create temp dir");
+    let paths = Paths {
+        home: temp.path().join("home"),
+        project: temp.path().join("project"),
+        state: temp.path().join("state"),
+        config: temp.path().join("config"),
+    };
+    for dir in [&paths.home, &paths.project, &paths.state, &paths.config] {
+        std::fs::create_dir_all(dir).expect("Failed to create dir");
+    }
+    (temp, paths)
+}
+
+fn list(paths: &Paths, options: &ListOptions) -> ListReport {
+    ListCommand::with_global_config_dir(
+        paths.home.clone(),
+        paths.project.clone(),
+        paths.state.clone(),
+        paths.config.clone(),
+        LinkMode::Copy,
+    )
+    .execute(options)
+    .expect("List should succeed")
+}
+
+fn write_configs(paths: &Paths) {
+    std::fs::write(
+        paths.config.join("sift.toml"),
+        r#"
+[mcp.global-echo]
+source = "local:echo"
+runtime = "shell"
+"#,
+    )
+    .expect("Failed to write global sift.toml");
+
+    let skill_dir = paths.project.join("skills").join("commit");
+    std::fs::create_dir_all(&skill_dir).expect("Failed to create skill dir");
+    std::fs::write(
+        skill_dir.join("SKILL.md"),
+        "---\nname: commit\ndescription: Test skill\n---\n\nTest instructions.\n",
+    )
+    .expect("Failed to write SKILL.md");
+    std::fs::write(
+        paths.project.join("sift.toml"),
+        r#"
+[mcp.remote]
+transport = "http"
+url = "https://mcp.example.com"
+targets = ["vscode"]
+
+[skill.commit]
+source = "local:./skills/commit"
+"#,
+    )
+    .expect("Failed to write sift.toml");
+}
+
+#[test]
+fn list_joins_declared_entries_with_lockfile() {
+    let (_temp, paths) = setup();
+    write_configs(&paths);
+
+    let before = list(&paths, &ListOptions::new());
+    assert_eq!(before.entries.len(), 3);
+    assert!(before.entries.iter().all(|e| e.resolved_version.is_none()));
+
+    SyncCommand::with_global_config_dir(
+        paths.home.clone(),
+        paths.project.clone(),
+        paths.state.clone(),
+        paths.config.clone(),
+        LinkMode::Copy,
+    )
+    .execute(&SyncOptions::new())
+    .expect("Sync should succeed");
+
+    let report = list(&paths, &ListOptions::new());
+    let names: Vec<_> = report.entries.iter().map(|e| e.name.as_str()).collect();
+    assert_eq!(names, ["global-echo", "remote", "commit"]);
+
+    let echo = &report.entries[0];
+    assert_eq!(echo.scope, ConfigScope::Global);
+    assert_eq!(echo.runtime.as_deref(), Some("shell"));
+    assert_eq!(echo.transport.as_deref(), Some("stdio"));
+    assert!(echo.targets.iter().any(|t| t == "claude-code"));
+
+    let remote = &report.entries[1];
+    assert_eq!(remote.transport.as_deref(), Some("http"));
+    assert_eq!(remote.runtime, None);
+    assert_eq!(remote.targets, ["vscode"]);
+
+    let commit = &report.entries[2];
+    assert_eq!(commit.kind, InstallTarget::Skill);
+    assert_eq!(commit.scope, ConfigScope::PerProjectShared);
+    assert!(commit.resolved_version.is_some());
+}
+
+#[test]
+fn list_filters_by_kind_scope_and_client() {
+    let (_temp, paths) = setup();
+    write_configs(&paths);
+
+    let skills = list(&paths, &ListOptions::new().with_kind(InstallTarget::Skill));
+    assert_eq!(skills.entries.len(), 1);
+    assert_eq!(skills.entries[0].name, "commit");
+
+    let global = list(&paths, &ListOptions::new().with_scope(ConfigScope::Global));
+    assert_eq!(global.entries.len(), 1);
+    assert_eq!(global.entries[0].name, "global-echo");
+
+    let claude = list(
+        &paths,
+        &ListOptions::new()
+            .with_kind(InstallTarget::Mcp)
+            .with_client("claude-code"),
+    );
+    let names: Vec<_> = claude.entries.iter().map(|e| e.name.as_str()).collect();
+    assert_eq!(names, ["global-echo"]);
+}
+
+#[test]
+fn list_rejects_unknown_client() {
+    let (_temp, paths) = setup();
+    let result = ListCommand::with_global_config_dir(
+        paths.home.clone(),
+        paths.project.clone(),
+        paths.state.clone(),
+        paths.config.clone(),
+        LinkMode::Copy,
+    )
+    .execute(&ListOptions::new().with_client("nope"));
+    assert!(result.is_err());
+}
@@ -217,6 +217,7 @@ fn mcp_registry_resolution_structure_contains_metadata() {
             args: vec![],
             url: None,
             headers: std::collections::HashMap::new(),
+            oauth: None,
             targets: None,
             ignore_targets: None,
             env: std::collections::HashMap::new(),
@@ -18,6 +18,7 @@ fn create_test_entry() -> McpConfigEntry {
         args: vec![],
         url: None,
         headers: HashMap::new(),
+        oauth: None,
         targets: None,
         ignore_targets: None,
         env: HashMap::new(),
@@ -25,6 +26,9 @@ fn create_test_entry() -> McpConfigEntry {
         reset_ignore_targets: false,
         reset_env: None,
         reset_env_all: false,
+        volumes: Vec::new(),
+        network: None,
+        always_allow: Vec::new(),
     }
 }
 
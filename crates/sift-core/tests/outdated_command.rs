@@ -0,0 +1,242 @@
+//! Integration tests for the outdated command
+//!
+//! Skills come from local git repositories; MCP servers come from a Sift
+//! registry served by a local HTTP stand-in.
+
+use std::path::{Path, PathBuf};
+
+use tempfile::TempDir;
+use url::Url;
+
+use sift_core::commands::{
+    InstallCommand, InstallOptions, InstallTarget, OutdatedCommand, OutdatedOptions, SyncCommand,
+    SyncOptions,
+};
+use sift_core::fs::LinkMode;
+use sift_core::types::ConfigScope;
+
+mod support;
+use support::git::git_command;
+use support::http::StaticHttpServer;
+
+struct Paths {
+    home: PathBuf,
+    project: PathBuf,
+    state: PathBuf,
+    config: PathBuf,
+    repo: PathBuf,
+}
+
+fn setup() -> (TempDir, Paths) {
+    let temp = TempDir::new().expect("Failed to create temp dir");
+    let paths = Paths {
+        home: temp.path().join("home"),
+        project: temp.path().join("project"),
+        state: temp.path().join("state"),
+        config: temp.path().join("config"),
+        repo: temp.path().join("repo"),
+    };
+    for dir in [&paths.home, &paths.project, &paths.state, &paths.config] {
+        std::fs::create_dir_all(dir).expect("Failed to create dir");
+    }
+    (temp, paths)
+}
+
+fn outdated_command(paths: &Paths) -> OutdatedCommand {
+    OutdatedCommand::with_global_config_dir(
+        paths.home.clone(),
+        paths.project.clone(),
+        paths.state.clone(),
+        paths.config.clone(),
+        LinkMode::Copy,
+    )
+}
+
+fn sync(paths: &Paths) {
+    SyncCommand::with_global_config_dir(
+        paths.home.clone(),
+        paths.project.clone(),
+        paths.state.clone(),
+        paths.config.clone(),
+        LinkMode::Copy,
+    )
+    .execute(&SyncOptions::new())
+    .expect("Sync should succeed");
+}
+
+fn run_git(repo: &Path, args: &[&str]) {
+    let status = git_command()
+        .args(args)
+        .current_dir(repo)
+        .status()
+        .expect("Failed to invoke git");
+    assert!(status.success(), "git command failed: {:?}", args);
+}
+
+/// Commit a new revision of the skill at the repository root.
+fn commit_skill(repo: &Path, body: &str) {
+    if !repo.exists() {
+        std::fs::create_dir_all(repo).expect("Failed to create repo dir");
+        run_git(repo, &["init"]);
+        run_git(repo, &["checkout", "-b", "main"]);
+        run_git(repo, &["config", "user.email", "test@example.com"]);
+        run_git(repo, &["config", "user.name", "Test User"]);
+        run_git(repo, &["config", "commit.gpgsign", "false"]);
+    }
+    let content = format!("---\nname: demo\ndescription: Test skill\n---\n\n{body}\n");
+    std::fs::write(repo.join("SKILL.md"), content).expect("Failed to write SKILL.md");
+    run_git(repo, &["add", "."]);
+    run_git(repo, &["commit", "-m", body]);
+}
+
+fn write_skill_config(paths: &Paths, version: Option<&str>) {
+    let url = Url::from_directory_path(&paths.repo)
+        .expect("repo root should convert to file URL")
+        .to_string();
+    let version = version
+        .map(|v| format!("version = \"{v}\"\n"))
+        .unwrap_or_default();
+    std::fs::write(
+        paths.project.join("sift.toml"),
+        format!(
+            "[skill.demo]\nsource = \"git:{}\"\n{}",
+            url.trim_end_matches('/'),
+            version
+        ),
+    )
+    .expect("Failed to write sift.toml");
+}
+
+#[test]
+fn outdated_reports_wanted_and_latest_skill_tags() {
+    let (_temp, paths) = setup();
+    commit_skill(&paths.repo, "release one");
+    run_git(&paths.repo, &["tag", "v1.0.0"]);
+    write_skill_config(&paths, Some("^1.0"));
+    sync(&paths);
+
+    commit_skill(&paths.repo, "release two");
+    run_git(&paths.repo, &["tag", "v1.1.0"]);
+    commit_skill(&paths.repo, "breaking");
+    run_git(&paths.repo, &["tag", "v2.0.0"]);
+
+    let report = outdated_command(&paths)
+        .execute(&OutdatedOptions::new())
+        .expect("Outdated should succeed");
+
+    assert!(report.has_outdated());
+    assert!(!report.has_errors(), "unexpected errors: {:?}", report);
+    let entry = &report.entries[0];
+    assert_eq!(entry.kind, InstallTarget::Skill);
+    assert_eq!(entry.constraint, "^1.0");
+    assert!(entry.wanted.as_deref().unwrap().starts_with("v1.1.0"));
+    assert!(entry.latest.as_deref().unwrap().starts_with("v2.0.0"));
+    assert!(entry.upgradable);
+}
+
+#[test]
+fn outdated_reports_new_commits_on_tracked_branch() {
+    let (_temp, paths) = setup();
+    commit_skill(&paths.repo, "first");
+    write_skill_config(&paths, None);
+    sync(&paths);
+
+    let report = outdated_command(&paths)
+        .execute(&OutdatedOptions::new())
+        .expect("Outdated should succeed");
+    assert!(!report.has_outdated());
+
+    commit_skill(&paths.repo, "second");
+    let report = outdated_command(&paths)
+        .execute(&OutdatedOptions::new())
+        .expect("Outdated should succeed");
+
+    let entry = &report.entries[0];
+    assert!(entry.outdated && entry.upgradable);
+    assert_eq!(entry.constraint, "HEAD");
+    assert_eq!(entry.wanted, entry.latest);
+}
+
+#[test]
+fn outdated_warns_for_entries_not_installed() {
+    let (_temp, paths) = setup();
+    commit_skill(&paths.repo, "first");
+    write_skill_config(&paths, None);
+
+    let report = outdated_command(&paths)
+        .execute(&OutdatedOptions::new())
+        .expect("Outdated should succeed");
+
+    assert!(report.entries.is_empty());
+    assert_eq!(report.warnings.len(), 1);
+}
+
+fn registry_index(versions: &[&str]) -> String {
+    let versions: Vec<String> = versions
+        .iter()
+        .map(|v| format!(r#""{v}": {{ "mcp": {{ "package": "@acme/postgres-mcp" }} }}"#))
+        .collect();
+    format!(
+        r#"{{
+            "schema_version": 1,
+            "packages": {{
+                "postgres": {{
+                    "default_runtime": "node",
+                    "versions": {{ {} }}
+                }}
+            }}
+        }}"#,
+        versions.join(", ")
+    )
+}
+
+fn use_registry(paths: &Paths, server: &StaticHttpServer, path: &str) {
+    std::fs::write(
+        paths.config.join("sift.toml"),
+        format!(
+            "[registry.internal]\ntype = \"sift\"\nurl = \"{}{}\"\n",
+            server.base_url(),
+            path
+        ),
+    )
+    .expect("Failed to write global sift.toml");
+}
+
+#[test]
+fn outdated_compares_mcp_lock_with_registry_versions() {
+    let (_temp, paths) = setup();
+    let server = StaticHttpServer::serve(vec![
+        ("/v1/index.json", registry_index(&["1.2.3"])),
+        (
+            "/v2/index.json",
+            registry_index(&["1.2.3", "1.3.0", "2.0.0"]),
+        ),
+    ]);
+    use_registry(&paths, &server, "/v1");
+    InstallCommand::with_global_config_dir(
+        paths.home.clone(),
+        paths.project.clone(),
+        paths.state.clone(),
+        paths.config.clone(),
+        LinkMode::Copy,
+    )
+    .execute(
+        &InstallOptions::mcp("postgres")
+            .with_version("^1.2")
+            .with_scope(ConfigScope::PerProjectShared),
+    )
+    .expect("Install should succeed");
+
+    use_registry(&paths, &server, "/v2");
+    let report = outdated_command(&paths)
+        .execute(&OutdatedOptions::new().with_kind(InstallTarget::Mcp))
+        .expect("Outdated should succeed");
+
+    assert!(!report.has_errors(), "unexpected errors: {:?}", report);
+    let entry = &report.entries[0];
+    assert_eq!(entry.name, "postgres");
+    assert_eq!(entry.current, "1.2.3");
+    assert_eq!(entry.wanted.as_deref(), Some("1.3.0"));
+    assert_eq!(entry.latest.as_deref(), Some("2.0.0"));
+    assert!(entry.outdated && entry.upgradable);
+}
@@ -0,0 +1,159 @@
+//! Integration tests for the probe command
+
+mod support;
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use tempfile::TempDir;
+
+use sift_core::commands::{ProbeCommand, ProbeOptions, ProbeReport};
+use sift_core::fs::LinkMode;
+use sift_core::types::ConfigScope;
+
+use support::http::McpHttpServer;
+
+struct Paths {
+    home: PathBuf,
+    project: PathBuf,
+    state: PathBuf,
+    config: PathBuf,
+}
+
+fn setup() -> (TempDir, Paths) {
+    let temp = TempDir::new().expect("Failed to create temp dir");
+    let paths = Paths {
+        home: temp.path().join("home"),
+        project: temp.path().join("project"),
+        state: temp.path().join("state"),
+        config: temp.path().join("config"),
+    };
+    for dir in [&paths.home, &paths.project, &paths.state, &paths.config] {
+        std::fs::create_dir_all(dir).expect("Failed to create dir");
+    }
+    (temp, paths)
+}
+
+fn probe(paths: &Paths, options: &ProbeOptions) -> anyhow::Result<ProbeReport> {
+    ProbeCommand::with_global_config_dir(
+        paths.home.clone(),
+        paths.project.clone(),
+        paths.state.clone(),
+        paths.config.clone(),
+        LinkMode::Copy,
+    )
+    .execute(options)
+}
+
+/// Answers initialize with `$SERVER_NAME` as its name and lists one tool.
+const FAKE_SERVER: &str = r#"while IFS= read -r line; do case "$line" in *'"initialize"'*) printf '{"jsonrpc":"2.0","id":1,"result":{"protocolVersion":"2025-06-18","serverInfo":{"name":"%s","version":"1.0.0"}}}\n' "$SERVER_NAME" ;; *'"tools/list"'*) echo '{"jsonrpc":"2.0","id":2,"result":{"tools":[{"name":"echo"}]}}' ;; esac; done"#;
+
+fn write_config(paths: &Paths, remote_url: &str) {
+    let config = format!(
+        r#"
+[mcp.local]
+source = "local:sh"
+runtime = "shell"
+args = ["-c", '''{}''']
+env = {{ SERVER_NAME = "${{SIFT_PROBE_TEST_NAME}}" }}
+
+[mcp.broken]
+source = "local:sift-probe-test-missing-binary"
+runtime = "shell"
+
+[mcp.remote]
+source = "local:remote"
+transport = "http"
+url = "{}"
+"#,
+        FAKE_SERVER, remote_url
+    );
+    std::fs::write(paths.project.join("sift.toml"), config).expect("Failed to write sift.toml");
+    std::fs::write(
+        paths.project.join(".env"),
+        "SIFT_PROBE_TEST_NAME=from-dotenv\n",
+    )
+    .expect("Failed to write .env");
+}
+
+#[test]
+fn probe_reports_each_declared_server() {
+    let (_temp, paths) = setup();
+    let remote = McpHttpServer::serve(3);
+    write_config(&paths, remote.url());
+
+    let report = probe(
+        &paths,
+        &ProbeOptions::new().with_timeout(Duration::from_secs(10)),
+    )
+    .expect("Probe should succeed");
+
+    let names: Vec<_> = report.entries.iter().map(|e| e.name.as_str()).collect();
+    assert_eq!(names, vec!["broken", "local", "remote"]);
+    assert!(report.has_failures());
+
+    let broken = &report.entries[0];
+    assert!(broken.result.is_none());
+    assert!(
+        broken
+            .error
+            .as_deref()
+            .unwrap()
+            .contains("Failed to start 'sift-probe-test-missing-binary'")
+    );
+
+    // ${VAR} references are resolved before the server starts
+    let local = report.entries[1].result.as_ref().expect("local answers");
+    assert_eq!(local.server_name.as_deref(), Some("from-dotenv"));
+    assert_eq!(local.server_version.as_deref(), Some("1.0.0"));
+    assert_eq!(local.tool_count, 1);
+
+    let remote = report.entries[2].result.as_ref().expect("remote answers");
+    assert_eq!(remote.server_name.as_deref(), Some("remote"));
+    assert_eq!(remote.protocol_version.as_deref(), Some("2025-06-18"));
+    assert_eq!(remote.tool_count, 3);
+}
+
+#[test]
+fn probe_single_server_by_name() {
+    let (_temp, paths) = setup();
+    let remote = McpHttpServer::serve(0);
+    write_config(&paths, remote.url());
+
+    let report = probe(&paths, &ProbeOptions::new().with_name("local")).unwrap();
+    assert_eq!(report.entries.len(), 1);
+    assert!(!report.has_failures());
+
+    let err = probe(&paths, &ProbeOptions::new().with_name("missing")).unwrap_err();
+    assert!(err.to_string().contains("not declared"));
+}
+
+#[test]
+fn probe_only_servers_declared_at_scope() {
+    let (_temp, paths) = setup();
+    let remote = McpHttpServer::serve(0);
+    write_config(&paths, remote.url());
+    std::fs::write(
+        paths.config.join("sift.toml"),
+        "[mcp.global-only]\nsource = \"local:sift-probe-test-missing-binary\"\nruntime = \"shell\"\n",
+    )
+    .expect("Failed to write global config");
+
+    let report = probe(
+        &paths,
+        &ProbeOptions::new()
+            .with_scope(ConfigScope::Global)
+            .with_timeout(Duration::from_secs(10)),
+    )
+    .unwrap();
+    let names: Vec<_> = report.entries.iter().map(|e| e.name.as_str()).collect();
+    assert_eq!(names, vec!["global-only"]);
+
+    let report = probe(
+        &paths,
+        &ProbeOptions::new().with_scope(ConfigScope::PerProjectShared),
+    )
+    .unwrap();
+    let names: Vec<_> = report.entries.iter().map(|e| e.name.as_str()).collect();
+    assert_eq!(names, vec!["broken", "local", "remote"]);
+}
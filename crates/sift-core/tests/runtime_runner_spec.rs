@@ -48,3 +48,29 @@ fn npx_runtime_includes_cache_dir_env() {
         Some("/tmp/sift-cache")
     );
 }
+
+#[test]
+fn docker_runtime_runs_tagged_image() {
+    let request = RuntimeRequest {
+        kind: RuntimeKind::Docker,
+        package: "ghcr.io/acme/mcp".to_string(),
+        version: "1.2.3".to_string(),
+        cache_dir: PathBuf::from("/tmp/sift-cache"),
+        extra_args: vec!["--stdio".to_string()],
+    };
+
+    let spec = resolve_runtime(&request).unwrap();
+
+    assert_eq!(spec.command, "docker");
+    assert_eq!(
+        spec.args,
+        vec![
+            "run".to_string(),
+            "-i".to_string(),
+            "--rm".to_string(),
+            "ghcr.io/acme/mcp:1.2.3".to_string(),
+            "--stdio".to_string()
+        ]
+    );
+    assert!(spec.env.is_empty());
+}
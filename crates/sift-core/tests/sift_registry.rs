@@ -0,0 +1,358 @@
+//! Integration tests for native Sift registry resolution over HTTP
+//!
+//! A local HTTP stand-in serves the registry index so no network is needed.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use sift_core::commands::{InstallCommand, InstallOptions};
+use sift_core::fs::LinkMode;
+use sift_core::lockfile::LockfileStore;
+use sift_core::mcp::TransportType;
+use sift_core::registry::{RegistryConfig, RegistryType};
+use sift_core::source::SourceResolver;
+use sift_core::types::ConfigScope;
+use tempfile::TempDir;
+
+mod support;
+use support::http::StaticHttpServer;
+
+fn registry_index() -> String {
+    r#"{
+        "schema_version": 1,
+        "packages": {
+            "postgres": {
+                "description": "PostgreSQL MCP server",
+                "default_runtime": "node",
+                "latest": "1.3.0",
+                "versions": {
+                    "1.2.3": { "mcp": { "package": "@acme/postgres-mcp", "env": { "PGHOST": "localhost" } } },
+                    "1.3.0": { "mcp": { "package": "@acme/postgres-mcp" } }
+                }
+            },
+            "fetch": {
+                "default_runtime": "python",
+                "versions": {
+                    "2025.1.0": { "mcp": { "package": "mcp-server-fetch" } }
+                }
+            },
+            "search": {
+                "versions": {
+                    "0.1.0": { "mcp": { "url": "https://search.example.com/mcp", "headers": { "X-Team": "core" } } }
+                }
+            },
+            "pdf": {
+                "versions": {
+                    "0.4.0": { "skill": { "source": "github:acme/skills", "ref": "v0.4.0", "path": "pdf" } }
+                }
+            },
+            "docs": {
+                "versions": {
+                    "2.0.0": { "skill": { "source": "github:acme/skills", "ref": "v2.0.0", "path": "docs", "skills": ["./xlsx", "./pptx"] } }
+                }
+            }
+        }
+    }"#
+    .to_string()
+}
+
+fn serve_registry() -> StaticHttpServer {
+    StaticHttpServer::serve(vec![("/v1/index.json", registry_index())])
+}
+
+fn resolver_for(server: &StaticHttpServer) -> SourceResolver {
+    let mut registries = HashMap::new();
+    registries.insert(
+        "internal".to_string(),
+        RegistryConfig {
+            r#type: RegistryType::Sift,
+            url: Some(url::Url::parse(&format!("{}/v1", server.base_url())).unwrap()),
+            source: None,
+        },
+    );
+    SourceResolver::new(
+        PathBuf::from("/tmp/state"),
+        PathBuf::from("/tmp/project"),
+        registries,
+    )
+}
+
+#[test]
+fn resolve_mcp_pins_requested_version() {
+    let server = serve_registry();
+    let resolver = resolver_for(&server);
+
+    let resolutions = resolver
+        .resolve_mcp_registry_version("internal/postgres", Some("1.2.3"))
+        .expect("Resolution should succeed")
+        .expect("Package provides an MCP server");
+
+    assert_eq!(resolutions.len(), 1);
+    let resolution = &resolutions[0];
+    assert_eq!(resolution.registry_key, "internal");
+    assert_eq!(resolution.plugin_name, "postgres");
+    assert_eq!(resolution.plugin_version, "1.2.3");
+    assert_eq!(
+        resolution.mcp_config.source,
+        "registry:internal/postgres:npx"
+    );
+    assert_eq!(
+        resolution.mcp_config.args,
+        vec!["-y", "@acme/postgres-mcp@1.2.3"]
+    );
+    assert_eq!(
+        resolution.mcp_config.env.get("PGHOST"),
+        Some(&"localhost".to_string())
+    );
+}
+
+#[test]
+fn resolve_mcp_defaults_to_latest() {
+    let server = serve_registry();
+    let resolver = resolver_for(&server);
+
+    let resolutions = resolver.resolve_mcp_registry("postgres").unwrap().unwrap();
+    assert_eq!(resolutions[0].plugin_version, "1.3.0");
+}
+
+#[test]
+fn resolve_mcp_inline_version_in_source() {
+    let server = serve_registry();
+    let resolver = resolver_for(&server);
+
+    let resolutions = resolver
+        .resolve_mcp_registry("internal/postgres@1.2.3")
+        .unwrap()
+        .unwrap();
+    assert_eq!(resolutions[0].plugin_version, "1.2.3");
+}
+
+#[test]
+fn resolve_mcp_http_entry() {
+    let server = serve_registry();
+    let resolver = resolver_for(&server);
+
+    let resolutions = resolver
+        .resolve_mcp_registry("internal/search")
+        .unwrap()
+        .unwrap();
+    let config = &resolutions[0].mcp_config;
+    assert_eq!(config.transport, TransportType::Http);
+    assert_eq!(
+        config.url.as_deref(),
+        Some("https://search.example.com/mcp")
+    );
+    assert_eq!(config.headers.get("X-Team"), Some(&"core".to_string()));
+}
+
+#[test]
+fn resolve_mcp_skill_only_package_returns_none() {
+    let server = serve_registry();
+    let resolver = resolver_for(&server);
+
+    let result = resolver.resolve_mcp_registry("internal/pdf").unwrap();
+    assert!(result.is_none());
+}
+
+#[test]
+fn resolve_mcp_unknown_package_errors() {
+    let server = serve_registry();
+    let resolver = resolver_for(&server);
+
+    let err = resolver
+        .resolve_mcp_registry("internal/missing")
+        .unwrap_err()
+        .to_string();
+    assert!(
+        err.contains("Package not found"),
+        "unexpected error: {}",
+        err
+    );
+}
+
+#[test]
+fn resolve_missing_index_reports_http_status() {
+    let server = StaticHttpServer::serve(vec![]);
+    let resolver = resolver_for(&server);
+
+    let err = format!(
+        "{:#}",
+        resolver.resolve_mcp_registry("postgres").unwrap_err()
+    );
+    assert!(err.contains("404"), "unexpected error: {}", err);
+}
+
+#[test]
+fn resolve_skill_builds_git_spec_and_metadata() {
+    let server = serve_registry();
+    let resolver = resolver_for(&server);
+
+    let (source, metadata) = resolver
+        .resolve_with_metadata("registry:internal/pdf")
+        .expect("Resolution should succeed");
+
+    let sift_core::source::ResolvedSource::Git(spec) = source else {
+        panic!("Expected git source");
+    };
+    assert_eq!(spec.repo_url, "https://github.com/acme/skills");
+    assert_eq!(spec.reference.as_deref(), Some("v0.4.0"));
+    assert_eq!(spec.subdir.as_deref(), Some("pdf"));
+
+    let metadata = metadata.expect("Registry metadata");
+    assert_eq!(metadata.registry_key, "internal");
+    assert_eq!(metadata.skill_name, "pdf");
+    assert_eq!(metadata.marketplace_version, "0.4.0");
+}
+
+#[test]
+fn resolve_skill_group_expands_nested_skills() {
+    let server = serve_registry();
+    let resolver = resolver_for(&server);
+
+    let resolutions = resolver
+        .resolve_registry_with_expansion("internal/docs")
+        .expect("Expansion should succeed");
+
+    assert_eq!(resolutions.len(), 3);
+    assert!(resolutions[0].metadata.is_group);
+    assert_eq!(resolutions[1].metadata.skill_name, "docs/xlsx");
+    assert_eq!(resolutions[1].git_spec.subdir.as_deref(), Some("docs/xlsx"));
+    assert_eq!(resolutions[2].metadata.aliases[0], "pptx");
+    assert_eq!(
+        resolutions[2].metadata.parent_plugin.as_deref(),
+        Some("docs")
+    );
+
+    let (source, _) = resolver
+        .resolve_with_metadata("registry:internal/docs/pptx")
+        .unwrap();
+    let sift_core::source::ResolvedSource::Git(spec) = source else {
+        panic!("Expected git source");
+    };
+    assert_eq!(spec.subdir.as_deref(), Some("docs/pptx"));
+}
+
+#[test]
+fn install_mcp_from_sift_registry_with_version() {
+    let server = serve_registry();
+    let temp = TempDir::new().unwrap();
+    let home = temp.path().join("home");
+    let project = temp.path().join("project");
+    let state = temp.path().join("state");
+    let global_config = temp.path().join("config");
+    for dir in [&home, &project, &state, &global_config] {
+        std::fs::create_dir_all(dir).unwrap();
+    }
+    std::fs::write(
+        global_config.join("sift.toml"),
+        format!(
+            r#"
+[registry.internal]
+type = "sift"
+url = "{}/v1"
+"#,
+            server.base_url()
+        ),
+    )
+    .unwrap();
+
+    let cmd = InstallCommand::with_global_config_dir(
+        home,
+        project.clone(),
+        state,
+        global_config,
+        LinkMode::Copy,
+    );
+    let opts = InstallOptions::mcp("postgres")
+        .with_version("1.2.3")
+        .with_scope(ConfigScope::PerProjectShared);
+
+    let report = cmd.execute(&opts).expect("Install should succeed");
+    assert!(report.changed);
+
+    let content = std::fs::read_to_string(project.join(".mcp.json")).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&content).unwrap();
+    let server_entry = &json["mcpServers"]["postgres"];
+    assert_eq!(server_entry["command"], "npx");
+    assert_eq!(
+        server_entry["args"],
+        serde_json::json!(["-y", "@acme/postgres-mcp@1.2.3"])
+    );
+}
+
+#[test]
+fn install_python_mcp_from_sift_registry_isolates_cache() {
+    let server = serve_registry();
+    let temp = TempDir::new().unwrap();
+    let home = temp.path().join("home");
+    let project = temp.path().join("project");
+    let state = temp.path().join("state");
+    let global_config = temp.path().join("config");
+    for dir in [&home, &project, &state, &global_config] {
+        std::fs::create_dir_all(dir).unwrap();
+    }
+    std::fs::write(
+        global_config.join("sift.toml"),
+        format!(
+            "[registry.internal]\ntype = \"sift\"\nurl = \"{}/v1\"\n",
+            server.base_url()
+        ),
+    )
+    .unwrap();
+
+    InstallCommand::with_global_config_dir(
+        home.clone(),
+        project.clone(),
+        state.clone(),
+        global_config.clone(),
+        LinkMode::Copy,
+    )
+    .execute(&InstallOptions::mcp("fetch").with_scope(ConfigScope::PerProjectShared))
+    .expect("Install should succeed");
+
+    // The shared config is used on other machines: always uvx with the
+    // pinned requirement, and no cache directory from this one
+    let content = std::fs::read_to_string(project.join(".mcp.json")).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&content).unwrap();
+    let server_entry = &json["mcpServers"]["fetch"];
+    assert_eq!(server_entry["command"], "uvx");
+    assert_eq!(
+        server_entry["args"],
+        serde_json::json!(["mcp-server-fetch==2025.1.0"])
+    );
+    assert!(
+        server_entry["env"]
+            .as_object()
+            .is_none_or(|env| env.is_empty())
+    );
+
+    // A local install keeps the launcher cache isolated under the state dir
+    InstallCommand::with_global_config_dir(
+        home.clone(),
+        project.clone(),
+        state.clone(),
+        global_config,
+        LinkMode::Copy,
+    )
+    .execute(&InstallOptions::mcp("fetch").with_scope(ConfigScope::PerProjectLocal))
+    .expect("Install should succeed");
+
+    let content = std::fs::read_to_string(home.join(".claude.json")).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&content).unwrap();
+    let project_key = project.to_string_lossy();
+    let server_entry = &json["projects"][project_key.as_ref()]["mcpServers"]["fetch"];
+    // uvx where available, pipx otherwise; both take the pinned requirement
+    let args = server_entry["args"].as_array().unwrap();
+    assert!(args.contains(&serde_json::json!("mcp-server-fetch==2025.1.0")));
+    let cache = server_entry["env"]
+        .as_object()
+        .unwrap()
+        .values()
+        .next()
+        .and_then(|v| v.as_str())
+        .expect("launcher cache should be set");
+    assert!(cache.starts_with(state.join("cache").to_str().unwrap()));
+
+    let lockfile = LockfileStore::load(Some(project), state.join("locks")).unwrap();
+    assert_eq!(lockfile.mcp_servers["fetch"].resolved_version, "2025.1.0");
+}
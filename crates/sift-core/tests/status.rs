@@ -146,12 +146,16 @@ fn mcp_entry_state_ok_when_config_has_empty_constraint() {
             args: Vec::new(),
             url: None,
             headers: HashMap::new(),
+            oauth: None,
             targets: None,
             ignore_targets: None,
             reset_targets: false,
             reset_ignore_targets: false,
             reset_env: None,
             reset_env_all: false,
+            volumes: Vec::new(),
+            network: None,
+            always_allow: Vec::new(),
             env: HashMap::new(),
         },
     );
@@ -187,12 +191,16 @@ fn mcp_entry_state_not_locked_when_only_in_config() {
             args: Vec::new(),
             url: None,
             headers: HashMap::new(),
+            oauth: None,
             targets: None,
             ignore_targets: None,
             reset_targets: false,
             reset_ignore_targets: false,
             reset_env: None,
             reset_env_all: false,
+            volumes: Vec::new(),
+            network: None,
+            always_allow: Vec::new(),
             env: HashMap::new(),
         },
     );
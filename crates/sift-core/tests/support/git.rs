@@ -1,3 +1,5 @@
+#![allow(dead_code)]
+
 use std::process::Command;
 
 const GIT_ENV_OVERRIDES: [&str; 4] = [
@@ -0,0 +1,165 @@
+#![allow(dead_code)]
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+/// Minimal HTTP stand-in serving fixed bodies by request path.
+///
+/// Unknown paths return 404. The server runs on a background thread for the
+/// lifetime of the test process.
+pub struct StaticHttpServer {
+    base_url: String,
+    hits: Arc<AtomicUsize>,
+}
+
+impl StaticHttpServer {
+    pub fn serve(routes: Vec<(&str, String)>) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind test HTTP server");
+        let addr = listener.local_addr().expect("local addr");
+        let routes: Vec<(String, String)> = routes
+            .into_iter()
+            .map(|(path, body)| (path.to_string(), body))
+            .collect();
+        let hits = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&hits);
+
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else { continue };
+                let mut reader = BufReader::new(stream.try_clone().expect("clone stream"));
+                let mut request_line = String::new();
+                if reader.read_line(&mut request_line).is_err() {
+                    continue;
+                }
+                // Drain headers
+                let mut line = String::new();
+                while reader.read_line(&mut line).map(|n| n > 2).unwrap_or(false) {
+                    line.clear();
+                }
+                counter.fetch_add(1, Ordering::SeqCst);
+
+                let path = request_line.split_whitespace().nth(1).unwrap_or("/");
+                let response = match routes.iter().find(|(p, _)| p == path) {
+                    Some((_, body)) => format!(
+                        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        body.len(),
+                        body
+                    ),
+                    None => {
+                        "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                            .to_string()
+                    }
+                };
+                let _ = stream.write_all(response.as_bytes());
+            }
+        });
+
+        Self {
+            base_url: format!("http://{}", addr),
+            hits,
+        }
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    pub fn hits(&self) -> usize {
+        self.hits.load(Ordering::SeqCst)
+    }
+}
+
+/// Minimal streamable-HTTP MCP server.
+///
+/// Answers `initialize` with JSON and a session id, and `tools/list` as an
+/// SSE stream; requests after `initialize` without the session id get 400.
+pub struct McpHttpServer {
+    url: String,
+}
+
+impl McpHttpServer {
+    pub fn serve(tools: usize) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind test HTTP server");
+        let addr = listener.local_addr().expect("local addr");
+
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else { continue };
+                let mut reader = BufReader::new(stream.try_clone().expect("clone stream"));
+                let mut request_line = String::new();
+                if reader.read_line(&mut request_line).is_err() {
+                    continue;
+                }
+                let mut content_length = 0;
+                let mut session = None;
+                let mut line = String::new();
+                while reader.read_line(&mut line).map(|n| n > 2).unwrap_or(false) {
+                    if let Some((name, value)) = line.trim_end().split_once(':') {
+                        match name.to_ascii_lowercase().as_str() {
+                            "content-length" => content_length = value.trim().parse().unwrap_or(0),
+                            "mcp-session-id" => session = Some(value.trim().to_string()),
+                            _ => {}
+                        }
+                    }
+                    line.clear();
+                }
+                let mut body = vec![0; content_length];
+                if std::io::Read::read_exact(&mut reader, &mut body).is_err() {
+                    continue;
+                }
+                let request: serde_json::Value = serde_json::from_slice(&body).unwrap_or_default();
+                let method = request["method"].as_str().unwrap_or_default();
+                let id = &request["id"];
+
+                let response = if method == "initialize" {
+                    let body = serde_json::json!({
+                        "jsonrpc": "2.0",
+                        "id": id,
+                        "result": {
+                            "protocolVersion": "2025-06-18",
+                            "serverInfo": {"name": "remote", "version": "0.9.0"},
+                            "capabilities": {"tools": {}},
+                        },
+                    })
+                    .to_string();
+                    format!(
+                        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nMcp-Session-Id: s-1\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        body.len(),
+                        body
+                    )
+                } else if session.as_deref() != Some("s-1") {
+                    "HTTP/1.1 400 Bad Request\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                        .to_string()
+                } else if id.is_null() {
+                    "HTTP/1.1 202 Accepted\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                        .to_string()
+                } else {
+                    let tools: Vec<_> = (0..tools)
+                        .map(|i| serde_json::json!({"name": format!("tool-{i}")}))
+                        .collect();
+                    let body = serde_json::json!({
+                        "jsonrpc": "2.0",
+                        "id": id,
+                        "result": {"tools": tools},
+                    });
+                    format!(
+                        "HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nConnection: close\r\n\r\n: ping\n\nevent: message\ndata: {}\n\n",
+                        body
+                    )
+                };
+                let _ = stream.write_all(response.as_bytes());
+            }
+        });
+
+        Self {
+            url: format!("http://{}/mcp", addr),
+        }
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+}
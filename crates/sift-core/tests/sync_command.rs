@@ -0,0 +1,685 @@
+//! Integration tests for the sync command (bare `sift install`)
+
+mod support;
+
+use std::path::Path;
+
+use tempfile::TempDir;
+
+use sift_core::commands::{
+    ClientInstallStatus, InstallCommand, InstallOptions, InstallTarget, PrunedEntry, SyncCommand,
+    SyncOptions,
+};
+use sift_core::fs::LinkMode;
+use sift_core::lockfile::{LockedMcpServer, LockfileService, LockfileStore};
+use sift_core::types::ConfigScope;
+use support::git::git_command;
+
+struct Paths {
+    home: std::path::PathBuf,
+    project: std::path::PathBuf,
+    state: std::path::PathBuf,
+    config: std::path::PathBuf,
+}
+
+fn setup() -> (TempDir, Paths) {
+    let temp = TempDir::new().expect("Failed to create temp dir");
+    let paths = Paths {
+        home: temp.path().join("home"),
+        project: temp.path().join("project"),
+        state: temp.path().join("state"),
+        config: temp.path().join("config"),
+    };
+    for dir in [&paths.home, &paths.project, &paths.state, &paths.config] {
+        std::fs::create_dir_all(dir).expect("Failed to create dir");
+    }
+    (temp, paths)
+}
+
+fn sync_command(paths: &Paths) -> SyncCommand {
+    SyncCommand::with_global_config_dir(
+        paths.home.clone(),
+        paths.project.clone(),
+        paths.state.clone(),
+        paths.config.clone(),
+        LinkMode::Copy,
+    )
+}
+
+fn install_command(paths: &Paths) -> InstallCommand {
+    InstallCommand::with_global_config_dir(
+        paths.home.clone(),
+        paths.project.clone(),
+        paths.state.clone(),
+        paths.config.clone(),
+        LinkMode::Copy,
+    )
+}
+
+fn write_skill_dir(root: &Path, relative: &str, name: &str) {
+    let skill_dir = root.join(relative);
+    std::fs::create_dir_all(&skill_dir).expect("Failed to create skill dir");
+    let content =
+        format!("---\nname: {name}\ndescription: Test skill\n---\n\nTest instructions.\n");
+    std::fs::write(skill_dir.join("SKILL.md"), content).expect("Failed to write SKILL.md");
+}
+
+fn run_git(repo: &Path, args: &[&str]) {
+    let status = git_command()
+        .args(args)
+        .current_dir(repo)
+        .status()
+        .expect("Failed to invoke git");
+    assert!(status.success(), "git command failed: {:?}", args);
+}
+
+/// Create a marketplace repo with one plugin publishing `mcp_servers_json`
+/// and register it as the `test-marketplace` registry.
+fn setup_plugin_registry(paths: &Paths, plugin_name: &str, mcp_servers_json: &str) {
+    let repo_root = paths.state.join("marketplace");
+    std::fs::create_dir_all(repo_root.join(".claude-plugin"))
+        .expect("Failed to create marketplace dir");
+    run_git(&repo_root, &["init", "-b", "main"]);
+    run_git(&repo_root, &["config", "user.email", "test@example.com"]);
+    run_git(&repo_root, &["config", "user.name", "Test User"]);
+    run_git(&repo_root, &["config", "commit.gpgsign", "false"]);
+    let marketplace_json = format!(
+        r#"{{
+            "marketplace": {{"name": "test-marketplace"}},
+            "plugins": [{{
+                "name": "{plugin_name}",
+                "description": "Test MCP plugin",
+                "version": "1.0.0",
+                "source": "./",
+                "mcpServers": {mcp_servers_json}
+            }}]
+        }}"#
+    );
+    std::fs::write(
+        repo_root.join(".claude-plugin").join("marketplace.json"),
+        marketplace_json,
+    )
+    .expect("Failed to write marketplace.json");
+    run_git(&repo_root, &["add", "."]);
+    run_git(&repo_root, &["commit", "-m", "init"]);
+
+    let url = url::Url::from_directory_path(&repo_root).expect("repo root should be a file URL");
+    std::fs::write(
+        paths.config.join("sift.toml"),
+        format!(
+            "[registry.test-marketplace]\ntype = \"claude-marketplace\"\nsource = \"git:{}\"\n",
+            url.as_str().trim_end_matches('/')
+        ),
+    )
+    .expect("Failed to write global config");
+}
+
+fn read_json(path: &Path) -> serde_json::Value {
+    let content = std::fs::read_to_string(path).expect("Should read JSON file");
+    serde_json::from_str(&content).expect("Should parse JSON")
+}
+
+#[test]
+fn sync_deploys_project_config_to_all_clients() {
+    let (_temp, paths) = setup();
+    write_skill_dir(&paths.project, "skills/commit", "commit");
+    std::fs::write(
+        paths.project.join("sift.toml"),
+        r#"
+[mcp.echo]
+source = "local:echo"
+runtime = "shell"
+args = ["hello"]
+
+[mcp.cat]
+source = "local:cat"
+runtime = "shell"
+
+[skill.commit]
+source = "local:./skills/commit"
+"#,
+    )
+    .expect("Failed to write sift.toml");
+
+    let report = sync_command(&paths)
+        .execute(&SyncOptions::new())
+        .expect("Sync should succeed");
+
+    assert!(!report.has_errors(), "unexpected errors: {:?}", report);
+    assert_eq!(report.entries.len(), 3);
+
+    let mcp_json = read_json(&paths.project.join(".mcp.json"));
+    assert!(mcp_json["mcpServers"]["echo"].is_object());
+    assert!(mcp_json["mcpServers"]["cat"].is_object());
+
+    let vscode_json = read_json(&paths.project.join(".vscode").join("mcp.json"));
+    assert!(vscode_json["servers"]["echo"].is_object());
+    assert!(vscode_json["servers"]["cat"].is_object());
+
+    assert!(
+        paths
+            .project
+            .join(".claude")
+            .join("skills")
+            .join("commit")
+            .join("SKILL.md")
+            .exists()
+    );
+
+    let skill = report
+        .entries
+        .iter()
+        .find(|e| e.kind == InstallTarget::Skill)
+        .expect("skill entry should be reported");
+    assert_eq!(skill.name, "commit");
+    assert_eq!(skill.scope, ConfigScope::PerProjectShared);
+    assert!(skill.applied());
+}
+
+#[test]
+fn sync_records_lockfile_entries() {
+    let (_temp, paths) = setup();
+    std::fs::write(
+        paths.project.join("sift.toml"),
+        r#"
+[mcp.echo]
+source = "local:echo"
+runtime = "shell"
+"#,
+    )
+    .expect("Failed to write sift.toml");
+
+    sync_command(&paths)
+        .execute(&SyncOptions::new())
+        .expect("Sync should succeed");
+
+    let lockfile = LockfileStore::load(Some(paths.project.clone()), paths.state.join("locks"))
+        .expect("Lockfile should load");
+    let locked = lockfile
+        .mcp_servers
+        .get("echo")
+        .expect("echo should be locked");
+    assert_eq!(locked.scope, ConfigScope::PerProjectShared);
+}
+
+#[test]
+fn sync_restores_every_server_after_sequential_installs() {
+    let (_temp, paths) = setup();
+    let install = install_command(&paths);
+    for name in ["first", "second"] {
+        let opts = InstallOptions::mcp(name)
+            .with_command(["echo", name])
+            .with_scope(ConfigScope::PerProjectShared);
+        install.execute(&opts).expect("Install should succeed");
+    }
+
+    sync_command(&paths)
+        .execute(&SyncOptions::new())
+        .expect("Sync should succeed");
+
+    let mcp_json = read_json(&paths.project.join(".mcp.json"));
+    assert!(mcp_json["mcpServers"]["first"].is_object());
+    assert!(mcp_json["mcpServers"]["second"].is_object());
+}
+
+#[test]
+fn sync_uses_declared_scope_for_global_entries() {
+    let (_temp, paths) = setup();
+    std::fs::write(
+        paths.config.join("sift.toml"),
+        r#"
+[mcp.global-echo]
+source = "local:echo"
+runtime = "shell"
+"#,
+    )
+    .expect("Failed to write global sift.toml");
+
+    let report = sync_command(&paths)
+        .execute(&SyncOptions::new())
+        .expect("Sync should succeed");
+
+    let entry = &report.entries[0];
+    assert_eq!(entry.scope, ConfigScope::Global);
+    assert!(!paths.project.join(".mcp.json").exists());
+
+    let claude_json = read_json(&paths.home.join(".claude.json"));
+    assert!(claude_json["mcpServers"]["global-echo"].is_object());
+}
+
+#[test]
+fn sync_reports_explicit_target_errors_and_continues() {
+    let (_temp, paths) = setup();
+    std::fs::write(
+        paths.project.join("sift.toml"),
+        r#"
+[mcp.codex-only]
+source = "local:echo"
+runtime = "shell"
+targets = ["codex"]
+
+[mcp.echo]
+source = "local:echo"
+runtime = "shell"
+"#,
+    )
+    .expect("Failed to write sift.toml");
+
+    let report = sync_command(&paths)
+        .execute(&SyncOptions::new())
+        .expect("Sync should return a report");
+
+    assert!(report.has_errors());
+    let failed = report
+        .entries
+        .iter()
+        .find(|e| e.name == "codex-only")
+        .expect("codex-only should be reported");
+    assert!(
+        failed
+            .error
+            .as_deref()
+            .unwrap_or_default()
+            .contains("codex")
+    );
+
+    let echo = report
+        .entries
+        .iter()
+        .find(|e| e.name == "echo")
+        .expect("echo should be reported");
+    assert!(echo.error.is_none());
+    assert!(
+        echo.clients
+            .iter()
+            .any(|c| c.client_id == "claude-code" && c.status == ClientInstallStatus::Applied)
+    );
+}
+
+#[test]
+fn sync_with_empty_config_warns() {
+    let (_temp, paths) = setup();
+
+    let report = sync_command(&paths)
+        .execute(&SyncOptions::new())
+        .expect("Sync should succeed");
+
+    assert!(report.entries.is_empty());
+    assert_eq!(report.warnings.len(), 1);
+}
+
+#[test]
+fn prune_removes_orphaned_skill_and_lock() {
+    let (_temp, paths) = setup();
+    write_skill_dir(&paths.project, "skills/old-skill", "old-skill");
+    let config_path = paths.project.join("sift.toml");
+    std::fs::write(
+        &config_path,
+        r#"
+[skill.old-skill]
+source = "local:./skills/old-skill"
+"#,
+    )
+    .expect("Failed to write sift.toml");
+    sync_command(&paths)
+        .execute(&SyncOptions::new())
+        .expect("Sync should succeed");
+    let delivered = paths
+        .project
+        .join(".claude")
+        .join("skills")
+        .join("old-skill");
+    assert!(delivered.exists());
+
+    std::fs::write(&config_path, "").expect("Failed to clear sift.toml");
+    let report = sync_command(&paths)
+        .execute(&SyncOptions::new().with_prune(true))
+        .expect("Prune should succeed");
+
+    assert_eq!(
+        report.pruned,
+        vec![PrunedEntry {
+            kind: InstallTarget::Skill,
+            name: "old-skill".to_string(),
+            scope: ConfigScope::PerProjectShared,
+        }]
+    );
+    assert!(!delivered.exists());
+    let lockfile = LockfileStore::load(Some(paths.project.clone()), paths.state.join("locks"))
+        .expect("Lockfile should load");
+    assert!(!lockfile.skills.contains_key("old-skill"));
+}
+
+#[test]
+fn prune_removes_orphaned_mcp_and_keeps_declared() {
+    let (_temp, paths) = setup();
+    let config_path = paths.project.join("sift.toml");
+    std::fs::write(
+        &config_path,
+        r#"
+[mcp.keep]
+source = "local:echo"
+runtime = "shell"
+
+[mcp.old]
+source = "local:cat"
+runtime = "shell"
+"#,
+    )
+    .expect("Failed to write sift.toml");
+    sync_command(&paths)
+        .execute(&SyncOptions::new())
+        .expect("Sync should succeed");
+
+    std::fs::write(
+        &config_path,
+        r#"
+[mcp.keep]
+source = "local:echo"
+runtime = "shell"
+"#,
+    )
+    .expect("Failed to rewrite sift.toml");
+    let report = sync_command(&paths)
+        .execute(&SyncOptions::new().with_prune(true))
+        .expect("Prune should succeed");
+
+    assert_eq!(report.pruned.len(), 1);
+    assert_eq!(report.pruned[0].name, "old");
+
+    let mcp_json = read_json(&paths.project.join(".mcp.json"));
+    assert!(mcp_json["mcpServers"]["keep"].is_object());
+    assert!(mcp_json["mcpServers"].get("old").is_none());
+
+    let lockfile = LockfileStore::load(Some(paths.project.clone()), paths.state.join("locks"))
+        .expect("Lockfile should load");
+    assert!(lockfile.mcp_servers.contains_key("keep"));
+    assert!(!lockfile.mcp_servers.contains_key("old"));
+}
+
+#[test]
+fn prune_removes_every_server_a_plugin_delivered() {
+    let (_temp, paths) = setup();
+    setup_plugin_registry(
+        &paths,
+        "toolbox",
+        r#"{"alpha":{"command":"alpha-mcp"},"beta":{"url":"https://beta.example.com/mcp"}}"#,
+    );
+    let config_path = paths.project.join("sift.toml");
+    std::fs::write(
+        &config_path,
+        r#"
+[mcp.toolbox]
+source = "registry:test-marketplace/toolbox"
+"#,
+    )
+    .expect("Failed to write sift.toml");
+    sync_command(&paths)
+        .execute(&SyncOptions::new())
+        .expect("Sync should succeed");
+
+    let mcp_path = paths.project.join(".mcp.json");
+    let mcp_json = read_json(&mcp_path);
+    assert!(mcp_json["mcpServers"]["alpha"].is_object());
+    assert!(mcp_json["mcpServers"]["beta"].is_object());
+
+    std::fs::write(&config_path, "").expect("Failed to clear sift.toml");
+    let report = sync_command(&paths)
+        .execute(&SyncOptions::new().with_prune(true))
+        .expect("Prune should succeed");
+
+    assert_eq!(report.pruned.len(), 1);
+    assert_eq!(report.pruned[0].name, "toolbox");
+    let mcp_json = read_json(&mcp_path);
+    assert!(mcp_json["mcpServers"].get("alpha").is_none());
+    assert!(mcp_json["mcpServers"].get("beta").is_none());
+}
+
+#[test]
+fn sync_keeps_delivered_servers_when_resolution_fails() {
+    let (_temp, paths) = setup();
+    setup_plugin_registry(&paths, "toolbox", r#"{"alpha":{"command":"alpha-mcp"}}"#);
+    std::fs::write(
+        paths.project.join("sift.toml"),
+        r#"
+[mcp.toolbox]
+source = "registry:test-marketplace/toolbox"
+
+[mcp.echo]
+source = "local:echo"
+runtime = "shell"
+"#,
+    )
+    .expect("Failed to write sift.toml");
+    sync_command(&paths)
+        .execute(&SyncOptions::new())
+        .expect("Sync should succeed");
+
+    // Point the registry at a repository that does not exist
+    let missing = paths.state.join("missing-marketplace");
+    let url = url::Url::from_directory_path(&missing).expect("path should be a file URL");
+    std::fs::write(
+        paths.config.join("sift.toml"),
+        format!(
+            "[registry.test-marketplace]\ntype = \"claude-marketplace\"\nsource = \"git:{}\"\n",
+            url.as_str().trim_end_matches('/')
+        ),
+    )
+    .expect("Failed to write global config");
+
+    let report = sync_command(&paths)
+        .execute(&SyncOptions::new().with_force(true))
+        .expect("Sync should report per-entry errors");
+
+    let toolbox = report
+        .entries
+        .iter()
+        .find(|e| e.name == "toolbox")
+        .expect("toolbox should be reported");
+    assert!(
+        toolbox.error.is_some(),
+        "resolution should fail: {:?}",
+        report
+    );
+
+    let mcp_json = read_json(&paths.project.join(".mcp.json"));
+    assert!(mcp_json["mcpServers"]["alpha"].is_object());
+    assert!(mcp_json["mcpServers"]["echo"].is_object());
+    let vscode_json = read_json(&paths.project.join(".vscode").join("mcp.json"));
+    assert!(vscode_json["servers"]["alpha"].is_object());
+    assert!(vscode_json["servers"]["echo"].is_object());
+}
+
+#[test]
+fn prune_leaves_user_modified_mcp_entry() {
+    let (_temp, paths) = setup();
+    let config_path = paths.project.join("sift.toml");
+    std::fs::write(
+        &config_path,
+        r#"
+[mcp.old]
+source = "local:cat"
+runtime = "shell"
+"#,
+    )
+    .expect("Failed to write sift.toml");
+    sync_command(&paths)
+        .execute(&SyncOptions::new())
+        .expect("Sync should succeed");
+
+    let mcp_path = paths.project.join(".mcp.json");
+    let mut mcp_json = read_json(&mcp_path);
+    mcp_json["mcpServers"]["old"]["command"] = serde_json::json!("user-edited");
+    std::fs::write(&mcp_path, serde_json::to_string_pretty(&mcp_json).unwrap())
+        .expect("Failed to edit .mcp.json");
+
+    std::fs::write(&config_path, "").expect("Failed to clear sift.toml");
+    let report = sync_command(&paths)
+        .execute(&SyncOptions::new().with_prune(true))
+        .expect("Prune should succeed");
+
+    assert_eq!(report.pruned.len(), 1);
+    assert!(report.warnings.iter().any(|w| w.contains("modified")));
+    let mcp_json = read_json(&mcp_path);
+    assert_eq!(mcp_json["mcpServers"]["old"]["command"], "user-edited");
+}
+
+#[test]
+fn sync_without_prune_keeps_orphaned_lock_records() {
+    let (_temp, paths) = setup();
+    write_skill_dir(&paths.project, "skills/old-skill", "old-skill");
+    let config_path = paths.project.join("sift.toml");
+    std::fs::write(
+        &config_path,
+        r#"
+[skill.old-skill]
+source = "local:./skills/old-skill"
+"#,
+    )
+    .expect("Failed to write sift.toml");
+    sync_command(&paths)
+        .execute(&SyncOptions::new())
+        .expect("Sync should succeed");
+
+    std::fs::write(&config_path, "").expect("Failed to clear sift.toml");
+    let report = sync_command(&paths)
+        .execute(&SyncOptions::new())
+        .expect("Sync should succeed");
+
+    assert!(report.pruned.is_empty());
+    let lockfile = LockfileStore::load(Some(paths.project.clone()), paths.state.join("locks"))
+        .expect("Lockfile should load");
+    assert!(lockfile.skills.contains_key("old-skill"));
+}
+
+#[test]
+fn sync_pins_docker_image_to_locked_digest() {
+    let (_temp, paths) = setup();
+    std::fs::write(
+        paths.project.join("sift.toml"),
+        r#"
+[mcp.pg]
+source = "docker:ghcr.io/acme/pg-mcp:1.4"
+volumes = ["/srv/pg:/data:ro"]
+network = "host"
+env = { PGHOST = "localhost" }
+"#,
+    )
+    .expect("Failed to write sift.toml");
+    let source = "docker:ghcr.io/acme/pg-mcp:1.4";
+    LockfileService::new(paths.state.join("locks"), Some(paths.project.clone()))
+        .add_mcp(
+            "pg",
+            LockedMcpServer::new(
+                "pg".to_string(),
+                "1.4".to_string(),
+                "1.4".to_string(),
+                source.to_string(),
+                ConfigScope::PerProjectShared,
+            )
+            .with_checksum("sha256:abc123".to_string()),
+        )
+        .expect("Failed to seed lockfile");
+
+    sync_command(&paths)
+        .execute(&SyncOptions::new())
+        .expect("Sync should succeed");
+
+    let json = read_json(&paths.project.join(".mcp.json"));
+    let server = &json["mcpServers"]["pg"];
+    assert_eq!(server["command"], "docker");
+    assert_eq!(
+        server["args"],
+        serde_json::json!([
+            "run",
+            "-i",
+            "--rm",
+            "--network",
+            "host",
+            "-v",
+            "/srv/pg:/data:ro",
+            "-e",
+            "PGHOST",
+            "ghcr.io/acme/pg-mcp:1.4@sha256:abc123"
+        ])
+    );
+    assert_eq!(server["env"]["PGHOST"], "localhost");
+
+    let lockfile = LockfileStore::load(Some(paths.project.clone()), paths.state.join("locks"))
+        .expect("Lockfile should load");
+    let locked = &lockfile.mcp_servers["pg"];
+    assert_eq!(locked.constraint, "1.4");
+    assert_eq!(locked.checksum.as_deref(), Some("sha256:abc123"));
+}
+
+#[test]
+fn sync_deploys_to_custom_client_declared_in_sift_toml() {
+    let (_temp, paths) = setup();
+    std::fs::write(
+        paths.project.join("sift.toml"),
+        r#"
+[mcp.echo]
+source = "local:echo"
+runtime = "shell"
+args = ["hello"]
+
+[clients.acme]
+mcp_project = ".acme/mcp.json"
+config_path = ["agent", "servers"]
+
+[clients.acme.template]
+stdio = { cmd = "${command}", argv = "${args}", label = "sift:${name}" }
+"#,
+    )
+    .expect("Failed to write sift.toml");
+
+    let report = sync_command(&paths)
+        .execute(&SyncOptions::new())
+        .expect("Sync should succeed");
+
+    assert!(!report.has_errors(), "unexpected errors: {:?}", report);
+    let acme = read_json(&paths.project.join(".acme").join("mcp.json"));
+    assert_eq!(
+        acme["agent"]["servers"]["echo"],
+        serde_json::json!({"cmd": "echo", "argv": ["hello"], "label": "sift:echo"})
+    );
+}
+
+#[test]
+fn sync_skips_clients_disabled_in_sift_toml() {
+    let (_temp, paths) = setup();
+    std::fs::write(
+        paths.project.join("sift.toml"),
+        r#"
+[mcp.echo]
+source = "local:echo"
+runtime = "shell"
+
+[clients.vscode]
+enabled = false
+"#,
+    )
+    .expect("Failed to write sift.toml");
+
+    let report = sync_command(&paths)
+        .execute(&SyncOptions::new())
+        .expect("Sync should succeed");
+
+    assert!(!report.has_errors(), "unexpected errors: {:?}", report);
+    assert!(paths.project.join(".mcp.json").exists());
+    assert!(!paths.project.join(".vscode").join("mcp.json").exists());
+    let vscode = report.entries[0]
+        .clients
+        .iter()
+        .find(|c| c.client_id == "vscode")
+        .expect("vscode should be reported");
+    assert_eq!(vscode.status, ClientInstallStatus::Skipped);
+    assert!(
+        vscode
+            .message
+            .as_deref()
+            .is_some_and(|m| m.contains("disabled")),
+        "message = {:?}",
+        vscode.message
+    );
+}
@@ -0,0 +1,186 @@
+//! Integration tests for the tools command
+
+mod support;
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use tempfile::TempDir;
+use url::Url;
+
+use sift_core::commands::{ToolsCommand, ToolsOptions, ToolsReport};
+use sift_core::fs::LinkMode;
+
+use support::git::git_command;
+
+struct Paths {
+    home: PathBuf,
+    project: PathBuf,
+    state: PathBuf,
+    config: PathBuf,
+}
+
+fn setup() -> (TempDir, Paths) {
+    let temp = TempDir::new().expect("Failed to create temp dir");
+    let paths = Paths {
+        home: temp.path().join("home"),
+        project: temp.path().join("project"),
+        state: temp.path().join("state"),
+        config: temp.path().join("config"),
+    };
+    for dir in [&paths.home, &paths.project, &paths.state, &paths.config] {
+        std::fs::create_dir_all(dir).expect("Failed to create dir");
+    }
+    (temp, paths)
+}
+
+fn inspect(paths: &Paths, name: &str) -> anyhow::Result<ToolsReport> {
+    ToolsCommand::with_global_config_dir(
+        paths.home.clone(),
+        paths.project.clone(),
+        paths.state.clone(),
+        paths.config.clone(),
+        LinkMode::Copy,
+    )
+    .execute(&ToolsOptions::new(name).with_timeout(Duration::from_secs(10)))
+}
+
+/// Serves two tools and one resource, answering with the request's id.
+const FAKE_SERVER: &str = r#"while IFS= read -r line; do id=$(printf '%s' "$line" | sed -n 's/.*"id":\([0-9]*\).*/\1/p'); case "$line" in *'"initialize"'*) result='{"protocolVersion":"2025-06-18","serverInfo":{"name":"notes","version":"2.0.0"},"capabilities":{"tools":{},"resources":{}}}' ;; *'"tools/list"'*) result='{"tools":[{"name":"read_note","description":"Read a note","inputSchema":{"type":"object","properties":{"id":{"type":"string"}}}},{"name":"delete_note"}]}' ;; *'"resources/list"'*) result='{"resources":[{"uri":"notes://inbox","name":"Inbox","mimeType":"text/markdown"}]}' ;; *) continue ;; esac; echo "{\"jsonrpc\":\"2.0\",\"id\":$id,\"result\":$result}"; done"#;
+
+fn run_git(repo: &Path, args: &[&str]) {
+    let status = git_command()
+        .args(args)
+        .current_dir(repo)
+        .status()
+        .expect("Failed to invoke git");
+    assert!(status.success(), "git command failed: {:?}", args);
+}
+
+/// A marketplace whose `notes` plugin publishes an MCPB bundle.
+fn create_marketplace_repo(root: &Path, mcpb_url: &str) -> String {
+    let repo_root = root.join("marketplace");
+    std::fs::create_dir_all(repo_root.join("plugins").join("notes")).unwrap();
+    std::fs::write(repo_root.join("plugins/notes/README.md"), "notes").unwrap();
+    std::fs::create_dir_all(repo_root.join(".claude-plugin")).unwrap();
+    let marketplace = serde_json::json!({
+        "marketplace": {"name": "test-marketplace"},
+        "plugins": [{
+            "name": "notes",
+            "description": "Notes bundle",
+            "version": "2.0.0",
+            "source": "./plugins/notes",
+            "mcpServers": {"notes": mcpb_url}
+        }]
+    });
+    std::fs::write(
+        repo_root.join(".claude-plugin/marketplace.json"),
+        marketplace.to_string(),
+    )
+    .unwrap();
+
+    run_git(&repo_root, &["init"]);
+    run_git(&repo_root, &["checkout", "-b", "main"]);
+    run_git(&repo_root, &["config", "user.email", "test@example.com"]);
+    run_git(&repo_root, &["config", "user.name", "Test User"]);
+    run_git(&repo_root, &["config", "commit.gpgsign", "false"]);
+    run_git(&repo_root, &["add", "."]);
+    run_git(&repo_root, &["commit", "-m", "init"]);
+
+    Url::from_directory_path(&repo_root)
+        .expect("repo root should convert to file URL")
+        .to_string()
+}
+
+fn write_mcpb_cache(state_dir: &Path, url: &str, manifest_json: &str) {
+    let hash = blake3::hash(url.as_bytes()).to_hex()[..32].to_string();
+    let extract_dir = state_dir.join("cache").join("mcpb").join(hash);
+    std::fs::create_dir_all(&extract_dir).expect("Failed to create MCPB cache dir");
+    std::fs::write(extract_dir.join("manifest.json"), manifest_json)
+        .expect("Failed to write MCPB manifest.json");
+}
+
+#[test]
+fn tools_lists_tools_and_resources() {
+    let (_temp, paths) = setup();
+    let config = format!(
+        r#"
+[mcp.notes]
+source = "local:sh"
+runtime = "shell"
+args = ["-c", '''{}''']
+"#,
+        FAKE_SERVER
+    );
+    std::fs::write(paths.project.join("sift.toml"), config).unwrap();
+
+    let report = inspect(&paths, "notes").expect("Inspection should succeed");
+
+    assert_eq!(report.entries.len(), 1);
+    let entry = &report.entries[0];
+    assert!(entry.manifest.is_none());
+    assert_eq!(entry.inventory.probe.server_name.as_deref(), Some("notes"));
+
+    let tools: Vec<_> = entry.inventory.tools.iter().map(|t| &t.name).collect();
+    assert_eq!(tools, vec!["read_note", "delete_note"]);
+    assert_eq!(
+        entry.inventory.tools[0].input_schema["properties"]["id"]["type"],
+        "string"
+    );
+    assert!(entry.inventory.prompts.is_empty());
+    assert_eq!(entry.inventory.resources.len(), 1);
+    assert_eq!(entry.inventory.resources[0].uri, "notes://inbox");
+    assert_eq!(
+        entry.inventory.resources[0].mime_type.as_deref(),
+        Some("text/markdown")
+    );
+    assert!(!report.has_mismatches());
+
+    let err = inspect(&paths, "missing").unwrap_err();
+    assert!(err.to_string().contains("not declared"));
+}
+
+#[test]
+fn tools_diffs_mcpb_manifest_against_live_tools() {
+    let (temp, paths) = setup();
+    let url = "https://example.com/notes.mcpb";
+    let manifest = serde_json::json!({
+        "manifest_version": "0.3",
+        "name": "notes",
+        "version": "2.0.0",
+        "description": "Notes",
+        "author": {"name": "Test"},
+        "server": {
+            "type": "binary",
+            "mcp_config": {"command": "sh", "args": ["-c", FAKE_SERVER]}
+        },
+        "tools": [{"name": "read_note"}, {"name": "search_notes"}]
+    });
+    write_mcpb_cache(&paths.state, url, &manifest.to_string());
+    let marketplace_url = create_marketplace_repo(temp.path(), url);
+    std::fs::write(
+        paths.config.join("sift.toml"),
+        format!(
+            "[registry.test-marketplace]\ntype = \"claude-marketplace\"\nsource = \"git:{}\"\n",
+            marketplace_url.trim_end_matches('/')
+        ),
+    )
+    .unwrap();
+    std::fs::write(
+        paths.project.join("sift.toml"),
+        "[mcp.notes]\nsource = \"registry:test-marketplace/notes\"\n",
+    )
+    .unwrap();
+
+    let report = inspect(&paths, "notes").expect("Inspection should succeed");
+
+    let diff = report.entries[0]
+        .manifest
+        .as_ref()
+        .expect("MCPB sources are compared with their manifest");
+    assert_eq!(diff.missing_tools, vec!["search_notes"]);
+    assert_eq!(diff.undeclared_tools, vec!["delete_note"]);
+    assert!(!diff.tools_generated);
+    assert_eq!(diff.mismatches().len(), 2);
+    assert!(report.has_mismatches());
+}
@@ -328,3 +328,44 @@ fn uninstall_managed_json_respects_ownership() {
     let content = std::fs::read_to_string(&config_path).expect("read config");
     assert!(!content.contains("owned"));
 }
+
+#[test]
+fn uninstall_leaves_disabled_client_config_alone() {
+    let (temp, install, uninstall) = setup_isolated_commands();
+
+    let opts = InstallOptions::mcp("kept")
+        .with_source("registry:kept")
+        .with_scope(ConfigScope::PerProjectShared);
+    install.execute(&opts).expect("project install");
+    let mcp_json = temp.path().join("project").join(".mcp.json");
+    assert!(
+        std::fs::read_to_string(&mcp_json)
+            .expect("read .mcp.json")
+            .contains("kept")
+    );
+
+    std::fs::write(
+        temp.path().join("config").join("sift.toml"),
+        "[clients.claude-code]\nenabled = false\n",
+    )
+    .expect("write global config");
+
+    let report = uninstall
+        .execute(&UninstallOptions::mcp("kept"))
+        .expect("uninstall");
+
+    assert!(report.changed);
+    assert!(
+        report.warnings.iter().any(|w| w.contains("disabled")),
+        "warnings = {:?}",
+        report.warnings
+    );
+    assert!(
+        std::fs::read_to_string(&mcp_json)
+            .expect("read .mcp.json")
+            .contains("kept")
+    );
+    let project_config = temp.path().join("project").join("sift.toml");
+    let content = std::fs::read_to_string(project_config).expect("read project config");
+    assert!(!content.contains("kept"));
+}
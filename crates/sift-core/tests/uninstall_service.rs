@@ -34,6 +34,7 @@ fn create_config_with_mcp(name: &str, store: &ConfigStore, scope: ConfigScope) -
         args: Vec::new(),
         url: None,
         headers: HashMap::new(),
+        oauth: None,
         targets: None,
         ignore_targets: None,
         env: HashMap::new(),
@@ -41,6 +42,9 @@ fn create_config_with_mcp(name: &str, store: &ConfigStore, scope: ConfigScope) -
         reset_ignore_targets: false,
         reset_env: None,
         reset_env_all: false,
+        volumes: Vec::new(),
+        network: None,
+        always_allow: Vec::new(),
     };
 
     if scope == ConfigScope::PerProjectLocal {
@@ -217,6 +221,7 @@ fn remove_mcp_multiple_in_same_scope_only_removes_target() {
             args: Vec::new(),
             url: None,
             headers: HashMap::new(),
+            oauth: None,
             targets: None,
             ignore_targets: None,
             env: HashMap::new(),
@@ -224,6 +229,9 @@ fn remove_mcp_multiple_in_same_scope_only_removes_target() {
             reset_ignore_targets: false,
             reset_env: None,
             reset_env_all: false,
+            volumes: Vec::new(),
+            network: None,
+            always_allow: Vec::new(),
         },
     );
     config.mcp.insert(
@@ -235,6 +243,7 @@ fn remove_mcp_multiple_in_same_scope_only_removes_target() {
             args: Vec::new(),
             url: None,
             headers: HashMap::new(),
+            oauth: None,
             targets: None,
             ignore_targets: None,
             env: HashMap::new(),
@@ -242,6 +251,9 @@ fn remove_mcp_multiple_in_same_scope_only_removes_target() {
             reset_ignore_targets: false,
             reset_env: None,
             reset_env_all: false,
+            volumes: Vec::new(),
+            network: None,
+            always_allow: Vec::new(),
         },
     );
     store.save(&config).expect("Failed to save config");
@@ -560,6 +572,7 @@ fn contains_skill_local_scope_project_exists_but_no_skill() {
             args: Vec::new(),
             url: None,
             headers: HashMap::new(),
+            oauth: None,
             targets: None,
             ignore_targets: None,
             env: HashMap::new(),
@@ -567,6 +580,9 @@ fn contains_skill_local_scope_project_exists_but_no_skill() {
             reset_ignore_targets: false,
             reset_env: None,
             reset_env_all: false,
+            volumes: Vec::new(),
+            network: None,
+            always_allow: Vec::new(),
         },
     );
     let mut projects = HashMap::new();
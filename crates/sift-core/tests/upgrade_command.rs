@@ -0,0 +1,336 @@
+//! Integration tests for the upgrade command
+//!
+//! Skills come from local git repositories; MCP servers come from a Sift
+//! registry served by a local HTTP stand-in.
+
+use std::path::{Path, PathBuf};
+
+use tempfile::TempDir;
+use url::Url;
+
+use sift_core::commands::{
+    InstallCommand, InstallOptions, InstallTarget, SyncCommand, SyncOptions, UpgradeCommand,
+    UpgradeOptions, UpgradeStatus,
+};
+use sift_core::fs::LinkMode;
+use sift_core::lockfile::{Lockfile, LockfileStore};
+use sift_core::types::ConfigScope;
+
+mod support;
+use support::git::git_command;
+use support::http::StaticHttpServer;
+
+struct Paths {
+    home: PathBuf,
+    project: PathBuf,
+    state: PathBuf,
+    config: PathBuf,
+    repo: PathBuf,
+}
+
+fn setup() -> (TempDir, Paths) {
+    let temp = TempDir::new().expect("Failed to create temp dir");
+    let paths = Paths {
+        home: temp.path().join("home"),
+        project: temp.path().join("project"),
+        state: temp.path().join("state"),
+        config: temp.path().join("config"),
+        repo: temp.path().join("repo"),
+    };
+    for dir in [&paths.home, &paths.project, &paths.state, &paths.config] {
+        std::fs::create_dir_all(dir).expect("Failed to create dir");
+    }
+    (temp, paths)
+}
+
+fn upgrade_command(paths: &Paths) -> UpgradeCommand {
+    UpgradeCommand::with_global_config_dir(
+        paths.home.clone(),
+        paths.project.clone(),
+        paths.state.clone(),
+        paths.config.clone(),
+        LinkMode::Copy,
+    )
+}
+
+fn sync_command(paths: &Paths) -> SyncCommand {
+    SyncCommand::with_global_config_dir(
+        paths.home.clone(),
+        paths.project.clone(),
+        paths.state.clone(),
+        paths.config.clone(),
+        LinkMode::Copy,
+    )
+}
+
+fn load_lockfile(paths: &Paths) -> Lockfile {
+    LockfileStore::load(Some(paths.project.clone()), paths.state.join("locks"))
+        .expect("Lockfile should load")
+}
+
+fn run_git(repo: &Path, args: &[&str]) {
+    let status = git_command()
+        .args(args)
+        .current_dir(repo)
+        .status()
+        .expect("Failed to invoke git");
+    assert!(status.success(), "git command failed: {:?}", args);
+}
+
+fn git_rev_parse(repo: &Path, rev: &str) -> String {
+    let output = git_command()
+        .args(["rev-parse", rev])
+        .current_dir(repo)
+        .output()
+        .expect("Failed to run git rev-parse");
+    assert!(output.status.success(), "git rev-parse failed");
+    String::from_utf8_lossy(&output.stdout).trim().to_string()
+}
+
+/// Commit a new revision of the skill at the repository root.
+fn commit_skill(repo: &Path, body: &str) -> String {
+    if !repo.exists() {
+        std::fs::create_dir_all(repo).expect("Failed to create repo dir");
+        run_git(repo, &["init"]);
+        run_git(repo, &["checkout", "-b", "main"]);
+        run_git(repo, &["config", "user.email", "test@example.com"]);
+        run_git(repo, &["config", "user.name", "Test User"]);
+        run_git(repo, &["config", "commit.gpgsign", "false"]);
+    }
+    let content = format!("---\nname: demo\ndescription: Test skill\n---\n\n{body}\n");
+    std::fs::write(repo.join("SKILL.md"), content).expect("Failed to write SKILL.md");
+    run_git(repo, &["add", "."]);
+    run_git(repo, &["commit", "-m", body]);
+    git_rev_parse(repo, "HEAD")
+}
+
+fn repo_source(repo: &Path) -> String {
+    let url = Url::from_directory_path(repo)
+        .expect("repo root should convert to file URL")
+        .to_string();
+    format!("git:{}", url.trim_end_matches('/'))
+}
+
+fn delivered_skill(paths: &Paths) -> String {
+    std::fs::read_to_string(
+        paths
+            .project
+            .join(".claude")
+            .join("skills")
+            .join("demo")
+            .join("SKILL.md"),
+    )
+    .expect("Skill should be delivered")
+}
+
+fn write_skill_config(paths: &Paths, version: Option<&str>) {
+    let version = version
+        .map(|v| format!("version = \"{v}\"\n"))
+        .unwrap_or_default();
+    std::fs::write(
+        paths.project.join("sift.toml"),
+        format!(
+            "[skill.demo]\nsource = \"{}\"\n{}",
+            repo_source(&paths.repo),
+            version
+        ),
+    )
+    .expect("Failed to write sift.toml");
+}
+
+#[test]
+fn upgrade_moves_skill_to_highest_matching_tag() {
+    let (_temp, paths) = setup();
+    commit_skill(&paths.repo, "release one");
+    run_git(&paths.repo, &["tag", "v1.0.0"]);
+    write_skill_config(&paths, Some("^1.0"));
+    sync_command(&paths)
+        .execute(&SyncOptions::new())
+        .expect("Sync should succeed");
+
+    let released = commit_skill(&paths.repo, "release two");
+    run_git(&paths.repo, &["tag", "-a", "v1.1.0", "-m", "v1.1.0"]);
+    commit_skill(&paths.repo, "unreleased");
+    commit_skill(&paths.repo, "breaking");
+    run_git(&paths.repo, &["tag", "v2.0.0"]);
+
+    let report = upgrade_command(&paths)
+        .execute(&UpgradeOptions::new())
+        .expect("Upgrade should succeed");
+
+    assert!(!report.has_errors(), "unexpected errors: {:?}", report);
+    let entry = &report.entries[0];
+    assert_eq!(entry.status, UpgradeStatus::Upgraded);
+    assert_eq!(entry.constraint, "^1.0");
+    assert!(entry.to.starts_with("v1.1.0"), "to = {}", entry.to);
+    assert!(delivered_skill(&paths).contains("release two"));
+
+    let lockfile = load_lockfile(&paths);
+    let locked = lockfile.skills.get("demo").expect("demo should be locked");
+    assert_eq!(locked.resolved_version, released);
+    assert_eq!(locked.git_ref.as_deref(), Some("v1.1.0"));
+    assert_eq!(locked.constraint, "^1.0");
+}
+
+#[test]
+fn upgrade_reports_up_to_date_skill() {
+    let (_temp, paths) = setup();
+    commit_skill(&paths.repo, "release one");
+    write_skill_config(&paths, None);
+    sync_command(&paths)
+        .execute(&SyncOptions::new())
+        .expect("Sync should succeed");
+
+    let report = upgrade_command(&paths)
+        .execute(&UpgradeOptions::new())
+        .expect("Upgrade should succeed");
+
+    assert_eq!(report.entries.len(), 1);
+    assert_eq!(report.entries[0].status, UpgradeStatus::UpToDate);
+    assert_eq!(report.entries[0].from, report.entries[0].to);
+}
+
+#[test]
+fn upgrade_follows_head_for_unversioned_skill() {
+    let (_temp, paths) = setup();
+    commit_skill(&paths.repo, "first");
+    write_skill_config(&paths, None);
+    sync_command(&paths)
+        .execute(&SyncOptions::new())
+        .expect("Sync should succeed");
+
+    let head = commit_skill(&paths.repo, "second");
+    let report = upgrade_command(&paths)
+        .execute(&UpgradeOptions::new().with_kind(InstallTarget::Skill))
+        .expect("Upgrade should succeed");
+
+    assert_eq!(report.entries[0].status, UpgradeStatus::Upgraded);
+    assert_eq!(report.entries[0].constraint, "HEAD");
+    assert!(delivered_skill(&paths).contains("second"));
+    let lockfile = load_lockfile(&paths);
+    assert_eq!(lockfile.skills["demo"].resolved_version, head);
+}
+
+#[test]
+fn sync_keeps_skill_pinned_to_upgraded_tag() {
+    let (_temp, paths) = setup();
+    commit_skill(&paths.repo, "release one");
+    run_git(&paths.repo, &["tag", "v1.0.0"]);
+    write_skill_config(&paths, Some("^1.0"));
+    sync_command(&paths)
+        .execute(&SyncOptions::new())
+        .expect("Sync should succeed");
+    commit_skill(&paths.repo, "release two");
+    run_git(&paths.repo, &["tag", "v1.1.0"]);
+    upgrade_command(&paths)
+        .execute(&UpgradeOptions::new())
+        .expect("Upgrade should succeed");
+
+    commit_skill(&paths.repo, "unreleased");
+    sync_command(&paths)
+        .execute(&SyncOptions::new().with_force(true))
+        .expect("Sync should succeed");
+
+    assert!(delivered_skill(&paths).contains("release two"));
+}
+
+#[test]
+fn upgrade_unknown_name_errors() {
+    let (_temp, paths) = setup();
+    let result = upgrade_command(&paths).execute(&UpgradeOptions::new().with_name("missing"));
+    assert!(result.is_err());
+}
+
+fn registry_index(versions: &[&str]) -> String {
+    let versions: Vec<String> = versions
+        .iter()
+        .map(|v| format!(r#""{v}": {{ "mcp": {{ "package": "@acme/postgres-mcp" }} }}"#))
+        .collect();
+    format!(
+        r#"{{
+            "schema_version": 1,
+            "packages": {{
+                "postgres": {{
+                    "default_runtime": "node",
+                    "versions": {{ {} }}
+                }}
+            }}
+        }}"#,
+        versions.join(", ")
+    )
+}
+
+fn use_registry(paths: &Paths, server: &StaticHttpServer, path: &str) {
+    std::fs::write(
+        paths.config.join("sift.toml"),
+        format!(
+            "[registry.internal]\ntype = \"sift\"\nurl = \"{}{}\"\n",
+            server.base_url(),
+            path
+        ),
+    )
+    .expect("Failed to write global sift.toml");
+}
+
+fn postgres_args(paths: &Paths) -> serde_json::Value {
+    let content = std::fs::read_to_string(paths.project.join(".mcp.json")).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&content).unwrap();
+    json["mcpServers"]["postgres"]["args"].clone()
+}
+
+#[test]
+fn upgrade_mcp_rewrites_lockfile_and_config() {
+    let (_temp, paths) = setup();
+    let server = StaticHttpServer::serve(vec![
+        ("/v1/index.json", registry_index(&["1.2.3"])),
+        (
+            "/v2/index.json",
+            registry_index(&["1.2.3", "1.3.0", "2.0.0"]),
+        ),
+    ]);
+    use_registry(&paths, &server, "/v1");
+    InstallCommand::with_global_config_dir(
+        paths.home.clone(),
+        paths.project.clone(),
+        paths.state.clone(),
+        paths.config.clone(),
+        LinkMode::Copy,
+    )
+    .execute(
+        &InstallOptions::mcp("postgres")
+            .with_version("^1.2")
+            .with_scope(ConfigScope::PerProjectShared),
+    )
+    .expect("Install should succeed");
+    assert_eq!(
+        load_lockfile(&paths).mcp_servers["postgres"].resolved_version,
+        "1.2.3"
+    );
+
+    // A newer release does not move the lock until upgrade runs
+    use_registry(&paths, &server, "/v2");
+    sync_command(&paths)
+        .execute(&SyncOptions::new())
+        .expect("Sync should succeed");
+    assert_eq!(
+        postgres_args(&paths),
+        serde_json::json!(["-y", "@acme/postgres-mcp@1.2.3"])
+    );
+
+    let report = upgrade_command(&paths)
+        .execute(&UpgradeOptions::new().with_name("postgres"))
+        .expect("Upgrade should succeed");
+
+    assert!(!report.has_errors(), "unexpected errors: {:?}", report);
+    let entry = &report.entries[0];
+    assert_eq!(entry.status, UpgradeStatus::Upgraded);
+    assert_eq!((entry.from.as_str(), entry.to.as_str()), ("1.2.3", "1.3.0"));
+    assert_eq!(
+        postgres_args(&paths),
+        serde_json::json!(["-y", "@acme/postgres-mcp@1.3.0"])
+    );
+
+    let locked = &load_lockfile(&paths).mcp_servers["postgres"];
+    assert_eq!(locked.resolved_version, "1.3.0");
+    assert_eq!(locked.constraint, "^1.2");
+}